@@ -104,6 +104,7 @@ fn create_parallel_engine_and_kb(
         max_threads: threads,
         min_rules_per_thread: 2,
         dependency_analysis: false,
+        ..Default::default()
     };
 
     let engine = ParallelRuleEngine::new(config);
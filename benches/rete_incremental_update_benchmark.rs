@@ -0,0 +1,96 @@
+//! Incremental Fact Update Benchmark
+//!
+//! Compares updating one field of one fact on a 500-rule network:
+//! - `IncrementalEngine::update`: replaces the fact and re-checks every fact of
+//!   the type against every dependent rule (full scan)
+//! - `IncrementalEngine::update_fact`: re-checks only the rules reading the
+//!   changed field, against working memory with the changed fact on top
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_rule_engine::rete::{
+    AlphaNode, FactHandle, IncrementalEngine, ReteUlNode, TypedFacts, TypedReteUlRule,
+};
+use std::hint::black_box;
+use std::sync::Arc;
+
+const RULES: usize = 500;
+const FIELDS: usize = 50;
+
+fn build_engine(fact_count: usize) -> (IncrementalEngine, Vec<FactHandle>) {
+    let mut engine = IncrementalEngine::new();
+
+    // 500 rules spread over 50 fields, 10 thresholds per field
+    for i in 0..RULES {
+        let rule = TypedReteUlRule {
+            name: format!("Rule{}", i),
+            node: ReteUlNode::UlAlpha(AlphaNode {
+                field: format!("Order.f{}", i % FIELDS),
+                operator: ">".to_string(),
+                value: ((i / FIELDS) * 10).to_string(),
+            }),
+            priority: 0,
+            no_loop: true,
+            action: Arc::new(|_, _| {}),
+        };
+        engine.add_rule(rule, vec!["Order".to_string()]);
+    }
+
+    let handles = (0..fact_count)
+        .map(|n| {
+            let mut order = TypedFacts::new();
+            for f in 0..FIELDS {
+                order.set(format!("f{}", f), (n % 100) as i64);
+            }
+            engine.insert("Order".to_string(), order)
+        })
+        .collect();
+    engine.agenda_mut().clear();
+
+    (engine, handles)
+}
+
+fn bench_single_field_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rete_update_500_rules");
+
+    for fact_count in [1usize, 10, 100] {
+        let (mut engine, handles) = build_engine(fact_count);
+        let target = handles[0];
+        let mut value = 0i64;
+
+        group.bench_with_input(
+            BenchmarkId::new("full_scan_update", fact_count),
+            &fact_count,
+            |b, _| {
+                b.iter(|| {
+                    value = (value + 37) % 100;
+                    let mut data = engine.working_memory().get(&target).unwrap().data.clone();
+                    data.set("f7", value);
+                    engine.update(target, data).unwrap();
+                    engine.agenda_mut().clear();
+                    black_box(value)
+                });
+            },
+        );
+
+        let (mut engine, handles) = build_engine(fact_count);
+        let target = handles[0];
+
+        group.bench_with_input(
+            BenchmarkId::new("incremental_update_fact", fact_count),
+            &fact_count,
+            |b, _| {
+                b.iter(|| {
+                    value = (value + 37) % 100;
+                    let delta = engine.update_fact(target, "f7", value).unwrap();
+                    engine.agenda_mut().clear();
+                    black_box(delta)
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_single_field_update);
+criterion_main!(benches);
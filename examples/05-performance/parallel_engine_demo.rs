@@ -98,6 +98,7 @@ fn demo_parallel_configuration() -> Result<(), Box<dyn std::error::Error>> {
                 max_threads: 8,
                 min_rules_per_thread: 1,
                 dependency_analysis: true,
+                ..Default::default()
             },
         ),
         (
@@ -107,6 +108,7 @@ fn demo_parallel_configuration() -> Result<(), Box<dyn std::error::Error>> {
                 max_threads: 2,
                 min_rules_per_thread: 5,
                 dependency_analysis: true,
+                ..Default::default()
             },
         ),
         (
@@ -162,6 +164,7 @@ fn demo_large_scale_parallel() -> Result<(), Box<dyn std::error::Error>> {
             max_threads: thread_count,
             min_rules_per_thread: 1,
             dependency_analysis: true,
+            ..Default::default()
         };
 
         let start = Instant::now();
@@ -27,11 +27,21 @@
 //!     "avg(?salary) WHERE salary(?name, ?salary) AND ?salary > 50000",
 //!     &mut facts
 //! )?;
+//!
+//! // Aggregate predicates can also be queried as goals
+//! let result = engine.query(
+//!     "count(Order where status == \"open\") > 5",
+//!     &mut facts
+//! )?;
+//! println!("{} open orders", result.value.unwrap());
 //! ```
 
+use super::expression::{Expression, ExpressionParser};
 use super::search::Solution;
 use crate::errors::{Result, RuleEngineError};
-use crate::types::Value;
+use crate::types::{Operator, Value};
+use crate::Facts;
+use std::collections::{BTreeMap, HashMap};
 
 /// Aggregate function types
 #[derive(Debug, Clone, PartialEq)]
@@ -305,6 +315,178 @@ pub fn apply_aggregate(function: &AggregateFunction, solutions: &[Solution]) ->
     }
 }
 
+/// Aggregate predicate used as a goal, e.g. `count(Order where status == "open") > 5`
+///
+/// Instances of the fact type are read from flat keys (`Order.1.status`,
+/// `Order.2.status`), the layout the forward engine's `accumulate` uses, or
+/// from an array of objects stored under the type name.
+#[derive(Debug, Clone)]
+pub struct AggregateGoal {
+    /// The aggregate function to apply
+    pub function: AggregateFunction,
+
+    /// Fact type whose instances are aggregated
+    pub fact_type: String,
+
+    /// Condition each instance must satisfy; fields are referenced unqualified
+    pub filter: Option<Expression>,
+
+    /// Comparison applied to the aggregate value
+    pub operator: Operator,
+
+    /// Value the aggregate is compared against
+    pub threshold: Value,
+}
+
+impl AggregateGoal {
+    /// Compute the aggregate over the matching instances in `facts`
+    pub fn compute(&self, facts: &Facts) -> Result<Value> {
+        let solutions: Vec<Solution> = fact_instances(facts, &self.fact_type)
+            .into_iter()
+            .filter(|fields| self.matches(fields))
+            .map(|bindings| Solution {
+                path: Vec::new(),
+                bindings,
+            })
+            .collect();
+        apply_aggregate(&self.function, &solutions)
+    }
+
+    /// Whether an aggregate value satisfies the goal's comparison
+    pub fn is_satisfied_by(&self, value: &Value) -> bool {
+        self.operator.evaluate(value, &self.threshold)
+    }
+
+    fn matches(&self, fields: &HashMap<String, Value>) -> bool {
+        let Some(filter) = &self.filter else {
+            return true;
+        };
+        let scope = Facts::new();
+        for (field, value) in fields {
+            scope.set(field, value.clone());
+            scope.set(&format!("{}.{}", self.fact_type, field), value.clone());
+        }
+        filter.is_satisfied(&scope)
+    }
+}
+
+/// Parse an aggregate goal
+///
+/// Supported formats:
+/// - `count(Order) > 5`
+/// - `count(Order where status == "open") >= 2`
+/// - `sum(Order.amount where region == "EU") > 1000`
+///
+/// Returns `Ok(None)` when the text is not shaped like an aggregate goal, so
+/// callers can fall back to ordinary goal parsing.
+pub fn parse_aggregate_goal(goal: &str) -> Result<Option<AggregateGoal>> {
+    let goal = goal.trim();
+    let (Some(open), Some(close)) = (goal.find('('), goal.rfind(')')) else {
+        return Ok(None);
+    };
+    let func_name = goal[..open].trim().to_lowercase();
+    if close < open || !["count", "sum", "avg", "min", "max"].contains(&func_name.as_str()) {
+        return Ok(None);
+    }
+    let Some((operator, threshold)) = split_comparison(goal[close + 1..].trim()) else {
+        return Ok(None);
+    };
+
+    let inner = goal[open + 1..close].trim();
+    let (source, filter) = match inner.to_ascii_lowercase().find(" where ") {
+        Some(idx) => (inner[..idx].trim(), Some(inner[idx + 7..].trim())),
+        None => (inner, None),
+    };
+    let (fact_type, field) = match source.split_once('.') {
+        Some((fact_type, field)) => (fact_type, Some(field.to_string())),
+        None => (source, None),
+    };
+
+    let function = match (func_name.as_str(), field) {
+        ("count", _) => AggregateFunction::Count,
+        ("sum", Some(field)) => AggregateFunction::Sum(field),
+        ("avg", Some(field)) => AggregateFunction::Avg(field),
+        ("min", Some(field)) => AggregateFunction::Min(field),
+        ("max", Some(field)) => AggregateFunction::Max(field),
+        (name, None) => {
+            return Err(RuleEngineError::ParseError {
+                message: format!(
+                    "{}() goal requires a field, e.g., {}({}.amount)",
+                    name, name, fact_type
+                ),
+            });
+        }
+        _ => unreachable!("aggregate goal function checked above"),
+    };
+
+    let threshold = match ExpressionParser::parse(threshold)? {
+        Expression::Literal(value) => value,
+        other => {
+            return Err(RuleEngineError::ParseError {
+                message: format!(
+                    "Aggregate goal must compare against a literal, got '{}'",
+                    other.to_string()
+                ),
+            });
+        }
+    };
+
+    Ok(Some(AggregateGoal {
+        function,
+        fact_type: fact_type.to_string(),
+        filter: filter.map(ExpressionParser::parse).transpose()?,
+        operator,
+        threshold,
+    }))
+}
+
+/// Split `> 5` into its operator and right-hand side
+fn split_comparison(s: &str) -> Option<(Operator, &str)> {
+    // Longer operators first so `>=` is not read as `>`
+    let operators = [
+        (">=", Operator::GreaterThanOrEqual),
+        ("<=", Operator::LessThanOrEqual),
+        ("==", Operator::Equal),
+        ("!=", Operator::NotEqual),
+        (">", Operator::GreaterThan),
+        ("<", Operator::LessThan),
+    ];
+    operators.into_iter().find_map(|(symbol, operator)| {
+        s.strip_prefix(symbol)
+            .map(|rest| (operator, rest.trim()))
+            .filter(|(_, rest)| !rest.is_empty())
+    })
+}
+
+/// Instances of `fact_type` in `facts`, each as a map of field values
+fn fact_instances(facts: &Facts, fact_type: &str) -> Vec<HashMap<String, Value>> {
+    if let Some(Value::Array(items)) = facts.get(fact_type) {
+        return items
+            .into_iter()
+            .filter_map(|item| match item {
+                Value::Object(fields) => Some(fields),
+                _ => None,
+            })
+            .collect();
+    }
+
+    // Flat keys: `Order.1.status` belongs to instance "1", `Order.status` to
+    // the single unnamed instance
+    let prefix = format!("{}.", fact_type);
+    let mut instances: BTreeMap<String, HashMap<String, Value>> = BTreeMap::new();
+    for (key, value) in facts.get_all_facts() {
+        let Some(rest) = key.strip_prefix(&prefix) else {
+            continue;
+        };
+        let (id, field) = rest.split_once('.').unwrap_or(("", rest));
+        instances
+            .entry(id.to_string())
+            .or_default()
+            .insert(field.to_string(), value);
+    }
+    instances.into_values().collect()
+}
+
 /// Convert a Value to f64 for numeric aggregations
 fn value_to_float(value: &Value) -> Result<f64> {
     match value {
@@ -551,4 +733,40 @@ mod tests {
             apply_aggregate(&AggregateFunction::Min("price".to_string()), &solutions).unwrap();
         assert_eq!(min, Value::Null);
     }
+
+    #[test]
+    fn test_aggregate_goal_over_flat_instances() {
+        let facts = Facts::new();
+        for (id, status, amount) in [
+            (1, "open", 120),
+            (2, "closed", 80),
+            (3, "open", 45),
+            (4, "open", 300),
+        ] {
+            facts.set(
+                &format!("Order.{}.status", id),
+                Value::String(status.to_string()),
+            );
+            facts.set(&format!("Order.{}.amount", id), Value::Integer(amount));
+        }
+
+        let goal = parse_aggregate_goal(r#"count(Order where status == "open") > 2"#)
+            .unwrap()
+            .expect("aggregate goal");
+        let value = goal.compute(&facts).unwrap();
+        assert_eq!(value, Value::Integer(3));
+        assert!(goal.is_satisfied_by(&value));
+
+        let goal = parse_aggregate_goal(r#"sum(Order.amount where status == "open") < 400"#)
+            .unwrap()
+            .unwrap();
+        let value = goal.compute(&facts).unwrap();
+        assert_eq!(value, Value::Number(465.0));
+        assert!(!goal.is_satisfied_by(&value));
+
+        assert!(parse_aggregate_goal("User.IsVIP == true")
+            .unwrap()
+            .is_none());
+        assert!(parse_aggregate_goal("sum(Order) > 5").is_err());
+    }
 }
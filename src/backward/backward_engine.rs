@@ -1,5 +1,6 @@
 //! Backward chaining engine implementation
 
+use super::aggregation::{parse_aggregate_goal, AggregateGoal};
 use super::conclusion_index::ConclusionIndex;
 use super::goal::{Goal, GoalManager, GoalStatus};
 use super::query::{ProofTrace, QueryParser, QueryResult, QueryStats};
@@ -8,12 +9,13 @@ use super::search::{
 };
 use crate::errors::Result;
 use crate::{Facts, KnowledgeBase};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Configuration for backward chaining engine
 #[derive(Debug, Clone)]
 pub struct BackwardConfig {
-    /// Maximum depth for goal search
+    /// Maximum depth for goal search; sub-goals deeper than this fail
     pub max_depth: usize,
 
     /// Search strategy to use
@@ -90,6 +92,10 @@ impl BackwardEngine {
     /// if result.provable {
     ///     println!("User is VIP!");
     /// }
+    ///
+    /// // Aggregate goals report the computed value
+    /// let result = engine.query("count(Order where status == \"open\") > 5", &mut facts)?;
+    /// println!("{:?} open orders", result.value);
     /// ```
     pub fn query(&mut self, query_str: &str, facts: &mut Facts) -> Result<QueryResult> {
         // Backward-compatible: no RETE engine provided
@@ -105,6 +111,10 @@ impl BackwardEngine {
             std::sync::Arc<std::sync::Mutex<crate::rete::propagation::IncrementalEngine>>,
         >,
     ) -> Result<QueryResult> {
+        if let Some(aggregate) = parse_aggregate_goal(query_str)? {
+            return Self::prove_aggregate_goal(&aggregate, query_str, facts);
+        }
+
         // Parse query into goal
         let mut goal = QueryParser::parse(query_str)
             .map_err(|e| crate::errors::RuleEngineError::ParseError { message: e })?;
@@ -183,6 +193,34 @@ impl BackwardEngine {
         })
     }
 
+    /// Prove an aggregate goal such as `count(Order where status == "open") > 5`
+    ///
+    /// The aggregate is computed over the current facts; rules are not chained
+    /// to derive further instances, and the result is never memoized since it
+    /// changes whenever instances are added.
+    fn prove_aggregate_goal(
+        goal: &AggregateGoal,
+        query_str: &str,
+        facts: &Facts,
+    ) -> Result<QueryResult> {
+        let value = goal.compute(facts)?;
+        let stats = QueryStats {
+            goals_explored: 1,
+            ..QueryStats::default()
+        };
+        let mut result = if goal.is_satisfied_by(&value) {
+            QueryResult::success(
+                HashMap::new(),
+                ProofTrace::new(query_str.to_string()),
+                stats,
+            )
+        } else {
+            QueryResult::failure(Vec::new(), stats)
+        };
+        result.value = Some(value);
+        Ok(result)
+    }
+
     /// Find all candidate rules that could prove a goal
     ///
     /// This uses the RETE-style conclusion index for O(1) lookup
@@ -701,4 +739,29 @@ mod tests {
             "Query should be provable with log action"
         );
     }
+
+    #[test]
+    fn test_query_aggregate_goal() {
+        let kb = KnowledgeBase::new("test");
+        let mut engine = BackwardEngine::new(kb);
+        let mut facts = Facts::new();
+        for id in 1..=6 {
+            let status = if id % 3 == 0 { "closed" } else { "open" };
+            facts.set(
+                &format!("Order.{}.status", id),
+                Value::String(status.to_string()),
+            );
+        }
+
+        let query = r#"count(Order where status == "open") > 3"#;
+        let result = engine.query(query, &mut facts).unwrap();
+        assert!(result.provable);
+        assert_eq!(result.value, Some(Value::Integer(4)));
+
+        // Not memoized: closing an order changes the outcome
+        facts.set("Order.1.status", Value::String("closed".to_string()));
+        let result = engine.query(query, &mut facts).unwrap();
+        assert!(!result.provable);
+        assert_eq!(result.value, Some(Value::Integer(3)));
+    }
 }
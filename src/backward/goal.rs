@@ -47,6 +47,10 @@ pub enum GoalStatus {
 
     /// Goal cannot be proven with available rules/facts
     Unprovable,
+
+    /// Goal reappeared on its own proof path (e.g. X needs Y and Y needs X)
+    /// and was treated as failed
+    Cyclic,
 }
 
 impl Goal {
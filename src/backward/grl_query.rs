@@ -706,6 +706,7 @@ impl GRLQueryExecutor {
                 missing_facts: Vec::new(),
                 stats: QueryStats::default(),
                 solutions: Vec::new(),
+                value: None,
             });
         }
 
@@ -787,6 +788,7 @@ impl GRLQueryExecutor {
             missing_facts: all_missing,
             stats: combined_stats,
             solutions: Vec::new(),
+            value: None,
         })
     }
 
@@ -849,6 +851,7 @@ impl GRLQueryExecutor {
             missing_facts: all_missing,
             stats: combined_stats,
             solutions: all_solutions,
+            value: None,
         })
     }
 
@@ -935,6 +938,7 @@ impl GRLQueryExecutor {
             missing_facts: all_missing,
             stats: combined_stats,
             solutions: all_solutions,
+            value: None,
         })
     }
 
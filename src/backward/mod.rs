@@ -40,7 +40,10 @@ pub mod search;
 pub mod unification;
 
 // Re-export main types
-pub use aggregation::{apply_aggregate, parse_aggregate_query, AggregateFunction, AggregateQuery};
+pub use aggregation::{
+    apply_aggregate, parse_aggregate_goal, parse_aggregate_query, AggregateFunction, AggregateGoal,
+    AggregateQuery,
+};
 pub use backward_engine::{BackwardConfig, BackwardEngine};
 pub use conclusion_index::{ConclusionIndex, IndexStats};
 pub use disjunction::{Disjunction, DisjunctionParser, DisjunctionResult};
@@ -25,6 +25,9 @@ pub struct QueryResult {
 
     /// All solutions found (when max_solutions > 1)
     pub solutions: Vec<Solution>,
+
+    /// Computed value of an aggregate goal such as `count(Order) > 5`
+    pub value: Option<Value>,
 }
 
 /// Trace showing how a goal was proven
@@ -79,6 +82,7 @@ impl QueryResult {
             missing_facts: Vec::new(),
             stats,
             solutions: Vec::new(),
+            value: None,
         }
     }
 
@@ -96,6 +100,7 @@ impl QueryResult {
             missing_facts: Vec::new(),
             stats,
             solutions,
+            value: None,
         }
     }
 
@@ -108,6 +113,7 @@ impl QueryResult {
             missing_facts: missing,
             stats,
             solutions: Vec::new(),
+            value: None,
         }
     }
 }
@@ -352,6 +352,34 @@ impl RuleExecutor {
 
                 Ok(())
             }
+
+            ActionType::Halt => {
+                // Backward chaining runs one rule at a time, nothing to stop
+                Ok(())
+            }
+
+            ActionType::Emit { .. } => {
+                // Emitted events are not collected in backward chaining
+                Ok(())
+            }
+
+            ActionType::AddScore { target, amount } => {
+                let current = facts
+                    .get_nested(target)
+                    .or_else(|| facts.get(target))
+                    .and_then(|value| value.to_number())
+                    .unwrap_or(0.0);
+                let total = Value::Number(current + amount);
+                if facts.set_nested(target, total.clone()).is_err() {
+                    facts.set(target, total);
+                }
+                Ok(())
+            }
+
+            ActionType::CallAndBind { .. } => {
+                // Registered functions live in the forward engine
+                Ok(())
+            }
         }
     }
 
@@ -581,6 +609,28 @@ mod tests {
         assert_eq!(facts.get("User.IsVIP"), Some(Value::Boolean(true)));
     }
 
+    #[test]
+    fn test_execute_add_score_on_nested_target() {
+        let kb = KnowledgeBase::new("test");
+        let executor = RuleExecutor::new(kb);
+
+        let mut facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![("score".to_string(), Value::Integer(10))]),
+            )
+            .unwrap();
+
+        let action = ActionType::AddScore {
+            target: "User.score".to_string(),
+            amount: 5.0,
+        };
+        executor.execute_action(None, &action, &mut facts).unwrap();
+
+        assert_eq!(facts.get_nested("User.score"), Some(Value::Number(15.0)));
+    }
+
     #[test]
     fn test_evaluate_compound_and_condition() {
         let kb = KnowledgeBase::new("test");
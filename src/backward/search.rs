@@ -59,6 +59,9 @@ pub struct SearchResult {
 
     /// All solutions found (if max_solutions > 1)
     pub solutions: Vec<Solution>,
+
+    /// Goals abandoned because they reappeared on their own proof path
+    pub cyclic_goals: Vec<String>,
 }
 
 impl SearchResult {
@@ -71,6 +74,7 @@ impl SearchResult {
             max_depth_reached: max_depth,
             bindings: std::collections::HashMap::new(),
             solutions: Vec::new(),
+            cyclic_goals: Vec::new(),
         }
     }
 
@@ -83,6 +87,7 @@ impl SearchResult {
             max_depth_reached: max_depth,
             bindings: std::collections::HashMap::new(),
             solutions: Vec::new(),
+            cyclic_goals: Vec::new(),
         }
     }
 }
@@ -96,6 +101,10 @@ pub struct DepthFirstSearch {
     max_solutions: usize,
     solutions: Vec<Solution>,
     proof_graph: Option<SharedProofGraph>,
+    /// Patterns of the goals on the current proof path
+    goal_stack: Vec<String>,
+    /// Goals found to depend on themselves during the current search
+    cyclic_goals: Vec<String>,
 }
 
 impl DepthFirstSearch {
@@ -109,6 +118,8 @@ impl DepthFirstSearch {
             max_solutions: 1,
             solutions: Vec::new(),
             proof_graph: None,
+            goal_stack: Vec::new(),
+            cyclic_goals: Vec::new(),
         }
     }
 
@@ -178,6 +189,8 @@ impl DepthFirstSearch {
             max_solutions: 1,
             solutions: Vec::new(),
             proof_graph,
+            goal_stack: Vec::new(),
+            cyclic_goals: Vec::new(),
         }
     }
 
@@ -191,6 +204,8 @@ impl DepthFirstSearch {
         self.goals_explored = 0;
         self.path.clear();
         self.solutions.clear();
+        self.goal_stack.clear();
+        self.cyclic_goals.clear();
 
         let success = self.search_recursive_with_execution(goal, facts, kb, 0);
 
@@ -201,6 +216,7 @@ impl DepthFirstSearch {
             max_depth_reached: goal.depth,
             bindings: goal.bindings.to_map(),
             solutions: self.solutions.clone(),
+            cyclic_goals: self.cyclic_goals.clone(),
         }
     }
 
@@ -218,6 +234,7 @@ impl DepthFirstSearch {
             max_depth_reached: goal.depth,
             bindings: goal.bindings.to_map(),
             solutions: Vec::new(),
+            cyclic_goals: Vec::new(),
         }
     }
 
@@ -257,15 +274,32 @@ impl DepthFirstSearch {
             }
         }
 
-        // Check for cycles
-        if goal.status == GoalStatus::InProgress {
-            goal.status = GoalStatus::Unprovable;
+        // A goal already on the current proof path could only prove itself
+        if goal.status == GoalStatus::InProgress || self.goal_stack.contains(&goal.pattern) {
+            goal.status = GoalStatus::Cyclic;
+            if !self.cyclic_goals.contains(&goal.pattern) {
+                self.cyclic_goals.push(goal.pattern.clone());
+            }
             return false;
         }
 
         goal.status = GoalStatus::InProgress;
         goal.depth = depth;
 
+        self.goal_stack.push(goal.pattern.clone());
+        let proven = self.expand_goal_with_execution(goal, facts, kb, depth);
+        self.goal_stack.pop();
+        proven
+    }
+
+    /// Try the candidate rules and sub-goals of a goal on the proof path
+    fn expand_goal_with_execution(
+        &mut self,
+        goal: &mut Goal,
+        facts: &mut Facts,
+        kb: &KnowledgeBase,
+        depth: usize,
+    ) -> bool {
         // Try each candidate rule
         for rule_name in goal.candidate_rules.clone() {
             self.path.push(rule_name.clone());
@@ -557,12 +591,18 @@ impl DepthFirstSearch {
                         self.try_prove_condition_group(left, facts, kb, depth)
                             || self.try_prove_condition_group(right, facts, kb, depth)
                     }
+                    LogicalOperator::Xor => {
+                        // Exactly one must be proven
+                        self.try_prove_condition_group(left, facts, kb, depth)
+                            != self.try_prove_condition_group(right, facts, kb, depth)
+                    }
                     LogicalOperator::Not => {
                         // Left should fail, right doesn't apply
                         !self.try_prove_condition_group(left, facts, kb, depth)
                     }
                 }
             }
+            ConditionGroup::Constant(value) => *value,
             ConditionGroup::Not(_)
             | ConditionGroup::Exists(_)
             | ConditionGroup::Forall(_)
@@ -626,6 +666,7 @@ impl DepthFirstSearch {
             ConditionExpression::FunctionCall { name, .. } => name.clone(),
             ConditionExpression::Test { name, .. } => format!("test({})", name),
             ConditionExpression::MultiField { field, .. } => field.clone(),
+            ConditionExpression::Bind { field, .. } => field.clone(),
         };
 
         let op_str = match condition.operator {
@@ -641,6 +682,16 @@ impl DepthFirstSearch {
             crate::types::Operator::EndsWith => "ends_with",
             crate::types::Operator::Matches => "matches",
             crate::types::Operator::In => "in",
+            crate::types::Operator::NotIn => "not in",
+            crate::types::Operator::ContainsAll => "contains_all",
+            crate::types::Operator::ContainsAny => "contains_any",
+            crate::types::Operator::IsNumber => "is_number",
+            crate::types::Operator::IsString => "is_string",
+            crate::types::Operator::IsBoolean => "is_boolean",
+            crate::types::Operator::IsArray => "is_array",
+            crate::types::Operator::IsObject => "is_object",
+            crate::types::Operator::IsNull => "is_null",
+            crate::types::Operator::IsNotNull => "is_not_null",
         };
 
         // Convert value to string format that matches goal patterns
@@ -976,6 +1027,7 @@ impl BreadthFirstSearch {
             max_depth_reached: max_depth,
             bindings: root_goal.bindings.to_map(),
             solutions: Vec::new(),
+            cyclic_goals: Vec::new(),
         }
     }
 
@@ -1148,6 +1200,7 @@ impl BreadthFirstSearch {
             max_depth_reached: max_depth,
             bindings: root_goal.bindings.to_map(),
             solutions: Vec::new(),
+            cyclic_goals: Vec::new(),
         }
     }
 }
@@ -1322,6 +1375,7 @@ mod tests {
             max_depth_reached: 3,
             bindings: bindings.clone(),
             solutions: Vec::new(),
+            cyclic_goals: Vec::new(),
         };
 
         assert_eq!(result.bindings.len(), 2);
@@ -1397,4 +1451,45 @@ mod tests {
         // Goals explored count is tracked (always >= 0 since it's usize)
         assert!(result.goals_explored > 0);
     }
+
+    #[test]
+    fn test_depth_first_search_detects_mutual_recursion() {
+        let kb = KnowledgeBase::new("cyclic");
+        kb.add_rules_from_grl(
+            r#"
+            rule "DeriveX" {
+                when
+                    Fact.Y == true
+                then
+                    Fact.X = true;
+            }
+
+            rule "DeriveY" {
+                when
+                    Fact.X == true
+                then
+                    Fact.Y = true;
+            }
+            "#,
+        )
+        .unwrap();
+
+        let mut facts = Facts::new();
+        facts
+            .add_value("Fact", Facts::create_object(vec![]))
+            .unwrap();
+
+        let mut goal = Goal::new("Fact.X == true".to_string());
+        goal.add_candidate_rule("DeriveX".to_string());
+
+        let mut dfs = DepthFirstSearch::new(50, kb.clone());
+        let result = dfs.search_with_execution(&mut goal, &mut facts, &kb);
+
+        // X needs Y, Y needs X again: the second X is cut off instead of
+        // recursing until max_depth
+        assert!(!result.success);
+        assert_eq!(result.cyclic_goals, vec!["Fact.X == true".to_string()]);
+        assert!(result.goals_explored <= 3);
+        assert_eq!(facts.get_nested("Fact.X"), None);
+    }
 }
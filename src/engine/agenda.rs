@@ -1,8 +1,47 @@
 #![allow(deprecated)]
 
 use crate::engine::rule::Rule;
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 
+/// Conflict resolution strategy for ordering rules with equal salience
+///
+/// Salience always wins; the strategy only breaks ties (CLIPS-style).
+/// Recency is approximated by load order in the knowledge base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictStrategy {
+    /// Most recently loaded rules first
+    Depth,
+    /// Earliest loaded rules first (historical behavior)
+    #[default]
+    Breadth,
+    /// Rules with fewer conditions first
+    Simplicity,
+    /// Rules with more conditions (higher specificity) first
+    Complexity,
+}
+
+impl ConflictStrategy {
+    /// Compare two rules of equal salience, given their load positions
+    pub fn compare(&self, a: &Rule, a_pos: usize, b: &Rule, b_pos: usize) -> Ordering {
+        let by_load = a_pos.cmp(&b_pos);
+        match self {
+            ConflictStrategy::Depth => by_load.reverse(),
+            ConflictStrategy::Breadth => by_load,
+            ConflictStrategy::Simplicity => a
+                .conditions
+                .condition_count()
+                .cmp(&b.conditions.condition_count())
+                .then(by_load),
+            ConflictStrategy::Complexity => b
+                .conditions
+                .condition_count()
+                .cmp(&a.conditions.condition_count())
+                .then(by_load),
+        }
+    }
+}
+
 /// Manages agenda groups for workflow control
 #[derive(Debug, Clone)]
 pub struct AgendaManager {
@@ -14,6 +53,10 @@ pub struct AgendaManager {
     activated_groups: HashSet<String>,
     /// Rules fired per agenda group activation (for lock-on-active)
     fired_rules_per_activation: HashMap<String, HashSet<String>>,
+    /// Return focus to the previous group when the focused one is exhausted
+    fallthrough: bool,
+    /// Base salience added to the salience of every rule in a group
+    group_salience: HashMap<String, i32>,
 }
 
 impl Default for AgendaManager {
@@ -30,9 +73,34 @@ impl AgendaManager {
             focus_stack: vec!["MAIN".to_string()],
             activated_groups: HashSet::new(),
             fired_rules_per_activation: HashMap::new(),
+            fallthrough: false,
+            group_salience: HashMap::new(),
         }
     }
 
+    /// Give every rule in `group` a base salience added to its own
+    pub fn with_group_salience(mut self, group: &str, base: i32) -> Self {
+        self.set_group_salience(group, base);
+        self
+    }
+
+    /// Set the base salience added to every rule in `group`
+    pub fn set_group_salience(&mut self, group: &str, base: i32) {
+        self.group_salience.insert(group.to_string(), base);
+    }
+
+    /// Base salience of the rule's agenda group (MAIN for rules without one)
+    pub fn group_salience(&self, rule: &Rule) -> i32 {
+        let group = rule.agenda_group.as_deref().unwrap_or("MAIN");
+        self.group_salience.get(group).copied().unwrap_or(0)
+    }
+
+    /// Enable or disable falling through to MAIN when a focused group is exhausted
+    pub fn with_fallthrough(mut self, fallthrough: bool) -> Self {
+        self.fallthrough = fallthrough;
+        self
+    }
+
     /// Set focus to a specific agenda group
     pub fn set_focus(&mut self, group: &str) {
         let group = group.to_string();
@@ -119,6 +187,15 @@ impl AgendaManager {
         }
     }
 
+    /// Leave the focused group after a cycle in which none of its rules fired
+    ///
+    /// Only applies when fall-through is enabled. Focus returns to the previous
+    /// group on the stack, which is ultimately MAIN. Returns `true` if focus
+    /// moved and the caller should evaluate another cycle.
+    pub fn fall_through(&mut self) -> bool {
+        self.fallthrough && self.pop_focus().is_some()
+    }
+
     /// Clear all focus and return to MAIN
     pub fn clear_focus(&mut self) {
         self.focus_stack.clear();
@@ -315,4 +392,72 @@ mod tests {
         manager.set_focus("MAIN");
         assert!(manager.can_fire_rule(&rule));
     }
+
+    #[test]
+    fn test_conflict_strategy_ordering() {
+        let cond = |n: usize| {
+            (1..n).fold(create_dummy_condition(), |acc, _| {
+                ConditionGroup::and(acc, create_dummy_condition())
+            })
+        };
+        let rules = [
+            Rule::new("One".to_string(), cond(1), vec![]),
+            Rule::new("Three".to_string(), cond(3), vec![]),
+            Rule::new("Two".to_string(), cond(2), vec![]),
+        ];
+
+        let order = |strategy: ConflictStrategy| -> Vec<&str> {
+            let mut indices: Vec<usize> = (0..rules.len()).collect();
+            indices.sort_by(|&a, &b| strategy.compare(&rules[a], a, &rules[b], b));
+            indices.iter().map(|&i| rules[i].name.as_str()).collect()
+        };
+
+        assert_eq!(
+            order(ConflictStrategy::Breadth),
+            vec!["One", "Three", "Two"]
+        );
+        assert_eq!(order(ConflictStrategy::Depth), vec!["Two", "Three", "One"]);
+        assert_eq!(
+            order(ConflictStrategy::Simplicity),
+            vec!["One", "Two", "Three"]
+        );
+        assert_eq!(
+            order(ConflictStrategy::Complexity),
+            vec!["Three", "Two", "One"]
+        );
+    }
+
+    #[test]
+    fn test_fall_through_pops_to_main_only_when_enabled() {
+        let mut manager = AgendaManager::new();
+        manager.set_focus("validation");
+        assert!(!manager.fall_through());
+        assert_eq!(manager.get_active_group(), "validation");
+
+        let mut manager = AgendaManager::new().with_fallthrough(true);
+        manager.set_focus("validation");
+        manager.set_focus("processing");
+        assert!(manager.fall_through());
+        assert_eq!(manager.get_active_group(), "validation");
+        assert!(manager.fall_through());
+        assert_eq!(manager.get_active_group(), "MAIN");
+        assert!(!manager.fall_through());
+    }
+
+    #[test]
+    fn test_group_salience_defaults_to_zero() {
+        let manager = AgendaManager::new()
+            .with_group_salience("validation", 50)
+            .with_group_salience("MAIN", -10);
+
+        let grouped = Rule::new("Grouped".to_string(), create_dummy_condition(), vec![])
+            .with_agenda_group("validation".to_string());
+        let ungrouped = Rule::new("Ungrouped".to_string(), create_dummy_condition(), vec![]);
+        let other = Rule::new("Other".to_string(), create_dummy_condition(), vec![])
+            .with_agenda_group("billing".to_string());
+
+        assert_eq!(manager.group_salience(&grouped), 50);
+        assert_eq!(manager.group_salience(&ungrouped), -10);
+        assert_eq!(manager.group_salience(&other), 0);
+    }
 }
@@ -3,8 +3,14 @@ use serde::{Deserialize, Serialize};
 /// This module provides comprehensive metrics collection, analysis,
 /// and performance insights for rule execution.
 use std::collections::HashMap;
+use std::fmt::Write;
 use std::time::{Duration, SystemTime};
 
+/// Upper bounds (in seconds) of the evaluation duration histogram buckets
+pub const DURATION_BUCKETS: [f64; 11] = [
+    0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+];
+
 /// Trend direction for performance metrics
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TrendDirection {
@@ -41,6 +47,14 @@ pub struct RuleMetrics {
     pub last_executed: Option<SystemTime>,
     /// Recent execution times (for trend analysis)
     pub recent_execution_times: Vec<Duration>,
+    /// Number of executions per [`DURATION_BUCKETS`] bound (not cumulative)
+    #[serde(default)]
+    pub duration_buckets: Vec<u64>,
+    /// Cumulative evaluation time per condition, keyed by the condition's
+    /// position in the rule (depth-first, left to right); only filled when
+    /// [`AnalyticsConfig::condition_profiling`] is on
+    #[serde(default)]
+    pub condition_times: HashMap<usize, Duration>,
 }
 
 impl RuleMetrics {
@@ -58,6 +72,8 @@ impl RuleMetrics {
             estimated_memory_usage: 0,
             last_executed: None,
             recent_execution_times: Vec::new(),
+            duration_buckets: vec![0; DURATION_BUCKETS.len()],
+            condition_times: HashMap::new(),
         }
     }
 
@@ -69,6 +85,7 @@ impl RuleMetrics {
         }
         self.total_successes += 1;
         self.total_execution_time += duration;
+        self.observe_duration(duration);
 
         // Update min/max times
         if duration < self.min_execution_time {
@@ -93,9 +110,36 @@ impl RuleMetrics {
         self.total_evaluations += 1;
         self.total_failures += 1;
         self.total_execution_time += duration;
+        self.observe_duration(duration);
         self.last_executed = Some(SystemTime::now());
     }
 
+    /// Add one evaluation's per-condition timings to the cumulative totals
+    pub fn record_condition_times(&mut self, timings: &[Duration]) {
+        for (index, duration) in timings.iter().enumerate() {
+            *self.condition_times.entry(index).or_insert(Duration::ZERO) += *duration;
+        }
+    }
+
+    /// Index and cumulative time of the most expensive condition
+    pub fn slowest_condition(&self) -> Option<(usize, Duration)> {
+        self.condition_times
+            .iter()
+            .max_by_key(|(index, duration)| (**duration, std::cmp::Reverse(**index)))
+            .map(|(index, duration)| (*index, *duration))
+    }
+
+    /// Count an execution in the first bucket whose bound it does not exceed
+    fn observe_duration(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        if let Some(index) = DURATION_BUCKETS.iter().position(|le| seconds <= *le) {
+            if self.duration_buckets.len() < DURATION_BUCKETS.len() {
+                self.duration_buckets.resize(DURATION_BUCKETS.len(), 0);
+            }
+            self.duration_buckets[index] += 1;
+        }
+    }
+
     /// Calculate average execution time
     pub fn avg_execution_time(&self) -> Duration {
         if self.total_evaluations > 0 {
@@ -146,6 +190,8 @@ pub struct AnalyticsConfig {
     pub retention_period: Duration,
     /// Maximum number of recent execution times to keep per rule
     pub max_recent_samples: usize,
+    /// Whether to time each condition of a rule separately
+    pub condition_profiling: bool,
 }
 
 impl Default for AnalyticsConfig {
@@ -157,6 +203,7 @@ impl Default for AnalyticsConfig {
             sampling_rate: 1.0,
             retention_period: Duration::from_secs(7 * 24 * 60 * 60), // 7 days
             max_recent_samples: 100,
+            condition_profiling: false,
         }
     }
 }
@@ -171,6 +218,7 @@ impl AnalyticsConfig {
             sampling_rate: 0.1, // Sample 10% of executions
             retention_period: Duration::from_secs(24 * 60 * 60), // 1 day
             max_recent_samples: 50,
+            condition_profiling: false,
         }
     }
 
@@ -183,6 +231,7 @@ impl AnalyticsConfig {
             sampling_rate: 1.0,                             // Track everything
             retention_period: Duration::from_secs(60 * 60), // 1 hour
             max_recent_samples: 100,
+            condition_profiling: true,
         }
     }
 }
@@ -287,6 +336,22 @@ impl RuleAnalytics {
         self.cleanup_old_data();
     }
 
+    /// Whether per-condition timings should be collected
+    pub fn profiles_conditions(&self) -> bool {
+        self.config.condition_profiling
+    }
+
+    /// Record the per-condition timings of one rule evaluation
+    pub fn record_condition_times(&mut self, rule_name: &str, timings: &[Duration]) {
+        if !self.config.condition_profiling || timings.is_empty() {
+            return;
+        }
+        self.rule_metrics
+            .entry(rule_name.to_string())
+            .or_insert_with(|| RuleMetrics::new(rule_name.to_string()))
+            .record_condition_times(timings);
+    }
+
     /// Get metrics for a specific rule
     pub fn get_rule_metrics(&self, rule_name: &str) -> Option<&RuleMetrics> {
         self.rule_metrics.get(rule_name)
@@ -449,6 +514,80 @@ impl RuleAnalytics {
     pub fn get_overall_stats(&self) -> OverallStats {
         self.overall_stats()
     }
+
+    /// Render the per-rule metrics in the Prometheus text exposition format
+    ///
+    /// Emits the `rule_eval_total` and `rule_fires_total` counters and the
+    /// `rule_eval_duration_seconds` histogram, each labelled with `rule`.
+    pub fn to_prometheus(&self) -> String {
+        let mut rules: Vec<&RuleMetrics> = self.rule_metrics.values().collect();
+        rules.sort_by(|a, b| a.rule_name.cmp(&b.rule_name));
+
+        let mut out = String::new();
+
+        out.push_str("# HELP rule_eval_total Total number of rule evaluations.\n");
+        out.push_str("# TYPE rule_eval_total counter\n");
+        for metrics in &rules {
+            let _ = writeln!(
+                out,
+                "rule_eval_total{{rule=\"{}\"}} {}",
+                escape_label(&metrics.rule_name),
+                metrics.total_evaluations
+            );
+        }
+
+        out.push_str("# HELP rule_fires_total Total number of times a rule fired.\n");
+        out.push_str("# TYPE rule_fires_total counter\n");
+        for metrics in &rules {
+            let _ = writeln!(
+                out,
+                "rule_fires_total{{rule=\"{}\"}} {}",
+                escape_label(&metrics.rule_name),
+                metrics.total_fires
+            );
+        }
+
+        out.push_str("# HELP rule_eval_duration_seconds Rule evaluation duration in seconds.\n");
+        out.push_str("# TYPE rule_eval_duration_seconds histogram\n");
+        for metrics in &rules {
+            let rule = escape_label(&metrics.rule_name);
+            let mut cumulative = 0;
+            for (index, le) in DURATION_BUCKETS.iter().enumerate() {
+                cumulative += metrics.duration_buckets.get(index).copied().unwrap_or(0);
+                let _ = writeln!(
+                    out,
+                    "rule_eval_duration_seconds_bucket{{rule=\"{}\",le=\"{}\"}} {}",
+                    rule, le, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "rule_eval_duration_seconds_bucket{{rule=\"{}\",le=\"+Inf\"}} {}",
+                rule, metrics.total_evaluations
+            );
+            let _ = writeln!(
+                out,
+                "rule_eval_duration_seconds_sum{{rule=\"{}\"}} {}",
+                rule,
+                metrics.total_execution_time.as_secs_f64()
+            );
+            let _ = writeln!(
+                out,
+                "rule_eval_duration_seconds_count{{rule=\"{}\"}} {}",
+                rule, metrics.total_evaluations
+            );
+        }
+
+        out
+    }
+}
+
+/// Escape a Prometheus label value
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
 }
 
 /// Overall performance statistics
@@ -519,4 +658,65 @@ mod tests {
         assert_eq!(analytics.total_executions, 1);
         assert!(analytics.get_rule_metrics("TestRule").is_some());
     }
+
+    #[test]
+    fn test_to_prometheus() {
+        let mut analytics = RuleAnalytics::new(AnalyticsConfig::development());
+        analytics.record_execution("Discount", Duration::from_millis(2), true, true, None, 0);
+        analytics.record_execution("Discount", Duration::from_millis(20), false, true, None, 0);
+        analytics.record_execution("Say \"hi\"", Duration::from_secs(2), true, true, None, 0);
+
+        let text = analytics.to_prometheus();
+
+        assert!(text.contains("# TYPE rule_eval_total counter"));
+        assert!(text.contains("rule_eval_total{rule=\"Discount\"} 2"));
+        assert!(text.contains("rule_fires_total{rule=\"Discount\"} 1"));
+        assert!(text.contains("# TYPE rule_eval_duration_seconds histogram"));
+        assert!(
+            text.contains("rule_eval_duration_seconds_bucket{rule=\"Discount\",le=\"0.001\"} 0")
+        );
+        assert!(
+            text.contains("rule_eval_duration_seconds_bucket{rule=\"Discount\",le=\"0.005\"} 1")
+        );
+        assert!(
+            text.contains("rule_eval_duration_seconds_bucket{rule=\"Discount\",le=\"0.025\"} 2")
+        );
+        assert!(text.contains("rule_eval_duration_seconds_bucket{rule=\"Discount\",le=\"+Inf\"} 2"));
+        assert!(text.contains("rule_eval_duration_seconds_sum{rule=\"Discount\"} 0.022"));
+        assert!(text.contains("rule_eval_duration_seconds_count{rule=\"Discount\"} 2"));
+
+        // Label values are escaped and slow evaluations only land in +Inf
+        assert!(text.contains("rule_fires_total{rule=\"Say \\\"hi\\\"\"} 1"));
+        assert!(
+            text.contains("rule_eval_duration_seconds_bucket{rule=\"Say \\\"hi\\\"\",le=\"1\"} 0")
+        );
+        assert!(text
+            .contains("rule_eval_duration_seconds_bucket{rule=\"Say \\\"hi\\\"\",le=\"+Inf\"} 1"));
+    }
+
+    #[test]
+    fn test_condition_times_accumulate_per_index() {
+        let mut analytics = RuleAnalytics::new(AnalyticsConfig::development());
+        analytics.record_condition_times(
+            "Slow",
+            &[Duration::from_millis(1), Duration::from_millis(8)],
+        );
+        analytics.record_condition_times(
+            "Slow",
+            &[Duration::from_millis(2), Duration::from_millis(4)],
+        );
+
+        let metrics = analytics.get_rule_metrics("Slow").unwrap();
+        assert_eq!(metrics.condition_times[&0], Duration::from_millis(3));
+        assert_eq!(metrics.condition_times[&1], Duration::from_millis(12));
+        assert_eq!(
+            metrics.slowest_condition(),
+            Some((1, Duration::from_millis(12)))
+        );
+
+        // Timings are ignored unless profiling is on
+        let mut analytics = RuleAnalytics::new(AnalyticsConfig::production());
+        analytics.record_condition_times("Slow", &[Duration::from_millis(1)]);
+        assert!(analytics.get_rule_metrics("Slow").is_none());
+    }
 }
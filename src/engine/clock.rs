@@ -0,0 +1,107 @@
+//! Injectable time source
+//!
+//! The engine reads the current time through a [`Clock`] held in
+//! [`EngineConfig`](crate::engine::EngineConfig). While rules run, that clock
+//! also backs `now()` and `today()` in expressions and the date plugin, so a
+//! [`MockClock`] makes time-dependent rules deterministic.
+
+use chrono::{DateTime, Utc};
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Source of the current time
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Current time in UTC
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock reading the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that only moves when told to, for tests
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Create a clock stopped at `now`
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Move the clock to `now`
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: chrono::Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Current time from the clock of the engine running on this thread, or the
+/// system time outside of a run
+pub fn now() -> DateTime<Utc> {
+    ACTIVE
+        .with(|active| active.borrow().as_ref().map(|clock| clock.now()))
+        .unwrap_or_else(Utc::now)
+}
+
+/// Make `clock` the active clock on this thread until the guard is dropped
+pub(crate) fn activate(clock: Arc<dyn Clock>) -> ActiveClockGuard {
+    let previous = ACTIVE.with(|active| active.replace(Some(clock)));
+    ActiveClockGuard { previous }
+}
+
+/// Restores the previously active clock when dropped
+pub(crate) struct ActiveClockGuard {
+    previous: Option<Arc<dyn Clock>>,
+}
+
+impl Drop for ActiveClockGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        ACTIVE.with(|active| *active.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_active_clock_is_scoped() {
+        let fixed = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let clock = Arc::new(MockClock::new(fixed));
+        {
+            let _guard = activate(clock.clone());
+            assert_eq!(now(), fixed);
+            clock.advance(chrono::Duration::hours(1));
+            assert_eq!(now(), fixed + chrono::Duration::hours(1));
+        }
+        assert_ne!(now(), fixed + chrono::Duration::hours(1));
+    }
+}
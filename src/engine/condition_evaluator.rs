@@ -67,12 +67,18 @@ impl ConditionEvaluator {
                         }
                         self.evaluate_conditions(right, facts)
                     }
+                    // Both sides are always needed
+                    crate::types::LogicalOperator::Xor => {
+                        Ok(left_result != self.evaluate_conditions(right, facts)?)
+                    }
                     crate::types::LogicalOperator::Not => Err(RuleEngineError::ExecutionError(
                         "NOT operator should not appear in compound conditions".to_string(),
                     )),
                 }
             }
 
+            ConditionGroup::Constant(value) => Ok(*value),
+
             ConditionGroup::Not(inner) => {
                 let result = self.evaluate_conditions(inner, facts)?;
                 Ok(!result)
@@ -111,13 +117,18 @@ impl ConditionEvaluator {
                     .get_nested(field_name)
                     .or_else(|| facts.get(field_name))
                 {
-                    Ok(condition.operator.evaluate(&value, &condition.value))
+                    Ok(facts.evaluate_operator(
+                        field_name,
+                        &condition.operator,
+                        &value,
+                        &condition.value,
+                    ))
                 } else {
                     // Field not found
                     // For some operators like NotEqual, this might be true
                     match condition.operator {
-                        Operator::NotEqual => {
-                            // null != value is true
+                        Operator::NotEqual | Operator::IsNull => {
+                            // null != value is true, and a missing field is null
                             Ok(true)
                         }
                         _ => Ok(false),
@@ -125,6 +136,16 @@ impl ConditionEvaluator {
                 }
             }
 
+            ConditionExpression::Bind { variable, field } => {
+                match facts.get_nested(field).or_else(|| facts.get(field)) {
+                    Some(Value::Null) | None => Ok(false),
+                    Some(value) => {
+                        facts.set(variable, value);
+                        Ok(true)
+                    }
+                }
+            }
+
             ConditionExpression::FunctionCall { name, args } => {
                 self.evaluate_function_call(name, args, condition, facts)
             }
@@ -215,6 +236,7 @@ impl ConditionEvaluator {
                     let len = match &arg_values[0] {
                         Value::String(s) => s.len() as f64,
                         Value::Array(arr) => arr.len() as f64,
+                        Value::Bytes(bytes) => bytes.len() as f64,
                         _ => return Ok(false),
                     };
 
@@ -527,4 +549,20 @@ mod tests {
         let result = evaluator.evaluate_condition(&condition, &facts).unwrap();
         assert!(result);
     }
+
+    #[test]
+    fn test_builtin_function_len_over_bytes() {
+        let evaluator = ConditionEvaluator::with_builtin_functions();
+        let facts = Facts::new();
+        facts.set("Token.Digest", Value::Bytes(vec![0u8; 32]));
+
+        let condition = Condition::with_function(
+            "len".to_string(),
+            vec!["Token.Digest".to_string()],
+            Operator::Equal,
+            Value::Number(32.0),
+        );
+
+        assert!(evaluator.evaluate_condition(&condition, &facts).unwrap());
+    }
 }
@@ -77,6 +77,7 @@ fn flatten_conditions(
         | ConditionGroup::Forall(inner) => {
             out.extend(flatten_conditions(inner));
         }
+        ConditionGroup::Constant(_) => {}
         ConditionGroup::Accumulate { .. } => {
             // Accumulate doesn't have simple single conditions to flatten
             // Skip for now
@@ -49,13 +49,59 @@ impl DependencyAnalyzer {
         let execution_groups = self.create_execution_groups(rules);
         let conflicts_len = conflicts.len();
 
+        // Expose per-rule read/write sets and the data-flow edges between them
+        let field_access = self.collect_field_access(rules);
+        let edges = Self::collect_edges(&field_access);
+
         DependencyAnalysisResult {
             total_rules: rules.len(),
             conflicts: conflicts_len,
             conflict_details: conflicts,
             execution_groups,
             can_parallelize_safely: conflicts_len == 0,
+            field_access,
+            edges,
+        }
+    }
+
+    /// Collect the deduplicated read and write sets of each rule, in rule order
+    fn collect_field_access(&self, rules: &[Rule]) -> Vec<RuleFieldAccess> {
+        let dedup = |mut fields: Vec<String>| {
+            fields.retain(|f| !f.is_empty());
+            fields.sort();
+            fields.dedup();
+            fields
+        };
+
+        rules
+            .iter()
+            .map(|rule| RuleFieldAccess {
+                rule_name: rule.name.clone(),
+                reads: dedup(self.extract_condition_reads(rule)),
+                writes: dedup(self.extract_action_writes(rule)),
+            })
+            .collect()
+    }
+
+    /// Build writer -> reader edges for every field shared between rules
+    fn collect_edges(field_access: &[RuleFieldAccess]) -> Vec<DependencyEdge> {
+        let mut edges = Vec::new();
+
+        for reader in field_access {
+            for field in &reader.reads {
+                for writer in field_access {
+                    if writer.rule_name != reader.rule_name && writer.writes.contains(field) {
+                        edges.push(DependencyEdge {
+                            from: writer.rule_name.clone(),
+                            to: reader.rule_name.clone(),
+                            field: field.clone(),
+                        });
+                    }
+                }
+            }
         }
+
+        edges
     }
 
     /// Clear previous analysis
@@ -97,7 +143,7 @@ impl DependencyAnalyzer {
     }
 
     /// Recursively extract fields from condition groups
-    fn extract_fields_from_condition_group(
+    pub(crate) fn extract_fields_from_condition_group(
         condition_group: &crate::engine::rule::ConditionGroup,
         reads: &mut Vec<String>,
     ) {
@@ -109,6 +155,7 @@ impl DependencyAnalyzer {
                 Self::extract_fields_from_condition_group(left, reads);
                 Self::extract_fields_from_condition_group(right, reads);
             }
+            crate::engine::rule::ConditionGroup::Constant(_) => {}
             crate::engine::rule::ConditionGroup::Not(inner) => {
                 Self::extract_fields_from_condition_group(inner, reads);
             }
@@ -194,6 +241,16 @@ impl DependencyAnalyzer {
                 crate::types::ActionType::ScheduleRule { .. } => {}
                 crate::types::ActionType::CompleteWorkflow { .. } => {}
                 crate::types::ActionType::SetWorkflowData { .. } => {}
+                // Halt only affects control flow
+                crate::types::ActionType::Halt => {}
+                // Emitted events are collected in the result, not written to facts
+                crate::types::ActionType::Emit { .. } => {}
+                crate::types::ActionType::AddScore { target, .. } => {
+                    writes.push(target.clone());
+                }
+                crate::types::ActionType::CallAndBind { field, .. } => {
+                    writes.push(field.clone());
+                }
             }
         }
 
@@ -476,6 +533,32 @@ pub struct DependencyAnalysisResult {
     pub execution_groups: Vec<ExecutionGroup>,
     /// Whether rules can be safely parallelized
     pub can_parallelize_safely: bool,
+    /// Fields each rule reads and writes, in rule order
+    pub field_access: Vec<RuleFieldAccess>,
+    /// Data-flow edges: a rule writing a field another rule reads
+    pub edges: Vec<DependencyEdge>,
+}
+
+/// Fields a rule reads in its conditions and writes in its actions
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleFieldAccess {
+    /// Name of the rule
+    pub rule_name: String,
+    /// Fields referenced in conditions (read-set)
+    pub reads: Vec<String>,
+    /// Fields assigned in actions (write-set)
+    pub writes: Vec<String>,
+}
+
+/// Dependency edge between two rules
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyEdge {
+    /// Rule that writes the field
+    pub from: String,
+    /// Rule that reads the field
+    pub to: String,
+    /// Field connecting the two rules
+    pub field: String,
 }
 
 /// A conflict between rules
@@ -520,7 +603,7 @@ pub struct ExecutionGroup {
 }
 
 /// Execution mode recommendation
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ExecutionMode {
     /// Safe to run in parallel
     Parallel,
@@ -539,6 +622,9 @@ pub enum ExecutionStrategy {
     Hybrid,
     /// Forced sequential due to configuration
     ForcedSequential,
+    /// Decide per salience group: run it in parallel only when its rules
+    /// are conflict-free and the group is larger than the configured threshold
+    Auto,
 }
 
 impl DependencyAnalysisResult {
@@ -553,6 +639,45 @@ impl DependencyAnalysisResult {
         )
     }
 
+    /// Get the read-set of a rule
+    pub fn read_set(&self, rule_name: &str) -> Option<&[String]> {
+        self.field_access
+            .iter()
+            .find(|access| access.rule_name == rule_name)
+            .map(|access| access.reads.as_slice())
+    }
+
+    /// Get the write-set of a rule
+    pub fn write_set(&self, rule_name: &str) -> Option<&[String]> {
+        self.field_access
+            .iter()
+            .find(|access| access.rule_name == rule_name)
+            .map(|access| access.writes.as_slice())
+    }
+
+    /// Render the dependency graph in Graphviz DOT format
+    ///
+    /// Each rule is a node; an edge `A -> B` labelled with a field means rule A
+    /// writes a field that rule B reads.
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+
+        let mut dot = String::from("digraph rule_dependencies {\n    rankdir=LR;\n");
+        for access in &self.field_access {
+            dot.push_str(&format!("    {};\n", quote(&access.rule_name)));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "    {} -> {} [label={}];\n",
+                quote(&edge.from),
+                quote(&edge.to),
+                quote(&edge.field)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
     /// Get detailed report
     pub fn get_detailed_report(&self) -> String {
         let mut report = self.get_summary();
@@ -680,4 +805,56 @@ mod tests {
         // Should detect conflicts between score calculation and VIP check
         assert!(!result.can_parallelize_safely);
     }
+
+    #[test]
+    fn test_read_write_sets_and_dot_export() {
+        let mut analyzer = DependencyAnalyzer::new();
+
+        let rules = vec![
+            Rule::new(
+                "CalculateScore".to_string(),
+                ConditionGroup::Single(Condition::new(
+                    "User.Data".to_string(),
+                    crate::types::Operator::Equal,
+                    crate::types::Value::String("valid".to_string()),
+                )),
+                vec![crate::types::ActionType::Set {
+                    field: "User.Score".to_string(),
+                    value: crate::types::Value::Integer(85),
+                }],
+            ),
+            Rule::new(
+                "CheckVIPStatus".to_string(),
+                ConditionGroup::Single(Condition::new(
+                    "User.Score".to_string(),
+                    crate::types::Operator::GreaterThan,
+                    crate::types::Value::Integer(80),
+                )),
+                vec![],
+            ),
+        ];
+
+        let result = analyzer.analyze(&rules);
+        assert_eq!(
+            result.read_set("CalculateScore"),
+            Some(&["User.Data".to_string()][..])
+        );
+        assert_eq!(
+            result.write_set("CalculateScore"),
+            Some(&["User.Score".to_string()][..])
+        );
+        assert_eq!(result.write_set("CheckVIPStatus"), Some(&[] as &[String]));
+        assert_eq!(
+            result.edges,
+            vec![DependencyEdge {
+                from: "CalculateScore".to_string(),
+                to: "CheckVIPStatus".to_string(),
+                field: "User.Score".to_string(),
+            }]
+        );
+
+        let dot = result.to_dot();
+        assert!(dot.starts_with("digraph rule_dependencies {"));
+        assert!(dot.contains("\"CalculateScore\" -> \"CheckVIPStatus\" [label=\"User.Score\"];"));
+    }
 }
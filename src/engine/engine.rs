@@ -1,16 +1,22 @@
 use crate::engine::{
-    agenda::{ActivationGroupManager, AgendaManager},
+    agenda::{ActivationGroupManager, AgendaManager, ConflictStrategy},
     analytics::RuleAnalytics,
+    clock::{self, Clock, SystemClock},
+    event_bus::{Event, EventBus},
     facts::Facts,
-    knowledge_base::KnowledgeBase,
+    globals::GlobalStore,
+    knowledge_base::{rule_fingerprint, KnowledgeBase},
     plugin::{PluginConfig, PluginInfo, PluginManager, PluginStats},
+    preflight::{validate_rule, ValidationIssue},
+    provenance::{rule_reads, rule_writes, ProvenanceChain, ProvenanceTracker},
     workflow::WorkflowEngine,
 };
 use crate::errors::{Result, RuleEngineError};
 use crate::types::{ActionType, Operator, Value};
 use chrono::{DateTime, Utc};
-use log::info;
+use log::{debug, info, warn};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Type for custom function implementations
@@ -19,6 +25,26 @@ pub type CustomFunction = Box<dyn Fn(&[Value], &Facts) -> Result<Value> + Send +
 /// Type for custom action handlers
 pub type ActionHandler = Box<dyn Fn(&HashMap<String, Value>, &Facts) -> Result<()> + Send + Sync>;
 
+/// Type for async custom action handlers, awaited by `execute_async`
+///
+/// The handler receives the action's resolved parameters and returns a future
+/// that must not borrow them.
+#[cfg(feature = "async")]
+pub type AsyncActionHandler = Box<
+    dyn Fn(
+            HashMap<String, Value>,
+        ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Future returned by an async action handler
+#[cfg(feature = "async")]
+type PendingAction = std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send>>;
+
+/// Type for runtime rule enable predicates, evaluated against facts before each run
+pub type RuleEnablePredicate = Box<dyn Fn(&Facts) -> bool + Send + Sync>;
+
 /// Configuration options for the rule engine
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
@@ -30,6 +56,36 @@ pub struct EngineConfig {
     pub enable_stats: bool,
     /// Enable debug mode with verbose logging
     pub debug_mode: bool,
+    /// How rules with equal salience are ordered on the agenda
+    pub conflict_strategy: ConflictStrategy,
+    /// What to do when a custom action has no registered handler
+    pub on_missing_handler: MissingHandler,
+    /// Source of the current time for runs, `now()` and `today()`
+    pub clock: Arc<dyn Clock>,
+    /// Upper bound on actions executed in one run, across all cycles
+    pub max_actions_per_run: Option<usize>,
+    /// Fall back to the previous agenda group (ultimately MAIN) when the
+    /// focused group has no rule left to fire, instead of ending the run
+    pub agenda_fallthrough: bool,
+    /// Consecutive failures after which a custom action type is skipped for
+    /// the rest of the run
+    ///
+    /// When set, a failing custom action handler no longer aborts the run: the
+    /// error is logged and counted, and the action type is reported in
+    /// [`GruleExecutionResult::tripped_actions`] once it trips.
+    pub action_failure_threshold: Option<usize>,
+}
+
+/// Behavior when a rule calls a custom action that has no registered handler
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingHandler {
+    /// Fail the run with an error
+    #[default]
+    Error,
+    /// Skip the action silently
+    Skip,
+    /// Skip the action and log a warning
+    Warn,
 }
 
 impl Default for EngineConfig {
@@ -39,6 +95,12 @@ impl Default for EngineConfig {
             timeout: Some(Duration::from_secs(30)),
             enable_stats: true,
             debug_mode: false,
+            conflict_strategy: ConflictStrategy::default(),
+            on_missing_handler: MissingHandler::default(),
+            clock: Arc::new(SystemClock),
+            max_actions_per_run: None,
+            agenda_fallthrough: false,
+            action_failure_threshold: None,
         }
     }
 }
@@ -54,6 +116,128 @@ pub struct GruleExecutionResult {
     pub rules_fired: usize,
     /// Total execution time
     pub execution_time: Duration,
+    /// Whether a rule stopped the run with `halt()`
+    pub halted: bool,
+    /// Events recorded by `emit(...)` actions, in emission order
+    pub emitted_events: Vec<EmittedEvent>,
+    /// Total weight each `AddScore` target accumulated during the run
+    pub scores: HashMap<String, f64>,
+    /// Custom action types skipped after reaching
+    /// [`EngineConfig::action_failure_threshold`], in the order they tripped
+    pub tripped_actions: Vec<String>,
+    /// Rules skipped for running past their own `timeout`, in the order they overran
+    pub timed_out_rules: Vec<RuleTimedOut>,
+}
+
+/// Event recorded by an `emit(...)` action
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmittedEvent {
+    /// Event type name
+    pub event_type: String,
+    /// Payload with field references resolved at emit time
+    pub payload: HashMap<String, Value>,
+}
+
+/// A rule skipped because it ran past its own `timeout`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTimedOut {
+    /// Name of the rule
+    pub rule_name: String,
+    /// The rule's configured timeout
+    pub timeout: Duration,
+    /// Time spent on the rule when the overrun was detected
+    pub elapsed: Duration,
+}
+
+/// Result of a single evaluation cycle
+#[derive(Debug, Clone, Default)]
+pub struct CycleResult {
+    /// Names of the rules that fired, in firing order
+    pub fired_rules: Vec<String>,
+    /// Number of rules whose conditions were evaluated
+    pub rules_evaluated: usize,
+    /// Whether any rule fired; when `false` the engine has reached a fixpoint
+    pub any_rule_fired: bool,
+    /// Whether a rule called `halt()`; no further rules fired after it
+    pub halted: bool,
+    /// Events recorded by `emit(...)` actions during the cycle
+    pub emitted_events: Vec<EmittedEvent>,
+}
+
+/// Totals accumulated over the cycles of one run
+#[derive(Debug, Default)]
+struct RunTotals {
+    cycle_count: usize,
+    rules_evaluated: usize,
+    rules_fired: usize,
+    halted: bool,
+    emitted_events: Vec<EmittedEvent>,
+}
+
+/// Working memory and agenda kept between `ingest` and `run_pending` calls
+#[derive(Debug, Default)]
+struct IngestSession {
+    facts: Facts,
+    /// Rules whose inputs changed since they were last evaluated, in queue order
+    pending: Vec<String>,
+    /// Rules whose conditions held when last evaluated; they fire again only
+    /// after their conditions stop holding
+    satisfied: std::collections::HashSet<String>,
+}
+
+impl IngestSession {
+    fn queue(&mut self, rule_name: &str) {
+        if !self.pending.iter().any(|name| name == rule_name) {
+            self.pending.push(rule_name.to_string());
+        }
+    }
+}
+
+/// Result of a what-if analysis: a baseline run compared to a run with
+/// hypothetical fact changes applied
+#[derive(Debug, Clone)]
+pub struct WhatIfResult {
+    /// Rules fired in the baseline run, in firing order
+    pub baseline_fired: Vec<String>,
+    /// Rules fired with the hypothetical changes applied, in firing order
+    pub what_if_fired: Vec<String>,
+    /// Rules that fire only with the hypothetical changes
+    pub newly_fired: Vec<String>,
+    /// Rules that fire in the baseline but not with the hypothetical changes
+    pub no_longer_fired: Vec<String>,
+    /// Fact paths whose final value differs, as `path -> (baseline, what_if)`
+    pub fact_changes: HashMap<String, (Option<Value>, Option<Value>)>,
+}
+
+/// An action a [`RustRuleEngine::dry_run`] found would be executed
+#[derive(Debug, Clone)]
+pub struct PlannedAction {
+    /// Rule whose firing executes the action
+    pub rule: String,
+    /// The action as written in the rule
+    pub action: ActionType,
+    /// Value a `Set` or append would write, with expressions evaluated
+    pub resolved_value: Option<Value>,
+}
+
+/// Rules affected by [`RustRuleEngine::reload_rules_from_file`], by name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// Rules in the file that were not in the knowledge base
+    pub added: Vec<String>,
+    /// Rules in the knowledge base that are no longer in the file
+    pub removed: Vec<String>,
+    /// Rules whose definition differs from the running one
+    pub changed: Vec<String>,
+    /// Rules whose definition is identical
+    pub unchanged: Vec<String>,
+}
+
+impl ReloadReport {
+    /// Whether the reload modified the knowledge base
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty())
+    }
 }
 
 /// Rust Rule Engine - High-performance rule execution engine
@@ -62,15 +246,60 @@ pub struct RustRuleEngine {
     config: EngineConfig,
     custom_functions: HashMap<String, CustomFunction>,
     action_handlers: HashMap<String, ActionHandler>,
+    /// Handlers awaited by `execute_async`
+    #[cfg(feature = "async")]
+    async_action_handlers: HashMap<String, AsyncActionHandler>,
+    /// Runtime predicates that decide per run whether a rule is enabled
+    rule_enable_predicates: HashMap<String, RuleEnablePredicate>,
     analytics: Option<RuleAnalytics>,
     agenda_manager: AgendaManager,
     activation_group_manager: ActivationGroupManager,
     /// Track rules that have fired globally (for no-loop support)
     fired_rules_global: std::collections::HashSet<String>,
+    /// Times each rule has fired in the current run (for max-fires support)
+    fire_counts: HashMap<String, usize>,
     /// Workflow engine for rule chaining and sequential execution
     workflow_engine: WorkflowEngine,
     /// Plugin manager for extensible functionality
     plugin_manager: PluginManager,
+    /// Namespace of the plugin whose functions are being registered
+    function_namespace: Option<String>,
+    /// Rules switched off by enable predicates for the current run
+    disabled_by_predicate: std::collections::HashSet<String>,
+    /// Global variables, shared with the knowledge base
+    globals: GlobalStore,
+    /// OpenTelemetry exporter for execution traces
+    #[cfg(feature = "otel")]
+    otel_exporter: Option<crate::engine::otel::TraceExporter>,
+    /// Trace being collected for the current run
+    #[cfg(feature = "otel")]
+    otel_trace: Option<crate::engine::otel::ExecutionTrace>,
+    /// Set by a `Halt` action; checked after each fired rule
+    halt_requested: bool,
+    /// Events emitted by rules since the start of the current run
+    emitted_events: Vec<EmittedEvent>,
+    /// Weight added to each score target since the start of the current run
+    scores: HashMap<String, f64>,
+    /// Consecutive failures of each custom action type in the current run
+    action_failures: HashMap<String, usize>,
+    /// Custom action types short-circuited in the current run
+    tripped_actions: Vec<String>,
+    /// Rules that ran past their own timeout in the current run
+    timed_out_rules: Vec<RuleTimedOut>,
+    /// Provenance of rule-written facts, when enabled
+    provenance: Option<ProvenanceTracker>,
+    /// Restricts a run to rules carrying one of these tags (`execute_tagged`)
+    tag_filter: Option<Vec<String>>,
+    /// Handlers for `dispatch`ed events
+    event_bus: EventBus,
+    /// Restricts a run to rules reading this fact (`dispatch`)
+    event_filter: Option<String>,
+    /// Actions executed since the start of the current run
+    actions_executed: usize,
+    /// Actions recorded instead of performed while a dry run is in progress
+    planned_actions: Option<Vec<PlannedAction>>,
+    /// Persistent facts and agenda for `ingest`/`run_pending`
+    session: IngestSession,
 }
 
 #[allow(dead_code)]
@@ -84,121 +313,261 @@ impl RustRuleEngine {
     where
         F: FnMut(&str, &Facts),
     {
-        use chrono::Utc;
-        let timestamp = Utc::now();
-        let start_time = std::time::Instant::now();
-        let mut cycle_count = 0;
-        let mut rules_evaluated = 0;
-        let mut rules_fired = 0;
+        let timestamp = self.config.clock.now();
+        self.run_cycles(
+            facts,
+            timestamp,
+            &mut on_rule_fired,
+            &mut |_, _| {},
+            &|_| false,
+        )
+    }
 
-        self.sync_workflow_agenda_activations();
+    /// Execute all rules and call `on_cycle` at the end of every cycle
+    ///
+    /// The callback receives the 1-based cycle number and the names of the
+    /// rules fired in that cycle, in firing order. The final cycle, in which
+    /// nothing fired or a rule halted the run, is reported too.
+    pub fn execute_with_cycle_callback<F>(
+        &mut self,
+        facts: &Facts,
+        mut on_cycle: F,
+    ) -> Result<GruleExecutionResult>
+    where
+        F: FnMut(usize, &[String]),
+    {
+        let timestamp = self.config.clock.now();
+        self.run_cycles(facts, timestamp, &mut |_, _| {}, &mut on_cycle, &|_| false)
+    }
+
+    /// Execute all rules, awaiting async action handlers in firing order
+    ///
+    /// Runs like `execute`, with the same rule selection, tag and event
+    /// filters, analytics, tracing and per-rule timeouts. A custom action with
+    /// a handler registered through `register_async_action_handler` is awaited
+    /// before the rule's next action runs; the time spent awaiting counts
+    /// towards the rule's `timeout`. Every other action, including custom
+    /// actions with a synchronous handler, executes as it does in `execute`.
+    #[cfg(feature = "async")]
+    pub async fn execute_async(&mut self, facts: &Facts) -> Result<GruleExecutionResult> {
+        let mut clock_guard = Some(clock::activate(self.config.clock.clone()));
+        let timestamp = self.config.clock.now();
+        let start_time = Instant::now();
+        let mut totals = RunTotals::default();
+
+        self.begin_run(facts);
 
         for cycle in 0..self.config.max_cycles {
-            cycle_count = cycle + 1;
-            let mut any_rule_fired = false;
-            let mut fired_rules_in_cycle = std::collections::HashSet::new();
-            self.activation_group_manager.reset_cycle();
-
-            if let Some(timeout) = self.config.timeout {
-                if start_time.elapsed() > timeout {
-                    return Err(crate::errors::RuleEngineError::EvaluationError {
-                        message: "Execution timeout exceeded".to_string(),
-                    });
+            totals.cycle_count = cycle + 1;
+            self.check_run_timeout(start_time)?;
+
+            // Same as execute_single_cycle, except that rules fire through fire_rule_async
+            let mut cycle_result = CycleResult::default();
+            for rule_index in self.begin_cycle(facts)? {
+                let Some(rule) = self.knowledge_base.get_rule_by_index(rule_index) else {
+                    continue;
+                };
+                let Some(rule_start) =
+                    self.match_rule(&rule, facts, timestamp, &mut cycle_result)?
+                else {
+                    continue;
+                };
+                if !self
+                    .fire_rule_async(&rule, facts, rule_start, &mut clock_guard)
+                    .await?
+                {
+                    continue;
+                }
+                if self.count_fired(&rule, facts, &mut cycle_result, &mut |_, _| {}) {
+                    break;
                 }
             }
+            cycle_result.emitted_events = std::mem::take(&mut self.emitted_events);
 
-            let rule_indices = self.knowledge_base.get_rules_by_salience();
+            if !self.end_cycle(&mut totals, cycle_result, facts, &|_| false) {
+                break;
+            }
+        }
 
-            for &rule_index in &rule_indices {
-                if let Some(rule) = self.knowledge_base.get_rule_by_index(rule_index) {
-                    if !rule.enabled {
-                        continue;
-                    }
-                    if !self.agenda_manager.should_evaluate_rule(&rule) {
-                        continue;
-                    }
-                    if !rule.is_active_at(timestamp) {
-                        continue;
-                    }
-                    if !self.agenda_manager.can_fire_rule(&rule) {
-                        continue;
-                    }
-                    if !self.activation_group_manager.can_fire(&rule) {
-                        continue;
-                    }
-                    if rule.no_loop && self.fired_rules_global.contains(&rule.name) {
-                        continue;
-                    }
-                    rules_evaluated += 1;
-                    let condition_result = self.evaluate_conditions(&rule.conditions, facts)?;
-                    if condition_result {
-                        for action in &rule.actions {
-                            self.execute_action(action, facts)?;
-                        }
-                        rules_fired += 1;
-                        any_rule_fired = true;
-                        fired_rules_in_cycle.insert(rule.name.clone());
-                        if rule.no_loop {
-                            self.fired_rules_global.insert(rule.name.clone());
+        Ok(self.finish_run(totals, start_time))
+    }
+
+    /// `fire_rule`, awaiting custom actions that have an async handler
+    ///
+    /// The active clock is thread-local, so it is released while the task may
+    /// be suspended or moved to another thread.
+    #[cfg(feature = "async")]
+    async fn fire_rule_async(
+        &mut self,
+        rule: &crate::engine::rule::Rule,
+        facts: &Facts,
+        rule_start: Instant,
+        clock_guard: &mut Option<clock::ActiveClockGuard>,
+    ) -> Result<bool> {
+        self.begin_firing(rule, facts);
+        for action in &rule.actions {
+            let outcome = match action {
+                ActionType::Custom {
+                    action_type,
+                    params,
+                } if self.async_action_handlers.contains_key(action_type) => {
+                    match self.start_async_action(action_type, params, facts) {
+                        Ok(Some(pending)) => {
+                            drop(clock_guard.take());
+                            let outcome = pending.await;
+                            *clock_guard = Some(clock::activate(self.config.clock.clone()));
+                            self.record_action_outcome(action_type, outcome)
                         }
-                        self.agenda_manager.mark_rule_fired(&rule);
-                        self.activation_group_manager.mark_fired(&rule);
-                        on_rule_fired(&rule.name, facts);
+                        Ok(None) => Ok(()),
+                        Err(err) => Err(err),
                     }
                 }
+                _ => self.perform_action(&rule.name, action, facts),
+            };
+            if !self.settle_action(rule, facts, rule_start, outcome)? {
+                return Ok(false);
             }
-            if !any_rule_fired {
-                break;
-            }
-            self.sync_workflow_agenda_activations();
         }
-        let execution_time = start_time.elapsed();
-        Ok(crate::engine::GruleExecutionResult {
-            cycle_count,
-            rules_evaluated,
-            rules_fired,
-            execution_time,
-        })
+        self.finish_firing(rule, facts, rule_start);
+        Ok(true)
+    }
+
+    /// Count, resolve and start a custom action with an async handler
+    ///
+    /// Returns `None` when the action's circuit breaker has tripped.
+    #[cfg(feature = "async")]
+    fn start_async_action(
+        &mut self,
+        action_type: &str,
+        params: &HashMap<String, Value>,
+        facts: &Facts,
+    ) -> Result<Option<PendingAction>> {
+        self.count_action()?;
+        if self.action_tripped(action_type) {
+            return Ok(None);
+        }
+        let params = self.resolve_action_parameters(params, facts)?;
+        Ok(Some(self.async_action_handlers[action_type](params)))
     }
+
     /// Create a new RustRuleEngine with default configuration
     pub fn new(knowledge_base: KnowledgeBase) -> Self {
+        let globals = knowledge_base.globals().clone();
         Self {
             knowledge_base,
             config: EngineConfig::default(),
             custom_functions: HashMap::new(),
             action_handlers: HashMap::new(),
+            #[cfg(feature = "async")]
+            async_action_handlers: HashMap::new(),
+            rule_enable_predicates: HashMap::new(),
             analytics: None,
             agenda_manager: AgendaManager::new(),
             activation_group_manager: ActivationGroupManager::new(),
             fired_rules_global: std::collections::HashSet::new(),
+            fire_counts: HashMap::new(),
             workflow_engine: WorkflowEngine::new(),
             plugin_manager: PluginManager::with_default_config(),
+            function_namespace: None,
+            disabled_by_predicate: std::collections::HashSet::new(),
+            globals,
+            #[cfg(feature = "otel")]
+            otel_exporter: None,
+            #[cfg(feature = "otel")]
+            otel_trace: None,
+            halt_requested: false,
+            emitted_events: Vec::new(),
+            scores: HashMap::new(),
+            action_failures: HashMap::new(),
+            tripped_actions: Vec::new(),
+            timed_out_rules: Vec::new(),
+            provenance: None,
+            tag_filter: None,
+            event_bus: EventBus::new(),
+            event_filter: None,
+            actions_executed: 0,
+            planned_actions: None,
+            session: IngestSession::default(),
         }
     }
 
     /// Create a new RustRuleEngine with custom configuration
     pub fn with_config(knowledge_base: KnowledgeBase, config: EngineConfig) -> Self {
+        let globals = knowledge_base.globals().clone();
+        let agenda_manager = AgendaManager::new().with_fallthrough(config.agenda_fallthrough);
         Self {
             knowledge_base,
             config,
             custom_functions: HashMap::new(),
             action_handlers: HashMap::new(),
+            #[cfg(feature = "async")]
+            async_action_handlers: HashMap::new(),
+            rule_enable_predicates: HashMap::new(),
             analytics: None,
-            agenda_manager: AgendaManager::new(),
+            agenda_manager,
             activation_group_manager: ActivationGroupManager::new(),
             fired_rules_global: std::collections::HashSet::new(),
+            fire_counts: HashMap::new(),
             workflow_engine: WorkflowEngine::new(),
             plugin_manager: PluginManager::with_default_config(),
+            function_namespace: None,
+            disabled_by_predicate: std::collections::HashSet::new(),
+            globals,
+            #[cfg(feature = "otel")]
+            otel_exporter: None,
+            #[cfg(feature = "otel")]
+            otel_trace: None,
+            halt_requested: false,
+            emitted_events: Vec::new(),
+            scores: HashMap::new(),
+            action_failures: HashMap::new(),
+            tripped_actions: Vec::new(),
+            timed_out_rules: Vec::new(),
+            provenance: None,
+            tag_filter: None,
+            event_bus: EventBus::new(),
+            event_filter: None,
+            actions_executed: 0,
+            planned_actions: None,
+            session: IngestSession::default(),
         }
     }
 
+    /// Export each `execute` run as OpenTelemetry spans on `tracer`
+    ///
+    /// Produces a `rule_engine.execute` span (parented to the current context)
+    /// with one child span per fired rule.
+    #[cfg(feature = "otel")]
+    pub fn set_otel_tracer<T>(&mut self, tracer: T)
+    where
+        T: opentelemetry::trace::Tracer + Send + Sync + 'static,
+        T::Span: Send + Sync + 'static,
+    {
+        self.otel_exporter = Some(crate::engine::otel::exporter(tracer));
+    }
+
     /// Register a custom function
+    ///
+    /// Functions registered by a plugin with a
+    /// [`namespace`](crate::engine::plugin::RulePlugin::namespace) are stored
+    /// as `namespace.name`.
     pub fn register_function<F>(&mut self, name: &str, func: F)
     where
         F: Fn(&[Value], &Facts) -> Result<Value> + Send + Sync + 'static,
     {
-        self.custom_functions
-            .insert(name.to_string(), Box::new(func));
+        let name = match &self.function_namespace {
+            Some(namespace) => format!("{}.{}", namespace, name),
+            None => name.to_string(),
+        };
+        self.custom_functions.insert(name, Box::new(func));
+    }
+
+    /// Look up a custom function, falling back from `namespace.name` to `name`
+    fn custom_function(&self, name: &str) -> Option<&CustomFunction> {
+        self.custom_functions.get(name).or_else(|| {
+            let (_, unqualified) = name.split_once('.')?;
+            self.custom_functions.get(unqualified)
+        })
     }
 
     /// Register a custom action handler
@@ -210,6 +579,76 @@ impl RustRuleEngine {
             .insert(action_type.to_string(), Box::new(handler));
     }
 
+    /// Register an async handler for a custom action type
+    ///
+    /// `execute_async` awaits it in firing order. Other runs do not call async
+    /// handlers, so those runs treat the action as having no handler.
+    #[cfg(feature = "async")]
+    pub fn register_async_action_handler<F, Fut>(&mut self, action_type: &str, handler: F)
+    where
+        F: Fn(HashMap<String, Value>) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<()>> + Send + 'static,
+    {
+        self.async_action_handlers.insert(
+            action_type.to_string(),
+            Box::new(move |params| Box::pin(handler(params))),
+        );
+    }
+
+    /// Check every rule for problems that would otherwise only surface at runtime
+    ///
+    /// Reports functions that are not registered, custom actions without a
+    /// handler and malformed expressions. Nothing is executed, so this is meant
+    /// to run once at startup after all functions, handlers and plugins are
+    /// registered.
+    pub fn validate_rules(&self) -> Vec<ValidationIssue> {
+        let has_function = |name: &str| self.custom_function(name).is_some();
+        let has_action = |name: &str| self.action_handlers.contains_key(name);
+        self.knowledge_base
+            .get_rules()
+            .iter()
+            .flat_map(|rule| validate_rule(rule, &has_function, &has_action))
+            .collect()
+    }
+
+    /// Enable or disable a rule at runtime based on a predicate over facts
+    ///
+    /// The predicate is evaluated once at the start of every run; when it returns
+    /// `false` the rule is skipped for that run, in addition to the static
+    /// `Rule::enabled` flag.
+    ///
+    /// ```rust
+    /// # use rust_rule_engine::*;
+    /// let mut engine = RustRuleEngine::new(KnowledgeBase::new("Flags"));
+    /// engine.set_rule_condition_enabled("NewCheckout", |facts| {
+    ///     facts
+    ///         .get_nested("Features.NewCheckout")
+    ///         .and_then(|v| v.as_boolean())
+    ///         .unwrap_or(false)
+    /// });
+    /// ```
+    pub fn set_rule_condition_enabled<F>(&mut self, rule_name: &str, predicate: F)
+    where
+        F: Fn(&Facts) -> bool + Send + Sync + 'static,
+    {
+        self.rule_enable_predicates
+            .insert(rule_name.to_string(), Box::new(predicate));
+    }
+
+    /// Remove a runtime enable predicate, returning whether one was registered
+    pub fn clear_rule_condition_enabled(&mut self, rule_name: &str) -> bool {
+        self.rule_enable_predicates.remove(rule_name).is_some()
+    }
+
+    /// Names of rules whose enable predicate rejects the given facts
+    fn rules_disabled_by_predicate(&self, facts: &Facts) -> std::collections::HashSet<String> {
+        self.rule_enable_predicates
+            .iter()
+            .filter(|(_, predicate)| !predicate(facts))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
     /// Enable analytics with custom configuration
     pub fn enable_analytics(&mut self, analytics: RuleAnalytics) {
         self.analytics = Some(analytics);
@@ -220,6 +659,22 @@ impl RustRuleEngine {
         self.fired_rules_global.clear();
     }
 
+    /// Record which rules produced each fact value during a run
+    ///
+    /// Query the result with [`RustRuleEngine::provenance`]. Provenance is
+    /// reset at the start of every `execute`.
+    pub fn enable_provenance(&mut self) {
+        self.provenance = Some(ProvenanceTracker::default());
+    }
+
+    /// Explain how `fact_path` got its current value
+    ///
+    /// Returns `None` if provenance is disabled or no rule wrote the fact in
+    /// the last run.
+    pub fn provenance(&self, fact_path: &str) -> Option<ProvenanceChain> {
+        self.provenance.as_ref()?.get(fact_path).cloned()
+    }
+
     /// Disable analytics
     pub fn disable_analytics(&mut self) {
         self.analytics = None;
@@ -276,6 +731,11 @@ impl RustRuleEngine {
         self.agenda_manager.set_focus(&group);
     }
 
+    /// Global variables available to rules as `?*NAME*`
+    pub fn globals(&self) -> &GlobalStore {
+        &self.globals
+    }
+
     /// Get the knowledge base
     pub fn knowledge_base(&self) -> &KnowledgeBase {
         &self.knowledge_base
@@ -286,6 +746,90 @@ impl RustRuleEngine {
         &mut self.knowledge_base
     }
 
+    /// Enable or disable a loaded rule by name, returning whether it exists
+    ///
+    /// The change applies from the next `execute` on.
+    pub fn set_rule_enabled(&self, rule_name: &str, enabled: bool) -> Result<bool> {
+        self.knowledge_base.set_rule_enabled(rule_name, enabled)
+    }
+
+    /// Disable every rule tagged with `tag`, returning how many carry it
+    ///
+    /// The rules stay loaded and are skipped from the next `execute` on, until
+    /// [`enable_rules_by_tag`](Self::enable_rules_by_tag) turns them back on.
+    pub fn disable_rules_by_tag(&self, tag: &str) -> usize {
+        self.knowledge_base.set_enabled_by_tag(tag, false)
+    }
+
+    /// Enable every rule tagged with `tag`, returning how many carry it
+    pub fn enable_rules_by_tag(&self, tag: &str) -> usize {
+        self.knowledge_base.set_enabled_by_tag(tag, true)
+    }
+
+    /// Reload rules from a GRL file, applying only the differences
+    ///
+    /// The file is treated as the complete rule set: rules missing from it
+    /// are removed, new ones added, and rules whose definition changed are
+    /// replaced and forget their no-loop history. The file is fully parsed
+    /// before anything is applied, so a parse error leaves the knowledge base
+    /// untouched.
+    pub fn reload_rules_from_file<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<ReloadReport> {
+        let grl_text = std::fs::read_to_string(&path).map_err(|e| RuleEngineError::io(&path, e))?;
+        let globals = crate::parser::grl::GRLParser::parse_globals(&grl_text)?;
+        let rules = crate::parser::grl::GRLParser::parse_rules(&grl_text)?;
+
+        let mut incoming = std::collections::HashSet::new();
+        for rule in &rules {
+            if !incoming.insert(rule.name.clone()) {
+                return Err(RuleEngineError::ParseError {
+                    message: format!("Rule '{}' is defined more than once", rule.name),
+                });
+            }
+        }
+
+        let mut report = ReloadReport::default();
+        let mut current = self.knowledge_base.get_rule_names();
+        current.sort();
+        for name in current {
+            if !incoming.contains(&name) {
+                self.knowledge_base.remove_rule(&name)?;
+                self.fired_rules_global.remove(&name);
+                report.removed.push(name);
+            }
+        }
+
+        for rule in rules {
+            let name = rule.name.clone();
+            match self.knowledge_base.get_rule(&name) {
+                None => {
+                    self.knowledge_base.add_rule(rule)?;
+                    report.added.push(name);
+                }
+                Some(existing) if rule_fingerprint(&existing) != rule_fingerprint(&rule) => {
+                    self.knowledge_base.replace_rule(rule)?;
+                    self.fired_rules_global.remove(&name);
+                    report.changed.push(name);
+                }
+                Some(_) => report.unchanged.push(name),
+            }
+        }
+
+        for (name, value) in globals {
+            self.globals.define(&name, value);
+        }
+
+        info!(
+            "Reloaded rules: {} added, {} removed, {} changed",
+            report.added.len(),
+            report.removed.len(),
+            report.changed.len()
+        );
+        Ok(report)
+    }
+
     /// Sync workflow engine agenda activations with agenda manager
     fn sync_workflow_agenda_activations(&mut self) {
         // Process any pending agenda activations from workflow engine
@@ -302,6 +846,17 @@ impl RustRuleEngine {
         self.agenda_manager.set_focus(group);
     }
 
+    /// Add `base` to the salience of every rule in an agenda group
+    ///
+    /// Rules are ordered by their own salience plus their group's base, so a
+    /// whole stage can be prioritized without editing its rules. `execute`
+    /// only evaluates the focused group, so the base reorders groups against
+    /// each other only where rules of several groups share the agenda, as in
+    /// [`run_pending`](Self::run_pending).
+    pub fn set_group_salience(&mut self, group: &str, base: i32) {
+        self.agenda_manager.set_group_salience(group, base);
+    }
+
     /// Get the currently active agenda group
     pub fn get_active_agenda_group(&self) -> &str {
         self.agenda_manager.get_active_group()
@@ -440,214 +995,957 @@ impl RustRuleEngine {
         Ok(())
     }
 
-    /// Execute all rules in the knowledge base against the given facts
-    pub fn execute(&mut self, facts: &Facts) -> Result<GruleExecutionResult> {
-        self.execute_at_time(facts, Utc::now())
-    }
-
-    /// Execute all rules at a specific timestamp (for date-effective/expires testing)
-    pub fn execute_at_time(
+    /// Compare a baseline run against a run with hypothetical fact changes
+    ///
+    /// Both runs operate on private copies of `facts`, and the engine's
+    /// no-loop/agenda state is restored afterwards, so neither the real facts
+    /// nor later executions are affected. Side effects of custom action
+    /// handlers still happen.
+    pub fn what_if(
         &mut self,
         facts: &Facts,
-        timestamp: DateTime<Utc>,
-    ) -> Result<GruleExecutionResult> {
-        let start_time = Instant::now();
-        let mut cycle_count = 0;
-        let mut rules_evaluated = 0;
-        let mut rules_fired = 0;
-
-        // Process any pending agenda group activations from workflow engine
-        self.sync_workflow_agenda_activations();
+        changes: HashMap<String, Value>,
+    ) -> Result<WhatIfResult> {
+        let baseline_facts = Facts::new();
+        baseline_facts.restore(facts.snapshot());
+        let what_if_facts = Facts::new();
+        what_if_facts.restore(facts.snapshot());
+
+        for (path, value) in changes {
+            if what_if_facts.set_nested(&path, value.clone()).is_err() {
+                what_if_facts.set(&path, value);
+            }
+        }
 
-        if self.config.debug_mode {
-            println!(
-                "🚀 Starting rule execution with {} rules (agenda group: {})",
-                self.knowledge_base.rule_count(),
-                self.agenda_manager.get_active_group()
-            );
+        let baseline_fired = self.execute_isolated(&baseline_facts)?;
+        let what_if_fired = self.execute_isolated(&what_if_facts)?;
+
+        let newly_fired = what_if_fired
+            .iter()
+            .filter(|name| !baseline_fired.contains(name))
+            .cloned()
+            .collect();
+        let no_longer_fired = baseline_fired
+            .iter()
+            .filter(|name| !what_if_fired.contains(name))
+            .cloned()
+            .collect();
+
+        let mut baseline_values = HashMap::new();
+        flatten_fact_values("", &baseline_facts.get_all_facts(), &mut baseline_values);
+        let mut what_if_values = HashMap::new();
+        flatten_fact_values("", &what_if_facts.get_all_facts(), &mut what_if_values);
+
+        let mut fact_changes = HashMap::new();
+        for path in baseline_values.keys().chain(what_if_values.keys()) {
+            let before = baseline_values.get(path);
+            let after = what_if_values.get(path);
+            if before != after {
+                fact_changes.insert(path.clone(), (before.cloned(), after.cloned()));
+            }
         }
 
-        for cycle in 0..self.config.max_cycles {
-            cycle_count = cycle + 1;
-            let mut any_rule_fired = false;
-            let mut fired_rules_in_cycle = std::collections::HashSet::new();
+        Ok(WhatIfResult {
+            baseline_fired,
+            what_if_fired,
+            newly_fired,
+            no_longer_fired,
+            fact_changes,
+        })
+    }
 
-            // Reset activation groups for each cycle
-            self.activation_group_manager.reset_cycle();
+    /// Report the actions a run would execute, without applying them
+    ///
+    /// Conditions are evaluated as in `execute`. Fact updates (`Set`, appends,
+    /// method calls, retractions) are applied while the run lasts, so later
+    /// rules see them, and `facts` is restored from a snapshot afterwards.
+    /// Custom action handlers, logging, events and workflow or agenda actions
+    /// are only recorded. The engine's no-loop/agenda state is restored as well.
+    pub fn dry_run(&mut self, facts: &Facts) -> Result<Vec<PlannedAction>> {
+        let snapshot = facts.snapshot();
+        self.planned_actions = Some(Vec::new());
+
+        let result = self.execute_isolated(facts);
+
+        let planned = self.planned_actions.take().unwrap_or_default();
+        facts.restore(snapshot);
+        result.map(|_| planned)
+    }
 
-            // Check for timeout
-            if let Some(timeout) = self.config.timeout {
-                if start_time.elapsed() > timeout {
-                    return Err(RuleEngineError::EvaluationError {
-                        message: "Execution timeout exceeded".to_string(),
-                    });
-                }
+    /// Count an action against `max_actions_per_run`
+    fn count_action(&mut self) -> Result<()> {
+        self.actions_executed += 1;
+        if let Some(limit) = self.config.max_actions_per_run {
+            if self.actions_executed > limit {
+                return Err(RuleEngineError::ActionLimitExceeded { limit });
             }
+        }
+        Ok(())
+    }
 
-            // Get rule indices sorted by salience (highest first) - avoids cloning rules
-            let rule_indices = self.knowledge_base.get_rules_by_salience();
+    /// Execute an action, or record it when a dry run is in progress
+    fn perform_action(
+        &mut self,
+        rule_name: &str,
+        action: &ActionType,
+        facts: &Facts,
+    ) -> Result<()> {
+        if self.planned_actions.is_none() {
+            return self.execute_action(action, facts);
+        }
 
-            // Process rules by index to avoid cloning
-            for &rule_index in &rule_indices {
-                if let Some(rule) = self.knowledge_base.get_rule_by_index(rule_index) {
-                    if !rule.enabled {
-                        continue;
-                    }
+        let resolved_value = match action {
+            ActionType::Set { value, .. } | ActionType::Append { value, .. } => {
+                Some(self.resolve_action_value(value, facts)?)
+            }
+            _ => None,
+        };
+        if let Some(planned) = self.planned_actions.as_mut() {
+            planned.push(PlannedAction {
+                rule: rule_name.to_string(),
+                action: action.clone(),
+                resolved_value,
+            });
+        }
 
-                    if !self.agenda_manager.should_evaluate_rule(&rule) {
-                        continue;
-                    }
+        match action {
+            // Globals live in the engine, outside the restored facts
+            ActionType::Set { field, .. }
+                if crate::engine::globals::global_name(field).is_some() =>
+            {
+                Ok(())
+            }
+            ActionType::Set { .. }
+            | ActionType::Append { .. }
+            | ActionType::MethodCall { .. }
+            | ActionType::Retract { .. }
+            | ActionType::AddScore { .. }
+            | ActionType::CallAndBind { .. }
+            | ActionType::Halt => self.execute_action(action, facts),
+            _ => Ok(()),
+        }
+    }
 
-                    // Check date effective/expires
-                    if !rule.is_active_at(timestamp) {
-                        continue;
-                    }
+    /// Whether `action_type` has tripped its circuit breaker in this run
+    fn action_tripped(&self, action_type: &str) -> bool {
+        self.tripped_actions
+            .iter()
+            .any(|tripped| tripped == action_type)
+    }
 
-                    // Check agenda group constraints (lock-on-active)
-                    if !self.agenda_manager.can_fire_rule(&rule) {
-                        continue;
-                    }
+    /// Track a custom action handler's outcome for the circuit breaker
+    ///
+    /// Without [`EngineConfig::action_failure_threshold`] the error is returned
+    /// as is. With it, failures are logged and counted instead, and the action
+    /// type trips once it fails that many times in a row.
+    fn record_action_outcome(&mut self, action_type: &str, outcome: Result<()>) -> Result<()> {
+        let Some(threshold) = self.config.action_failure_threshold else {
+            return outcome;
+        };
+        let Err(error) = outcome else {
+            self.action_failures.remove(action_type);
+            return Ok(());
+        };
 
-                    // Check activation group constraints (only one rule per group can fire)
-                    if !self.activation_group_manager.can_fire(&rule) {
-                        continue;
-                    }
+        let failures = self
+            .action_failures
+            .entry(action_type.to_string())
+            .or_insert(0);
+        *failures += 1;
+        warn!("Custom action '{action_type}' failed ({failures} in a row): {error}");
+        if *failures >= threshold {
+            warn!("Skipping custom action '{action_type}' for the rest of the run");
+            self.tripped_actions.push(action_type.to_string());
+        }
+        Ok(())
+    }
 
-                    // Check no-loop: skip if already fired in this execution cycle
-                    if rule.no_loop && self.fired_rules_global.contains(&rule.name) {
-                        if self.config.debug_mode {
-                            println!("⛔ Skipping '{}' due to no_loop (already fired)", rule.name);
-                        }
-                        continue;
-                    }
+    /// Store the value of an assignment in a global or a fact
+    fn assign_field(&mut self, field: &str, value: Value, facts: &Facts) -> Result<()> {
+        // Assignment to a global: ?*NAME* = value
+        if let Some(name) = crate::engine::globals::global_name(field) {
+            return self.globals.set(name, value);
+        }
 
-                    // Debug
-                    if self.config.debug_mode {
-                        println!(
-                            "🔍 Checking rule '{}' (no_loop: {})",
-                            rule.name, rule.no_loop
-                        );
-                    }
+        // Try nested first, then fall back to flat key setting
+        match facts.set_nested(field, value.clone()) {
+            Ok(()) => {}
+            Err(e @ RuleEngineError::SchemaViolation { .. }) => return Err(e),
+            // If nested fails, use flat key
+            Err(_) => facts.set(field, value.clone()),
+        }
+        if self.config.debug_mode {
+            println!("  ✅ Set {field} = {value:?}");
+        }
+        Ok(())
+    }
+
+    /// Value an assignment writes: globals and expressions are evaluated
+    fn resolve_action_value(&self, value: &Value, facts: &Facts) -> Result<Value> {
+        match value {
+            Value::Expression(expr) if crate::engine::globals::global_name(expr).is_some() => {
+                self.globals.resolve(expr)
+            }
+            Value::Expression(expr) => crate::expression::evaluate_expression(expr, facts),
+            _ => Ok(value.clone()),
+        }
+    }
 
-                    let rule_start = std::time::Instant::now();
+    /// Run the engine and return fired rule names, restoring execution state
+    fn execute_isolated(&mut self, facts: &Facts) -> Result<Vec<String>> {
+        let fired_rules_global = self.fired_rules_global.clone();
+        let agenda_manager = self.agenda_manager.clone();
+        let activation_group_manager = self.activation_group_manager.clone();
 
-                    // Count rule evaluation
-                    rules_evaluated += 1;
+        let mut fired = Vec::new();
+        let result = self.execute_with_callback(facts, |name, _| fired.push(name.to_string()));
 
-                    // Evaluate rule conditions
-                    let condition_result = self.evaluate_conditions(&rule.conditions, facts)?;
+        self.fired_rules_global = fired_rules_global;
+        self.agenda_manager = agenda_manager;
+        self.activation_group_manager = activation_group_manager;
 
-                    if self.config.debug_mode {
-                        println!(
-                            "   Rule '{}' condition result: {}",
-                            rule.name, condition_result
-                        );
-                    }
+        result.map(|_| fired)
+    }
 
-                    // If conditions match, fire the rule
-                    if condition_result {
-                        if self.config.debug_mode {
-                            println!(
-                                "🔥 Firing rule '{}' (salience: {})",
-                                rule.name, rule.salience
-                            );
-                        }
+    /// Execute all rules in the knowledge base against the given facts
+    pub fn execute(&mut self, facts: &Facts) -> Result<GruleExecutionResult> {
+        self.execute_at_time(facts, self.config.clock.now())
+    }
 
-                        // Execute actions
-                        for action in &rule.actions {
-                            self.execute_action(action, facts)?;
-                        }
+    /// Execute only the rules tagged with at least one of `tags`
+    ///
+    /// Selection is independent of agenda groups: the selected rules still
+    /// obey focus, activation groups and no-loop as in `execute`.
+    pub fn execute_tagged(&mut self, facts: &Facts, tags: &[&str]) -> Result<GruleExecutionResult> {
+        self.tag_filter = Some(tags.iter().map(|tag| tag.to_string()).collect());
+        let result = self.execute(facts);
+        self.tag_filter = None;
+        result
+    }
 
-                        let rule_duration = rule_start.elapsed();
-
-                        // Record analytics if enabled
-                        if let Some(analytics) = &mut self.analytics {
-                            analytics.record_execution(
-                                &rule.name,
-                                rule_duration,
-                                true,
-                                true,
-                                None,
-                                0,
-                            );
-                        }
+    /// Whether the current tag filter, if any, selects `rule`
+    fn selected_by_tags(&self, rule: &crate::engine::rule::Rule) -> bool {
+        match &self.tag_filter {
+            Some(tags) => rule.tags.iter().any(|tag| tags.contains(tag)),
+            None => true,
+        }
+    }
 
-                        rules_fired += 1;
-                        any_rule_fired = true;
+    /// Whether the event being dispatched, if any, is read by `rule`
+    fn selected_by_event(&self, rule: &crate::engine::rule::Rule) -> bool {
+        match &self.event_filter {
+            Some(event_type) => rule_reads(&rule.conditions)
+                .iter()
+                .any(|read| read == event_type || read.starts_with(&format!("{}.", event_type))),
+            None => true,
+        }
+    }
 
-                        // Track that this rule fired in this cycle (for cycle counting)
-                        fired_rules_in_cycle.insert(rule.name.clone());
+    /// Call `handler` for every event of `event_type` passed to
+    /// [`dispatch`](Self::dispatch)
+    pub fn on_event<F>(&mut self, event_type: &str, handler: F)
+    where
+        F: Fn(&Event, &Facts) -> Result<()> + Send + Sync + 'static,
+    {
+        self.event_bus.subscribe(event_type, Box::new(handler));
+    }
 
-                        // Track that this rule fired globally (for no-loop support)
-                        if rule.no_loop {
-                            self.fired_rules_global.insert(rule.name.clone());
-                            if self.config.debug_mode {
-                                println!("  🔒 Marked '{}' as fired (no_loop tracking)", rule.name);
-                            }
-                        }
+    /// Run the rules reading `event`, then the handlers registered for its type
+    ///
+    /// The payload is exposed as a fact named after the event type, so an
+    /// `OrderPlaced` event with `Amount = 120` matches `OrderPlaced.Amount > 100`.
+    /// Only rules whose conditions read that fact are evaluated, in a single
+    /// cycle with fresh no-loop tracking, so each matching rule fires once per
+    /// event. Handlers see the facts as the rules left them; events the rules
+    /// `emit` are passed to their handlers as well.
+    pub fn dispatch(&mut self, event: Event) -> Result<GruleExecutionResult> {
+        self.dispatch_with_facts(event, &Facts::new())
+    }
 
-                        // Mark rule as fired for agenda and activation group management
-                        self.agenda_manager.mark_rule_fired(&rule);
-                        self.activation_group_manager.mark_fired(&rule);
-                    } else {
-                        let rule_duration = rule_start.elapsed();
-
-                        // Record analytics for failed rules too
-                        if let Some(analytics) = &mut self.analytics {
-                            analytics.record_execution(
-                                &rule.name,
-                                rule_duration,
-                                false,
-                                false,
-                                None,
-                                0,
-                            );
-                        }
-                    }
-                } // Close if let Some(rule)
+    /// [`dispatch`](Self::dispatch) with `facts` also visible to the rules
+    ///
+    /// The event fact is added to `facts` for the duration of the call; a fact
+    /// of the same name is restored afterwards.
+    pub fn dispatch_with_facts(
+        &mut self,
+        event: Event,
+        facts: &Facts,
+    ) -> Result<GruleExecutionResult> {
+        let previous = facts.get(&event.event_type);
+        facts.add_value(&event.event_type, event.to_fact())?;
+
+        let fired_rules_global = std::mem::take(&mut self.fired_rules_global);
+        let max_cycles = std::mem::replace(&mut self.config.max_cycles, 1);
+        self.event_filter = Some(event.event_type.clone());
+        let result = self.execute(facts);
+        self.event_filter = None;
+        self.config.max_cycles = max_cycles;
+        self.fired_rules_global = fired_rules_global;
+
+        let result = result.and_then(|result| {
+            self.event_bus.publish(&event, facts)?;
+            for emitted in &result.emitted_events {
+                self.event_bus
+                    .publish(&Event::from(emitted.clone()), facts)?;
             }
+            Ok(result)
+        });
 
-            // If no rules fired in this cycle, we're done
-            if !any_rule_fired {
-                break;
+        match previous {
+            Some(value) => facts.set(&event.event_type, value),
+            None => {
+                facts.remove(&event.event_type);
             }
-
-            // Sync any new workflow agenda activations at the end of each cycle
-            self.sync_workflow_agenda_activations();
         }
-
-        let execution_time = start_time.elapsed();
-
-        Ok(GruleExecutionResult {
-            cycle_count,
-            rules_evaluated,
-            rules_fired,
-            execution_time,
-        })
+        result
     }
 
-    /// Evaluate conditions against facts
-    fn evaluate_conditions(
-        &self,
-        conditions: &crate::engine::rule::ConditionGroup,
+    /// Execute all rules at a specific timestamp (for date-effective/expires testing)
+    pub fn execute_at_time(
+        &mut self,
+        facts: &Facts,
+        timestamp: DateTime<Utc>,
+    ) -> Result<GruleExecutionResult> {
+        self.run_cycles(facts, timestamp, &mut |_, _| {}, &mut |_, _| {}, &|_| false)
+    }
+
+    /// Run cycles until `stop(facts)` holds after a cycle
+    ///
+    /// Also ends like `execute` when nothing fires, a rule halts or
+    /// `max_cycles` cycles have run, whichever comes first. Returns the result
+    /// together with whether the predicate was met, so a run that reached a
+    /// fixpoint without converging can be told apart from one that did.
+    pub fn execute_until<F>(
+        &mut self,
+        facts: &Facts,
+        stop: F,
+        max_cycles: usize,
+    ) -> Result<(GruleExecutionResult, bool)>
+    where
+        F: Fn(&Facts) -> bool,
+    {
+        let configured_max_cycles = std::mem::replace(&mut self.config.max_cycles, max_cycles);
+        let timestamp = self.config.clock.now();
+        let result = self.run_cycles(facts, timestamp, &mut |_, _| {}, &mut |_, _| {}, &stop);
+        self.config.max_cycles = configured_max_cycles;
+        let result = result?;
+        Ok((result, stop(facts)))
+    }
+
+    /// Run cycles until nothing fires, a rule halts, `stop` holds or
+    /// `max_cycles` is reached
+    ///
+    /// `on_rule_fired` is called after each fired rule and `on_cycle` after
+    /// each cycle. Every `execute*` variant goes through here.
+    fn run_cycles(
+        &mut self,
+        facts: &Facts,
+        timestamp: DateTime<Utc>,
+        on_rule_fired: &mut dyn FnMut(&str, &Facts),
+        on_cycle: &mut dyn FnMut(usize, &[String]),
+        stop: &dyn Fn(&Facts) -> bool,
+    ) -> Result<GruleExecutionResult> {
+        let _clock = clock::activate(self.config.clock.clone());
+        let start_time = Instant::now();
+        let mut totals = RunTotals::default();
+
+        self.begin_run(facts);
+
+        if self.config.debug_mode {
+            println!(
+                "🚀 Starting rule execution with {} rules (agenda group: {})",
+                self.knowledge_base.rule_count(),
+                self.agenda_manager.get_active_group()
+            );
+        }
+
+        for cycle in 0..self.config.max_cycles {
+            totals.cycle_count = cycle + 1;
+            self.check_run_timeout(start_time)?;
+
+            let cycle_result = self.execute_single_cycle(facts, timestamp, on_rule_fired)?;
+            on_cycle(totals.cycle_count, &cycle_result.fired_rules);
+            if !self.end_cycle(&mut totals, cycle_result, facts, stop) {
+                break;
+            }
+        }
+
+        Ok(self.finish_run(totals, start_time))
+    }
+
+    /// Fail the run once it has been going for longer than the engine's `timeout`
+    fn check_run_timeout(&self, start_time: Instant) -> Result<()> {
+        match self.config.timeout {
+            Some(timeout) if start_time.elapsed() > timeout => {
+                Err(RuleEngineError::EvaluationError {
+                    message: "Execution timeout exceeded".to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Add a finished cycle to the run's totals and return whether another
+    /// cycle should run
+    ///
+    /// The run stops when a rule halted it, when `stop` holds, or when nothing
+    /// fired and no exhausted agenda group falls through to the previous one.
+    fn end_cycle(
+        &mut self,
+        totals: &mut RunTotals,
+        mut cycle: CycleResult,
+        facts: &Facts,
+        stop: &dyn Fn(&Facts) -> bool,
+    ) -> bool {
+        totals.rules_evaluated += cycle.rules_evaluated;
+        totals.rules_fired += cycle.fired_rules.len();
+        totals.emitted_events.append(&mut cycle.emitted_events);
+
+        if cycle.halted {
+            totals.halted = true;
+            return false;
+        }
+        if stop(facts) {
+            return false;
+        }
+        if !cycle.any_rule_fired && !self.agenda_manager.fall_through() {
+            return false;
+        }
+
+        // Sync any new workflow agenda activations at the end of each cycle
+        self.sync_workflow_agenda_activations();
+        true
+    }
+
+    /// Reset the per-run state shared by `run_cycles` and `execute_async`
+    fn begin_run(&mut self, facts: &Facts) {
+        // Process any pending agenda group activations from workflow engine
+        self.sync_workflow_agenda_activations();
+
+        // Evaluate runtime enable predicates once, before the first cycle
+        self.disabled_by_predicate = self.rules_disabled_by_predicate(facts);
+        self.fire_counts.clear();
+        self.scores.clear();
+        self.action_failures.clear();
+        self.tripped_actions.clear();
+        self.timed_out_rules.clear();
+        self.actions_executed = 0;
+
+        if let Some(provenance) = self.provenance.as_mut() {
+            provenance.clear();
+        }
+
+        #[cfg(feature = "otel")]
+        {
+            self.otel_trace = self
+                .otel_exporter
+                .as_ref()
+                .map(|_| crate::engine::otel::ExecutionTrace::new());
+        }
+    }
+
+    /// Export the run's trace and collect the per-run state into its result
+    fn finish_run(&mut self, totals: RunTotals, start_time: Instant) -> GruleExecutionResult {
+        let execution_time = start_time.elapsed();
+
+        #[cfg(feature = "otel")]
+        if let (Some(export), Some(mut trace)) = (&self.otel_exporter, self.otel_trace.take()) {
+            trace.finish(totals.cycle_count);
+            export(&trace);
+        }
+
+        GruleExecutionResult {
+            cycle_count: totals.cycle_count,
+            rules_evaluated: totals.rules_evaluated,
+            rules_fired: totals.rules_fired,
+            execution_time,
+            halted: totals.halted,
+            emitted_events: totals.emitted_events,
+            scores: std::mem::take(&mut self.scores),
+            tripped_actions: std::mem::take(&mut self.tripped_actions),
+            timed_out_rules: std::mem::take(&mut self.timed_out_rules),
+        }
+    }
+
+    /// Run exactly one evaluation cycle against the given facts
+    ///
+    /// Pending workflow activations and enable predicates are processed first,
+    /// as at the start of `execute`. Use [`CycleResult::any_rule_fired`] to
+    /// decide whether another cycle is worth running.
+    pub fn execute_once(&mut self, facts: &Facts) -> Result<CycleResult> {
+        self.sync_workflow_agenda_activations();
+        let _clock = clock::activate(self.config.clock.clone());
+        self.disabled_by_predicate = self.rules_disabled_by_predicate(facts);
+        self.actions_executed = 0;
+        self.execute_single_cycle(facts, self.config.clock.now(), &mut |_, _| {})
+    }
+
+    /// Update a fact in the engine's persistent working memory and queue the
+    /// rules whose conditions read it
+    ///
+    /// Nothing fires until [`run_pending`](Self::run_pending). A missing root
+    /// object is created, so `ingest("User.Age", ..)` works on an empty session.
+    pub fn ingest(&mut self, path: &str, value: Value) -> Result<()> {
+        let root = path.split('.').next().unwrap_or(path);
+        if root != path && !self.session.facts.contains(root) {
+            self.session
+                .facts
+                .add_value(root, Facts::create_object(vec![]))?;
+        }
+        self.session.facts.set_nested(path, value)?;
+        self.queue_rules_reading(path);
+        Ok(())
+    }
+
+    /// Fire the queued rules whose conditions have newly become true
+    ///
+    /// Only rules queued by [`ingest`](Self::ingest), or by facts written by
+    /// rules fired here, are evaluated. A rule fires once when its conditions
+    /// start to hold and again only after they have stopped holding in
+    /// between. Agenda and activation groups are not applied.
+    pub fn run_pending(&mut self) -> Result<GruleExecutionResult> {
+        let start_time = Instant::now();
+        let _clock = clock::activate(self.config.clock.clone());
+        let facts = self.session.facts.clone();
+        let now = self.config.clock.now();
+        let mut cycle_count = 0;
+        let mut rules_evaluated = 0;
+        let mut rules_fired = 0;
+        let mut halted = false;
+
+        self.halt_requested = false;
+        self.emitted_events.clear();
+        self.disabled_by_predicate = self.rules_disabled_by_predicate(&facts);
+        self.fire_counts.clear();
+        self.scores.clear();
+        self.action_failures.clear();
+        self.tripped_actions.clear();
+        self.timed_out_rules.clear();
+        self.actions_executed = 0;
+
+        while !self.session.pending.is_empty() && cycle_count < self.config.max_cycles {
+            cycle_count += 1;
+            let queued = std::mem::take(&mut self.session.pending);
+
+            let rule_indices = self.agenda_order(&facts);
+            for rule_index in rule_indices {
+                let Some(rule) = self.knowledge_base.get_rule_by_index(rule_index) else {
+                    continue;
+                };
+                if !queued.contains(&rule.name)
+                    || !rule.enabled
+                    || self.disabled_by_predicate.contains(&rule.name)
+                    || !rule.is_active_at(now)
+                    || self.fire_budget_exhausted(&rule)
+                {
+                    continue;
+                }
+
+                let rule_start = Instant::now();
+                rules_evaluated += 1;
+                let matched = self.evaluate_rule_conditions(&rule, &facts)?;
+                if self.rule_overran(&rule, rule_start) {
+                    continue;
+                }
+                if !matched {
+                    self.session.satisfied.remove(&rule.name);
+                    continue;
+                }
+                if !self.session.satisfied.insert(rule.name.clone()) {
+                    continue;
+                }
+
+                if !self.fire_rule(&rule, &facts, rule_start)? {
+                    self.session.satisfied.remove(&rule.name);
+                    continue;
+                }
+                rules_fired += 1;
+                for written in rule_writes(&rule.actions) {
+                    self.queue_rules_reading(&written);
+                }
+
+                if self.halt_requested {
+                    halted = true;
+                    break;
+                }
+            }
+
+            if halted {
+                break;
+            }
+        }
+
+        Ok(GruleExecutionResult {
+            cycle_count,
+            rules_evaluated,
+            rules_fired,
+            execution_time: start_time.elapsed(),
+            halted,
+            emitted_events: std::mem::take(&mut self.emitted_events),
+            scores: std::mem::take(&mut self.scores),
+            tripped_actions: std::mem::take(&mut self.tripped_actions),
+            timed_out_rules: std::mem::take(&mut self.timed_out_rules),
+        })
+    }
+
+    /// Persistent working memory used by `ingest` and `run_pending`
+    pub fn session_facts(&self) -> &Facts {
+        &self.session.facts
+    }
+
+    /// Queue every rule with a condition on `path`, a parent of it or a child of it
+    fn queue_rules_reading(&mut self, path: &str) {
+        for rule in self.knowledge_base.get_rules() {
+            let affected = rule_reads(&rule.conditions).iter().any(|read| {
+                read == path
+                    || read.starts_with(&format!("{}.", path))
+                    || path.starts_with(&format!("{}.", read))
+            });
+            if affected {
+                self.session.queue(&rule.name);
+            }
+        }
+    }
+
+    /// Evaluate every eligible rule once, firing those whose conditions match
+    fn execute_single_cycle(
+        &mut self,
+        facts: &Facts,
+        timestamp: DateTime<Utc>,
+        on_rule_fired: &mut dyn FnMut(&str, &Facts),
+    ) -> Result<CycleResult> {
+        let mut result = CycleResult::default();
+
+        // Process rules by index to avoid cloning
+        for rule_index in self.begin_cycle(facts)? {
+            let Some(rule) = self.knowledge_base.get_rule_by_index(rule_index) else {
+                continue;
+            };
+            let Some(rule_start) = self.match_rule(&rule, facts, timestamp, &mut result)? else {
+                continue;
+            };
+            if self.fire_rule(&rule, facts, rule_start)?
+                && self.count_fired(&rule, facts, &mut result, on_rule_fired)
+            {
+                break;
+            }
+        }
+
+        result.emitted_events = std::mem::take(&mut self.emitted_events);
+        Ok(result)
+    }
+
+    /// Prepare a new cycle and return the rule indices in firing order
+    fn begin_cycle(&mut self, facts: &Facts) -> Result<Vec<usize>> {
+        self.halt_requested = false;
+        self.emitted_events.clear();
+
+        // Load rules for a lazily registered namespace the first time it has focus
+        self.knowledge_base
+            .load_namespace(self.agenda_manager.get_active_group())?;
+
+        // Reset activation groups for each cycle
+        self.activation_group_manager.reset_cycle();
+
+        // Get rule indices sorted by salience (highest first) - avoids cloning rules
+        // Dynamic salience is re-evaluated every cycle before sorting
+        Ok(self.agenda_order(facts))
+    }
+
+    /// Whether the rule may be evaluated in the current cycle at `timestamp`
+    fn rule_eligible(&self, rule: &crate::engine::rule::Rule, timestamp: DateTime<Utc>) -> bool {
+        if !rule.enabled
+            || self.disabled_by_predicate.contains(&rule.name)
+            || !self.selected_by_tags(rule)
+            || !self.selected_by_event(rule)
+        {
+            return false;
+        }
+
+        if !self.agenda_manager.should_evaluate_rule(rule) {
+            return false;
+        }
+
+        // Check date effective/expires
+        if !rule.is_active_at(timestamp) {
+            return false;
+        }
+
+        // Check agenda group constraints (lock-on-active)
+        if !self.agenda_manager.can_fire_rule(rule) {
+            return false;
+        }
+
+        // Check activation group constraints (only one rule per group can fire)
+        if !self.activation_group_manager.can_fire(rule) {
+            return false;
+        }
+
+        // Check no-loop: skip if already fired in this execution cycle
+        if rule.no_loop && self.fired_rules_global.contains(&rule.name) {
+            if self.config.debug_mode {
+                println!("⛔ Skipping '{}' due to no_loop (already fired)", rule.name);
+            }
+            return false;
+        }
+
+        // Check max-fires: skip once the rule has used its budget for this run
+        if self.fire_budget_exhausted(rule) {
+            if self.config.debug_mode {
+                println!("⛔ Skipping '{}' due to max_fires", rule.name);
+            }
+            return false;
+        }
+
+        true
+    }
+
+    /// Evaluate the conditions of an eligible rule, counting it in `result`
+    ///
+    /// Returns the time the rule started when its conditions matched within
+    /// its `timeout`, and `None` otherwise.
+    fn match_rule(
+        &mut self,
+        rule: &crate::engine::rule::Rule,
+        facts: &Facts,
+        timestamp: DateTime<Utc>,
+        result: &mut CycleResult,
+    ) -> Result<Option<Instant>> {
+        if !self.rule_eligible(rule, timestamp) {
+            return Ok(None);
+        }
+
+        if self.config.debug_mode {
+            println!(
+                "🔍 Checking rule '{}' (no_loop: {})",
+                rule.name, rule.no_loop
+            );
+        }
+
+        let rule_start = Instant::now();
+        result.rules_evaluated += 1;
+
+        let condition_result = self.evaluate_rule_conditions(rule, facts)?;
+        if self.rule_overran(rule, rule_start) {
+            return Ok(None);
+        }
+
+        if self.config.debug_mode {
+            println!(
+                "   Rule '{}' condition result: {}",
+                rule.name, condition_result
+            );
+        }
+
+        if !condition_result {
+            if let Some(analytics) = &mut self.analytics {
+                analytics.record_execution(&rule.name, rule_start.elapsed(), false, false, None, 0);
+            }
+            return Ok(None);
+        }
+        Ok(Some(rule_start))
+    }
+
+    /// Count a rule that fired in `result` and return whether it halted the
+    /// run, in which case no other rule may fire in this cycle
+    fn count_fired(
+        &mut self,
+        rule: &crate::engine::rule::Rule,
+        facts: &Facts,
+        result: &mut CycleResult,
+        on_rule_fired: &mut dyn FnMut(&str, &Facts),
+    ) -> bool {
+        result.any_rule_fired = true;
+        result.fired_rules.push(rule.name.clone());
+        on_rule_fired(&rule.name, facts);
+
+        result.halted = self.halt_requested;
+        result.halted
+    }
+
+    /// Rule indices in firing order: salience plus the group's base salience,
+    /// ties broken by the configured conflict strategy
+    fn agenda_order(&self, facts: &Facts) -> Vec<usize> {
+        self.knowledge_base.get_rules_by_strategy_with_base(
+            facts,
+            self.config.conflict_strategy,
+            |rule| self.agenda_manager.group_salience(rule),
+        )
+    }
+
+    /// Whether the rule has already fired `max_fires` times in this run
+    fn fire_budget_exhausted(&self, rule: &crate::engine::rule::Rule) -> bool {
+        rule.max_fires
+            .is_some_and(|max| self.fire_counts.get(&rule.name).copied().unwrap_or(0) >= max)
+    }
+
+    /// Whether the rule has spent longer than its own `timeout` since
+    /// `rule_start`; an overrun is logged and recorded for the run result
+    fn rule_overran(&mut self, rule: &crate::engine::rule::Rule, rule_start: Instant) -> bool {
+        let Some(timeout) = rule.timeout else {
+            return false;
+        };
+        let elapsed = rule_start.elapsed();
+        if elapsed <= timeout {
+            return false;
+        }
+
+        warn!(
+            "Rule '{}' took {:?}, over its {:?} timeout; skipping it",
+            rule.name, elapsed, timeout
+        );
+        self.timed_out_rules.push(RuleTimedOut {
+            rule_name: rule.name.clone(),
+            timeout,
+            elapsed,
+        });
+        true
+    }
+
+    /// Run a matched rule's actions and record that it fired
+    ///
+    /// Returns `false` when the rule overran its `timeout` partway through its
+    /// actions. Its fact writes are then rolled back and it does not count as
+    /// fired. `fire_rule_async` fires rules the same way.
+    fn fire_rule(
+        &mut self,
+        rule: &crate::engine::rule::Rule,
         facts: &Facts,
+        rule_start: Instant,
+    ) -> Result<bool> {
+        self.begin_firing(rule, facts);
+        for action in &rule.actions {
+            let outcome = self.perform_action(&rule.name, action, facts);
+            if !self.settle_action(rule, facts, rule_start, outcome)? {
+                return Ok(false);
+            }
+        }
+        self.finish_firing(rule, facts, rule_start);
+        Ok(true)
+    }
+
+    /// Start firing a matched rule; a rule with its own `timeout` records its
+    /// fact writes so that an overrun can undo them
+    fn begin_firing(&self, rule: &crate::engine::rule::Rule, facts: &Facts) {
+        if self.config.debug_mode {
+            println!(
+                "🔥 Firing rule '{}' (salience: {})",
+                rule.name, rule.salience
+            );
+        }
+        if rule.timeout.is_some() {
+            facts.begin_undo_frame();
+        }
+    }
+
+    /// Check the outcome of one of a firing rule's actions
+    ///
+    /// Returns `Ok(true)` when the next action may run. When the action failed
+    /// or the rule overran its `timeout`, the rule's bindings are cleared, the
+    /// writes of a rule with a `timeout` are rolled back, and the error (or
+    /// `Ok(false)` for an overrun) is returned.
+    fn settle_action(
+        &mut self,
+        rule: &crate::engine::rule::Rule,
+        facts: &Facts,
+        rule_start: Instant,
+        outcome: Result<()>,
+    ) -> Result<bool> {
+        if outcome.is_ok() && !self.rule_overran(rule, rule_start) {
+            return Ok(true);
+        }
+        if rule.timeout.is_some() {
+            facts.rollback_undo_frame();
+        }
+        clear_rule_bindings(rule, facts);
+        outcome.map(|_| false)
+    }
+
+    /// Finish firing a rule whose actions have all run
+    fn finish_firing(
+        &mut self,
+        rule: &crate::engine::rule::Rule,
+        facts: &Facts,
+        rule_start: Instant,
+    ) {
+        if rule.timeout.is_some() {
+            facts.commit_undo_frame();
+        }
+        self.record_fired(rule, facts, rule_start);
+    }
+
+    /// Bookkeeping after a rule's actions have all run: bindings, analytics,
+    /// provenance, tracing, no-loop and group tracking
+    fn record_fired(
+        &mut self,
+        rule: &crate::engine::rule::Rule,
+        facts: &Facts,
+        rule_start: Instant,
+    ) {
+        clear_rule_bindings(rule, facts);
+
+        let rule_duration = rule_start.elapsed();
+
+        // Record analytics if enabled
+        if let Some(analytics) = &mut self.analytics {
+            analytics.record_execution(&rule.name, rule_duration, true, true, None, 0);
+        }
+
+        if let Some(provenance) = self.provenance.as_mut() {
+            provenance.record_rule(rule, facts);
+        }
+
+        #[cfg(feature = "otel")]
+        if let Some(trace) = self.otel_trace.as_mut() {
+            trace.record_rule(rule, facts, rule_duration);
+        }
+
+        // Track that this rule fired globally (for no-loop support)
+        if rule.no_loop {
+            self.fired_rules_global.insert(rule.name.clone());
+            if self.config.debug_mode {
+                println!("  🔒 Marked '{}' as fired (no_loop tracking)", rule.name);
+            }
+        }
+        *self.fire_counts.entry(rule.name.clone()).or_insert(0) += 1;
+
+        // Mark rule as fired for agenda and activation group management
+        self.agenda_manager.mark_rule_fired(rule);
+        self.activation_group_manager.mark_fired(rule);
+    }
+
+    /// Evaluate conditions against facts
+    fn evaluate_conditions(
+        &self,
+        conditions: &crate::engine::rule::ConditionGroup,
+        facts: &Facts,
+    ) -> Result<bool> {
+        self.evaluate_conditions_timed(conditions, facts, &mut None)
+    }
+
+    /// Evaluate conditions, pushing the time spent on each leaf condition
+    /// (depth-first, left to right) into `timings` when it is set
+    fn evaluate_conditions_timed(
+        &self,
+        conditions: &crate::engine::rule::ConditionGroup,
+        facts: &Facts,
+        timings: &mut Option<Vec<Duration>>,
     ) -> Result<bool> {
-        use crate::engine::pattern_matcher::PatternMatcher;
         use crate::engine::rule::ConditionGroup;
 
         match conditions {
-            ConditionGroup::Single(condition) => self.evaluate_single_condition(condition, facts),
             ConditionGroup::Compound {
                 left,
                 operator,
                 right,
             } => {
-                let left_result = self.evaluate_conditions(left, facts)?;
-                let right_result = self.evaluate_conditions(right, facts)?;
+                let left_result = self.evaluate_conditions_timed(left, facts, timings)?;
+                let right_result = self.evaluate_conditions_timed(right, facts, timings)?;
 
                 match operator {
                     crate::types::LogicalOperator::And => Ok(left_result && right_result),
                     crate::types::LogicalOperator::Or => Ok(left_result || right_result),
+                    crate::types::LogicalOperator::Xor => Ok(left_result != right_result),
                     crate::types::LogicalOperator::Not => Err(RuleEngineError::EvaluationError {
                         message: "NOT operator should not appear in compound conditions"
                             .to_string(),
@@ -655,9 +1953,35 @@ impl RustRuleEngine {
                 }
             }
             ConditionGroup::Not(condition) => {
-                let result = self.evaluate_conditions(condition, facts)?;
+                let result = self.evaluate_conditions_timed(condition, facts, timings)?;
                 Ok(!result)
             }
+            leaf => {
+                let start = Instant::now();
+                let result = self.evaluate_leaf_condition(leaf, facts);
+                if let Some(timings) = timings {
+                    timings.push(start.elapsed());
+                }
+                result
+            }
+        }
+    }
+
+    /// Evaluate a condition that is not a compound or a negation
+    fn evaluate_leaf_condition(
+        &self,
+        condition: &crate::engine::rule::ConditionGroup,
+        facts: &Facts,
+    ) -> Result<bool> {
+        use crate::engine::pattern_matcher::PatternMatcher;
+        use crate::engine::rule::ConditionGroup;
+
+        match condition {
+            ConditionGroup::Single(condition) => self.evaluate_single_condition(condition, facts),
+            ConditionGroup::Constant(value) => Ok(*value),
+            ConditionGroup::Compound { .. } | ConditionGroup::Not(_) => {
+                self.evaluate_conditions(condition, facts)
+            }
             // Pattern matching conditions
             ConditionGroup::Exists(condition) => {
                 Ok(PatternMatcher::evaluate_exists(condition, facts))
@@ -755,7 +2079,7 @@ impl RustRuleEngine {
 
             for condition_str in source_conditions {
                 // Parse condition: "status == \"completed\""
-                if !self.evaluate_condition_string(condition_str, &instance_facts) {
+                if !self.evaluate_condition_string(condition_str, &instance_facts, facts) {
                     matches = false;
                     break;
                 }
@@ -806,6 +2130,27 @@ impl RustRuleEngine {
                 }
                 self.fact_value_to_value(&state.get_result())
             }
+            "count_distinct" => {
+                let mut state = CountDistinctFunction.init();
+                for value in &matching_values {
+                    state.accumulate(&self.value_to_fact_value(value));
+                }
+                self.fact_value_to_value(&state.get_result())
+            }
+            "median" => {
+                let mut state = MedianFunction.init();
+                for value in &matching_values {
+                    state.accumulate(&self.value_to_fact_value(value));
+                }
+                self.fact_value_to_value(&state.get_result())
+            }
+            "stddev" => {
+                let mut state = StdDevFunction.init();
+                for value in &matching_values {
+                    state.accumulate(&self.value_to_fact_value(value));
+                }
+                self.fact_value_to_value(&state.get_result())
+            }
             _ => {
                 return Err(RuleEngineError::EvaluationError {
                     message: format!("Unknown accumulate function: {}", function),
@@ -851,12 +2196,20 @@ impl RustRuleEngine {
             FactValue::String(s) => Value::String(s.clone()),
             FactValue::Boolean(b) => Value::Boolean(*b),
             FactValue::Array(_) => Value::String(format!("{:?}", fact_value)),
-            FactValue::Null => Value::String("null".to_string()),
+            FactValue::Null => Value::Null,
         }
     }
 
     /// Helper: Evaluate a condition string against facts
-    fn evaluate_condition_string(&self, condition: &str, facts: &HashMap<String, Value>) -> bool {
+    ///
+    /// An unquoted dotted RHS (e.g. `region == User.Region`) is resolved against
+    /// `outer_facts` first and falls back to literal parsing if no such fact exists.
+    fn evaluate_condition_string(
+        &self,
+        condition: &str,
+        facts: &HashMap<String, Value>,
+        outer_facts: &Facts,
+    ) -> bool {
         // Simple condition parser: "field == value" or "field != value", etc.
         let condition = condition.trim();
 
@@ -866,13 +2219,21 @@ impl RustRuleEngine {
         for op in &operators {
             if let Some(pos) = condition.find(op) {
                 let field = condition[..pos].trim();
-                let value_str = condition[pos + op.len()..]
-                    .trim()
-                    .trim_matches('"')
-                    .trim_matches('\'');
+                let raw_value = condition[pos + op.len()..].trim();
+
+                let resolved = if Self::is_fact_path(raw_value) {
+                    outer_facts
+                        .get_nested(raw_value)
+                        .or_else(|| outer_facts.get(raw_value))
+                        .map(|value| value.to_string())
+                } else {
+                    None
+                };
+                let value_str = resolved
+                    .unwrap_or_else(|| raw_value.trim_matches('"').trim_matches('\'').to_string());
 
                 if let Some(field_value) = facts.get(field) {
-                    return self.compare_values(field_value, op, value_str);
+                    return self.compare_values(field_value, op, &value_str);
                 } else {
                     return false;
                 }
@@ -882,6 +2243,15 @@ impl RustRuleEngine {
         false
     }
 
+    /// Check whether a token looks like an unquoted dotted fact path (`User.Region`)
+    fn is_fact_path(token: &str) -> bool {
+        token.contains('.')
+            && token.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+    }
+
     /// Helper: Compare values
     fn compare_values(&self, field_value: &Value, operator: &str, value_str: &str) -> bool {
         match field_value {
@@ -935,13 +2305,33 @@ impl RustRuleEngine {
         }
     }
 
-    /// Evaluate rule conditions - wrapper for evaluate_conditions for compatibility
+    /// Evaluate rule conditions, scoping `matches` captures and `$name : Field`
+    /// bindings to the rule
+    ///
+    /// Values left by an earlier rule are dropped first, and the rule's own
+    /// values are dropped again when it does not match.
+    ///
+    /// With condition profiling enabled in the analytics config, the time spent
+    /// on each condition is added to the rule's metrics.
     fn evaluate_rule_conditions(
-        &self,
+        &mut self,
         rule: &crate::engine::rule::Rule,
         facts: &Facts,
     ) -> Result<bool> {
-        self.evaluate_conditions(&rule.conditions, facts)
+        clear_rule_bindings(rule, facts);
+        let mut timings = self
+            .analytics
+            .as_ref()
+            .filter(|analytics| analytics.profiles_conditions())
+            .map(|_| Vec::new());
+        let matched = self.evaluate_conditions_timed(&rule.conditions, facts, &mut timings)?;
+        if let (Some(analytics), Some(timings)) = (self.analytics.as_mut(), timings) {
+            analytics.record_condition_times(&rule.name, &timings);
+        }
+        if !matched {
+            clear_rule_bindings(rule, facts);
+        }
+        Ok(matched)
     }
 
     /// Check if a fact object has been retracted
@@ -963,6 +2353,7 @@ impl RustRuleEngine {
                 // Check if the fact object has been retracted
                 // Extract object name from field (e.g., "Session.expired" -> "Session")
                 if let Some(object_name) = field_name.split('.').next() {
+                    let object_name = object_name.trim_end_matches('?');
                     if self.is_retracted(object_name, facts) {
                         if self.config.debug_mode {
                             println!("    🗑️ Skipping retracted fact: {}", object_name);
@@ -994,6 +2385,11 @@ impl RustRuleEngine {
                 // rules like `L1 > L1Min` where the parser may have stored "L1Min"
                 // as a string literal.
                 let rhs = match &condition.value {
+                    crate::types::Value::Expression(expr)
+                        if crate::engine::globals::global_name(expr).is_some() =>
+                    {
+                        self.globals.resolve(expr)?
+                    }
                     crate::types::Value::String(s) => {
                         // Try nested lookup first, then flat lookup
                         facts
@@ -1021,7 +2417,57 @@ impl RustRuleEngine {
                     println!("      Resolved RHS for comparison: {:?}", rhs);
                 }
 
-                condition.operator.evaluate(&field_value, &rhs)
+                // Objects (and arrays against arrays) only support equality
+                if condition.operator.is_ordering() {
+                    let structured = match (&field_value, &rhs) {
+                        (Value::Object(_), _) | (_, Value::Object(_)) => Some("object"),
+                        (Value::Array(_), Value::Array(_)) => Some("array"),
+                        _ => None,
+                    };
+                    if let Some(actual) = structured {
+                        return Err(RuleEngineError::TypeMismatch {
+                            expected: format!("orderable values for '{}'", field_name),
+                            actual: actual.to_string(),
+                        });
+                    }
+                }
+
+                // A pattern with capture groups is a regex whose groups are bound as $0, $1, ...
+                let regex_match = match (&condition.operator, &field_value, &rhs) {
+                    (Operator::Matches, Value::String(text), Value::String(pattern))
+                        if pattern.contains('(') =>
+                    {
+                        rexile::Pattern::new(pattern)
+                            .ok()
+                            .map(|regex| match regex.captures(text) {
+                                Some(captures) => {
+                                    bind_match_captures(facts, |i| captures.get(i));
+                                    true
+                                }
+                                None => false,
+                            })
+                    }
+                    _ => None,
+                };
+
+                regex_match.unwrap_or_else(|| {
+                    facts.evaluate_operator(field_name, &condition.operator, &field_value, &rhs)
+                })
+            }
+            ConditionExpression::Bind { variable, field } => {
+                let value = facts
+                    .get_nested(field)
+                    .or_else(|| facts.get(field))
+                    .unwrap_or(Value::Null);
+                if self.config.debug_mode {
+                    println!("    📌 Binding {} = {:?}", variable, value);
+                }
+                if value == Value::Null {
+                    false
+                } else {
+                    facts.set(variable, value);
+                    true
+                }
             }
             ConditionExpression::FunctionCall { name, args } => {
                 // Function call condition
@@ -1035,7 +2481,7 @@ impl RustRuleEngine {
                     );
                 }
 
-                if let Some(function) = self.custom_functions.get(name) {
+                if let Some(function) = self.custom_function(name) {
                     // Resolve arguments from facts
                     let arg_values: Vec<Value> = args
                         .iter()
@@ -1076,7 +2522,7 @@ impl RustRuleEngine {
                 }
 
                 // Check if name is a registered custom function
-                if let Some(function) = self.custom_functions.get(name) {
+                if let Some(function) = self.custom_function(name) {
                     // Resolve arguments from facts
                     let arg_values: Vec<Value> = args
                         .iter()
@@ -1202,27 +2648,33 @@ impl RustRuleEngine {
 
     /// Execute an action
     fn execute_action(&mut self, action: &ActionType, facts: &Facts) -> Result<()> {
+        self.count_action()?;
+
         match action {
             ActionType::Set { field, value } => {
                 // Evaluate expression if value is an Expression
-                let evaluated_value = match value {
-                    Value::Expression(expr) => {
-                        // Evaluate the expression with current facts
-                        crate::expression::evaluate_expression(expr, facts)?
-                    }
-                    _ => value.clone(),
+                let evaluated_value = self.resolve_action_value(value, facts)?;
+                self.assign_field(field, evaluated_value, facts)?;
+            }
+            ActionType::CallAndBind {
+                field,
+                function,
+                args,
+            } => {
+                let args = self.resolve_method_args(args, facts);
+                let Some(custom_func) = self.custom_function(function) else {
+                    return Err(RuleEngineError::EvaluationError {
+                        message: format!("Function '{}' is not registered. Use engine.register_function() to add custom functions.", function),
+                    });
                 };
-
-                // Try nested first, then fall back to flat key setting
-                if facts.set_nested(field, evaluated_value.clone()).is_err() {
-                    // If nested fails, use flat key
-                    facts.set(field, evaluated_value.clone());
-                }
+                let result = custom_func(&args, facts)?;
                 if self.config.debug_mode {
-                    println!("  ✅ Set {field} = {evaluated_value:?}");
+                    println!("  🎯 Called {function}({args:?}) -> {result:?}");
                 }
+                self.assign_field(field, result, facts)?;
             }
             ActionType::Log { message } => {
+                let message = self.interpolate_message(message, facts);
                 println!("📋 LOG: {}", message);
             }
             ActionType::MethodCall {
@@ -1230,9 +2682,22 @@ impl RustRuleEngine {
                 method,
                 args,
             } => {
-                let result = self.execute_method_call(object, method, args, facts)?;
-                if self.config.debug_mode {
-                    println!("  🔧 Called {object}.{method}({args:?}) -> {result}");
+                let args = self.resolve_method_args(args, facts);
+
+                // A handler registered under the method name takes precedence
+                // over the generic setter/getter handling
+                if let Some(handler) = self.action_handlers.get(method) {
+                    let params: HashMap<String, Value> = args
+                        .iter()
+                        .enumerate()
+                        .map(|(i, arg)| (i.to_string(), arg.clone()))
+                        .collect();
+                    handler(&params, facts)?;
+                } else {
+                    let result = self.execute_method_call(object, method, &args, facts)?;
+                    if self.config.debug_mode {
+                        println!("  🔧 Called {object}.{method}({args:?}) -> {result}");
+                    }
                 }
             }
             ActionType::Retract { object } => {
@@ -1246,7 +2711,11 @@ impl RustRuleEngine {
                 action_type,
                 params,
             } => {
-                if let Some(handler) = self.action_handlers.get(action_type) {
+                if self.action_tripped(action_type) {
+                    if self.config.debug_mode {
+                        println!("  ⛔ Skipping tripped custom action: {action_type}");
+                    }
+                } else if let Some(handler) = self.action_handlers.get(action_type) {
                     if self.config.debug_mode {
                         println!(
                             "  🎯 Executing custom action: {action_type} with params: {params:?}"
@@ -1257,7 +2726,8 @@ impl RustRuleEngine {
                     let resolved_params = self.resolve_action_parameters(params, facts)?;
 
                     // Execute the registered handler
-                    handler(&resolved_params, facts)?;
+                    let outcome = handler(&resolved_params, facts);
+                    self.record_action_outcome(action_type, outcome)?;
                 } else {
                     if self.config.debug_mode {
                         println!("  ⚠️ No handler registered for custom action: {action_type}");
@@ -1267,12 +2737,19 @@ impl RustRuleEngine {
                         );
                     }
 
-                    // Return error if no handler found
-                    return Err(RuleEngineError::EvaluationError {
-                        message: format!(
-                            "No action handler registered for '{action_type}'. Use engine.register_action_handler() to add custom action handlers."
-                        ),
-                    });
+                    match self.config.on_missing_handler {
+                        MissingHandler::Error => {
+                            return Err(RuleEngineError::EvaluationError {
+                                message: format!(
+                                    "No action handler registered for '{action_type}'. Use engine.register_action_handler() to add custom action handlers."
+                                ),
+                            });
+                        }
+                        MissingHandler::Warn => {
+                            warn!("Skipping custom action '{action_type}': no handler registered");
+                        }
+                        MissingHandler::Skip => {}
+                    }
                 }
             }
             // 🔄 Workflow Actions
@@ -1314,6 +2791,46 @@ impl RustRuleEngine {
                 self.workflow_engine
                     .set_workflow_data(workflow_id, key.clone(), value.clone());
             }
+            ActionType::Halt => {
+                if self.config.debug_mode {
+                    println!("  🛑 Halt requested");
+                }
+                self.halt_requested = true;
+            }
+            ActionType::Emit {
+                event_type,
+                payload,
+            } => {
+                let mut payload = self.resolve_action_parameters(payload, facts)?;
+                for value in payload.values_mut() {
+                    if let Value::Expression(expr) = value {
+                        *value = crate::expression::evaluate_expression(expr, facts)?;
+                    }
+                }
+                if self.config.debug_mode {
+                    println!("  📣 Emitted {event_type}: {payload:?}");
+                }
+                self.emitted_events.push(EmittedEvent {
+                    event_type: event_type.clone(),
+                    payload,
+                });
+            }
+            ActionType::AddScore { target, amount } => {
+                let current = facts
+                    .get_nested(target)
+                    .or_else(|| facts.get(target))
+                    .and_then(|value| value.to_number())
+                    .unwrap_or(0.0);
+                let total = Value::Number(current + amount);
+                if facts.set_nested(target, total.clone()).is_err() {
+                    facts.set(target, total);
+                }
+                *self.scores.entry(target.clone()).or_insert(0.0) += amount;
+
+                if self.config.debug_mode {
+                    println!("  🎯 Added {} to score {}", amount, target);
+                }
+            }
             ActionType::Append { field, value } => {
                 // Evaluate expression if value is an Expression
                 let evaluated_value = match value {
@@ -1357,9 +2874,9 @@ impl RustRuleEngine {
     /// Evaluate arithmetic condition like "User.Age % 3 == 0"
     fn evaluate_arithmetic_condition(&self, expr: &str, facts: &Facts) -> Result<bool> {
         // Parse expression format: "left_expr operator right_value"
-        // e.g., "User.Age % 3 == 0" or "User.Price * 2 > 100"
+        // e.g., "User.Age % 3 == 0", "User.Price * 2 > 100" or "\"vip\" in User.Tags"
 
-        let operators = [">=", "<=", "==", "!=", ">", "<"];
+        let operators = [">=", "<=", "==", "!=", ">", "<", " in "];
         let mut split_pos = None;
         let mut found_op = "";
 
@@ -1398,10 +2915,11 @@ impl RustRuleEngine {
         };
 
         // Compare values
-        let operator =
-            Operator::from_str(found_op).ok_or_else(|| RuleEngineError::InvalidOperator {
+        let operator = Operator::from_str(found_op.trim()).ok_or_else(|| {
+            RuleEngineError::InvalidOperator {
                 operator: found_op.to_string(),
-            })?;
+            }
+        })?;
 
         Ok(operator.evaluate(&left_result, &right_val))
     }
@@ -1446,6 +2964,34 @@ impl RustRuleEngine {
         }
     }
 
+    /// Substitute `{field.path}` tokens in a message with current fact values
+    ///
+    /// Tokens that do not resolve to a fact are left in the message verbatim.
+    fn interpolate_message(&self, message: &str, facts: &Facts) -> String {
+        let mut result = String::with_capacity(message.len());
+        let mut rest = message;
+
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open + 1..].find('}').map(|i| open + 1 + i) else {
+                break;
+            };
+            result.push_str(&rest[..open]);
+
+            let token = rest[open + 1..close].trim();
+            match facts.get_nested(token).or_else(|| facts.get(token)) {
+                Some(value) if !token.is_empty() => result.push_str(&value.to_string()),
+                _ => {
+                    debug!("Unresolved log token '{{{}}}'", token);
+                    result.push_str(&rest[open..=close]);
+                }
+            }
+            rest = &rest[close + 1..];
+        }
+
+        result.push_str(rest);
+        result
+    }
+
     /// Handle logging functions (log, print, println)
     fn handle_log_function(&self, args: &[Value]) -> Result<String> {
         let message = if args.is_empty() {
@@ -1529,6 +3075,7 @@ impl RustRuleEngine {
                 Value::String(s) => Ok(s.len().to_string()),
                 Value::Array(arr) => Ok(arr.len().to_string()),
                 Value::Object(obj) => Ok(obj.len().to_string()),
+                Value::Bytes(bytes) => Ok(bytes.len().to_string()),
                 _ => Ok("1".to_string()), // Single value has length 1
             }
         } else {
@@ -1734,7 +3281,7 @@ impl RustRuleEngine {
         facts: &Facts,
     ) -> Result<String> {
         // Check if we have a registered custom function
-        if let Some(custom_func) = self.custom_functions.get(function) {
+        if let Some(custom_func) = self.custom_function(function) {
             if self.config.debug_mode {
                 println!("🎯 Calling registered function: {}({:?})", function, args);
             }
@@ -1925,6 +3472,24 @@ impl RustRuleEngine {
         }
     }
 
+    /// Resolve method call arguments, evaluating fact references (`$Car.Speed`
+    /// or `Car.Speed`) and arithmetic; anything that does not evaluate is
+    /// passed through unchanged
+    fn resolve_method_args(&self, args: &[Value], facts: &Facts) -> Vec<Value> {
+        // `$age` may name a bound variable; otherwise `$Order.Total` means `Order.Total`
+        let evaluate = |text: &str| {
+            crate::expression::evaluate_expression(text, facts)
+                .or_else(|_| crate::expression::evaluate_expression(&text.replace('$', ""), facts))
+        };
+        args.iter()
+            .map(|arg| match arg {
+                Value::Expression(text) => evaluate(text).unwrap_or_else(|_| arg.clone()),
+                // Strings are literals, even when they look like "v1.2-beta"
+                _ => arg.clone(),
+            })
+            .collect()
+    }
+
     /// Resolve action parameters by replacing fact references with actual values
     fn resolve_action_parameters(
         &self,
@@ -1949,6 +3514,10 @@ impl RustRuleEngine {
                         value.clone()
                     }
                 }
+                // Bound variable ($age) or regex capture ($1); unbound names stay as text
+                Value::Expression(name) if name.starts_with('$') => facts
+                    .get(name)
+                    .unwrap_or_else(|| Value::String(name.clone())),
                 _ => value.clone(),
             };
             resolved.insert(key.clone(), resolved_value);
@@ -1960,16 +3529,28 @@ impl RustRuleEngine {
     // 🔌 Plugin System Methods
 
     /// Load a plugin into the engine
+    ///
+    /// Fails with [`RuleEngineError::PluginConflict`] if the plugin declares an
+    /// action or function that a loaded plugin already provides, unless
+    /// [`PluginConfig::allow_override`] is set.
     pub fn load_plugin(
         &mut self,
         plugin: std::sync::Arc<dyn crate::engine::plugin::RulePlugin>,
     ) -> Result<()> {
-        // First register the plugin actions with this engine
-        plugin.register_actions(self)?;
-        plugin.register_functions(self)?;
-
-        // Then store it in the plugin manager
-        self.plugin_manager.load_plugin(plugin)
+        // Validate and store it in the plugin manager before touching handlers
+        let name = plugin.get_metadata().name.clone();
+        self.plugin_manager.load_plugin(plugin.clone())?;
+
+        // Then register the plugin actions with this engine
+        self.function_namespace = plugin.namespace().map(str::to_string);
+        let registered = plugin
+            .register_actions(self)
+            .and_then(|_| plugin.register_functions(self));
+        self.function_namespace = None;
+        if registered.is_err() {
+            self.plugin_manager.unload_plugin(&name)?;
+        }
+        registered
     }
 
     /// Unload a plugin from the engine
@@ -1986,12 +3567,16 @@ impl RustRuleEngine {
         // Unload old plugin
         self.plugin_manager.unload_plugin(name)?;
 
-        // Register new plugin actions
-        new_plugin.register_actions(self)?;
-        new_plugin.register_functions(self)?;
-
         // Load new plugin
-        self.plugin_manager.load_plugin(new_plugin)
+        self.plugin_manager.load_plugin(new_plugin.clone())?;
+
+        // Register new plugin actions
+        self.function_namespace = new_plugin.namespace().map(str::to_string);
+        let registered = new_plugin
+            .register_actions(self)
+            .and_then(|_| new_plugin.register_functions(self));
+        self.function_namespace = None;
+        registered
     }
 
     /// Get plugin information
@@ -2019,3 +3604,3253 @@ impl RustRuleEngine {
         self.plugin_manager = PluginManager::new(config);
     }
 }
+
+/// Store the groups of a `matches` regex as `$0` (whole match), `$1`, ...
+///
+/// Binding stops at the first group that did not participate in the match.
+fn bind_match_captures<'a>(facts: &Facts, group: impl Fn(usize) -> Option<&'a str>) {
+    clear_match_captures(facts);
+    let mut i = 0;
+    while let Some(text) = group(i) {
+        facts.set(&format!("${}", i), Value::String(text.to_string()));
+        i += 1;
+    }
+}
+
+/// Remove the `$0`, `$1`, ... facts bound by a `matches` condition
+fn clear_match_captures(facts: &Facts) {
+    let mut i = 0;
+    while facts.remove(&format!("${}", i)).is_some() {
+        i += 1;
+    }
+}
+
+/// Remove the rule's `$name : Field` bindings along with any `matches` captures
+fn clear_rule_bindings(rule: &crate::engine::rule::Rule, facts: &Facts) {
+    clear_match_captures(facts);
+    for variable in rule.conditions.bound_variables() {
+        facts.remove(variable);
+    }
+}
+
+/// Flatten nested object facts into dotted leaf paths
+fn flatten_fact_values(
+    prefix: &str,
+    values: &HashMap<String, Value>,
+    out: &mut HashMap<String, Value>,
+) {
+    for (key, value) in values {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Object(fields) if !fields.is_empty() => flatten_fact_values(&path, fields, out),
+            _ => {
+                out.insert(path, value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::rule::{Condition, ConditionGroup, Rule};
+
+    fn feature_flag_engine() -> RustRuleEngine {
+        let kb = KnowledgeBase::new("FeatureFlags");
+        let rule = Rule::new(
+            "NewCheckout".to_string(),
+            ConditionGroup::single(Condition::new(
+                "Cart.Total".to_string(),
+                Operator::GreaterThan,
+                Value::Integer(0),
+            )),
+            vec![ActionType::Set {
+                field: "Cart.Flow".to_string(),
+                value: Value::String("new".to_string()),
+            }],
+        )
+        .with_no_loop(true);
+        kb.add_rule(rule).unwrap();
+
+        let mut engine = RustRuleEngine::new(kb);
+        engine.set_rule_condition_enabled("NewCheckout", |facts| {
+            facts
+                .get_nested("Features.NewCheckout")
+                .and_then(|v| v.as_boolean())
+                .unwrap_or(false)
+        });
+        engine
+    }
+
+    #[test]
+    fn test_rule_condition_enabled_flips_across_runs() {
+        let mut engine = feature_flag_engine();
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Cart",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(50))]),
+            )
+            .unwrap();
+        facts
+            .add_value(
+                "Features",
+                Facts::create_object(vec![("NewCheckout".to_string(), Value::Boolean(false))]),
+            )
+            .unwrap();
+
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 0);
+        assert_eq!(facts.get_nested("Cart.Flow"), None);
+
+        facts
+            .set_nested("Features.NewCheckout", Value::Boolean(true))
+            .unwrap();
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 1);
+        assert_eq!(
+            facts.get_nested("Cart.Flow"),
+            Some(Value::String("new".to_string()))
+        );
+
+        facts
+            .set_nested("Features.NewCheckout", Value::Boolean(false))
+            .unwrap();
+        engine.reset_no_loop_tracking();
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 0);
+    }
+
+    #[test]
+    fn test_log_message_interpolation() {
+        let engine = RustRuleEngine::new(KnowledgeBase::new("LogTest"));
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![
+                    ("Name".to_string(), Value::String("Alice".to_string())),
+                    ("Score".to_string(), Value::Integer(42)),
+                ]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            engine.interpolate_message("User {User.Name} scored {User.Score}", &facts),
+            "User Alice scored 42"
+        );
+        assert_eq!(
+            engine.interpolate_message("Missing {User.Email} and {} stay", &facts),
+            "Missing {User.Email} and {} stay"
+        );
+        assert_eq!(
+            engine.interpolate_message("Unclosed {User.Name", &facts),
+            "Unclosed {User.Name"
+        );
+    }
+
+    #[test]
+    fn test_clear_rule_condition_enabled() {
+        let mut engine = feature_flag_engine();
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Cart",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(50))]),
+            )
+            .unwrap();
+
+        assert_eq!(engine.execute(&facts).unwrap().rules_fired, 0);
+
+        assert!(engine.clear_rule_condition_enabled("NewCheckout"));
+        assert_eq!(engine.execute(&facts).unwrap().rules_fired, 1);
+    }
+
+    #[test]
+    fn test_dynamic_salience_resorts_with_facts() {
+        let kb = KnowledgeBase::new("DynamicSalience");
+        let condition = || {
+            ConditionGroup::single(Condition::new(
+                "Order.Total".to_string(),
+                Operator::GreaterThan,
+                Value::Integer(0),
+            ))
+        };
+        kb.add_rule(Rule::new("Fixed".to_string(), condition(), vec![]).with_salience(50))
+            .unwrap();
+        kb.add_rule(
+            Rule::new("ByTotal".to_string(), condition(), vec![])
+                .with_dynamic_salience("Order.Total / 10"),
+        )
+        .unwrap();
+        let engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(1000))]),
+            )
+            .unwrap();
+
+        let order_names = |facts: &Facts| -> Vec<String> {
+            engine
+                .knowledge_base()
+                .get_rules_by_effective_salience(facts)
+                .into_iter()
+                .filter_map(|i| engine.knowledge_base().get_rule_by_index(i))
+                .map(|rule| rule.name)
+                .collect()
+        };
+
+        assert_eq!(order_names(&facts), vec!["ByTotal", "Fixed"]);
+
+        facts
+            .set_nested("Order.Total", Value::Integer(100))
+            .unwrap();
+        assert_eq!(order_names(&facts), vec!["Fixed", "ByTotal"]);
+    }
+
+    #[test]
+    fn test_conflict_strategy_respects_salience() {
+        let kb = KnowledgeBase::new("Strategies");
+        let single = || {
+            ConditionGroup::single(Condition::new(
+                "Order.Total".to_string(),
+                Operator::GreaterThan,
+                Value::Integer(0),
+            ))
+        };
+        kb.add_rule(Rule::new("Simple".to_string(), single(), vec![]).with_salience(10))
+            .unwrap();
+        kb.add_rule(Rule::new(
+            "Specific".to_string(),
+            ConditionGroup::and(single(), single()),
+            vec![],
+        ))
+        .unwrap();
+        kb.add_rule(Rule::new("Plain".to_string(), single(), vec![]))
+            .unwrap();
+
+        let facts = Facts::new();
+        let names = |strategy| -> Vec<String> {
+            kb.get_rules_by_strategy(&facts, strategy)
+                .into_iter()
+                .filter_map(|i| kb.get_rule_by_index(i))
+                .map(|rule| rule.name)
+                .collect()
+        };
+
+        assert_eq!(
+            names(ConflictStrategy::Complexity),
+            vec!["Simple", "Specific", "Plain"]
+        );
+        assert_eq!(
+            names(ConflictStrategy::Simplicity),
+            vec!["Simple", "Plain", "Specific"]
+        );
+        assert_eq!(
+            names(ConflictStrategy::Depth),
+            vec!["Simple", "Plain", "Specific"]
+        );
+    }
+
+    #[test]
+    fn test_starts_with_ends_with_conditions() {
+        let kb = KnowledgeBase::new("StringOps");
+        kb.add_rules_from_grl(
+            r#"
+            rule "ApiRequest" no-loop {
+                when
+                    Request.Path startsWith "/api/" && Request.File endsWith ".pdf"
+                then
+                    Request.Routed = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let request = |path: &str, file: &str| {
+            Facts::create_object(vec![
+                ("Path".to_string(), Value::String(path.to_string())),
+                ("File".to_string(), Value::String(file.to_string())),
+            ])
+        };
+
+        let facts = Facts::new();
+        facts
+            .add_value("Request", request("/api/v1/docs", "report.pdf"))
+            .unwrap();
+        engine.execute(&facts).unwrap();
+        assert_eq!(
+            facts.get_nested("Request.Routed"),
+            Some(Value::Boolean(true))
+        );
+
+        let facts = Facts::new();
+        facts
+            .add_value("Request", request("/static/docs", "report.pdf"))
+            .unwrap();
+        engine.reset_no_loop_tracking();
+        engine.execute(&facts).unwrap();
+        assert_eq!(facts.get_nested("Request.Routed"), None);
+    }
+
+    #[test]
+    fn test_what_if_reports_flipped_discount() {
+        let kb = KnowledgeBase::new("Discounts");
+        kb.add_rules_from_grl(
+            r#"
+            rule "GoldDiscount" no-loop {
+                when
+                    Customer.Tier == "gold"
+                then
+                    Order.Discount = 10;
+            }
+
+            rule "StandardDiscount" no-loop {
+                when
+                    Customer.Tier == "standard"
+                then
+                    Order.Discount = 2;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Customer",
+                Facts::create_object(vec![(
+                    "Tier".to_string(),
+                    Value::String("standard".to_string()),
+                )]),
+            )
+            .unwrap();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Discount".to_string(), Value::Integer(0))]),
+            )
+            .unwrap();
+
+        let mut changes = HashMap::new();
+        changes.insert(
+            "Customer.Tier".to_string(),
+            Value::String("gold".to_string()),
+        );
+        let result = engine.what_if(&facts, changes).unwrap();
+
+        assert_eq!(result.baseline_fired, vec!["StandardDiscount"]);
+        assert_eq!(result.what_if_fired, vec!["GoldDiscount"]);
+        assert_eq!(result.newly_fired, vec!["GoldDiscount"]);
+        assert_eq!(result.no_longer_fired, vec!["StandardDiscount"]);
+        assert_eq!(result.fact_changes.len(), 2);
+        assert_eq!(
+            result.fact_changes["Order.Discount"],
+            (Some(Value::Integer(2)), Some(Value::Integer(10)))
+        );
+
+        // Real facts and engine state are untouched
+        assert_eq!(
+            facts.get_nested("Customer.Tier"),
+            Some(Value::String("standard".to_string()))
+        );
+        assert_eq!(facts.get_nested("Order.Discount"), Some(Value::Integer(0)));
+        assert_eq!(engine.execute(&facts).unwrap().rules_fired, 1);
+    }
+
+    #[test]
+    fn test_accumulate_filter_with_fact_reference() {
+        let engine = RustRuleEngine::new(KnowledgeBase::new("Accumulate"));
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![(
+                    "Region".to_string(),
+                    Value::String("EU".to_string()),
+                )]),
+            )
+            .unwrap();
+        facts.set("Order.1.amount", Value::Integer(100));
+        facts.set("Order.1.region", Value::String("EU".to_string()));
+        facts.set("Order.2.amount", Value::Integer(250));
+        facts.set("Order.2.region", Value::String("US".to_string()));
+        facts.set("Order.3.amount", Value::Integer(40));
+        facts.set("Order.3.region", Value::String("EU".to_string()));
+
+        let accumulate = ConditionGroup::accumulate(
+            "$total".to_string(),
+            "Order".to_string(),
+            "amount".to_string(),
+            vec!["region == User.Region".to_string()],
+            "sum".to_string(),
+            "$a".to_string(),
+        );
+        assert!(engine.evaluate_conditions(&accumulate, &facts).unwrap());
+        assert_eq!(
+            facts.get("Order.sum").and_then(|v| v.to_number()),
+            Some(140.0)
+        );
+    }
+
+    #[test]
+    fn test_accumulate_median_and_count_distinct() {
+        let engine = RustRuleEngine::new(KnowledgeBase::new("Accumulate"));
+        let facts = Facts::new();
+        facts.set("Order.1.amount", Value::Integer(100));
+        facts.set("Order.1.region", Value::String("EU".to_string()));
+        facts.set("Order.2.amount", Value::Integer(250));
+        facts.set("Order.2.region", Value::String("US".to_string()));
+        facts.set("Order.3.amount", Value::Integer(40));
+        facts.set("Order.3.region", Value::String("EU".to_string()));
+        facts.set("Order.4.amount", Value::Integer(60));
+        facts.set("Order.4.region", Value::String("EU".to_string()));
+
+        let median = ConditionGroup::accumulate(
+            "$median".to_string(),
+            "Order".to_string(),
+            "amount".to_string(),
+            vec![],
+            "median".to_string(),
+            "$a".to_string(),
+        );
+        assert!(engine.evaluate_conditions(&median, &facts).unwrap());
+        assert_eq!(facts.get("Order.median"), Some(Value::Number(80.0)));
+
+        let regions = ConditionGroup::accumulate(
+            "$regions".to_string(),
+            "Order".to_string(),
+            "region".to_string(),
+            vec![],
+            "count_distinct".to_string(),
+            "$r".to_string(),
+        );
+        assert!(engine.evaluate_conditions(&regions, &facts).unwrap());
+        assert_eq!(facts.get("Order.count_distinct"), Some(Value::Integer(2)));
+    }
+
+    #[test]
+    fn test_range_fact_membership() {
+        let kb = KnowledgeBase::new("Ranges");
+        kb.add_rules_from_grl(
+            r#"
+            rule "PromoDay" no-loop {
+                when
+                    Promotion.ValidDays contains Order.Day
+                then
+                    Order.Discounted = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let run = |engine: &mut RustRuleEngine, day: i64| {
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Promotion",
+                    Facts::create_object(vec![(
+                        "ValidDays".to_string(),
+                        Value::Range {
+                            start: 10.0,
+                            end: 20.0,
+                            inclusive: false,
+                        },
+                    )]),
+                )
+                .unwrap();
+            facts
+                .add_value(
+                    "Order",
+                    Facts::create_object(vec![("Day".to_string(), Value::Integer(day))]),
+                )
+                .unwrap();
+            engine.reset_no_loop_tracking();
+            engine.execute(&facts).unwrap();
+            facts.get_nested("Order.Discounted")
+        };
+
+        assert_eq!(run(&mut engine, 10), Some(Value::Boolean(true)));
+        assert_eq!(run(&mut engine, 19), Some(Value::Boolean(true)));
+        assert_eq!(run(&mut engine, 20), None);
+        assert_eq!(run(&mut engine, 3), None);
+
+        let inclusive = Value::Range {
+            start: 1.0,
+            end: 31.0,
+            inclusive: true,
+        };
+        assert!(inclusive.contains(&Value::Integer(31)));
+        assert!(!inclusive.contains(&Value::String("May".to_string())));
+        assert_eq!(inclusive.to_string(), "1..=31");
+    }
+
+    #[test]
+    fn test_type_check_operators() {
+        let samples = [
+            ("number", Value::Number(1.5)),
+            ("integer", Value::Integer(3)),
+            ("string", Value::String("3".to_string())),
+            ("boolean", Value::Boolean(true)),
+            ("array", Value::Array(vec![Value::Integer(1)])),
+            ("object", Facts::create_object(vec![])),
+            ("null", Value::Null),
+        ];
+        let cases: [(&str, &[&str]); 5] = [
+            ("is_number", &["number", "integer"]),
+            ("is_string", &["string"]),
+            ("is_boolean", &["boolean"]),
+            ("is_array", &["array"]),
+            ("is_object", &["object"]),
+        ];
+
+        for (operator, matching) in cases {
+            let kb = KnowledgeBase::new("TypeChecks");
+            kb.add_rules_from_grl(&format!(
+                r#"
+                rule "Guard" no-loop {{
+                    when
+                        Input.Value {}
+                    then
+                        Input.Accepted = true;
+                }}
+                "#,
+                operator
+            ))
+            .unwrap();
+            let mut engine = RustRuleEngine::new(kb);
+
+            for (kind, value) in &samples {
+                let facts = Facts::new();
+                facts
+                    .add_value(
+                        "Input",
+                        Facts::create_object(vec![("Value".to_string(), value.clone())]),
+                    )
+                    .unwrap();
+                engine.reset_no_loop_tracking();
+                engine.execute(&facts).unwrap();
+
+                let fired = facts.get_nested("Input.Accepted") == Some(Value::Boolean(true));
+                assert_eq!(
+                    fired,
+                    matching.contains(kind),
+                    "{} against {} value",
+                    operator,
+                    kind
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_once_steps_one_cycle() {
+        let kb = KnowledgeBase::new("Stepping");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Approve" salience 10 no-loop {
+                when
+                    Order.Verified == true
+                then
+                    Order.Approved = true;
+            }
+
+            rule "Verify" salience 5 no-loop {
+                when
+                    Order.Total > 0
+                then
+                    Order.Verified = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![
+                    ("Total".to_string(), Value::Integer(50)),
+                    ("Verified".to_string(), Value::Boolean(false)),
+                ]),
+            )
+            .unwrap();
+
+        let first = engine.execute_once(&facts).unwrap();
+        assert!(first.any_rule_fired);
+        assert_eq!(first.fired_rules, vec!["Verify".to_string()]);
+        assert_eq!(facts.get_nested("Order.Approved"), None);
+
+        let second = engine.execute_once(&facts).unwrap();
+        assert_eq!(second.fired_rules, vec!["Approve".to_string()]);
+        assert_eq!(
+            facts.get_nested("Order.Approved"),
+            Some(Value::Boolean(true))
+        );
+
+        let third = engine.execute_once(&facts).unwrap();
+        assert!(!third.any_rule_fired);
+        assert!(third.fired_rules.is_empty());
+        assert_eq!(third.rules_evaluated, 0);
+    }
+
+    #[test]
+    fn test_lazy_namespace_loaded_on_focus() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let kb = KnowledgeBase::new("Catalog");
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = loads.clone();
+        kb.register_lazy_loader(
+            "billing",
+            Box::new(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![Rule::new(
+                    "ChargeLateFee".to_string(),
+                    ConditionGroup::single(Condition::new(
+                        "Invoice.DaysLate".to_string(),
+                        Operator::GreaterThan,
+                        Value::Integer(30),
+                    )),
+                    vec![ActionType::Set {
+                        field: "Invoice.LateFee".to_string(),
+                        value: Value::Number(25.0),
+                    }],
+                )
+                .with_no_loop(true)])
+            }),
+        );
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Invoice",
+                Facts::create_object(vec![("DaysLate".to_string(), Value::Integer(45))]),
+            )
+            .unwrap();
+
+        engine.execute(&facts).unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 0);
+        assert_eq!(engine.knowledge_base().rule_count(), 0);
+
+        engine.set_agenda_focus("billing");
+        engine.execute(&facts).unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert!(!engine.knowledge_base().has_lazy_loader("billing"));
+        assert_eq!(
+            facts.get_nested("Invoice.LateFee"),
+            Some(Value::Number(25.0))
+        );
+
+        engine.execute(&facts).unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_defglobals_in_conditions_and_actions() {
+        let kb = KnowledgeBase::new("Globals");
+        kb.add_rules_from_grl(
+            r#"
+            defglobal ?*MAX_SPEED* = 120;
+            defglobal ?*VIOLATIONS* = 0;
+
+            rule "Speeding" no-loop {
+                when
+                    Car.Speed > ?*MAX_SPEED*
+                then
+                    Car.Flagged = true;
+                    ?*VIOLATIONS* = 1;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        assert_eq!(engine.globals().get("MAX_SPEED"), Some(Value::Integer(120)));
+
+        let run = |engine: &mut RustRuleEngine, speed: i64| {
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Car",
+                    Facts::create_object(vec![("Speed".to_string(), Value::Integer(speed))]),
+                )
+                .unwrap();
+            engine.reset_no_loop_tracking();
+            engine.execute(&facts).unwrap();
+            facts.get_nested("Car.Flagged")
+        };
+
+        assert_eq!(run(&mut engine, 100), None);
+        assert_eq!(engine.globals().get("VIOLATIONS"), Some(Value::Integer(0)));
+
+        assert_eq!(run(&mut engine, 130), Some(Value::Boolean(true)));
+        assert_eq!(engine.globals().get("VIOLATIONS"), Some(Value::Integer(1)));
+
+        // Globals can be changed from the host too
+        engine
+            .globals()
+            .set("MAX_SPEED", Value::Integer(140))
+            .unwrap();
+        assert_eq!(run(&mut engine, 130), None);
+    }
+
+    #[test]
+    fn test_halt_stops_execution() {
+        let kb = KnowledgeBase::new("Halting");
+        kb.add_rules_from_grl(
+            r#"
+            rule "RejectInvalid" salience 100 {
+                when
+                    Order.Total < 0
+                then
+                    Order.Rejected = true;
+                    halt();
+            }
+
+            rule "ApplyDiscount" salience 10 no-loop {
+                when
+                    Order.Total < 1000
+                then
+                    Order.Discounted = true;
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            kb.get_rule("RejectInvalid").unwrap().actions[1],
+            ActionType::Halt
+        );
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(-5))]),
+            )
+            .unwrap();
+
+        let result = engine.execute(&facts).unwrap();
+        assert!(result.halted);
+        assert_eq!(result.rules_fired, 1);
+        assert_eq!(result.cycle_count, 1);
+        assert_eq!(
+            facts.get_nested("Order.Rejected"),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(facts.get_nested("Order.Discounted"), None);
+
+        // A run without halt() completes normally
+        facts.set_nested("Order.Total", Value::Integer(50)).unwrap();
+        let result = engine.execute(&facts).unwrap();
+        assert!(!result.halted);
+        assert_eq!(
+            facts.get_nested("Order.Discounted"),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_provenance_chain_across_rules() {
+        let kb = KnowledgeBase::new("Audit");
+        kb.add_rules_from_grl(
+            r#"
+            rule "TotalSpend" salience 30 no-loop {
+                when
+                    Customer.Orders > 3
+                then
+                    Customer.Spend = 1200;
+            }
+
+            rule "PromoteToGold" salience 20 no-loop {
+                when
+                    Customer.Spend > 1000
+                then
+                    Customer.Tier = "gold";
+            }
+
+            rule "GoldDiscount" salience 10 no-loop {
+                when
+                    Customer.Tier == "gold"
+                then
+                    Order.Discount = 0.15;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.enable_provenance();
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Customer",
+                Facts::create_object(vec![
+                    ("Orders".to_string(), Value::Integer(5)),
+                    ("Spend".to_string(), Value::Integer(0)),
+                    ("Tier".to_string(), Value::String("basic".to_string())),
+                ]),
+            )
+            .unwrap();
+        facts
+            .add_value("Order", Facts::create_object(vec![]))
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        let chain = engine.provenance("Order.Discount").unwrap();
+        assert_eq!(chain.rule, "GoldDiscount");
+        assert_eq!(chain.value, Value::Number(0.15));
+        assert_eq!(chain.depth(), 3);
+        assert_eq!(
+            chain.rules(),
+            vec!["TotalSpend", "PromoteToGold", "GoldDiscount"]
+        );
+
+        let tier = &chain.causes[0];
+        assert_eq!(tier.fact, "Customer.Tier");
+        assert_eq!(tier.value, Value::String("gold".to_string()));
+        assert_eq!(tier.causes[0].fact, "Customer.Spend");
+        assert!(tier.causes[0].causes.is_empty());
+
+        // Input facts have no provenance
+        assert!(engine.provenance("Customer.Orders").is_none());
+    }
+
+    #[test]
+    fn test_execute_tagged_selects_rules() {
+        let kb = KnowledgeBase::new("Tagged");
+        kb.add_rules_from_grl(
+            r#"
+            rule "BasePrice" tags ["pricing"] no-loop {
+                when
+                    Order.Total > 0
+                then
+                    Order.Priced = true;
+            }
+
+            rule "SummerPromo" salience 5 tags ["pricing", "promo"] no-loop {
+                when
+                    Order.Total > 100
+                then
+                    Order.Promo = true;
+            }
+
+            rule "FraudCheck" tags ["risk"] no-loop {
+                when
+                    Order.Total > 0
+                then
+                    Order.Checked = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let promo = kb.get_rule("SummerPromo").unwrap();
+        assert_eq!(promo.tags, vec!["pricing", "promo"]);
+        assert_eq!(promo.salience, 5);
+        assert!(promo.no_loop);
+
+        let mut engine = RustRuleEngine::new(kb);
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(150))]),
+            )
+            .unwrap();
+
+        let result = engine.execute_tagged(&facts, &["promo"]).unwrap();
+        assert_eq!(result.rules_fired, 1);
+        assert_eq!(facts.get_nested("Order.Promo"), Some(Value::Boolean(true)));
+        assert_eq!(facts.get_nested("Order.Priced"), None);
+
+        let result = engine.execute_tagged(&facts, &["pricing", "risk"]).unwrap();
+        assert_eq!(result.rules_fired, 2);
+        assert_eq!(facts.get_nested("Order.Priced"), Some(Value::Boolean(true)));
+        assert_eq!(
+            facts.get_nested("Order.Checked"),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_ingest_fires_only_newly_satisfied_rules() {
+        let kb = KnowledgeBase::new("Session");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Adult" {
+                when
+                    User.Age >= 18
+                then
+                    User.IsAdult = true;
+            }
+
+            rule "HighValue" {
+                when
+                    Order.Total > 100
+                then
+                    Order.Priority = "high";
+            }
+
+            rule "VipAdult" {
+                when
+                    User.IsAdult == true && Order.Priority == "high"
+                then
+                    User.Vip = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        engine.ingest("User.Age", Value::Integer(20)).unwrap();
+        let result = engine.run_pending().unwrap();
+        assert_eq!(result.rules_fired, 1);
+        let facts = engine.session_facts();
+        assert_eq!(facts.get_nested("User.IsAdult"), Some(Value::Boolean(true)));
+
+        engine.ingest("Order.Total", Value::Integer(50)).unwrap();
+        assert_eq!(engine.run_pending().unwrap().rules_fired, 0);
+
+        // HighValue fires, and its write makes VipAdult newly satisfied
+        engine.ingest("Order.Total", Value::Integer(150)).unwrap();
+        let result = engine.run_pending().unwrap();
+        assert_eq!(result.rules_fired, 2);
+        let facts = engine.session_facts();
+        assert_eq!(
+            facts.get_nested("Order.Priority"),
+            Some(Value::String("high".to_string()))
+        );
+        assert_eq!(facts.get_nested("User.Vip"), Some(Value::Boolean(true)));
+
+        // Still satisfied: no re-fire
+        engine.ingest("User.Age", Value::Integer(21)).unwrap();
+        assert_eq!(engine.run_pending().unwrap().rules_fired, 0);
+
+        // Unsatisfied, then satisfied again: fires once more
+        engine.ingest("User.Age", Value::Integer(10)).unwrap();
+        assert_eq!(engine.run_pending().unwrap().rules_fired, 0);
+        engine.ingest("User.Age", Value::Integer(30)).unwrap();
+        assert_eq!(engine.run_pending().unwrap().rules_fired, 1);
+
+        assert_eq!(engine.run_pending().unwrap().cycle_count, 0);
+    }
+
+    #[test]
+    fn test_add_rules_from_file_resolves_includes() {
+        let dir = std::env::temp_dir().join(format!("includes_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+        std::fs::write(
+            dir.join("shared.grl"),
+            r#"
+            defglobal ?*MIN_TOTAL* = 100;
+            rule "Shared" { when Order.Total > ?*MIN_TOTAL* then Order.Large = true; }
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("lib/common.grl"),
+            r#"include "../shared.grl";
+            rule "Common" { when Order.Total > 0 then Order.Valid = true; }
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("main.grl"),
+            r#"
+            // Shared libraries
+            include "lib/common.grl";
+            include "shared.grl";
+
+            rule "Main" { when Order.Large == true then Order.Flagged = true; }
+            "#,
+        )
+        .unwrap();
+
+        // shared.grl is reached twice but loaded once
+        let kb = KnowledgeBase::new("Includes");
+        assert_eq!(kb.add_rules_from_file(dir.join("main.grl")).unwrap(), 3);
+        let mut names = kb.get_rule_names();
+        names.sort();
+        assert_eq!(names, vec!["Common", "Main", "Shared"]);
+        assert_eq!(kb.globals().get("MIN_TOTAL"), Some(Value::Integer(100)));
+
+        std::fs::write(dir.join("a.grl"), r#"include "b.grl";"#).unwrap();
+        std::fs::write(dir.join("b.grl"), r#"include "a.grl";"#).unwrap();
+        let err = KnowledgeBase::new("Cycle")
+            .add_rules_from_file(dir.join("a.grl"))
+            .unwrap_err();
+        assert!(err.to_string().contains("Include cycle"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reload_rules_from_file_applies_delta() {
+        let path = std::env::temp_dir().join(format!("reload_{}.grl", std::process::id()));
+        let kb = KnowledgeBase::new("Reload");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Keep" { when A.x > 1 then A.keep = true; }
+            rule "Tweak" no-loop { when A.x > 1 then A.level = 1; }
+            rule "Drop" { when A.x > 1 then A.drop = true; }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.fired_rules_global.insert("Tweak".to_string());
+        engine.fired_rules_global.insert("Keep".to_string());
+
+        std::fs::write(
+            &path,
+            r#"
+            rule "Keep" { when A.x > 1 then A.keep = true; }
+            rule "Tweak" no-loop { when A.x > 1 then A.level = 2; }
+            rule "Fresh" { when A.x > 1 then A.fresh = true; }
+            "#,
+        )
+        .unwrap();
+        let report = engine.reload_rules_from_file(&path).unwrap();
+        assert_eq!(report.added, vec!["Fresh"]);
+        assert_eq!(report.removed, vec!["Drop"]);
+        assert_eq!(report.changed, vec!["Tweak"]);
+        assert_eq!(report.unchanged, vec!["Keep"]);
+        assert!(!engine.fired_rules_global.contains("Tweak"));
+        assert!(engine.fired_rules_global.contains("Keep"));
+        assert_eq!(engine.knowledge_base().rule_count(), 3);
+
+        // A broken file leaves the running rules alone
+        std::fs::write(&path, r#"rule "Broken" { when A.x > 1 }"#).unwrap();
+        assert!(engine.reload_rules_from_file(&path).is_err());
+        let mut names = engine.knowledge_base().get_rule_names();
+        names.sort();
+        assert_eq!(names, vec!["Fresh", "Keep", "Tweak"]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_rule_file_keeps_path_and_io_source() {
+        use std::error::Error;
+
+        let path = std::env::temp_dir().join("rre_missing_rules_file.grl");
+        let kb = KnowledgeBase::new("MissingFile");
+
+        let err = kb.add_rules_from_file(&path).unwrap_err();
+        match &err {
+            RuleEngineError::Io { path: failed, .. } => assert_eq!(failed, &path),
+            other => panic!("expected Io error, got {:?}", other),
+        }
+        let source = err.source().expect("IO cause is chained");
+        let io = source.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_contains_all_and_any_compare_arrays() {
+        let kb = KnowledgeBase::new("Permissions");
+        kb.add_rules_from_grl(
+            r#"
+            rule "CanEdit" no-loop {
+                when User.Roles contains_all ["read", "write"]
+                then User.CanEdit = true;
+            }
+            rule "IsStaff" no-loop {
+                when User.Roles contains_any ["admin", "owner"]
+                then User.IsStaff = true;
+            }
+            "#,
+        )
+        .unwrap();
+
+        let roles = |names: &[&str]| {
+            Value::Array(names.iter().map(|n| Value::String(n.to_string())).collect())
+        };
+        let run = |user_roles: Value| {
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "User",
+                    Facts::create_object(vec![("Roles".to_string(), user_roles)]),
+                )
+                .unwrap();
+            let mut engine = RustRuleEngine::new(kb.clone());
+            engine.execute(&facts).unwrap();
+            (
+                facts.get_nested("User.CanEdit"),
+                facts.get_nested("User.IsStaff"),
+            )
+        };
+
+        assert_eq!(
+            run(roles(&["write", "owner", "read"])),
+            (Some(Value::Boolean(true)), Some(Value::Boolean(true)))
+        );
+        assert_eq!(run(roles(&["read", "guest"])), (None, None));
+
+        // Elements compare deeply, and an empty right-hand side is a vacuous subset
+        let nested = Value::Array(vec![roles(&["a", "b"]), roles(&["c"])]);
+        assert!(Operator::ContainsAll.evaluate(&nested, &Value::Array(vec![roles(&["c"])])));
+        assert!(!Operator::ContainsAny.evaluate(&nested, &Value::Array(vec![roles(&["a"])])));
+        assert!(Operator::ContainsAll.evaluate(&nested, &Value::Array(vec![])));
+        assert!(!Operator::ContainsAny.evaluate(&nested, &Value::Array(vec![])));
+        assert!(!Operator::ContainsAll.evaluate(&Value::String("read".into()), &roles(&[])));
+    }
+
+    #[test]
+    fn test_rule_timeout_skips_slow_rule_without_aborting_run() {
+        let kb = KnowledgeBase::new("Timeouts");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Slow" salience 10 timeout 10ms {
+                when Order.Total > 0
+                then
+                    Order.Started = true;
+                    Order.Quote = slowQuote(Order.Total);
+            }
+            rule "Fast" no-loop {
+                when Order.Total > 0
+                then Order.Checked = true;
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            kb.get_rule("Slow").unwrap().timeout,
+            Some(Duration::from_millis(10))
+        );
+
+        let mut engine = RustRuleEngine::new(kb);
+        engine.register_function("slowQuote", |args, _| {
+            std::thread::sleep(Duration::from_millis(30));
+            Ok(args[0].clone())
+        });
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(5))]),
+            )
+            .unwrap();
+
+        let result = engine.execute(&facts).unwrap();
+
+        assert_eq!(result.rules_fired, 1);
+        assert!(!result.timed_out_rules.is_empty());
+        for timed_out in &result.timed_out_rules {
+            assert_eq!(timed_out.rule_name, "Slow");
+            assert!(timed_out.elapsed > timed_out.timeout);
+        }
+        // Writes made before the overrun are rolled back
+        assert_eq!(facts.get_nested("Order.Started"), None);
+        assert_eq!(facts.get_nested("Order.Quote"), None);
+        assert_eq!(
+            facts.get_nested("Order.Checked"),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_rule_timeout_rolls_back_setter_writes() {
+        let kb = KnowledgeBase::new("Timeouts");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Slow" timeout 10ms {
+                when Order.Total > 0
+                then
+                    $Order.setStatus("quoted");
+                    Order.Quote = slowQuote(Order.Total);
+            }
+            "#,
+        )
+        .unwrap();
+
+        let mut engine = RustRuleEngine::new(kb);
+        engine.register_function("slowQuote", |args, _| {
+            std::thread::sleep(Duration::from_millis(30));
+            Ok(args[0].clone())
+        });
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![
+                    ("Total".to_string(), Value::Integer(5)),
+                    ("Status".to_string(), Value::String("new".to_string())),
+                ]),
+            )
+            .unwrap();
+
+        let result = engine.execute(&facts).unwrap();
+
+        assert!(!result.timed_out_rules.is_empty());
+        assert_eq!(
+            facts.get_nested("Order.Status"),
+            Some(Value::String("new".to_string()))
+        );
+        assert_eq!(facts.get_nested("Order.Quote"), None);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_literals_compare_and_add_exactly() {
+        let kb = KnowledgeBase::new("Pricing");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Total" no-loop {
+                when Order.Subtotal > 0.00d
+                then Order.Total = Order.Subtotal + 0.20d;
+            }
+            rule "Exact" no-loop {
+                when Order.Total == 0.30d && Order.Total < 1
+                then Order.Exact = true;
+            }
+            "#,
+        )
+        .unwrap();
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![(
+                    "Subtotal".to_string(),
+                    Value::Decimal("0.10".parse().unwrap()),
+                )]),
+            )
+            .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.execute(&facts).unwrap();
+
+        assert_eq!(
+            facts.get_nested("Order.Total"),
+            Some(Value::Decimal("0.3".parse().unwrap()))
+        );
+        assert_eq!(facts.get_nested("Order.Exact"), Some(Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_missing_handler_policy() {
+        let grl = r#"
+            rule "Notify" no-loop {
+                when Order.Total > 100
+                then
+                    NotifySales(Order.Total);
+                    Order.Flagged = true;
+            }
+        "#;
+        let run = |policy: MissingHandler| {
+            let kb = KnowledgeBase::new("Missing");
+            kb.add_rules_from_grl(grl).unwrap();
+            let config = EngineConfig {
+                on_missing_handler: policy,
+                ..Default::default()
+            };
+            let mut engine = RustRuleEngine::with_config(kb, config);
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Order",
+                    Facts::create_object(vec![("Total".to_string(), Value::Integer(150))]),
+                )
+                .unwrap();
+            let result = engine.execute(&facts);
+            (result, facts.get_nested("Order.Flagged"))
+        };
+
+        assert!(run(MissingHandler::Error).0.is_err());
+        for policy in [MissingHandler::Skip, MissingHandler::Warn] {
+            let (result, flagged) = run(policy);
+            assert_eq!(result.unwrap().rules_fired, 1);
+            assert_eq!(flagged, Some(Value::Boolean(true)));
+        }
+    }
+
+    #[test]
+    fn test_plain_setter_calls_persist_without_handlers() {
+        let kb = KnowledgeBase::new("Setters");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Accelerate" no-loop {
+                when TestCar.SpeedUp == true
+                then
+                    TestCar.setSpeed(TestCar.Speed + TestCar.SpeedIncrement);
+                    $TestCar.setLabel("express");
+                    $TestCar.setModel("v1.2-beta");
+                    Dashboard.setMode("sport");
+            }
+            "#,
+        )
+        .unwrap();
+        let rule = kb.get_rule("Accelerate").unwrap();
+        assert!(matches!(
+            &rule.actions[0],
+            ActionType::MethodCall { object, method, .. }
+                if object == "TestCar" && method == "setSpeed"
+        ));
+
+        let mut engine = RustRuleEngine::new(kb);
+        // Registered handlers still win over the generic setter
+        engine.register_action_handler("setMode", |params, facts| {
+            facts.set_nested("Dashboard.Mode", params["0"].clone())?;
+            facts.set_nested("Dashboard.ViaHandler", Value::Boolean(true))
+        });
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "TestCar",
+                Facts::create_object(vec![
+                    ("SpeedUp".to_string(), Value::Boolean(true)),
+                    ("Speed".to_string(), Value::Integer(30)),
+                    ("SpeedIncrement".to_string(), Value::Integer(10)),
+                ]),
+            )
+            .unwrap();
+        facts
+            .add_value("Dashboard", Facts::create_object(vec![]))
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        assert_eq!(facts.get_nested("TestCar.Speed"), Some(Value::Integer(40)));
+        assert_eq!(
+            facts.get_nested("TestCar.Label"),
+            Some(Value::String("express".to_string()))
+        );
+        // Quoted literals are never evaluated, even when they contain operators
+        assert_eq!(
+            facts.get_nested("TestCar.Model"),
+            Some(Value::String("v1.2-beta".to_string()))
+        );
+        assert_eq!(
+            facts.get_nested("Dashboard.ViaHandler"),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_datetime_conditions_compare_chronologically() {
+        let kb = KnowledgeBase::new("Dates");
+        kb.add_rules_from_grl(
+            r#"
+            rule "LegacyOrder" {
+                when Order.CreatedAt < 2024-01-01
+                then Order.Legacy = true;
+            }
+            rule "RecentShipment" {
+                when Shipment.SentAt >= 2024-03-01T12:00:00Z
+                then Shipment.Recent = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        // Lexicographically smaller, but 2024-01-01T01:00:00Z in UTC
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![(
+                    "CreatedAt".to_string(),
+                    Value::String("2023-12-31T23:00:00-02:00".to_string()),
+                )]),
+            )
+            .unwrap();
+        facts
+            .add_value(
+                "Shipment",
+                Facts::create_object(vec![(
+                    "SentAt".to_string(),
+                    Value::DateTime(Value::parse_datetime("2024-03-01T14:00:00+01:00").unwrap()),
+                )]),
+            )
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        assert_eq!(facts.get_nested("Order.Legacy"), None);
+        assert_eq!(
+            facts.get_nested("Shipment.Recent"),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_duration_conditions() {
+        let grl = r#"
+            rule "SessionExpiry" {
+                when now() - Session.LastSeen > 30m
+                then Session.Expired = true;
+            }
+        "#;
+
+        for (last_seen, expired) in [
+            (
+                chrono::Utc::now() - chrono::Duration::hours(2),
+                Some(Value::Boolean(true)),
+            ),
+            (chrono::Utc::now(), None),
+        ] {
+            let kb = KnowledgeBase::new("Sessions");
+            kb.add_rules_from_grl(grl).unwrap();
+            let mut engine = RustRuleEngine::new(kb);
+
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Session",
+                    Facts::create_object(vec![(
+                        "LastSeen".to_string(),
+                        Value::DateTime(last_seen),
+                    )]),
+                )
+                .unwrap();
+            engine.execute(&facts).unwrap();
+
+            assert_eq!(facts.get_nested("Session.Expired"), expired);
+        }
+    }
+
+    #[test]
+    fn test_knowledge_base_introspection() {
+        let kb = KnowledgeBase::new("Introspection");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Low" salience 1 { when A.x > 1 then A.y = 1; }
+            rule "High" salience 10 { when A.x > 2 then A.y = 2; }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(kb.rule_count(), 2);
+        assert_eq!(kb.rule_names(), vec!["High".to_string(), "Low".to_string()]);
+        assert_eq!(kb.get_rule("Low").map(|rule| rule.salience), Some(1));
+        assert!(kb.get_rule("Missing").is_none());
+    }
+
+    #[test]
+    fn test_max_fires_caps_rule_per_run() {
+        let kb = KnowledgeBase::new("Budget");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Bonus" salience 5 max-fires 3 {
+                when Account.Points < 100
+                then Account.Points = Account.Points + 10;
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(kb.get_rule("Bonus").unwrap().max_fires, Some(3));
+        let mut engine = RustRuleEngine::new(kb);
+
+        for _ in 0..2 {
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Account",
+                    Facts::create_object(vec![("Points".to_string(), Value::Integer(0))]),
+                )
+                .unwrap();
+            let result = engine.execute(&facts).unwrap();
+
+            // The budget resets with every run
+            assert_eq!(result.rules_fired, 3);
+            assert_eq!(facts.get_nested("Account.Points"), Some(Value::Integer(30)));
+        }
+    }
+
+    #[test]
+    fn test_validate_rules_reports_unresolved_references() {
+        let kb = KnowledgeBase::new("Preflight");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Scored" {
+                when riskScore(Customer.id) > 50
+                then Notify(Customer.id);
+            }
+            rule "Known" {
+                when creditLimit(Customer.id) > 1000
+                then Approve(Customer.id);
+            }
+            "#,
+        )
+        .unwrap();
+        let mut broken = Rule::new(
+            "Broken".to_string(),
+            ConditionGroup::single(Condition::new(
+                "Order.total".to_string(),
+                Operator::GreaterThan,
+                Value::Integer(0),
+            )),
+            vec![ActionType::Set {
+                field: "Order.tax".to_string(),
+                value: Value::Expression("Order.total * ".to_string()),
+            }],
+        );
+        broken.salience = -1;
+        kb.add_rule(broken).unwrap();
+
+        let mut engine = RustRuleEngine::new(kb);
+        engine.register_function("creditLimit", |_, _| Ok(Value::Integer(5000)));
+        engine.register_action_handler("Approve", |_, _| Ok(()));
+
+        let issues = engine.validate_rules();
+        assert_eq!(issues.len(), 3, "{:?}", issues);
+        assert!(issues.contains(&ValidationIssue::UnknownFunction {
+            rule: "Scored".to_string(),
+            function: "riskScore".to_string(),
+        }));
+        assert!(issues.contains(&ValidationIssue::UnknownAction {
+            rule: "Scored".to_string(),
+            action: "Notify".to_string(),
+        }));
+        assert!(matches!(
+            &issues[2],
+            ValidationIssue::MalformedExpression { rule, .. } if rule == "Broken"
+        ));
+    }
+
+    #[test]
+    fn test_plugin_conflicts_are_rejected() {
+        use crate::engine::plugin::{PluginHealth, PluginMetadata, PluginState, RulePlugin};
+        use crate::plugins::ValidationPlugin;
+        use std::sync::Arc;
+
+        struct EmailPlugin(PluginMetadata);
+
+        impl RulePlugin for EmailPlugin {
+            fn get_metadata(&self) -> &PluginMetadata {
+                &self.0
+            }
+
+            fn register_actions(&self, engine: &mut RustRuleEngine) -> Result<()> {
+                engine.register_action_handler("ValidateEmail", |_, _| Ok(()));
+                Ok(())
+            }
+        }
+
+        let email_plugin = || {
+            Arc::new(EmailPlugin(PluginMetadata {
+                name: "email".to_string(),
+                version: "0.1.0".to_string(),
+                description: String::new(),
+                author: String::new(),
+                state: PluginState::Loaded,
+                health: PluginHealth::Healthy,
+                actions: vec!["ValidateEmail".to_string()],
+                functions: vec![],
+                dependencies: vec![],
+            }))
+        };
+
+        let mut engine = RustRuleEngine::new(KnowledgeBase::new("test"));
+        engine
+            .load_plugin(Arc::new(ValidationPlugin::new()))
+            .unwrap();
+        match engine.load_plugin(email_plugin()) {
+            Err(RuleEngineError::PluginConflict {
+                name,
+                existing_plugin,
+                new_plugin,
+            }) => {
+                assert_eq!(name, "ValidateEmail");
+                assert_eq!(existing_plugin, "validation");
+                assert_eq!(new_plugin, "email");
+            }
+            other => panic!("expected a plugin conflict, got {:?}", other),
+        }
+        assert!(engine.get_plugin_info("email").is_none());
+
+        let mut engine = RustRuleEngine::new(KnowledgeBase::new("test"));
+        engine.configure_plugins(PluginConfig {
+            allow_override: true,
+            ..PluginConfig::default()
+        });
+        engine
+            .load_plugin(Arc::new(ValidationPlugin::new()))
+            .unwrap();
+        assert!(engine.load_plugin(email_plugin()).is_ok());
+    }
+
+    #[test]
+    fn test_namespaced_plugin_functions() {
+        use crate::engine::plugin::{PluginHealth, PluginMetadata, PluginState, RulePlugin};
+        use std::sync::Arc;
+
+        struct RoundingPlugin(PluginMetadata);
+
+        impl RulePlugin for RoundingPlugin {
+            fn get_metadata(&self) -> &PluginMetadata {
+                &self.0
+            }
+
+            fn namespace(&self) -> Option<&str> {
+                Some("mylib")
+            }
+
+            fn register_actions(&self, _engine: &mut RustRuleEngine) -> Result<()> {
+                Ok(())
+            }
+
+            fn register_functions(&self, engine: &mut RustRuleEngine) -> Result<()> {
+                engine.register_function("round", |_, _| Ok(Value::Integer(100)));
+                Ok(())
+            }
+        }
+
+        let kb = KnowledgeBase::new("Namespaces");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Qualified" {
+                when mylib.round(Order.Total) == 100
+                then Order.Qualified = true;
+            }
+            rule "Unqualified" {
+                when round(Order.Total) == 1
+                then Order.Unqualified = true;
+            }
+            rule "Fallback" {
+                when other.round(Order.Total) == 1
+                then Order.Fallback = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.register_function("round", |_, _| Ok(Value::Integer(1)));
+        engine
+            .load_plugin(Arc::new(RoundingPlugin(PluginMetadata {
+                name: "rounding".to_string(),
+                version: "0.1.0".to_string(),
+                description: String::new(),
+                author: String::new(),
+                state: PluginState::Loaded,
+                health: PluginHealth::Healthy,
+                actions: vec![],
+                functions: vec!["round".to_string()],
+                dependencies: vec![],
+            })))
+            .unwrap();
+
+        // The plugin's function did not replace the unqualified one
+        assert!(engine.has_function("round"));
+        assert!(engine.has_function("mylib.round"));
+        assert!(engine.validate_rules().is_empty());
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Number(99.6))]),
+            )
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        assert_eq!(
+            facts.get_nested("Order.Qualified"),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            facts.get_nested("Order.Unqualified"),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(
+            facts.get_nested("Order.Fallback"),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_in_operator_right_hand_sides() {
+        let kb = KnowledgeBase::new("Membership");
+        kb.add_rules_from_grl(
+            r#"
+            rule "LiteralArray" {
+                when Customer.Tier in ["gold", "platinum"]
+                then Result.LiteralArray = true;
+            }
+            rule "FactArray" {
+                when "premium" in Customer.Tags
+                then Result.FactArray = true;
+            }
+            rule "FieldInFactArray" {
+                when Customer.Tier in Settings.Tiers
+                then Result.FieldInFactArray = true;
+            }
+            rule "ObjectKey" {
+                when "vip" in Customer.Flags
+                then Result.ObjectKey = true;
+            }
+            rule "MissingKey" {
+                when "banned" in Customer.Flags
+                then Result.MissingKey = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Customer",
+                Facts::create_object(vec![
+                    ("Tier".to_string(), Value::String("gold".to_string())),
+                    (
+                        "Tags".to_string(),
+                        Value::Array(vec![
+                            Value::String("new".to_string()),
+                            Value::String("premium".to_string()),
+                        ]),
+                    ),
+                    (
+                        "Flags".to_string(),
+                        Facts::create_object(vec![("vip".to_string(), Value::Boolean(false))]),
+                    ),
+                ]),
+            )
+            .unwrap();
+        facts
+            .add_value(
+                "Settings",
+                Facts::create_object(vec![(
+                    "Tiers".to_string(),
+                    Value::Array(vec![Value::String("gold".to_string())]),
+                )]),
+            )
+            .unwrap();
+        facts
+            .add_value("Result", Facts::create_object(vec![]))
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        for name in ["LiteralArray", "FactArray", "FieldInFactArray", "ObjectKey"] {
+            assert_eq!(
+                facts.get_nested(&format!("Result.{}", name)),
+                Some(Value::Boolean(true)),
+                "rule {} should fire",
+                name
+            );
+        }
+        assert_eq!(facts.get_nested("Result.MissingKey"), None);
+    }
+
+    #[test]
+    fn test_retracted_instances_are_excluded_from_pattern_matching() {
+        let mut engine = RustRuleEngine::new(KnowledgeBase::new("Retract"));
+        let facts = Facts::new();
+        facts.set("Order.1.amount", Value::Integer(100));
+        facts.set("Order.2.amount", Value::Integer(250));
+        facts.set("Order.3.amount", Value::Integer(40));
+        facts.set("Order.10.amount", Value::Integer(5));
+
+        engine
+            .execute_action(
+                &ActionType::Retract {
+                    object: "Order.2".to_string(),
+                },
+                &facts,
+            )
+            .unwrap();
+
+        // Only the retracted instance disappears; Order.10 merely shares a prefix
+        let all = facts.get_all_facts();
+        assert!(!all.contains_key("Order.2.amount"));
+        assert!(all.contains_key("Order.1.amount"));
+        assert!(all.contains_key("Order.10.amount"));
+
+        let sum = ConditionGroup::accumulate(
+            "$total".to_string(),
+            "Order".to_string(),
+            "amount".to_string(),
+            vec![],
+            "sum".to_string(),
+            "$a".to_string(),
+        );
+        assert!(engine.evaluate_conditions(&sum, &facts).unwrap());
+        assert_eq!(
+            facts.get("Order.sum").and_then(|v| v.to_number()),
+            Some(145.0)
+        );
+
+        let exists = ConditionGroup::exists(ConditionGroup::single(Condition::new(
+            "Customer.Tier".to_string(),
+            Operator::Equal,
+            Value::String("gold".to_string()),
+        )));
+        facts
+            .add_value(
+                "Customer",
+                Facts::create_object(vec![(
+                    "Tier".to_string(),
+                    Value::String("gold".to_string()),
+                )]),
+            )
+            .unwrap();
+        assert!(engine.evaluate_conditions(&exists, &facts).unwrap());
+        engine
+            .execute_action(
+                &ActionType::Retract {
+                    object: "Customer".to_string(),
+                },
+                &facts,
+            )
+            .unwrap();
+        assert!(!engine.evaluate_conditions(&exists, &facts).unwrap());
+    }
+
+    #[test]
+    fn test_matches_binds_capture_groups() {
+        let kb = KnowledgeBase::new("Captures");
+        kb.add_rules_from_grl(
+            r#"
+            rule "SplitEmail" salience 10 {
+                when User.Email matches "(.+)@(.+)"
+                then
+                    User.Name = $1;
+                    User.Domain = $2;
+            }
+            rule "NoCaptures" {
+                when User.Email matches "example"
+                then User.Checked = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![(
+                    "Email".to_string(),
+                    Value::String("ada@example.com".to_string()),
+                )]),
+            )
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        assert_eq!(
+            facts.get_nested("User.Name"),
+            Some(Value::String("ada".to_string()))
+        );
+        assert_eq!(
+            facts.get_nested("User.Domain"),
+            Some(Value::String("example.com".to_string()))
+        );
+        assert_eq!(facts.get_nested("User.Checked"), Some(Value::Boolean(true)));
+
+        // Captures do not outlive the rule that bound them
+        assert_eq!(facts.get("$0"), None);
+        assert_eq!(facts.get("$1"), None);
+    }
+
+    #[test]
+    fn test_condition_profiling_records_each_condition() {
+        use crate::engine::analytics::AnalyticsConfig;
+
+        let kb = KnowledgeBase::new("Profiling");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Adult" {
+                when User.Age >= 18 && (User.Country == "US" || !(User.Banned == true))
+                then User.Adult = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.enable_analytics(RuleAnalytics::new(AnalyticsConfig {
+            condition_profiling: true,
+            ..AnalyticsConfig::default()
+        }));
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![
+                    ("Age".to_string(), Value::Integer(30)),
+                    ("Country".to_string(), Value::String("US".to_string())),
+                    ("Banned".to_string(), Value::Boolean(false)),
+                ]),
+            )
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        let metrics = engine
+            .analytics()
+            .unwrap()
+            .get_rule_metrics("Adult")
+            .unwrap();
+        let mut indices: Vec<usize> = metrics.condition_times.keys().copied().collect();
+        indices.sort();
+        assert_eq!(indices, vec![0, 1, 2]);
+        assert!(metrics.slowest_condition().is_some());
+
+        // Profiling is off by default
+        let kb = KnowledgeBase::new("Profiling");
+        kb.add_rules_from_grl(r#"rule "Adult" { when User.Age >= 18 then User.Adult = true; }"#)
+            .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.enable_analytics(RuleAnalytics::new(AnalyticsConfig::default()));
+        engine.execute(&facts).unwrap();
+        let metrics = engine
+            .analytics()
+            .unwrap()
+            .get_rule_metrics("Adult")
+            .unwrap();
+        assert!(metrics.condition_times.is_empty());
+    }
+
+    #[test]
+    fn test_mock_clock_drives_now() {
+        use crate::engine::clock::MockClock;
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let clock = Arc::new(MockClock::new(start));
+        let kb = KnowledgeBase::new("Sessions");
+        kb.add_rules_from_grl(
+            r#"
+            rule "SessionExpiry" {
+                when now() - Session.LastSeen > 30m
+                then Session.Expired = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::with_config(
+            kb,
+            EngineConfig {
+                clock: clock.clone(),
+                ..Default::default()
+            },
+        );
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Session",
+                Facts::create_object(vec![("LastSeen".to_string(), Value::DateTime(start))]),
+            )
+            .unwrap();
+        engine.execute(&facts).unwrap();
+        assert_eq!(facts.get_nested("Session.Expired"), None);
+
+        clock.advance(chrono::Duration::minutes(31));
+        engine.execute(&facts).unwrap();
+        assert_eq!(
+            facts.get_nested("Session.Expired"),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_date_plugin_uses_engine_clock() {
+        use crate::engine::clock::MockClock;
+        use crate::plugins::DateUtilsPlugin;
+        use chrono::TimeZone;
+
+        // A Saturday, at noon so the local date matches in most time zones
+        let saturday = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let kb = KnowledgeBase::new("Dates");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Stamp" no-loop {
+                when Order.Id == 1
+                then
+                    CurrentDate("OrderDate");
+                    IsWeekend("OrderDate", "OrderOnWeekend");
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::with_config(
+            kb,
+            EngineConfig {
+                clock: Arc::new(MockClock::new(saturday)),
+                ..Default::default()
+            },
+        );
+        engine
+            .load_plugin(Arc::new(DateUtilsPlugin::new()))
+            .unwrap();
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Id".to_string(), Value::Integer(1))]),
+            )
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        let expected = saturday
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d")
+            .to_string();
+        assert_eq!(facts.get("OrderDate"), Some(Value::String(expected)));
+        assert_eq!(facts.get("OrderOnWeekend"), Some(Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_emit_collects_events_in_result() {
+        let kb = KnowledgeBase::new("Events");
+        kb.add_rules_from_grl(
+            r#"
+            rule "FlagOrder" no-loop {
+                when Order.Score >= 80
+                then
+                    emit("OrderFlagged", { orderId: Order.Id, score: Order.Score * 2, "source": "rules" });
+                    Order.Flagged = true;
+            }
+            rule "Audit" no-loop {
+                when Order.Flagged == true
+                then emit("Audited");
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![
+                    ("Id".to_string(), Value::String("A-1".to_string())),
+                    ("Score".to_string(), Value::Integer(85)),
+                ]),
+            )
+            .unwrap();
+        let result = engine.execute(&facts).unwrap();
+
+        assert_eq!(result.emitted_events.len(), 2);
+        let flagged = &result.emitted_events[0];
+        assert_eq!(flagged.event_type, "OrderFlagged");
+        assert_eq!(
+            flagged.payload.get("orderId"),
+            Some(&Value::String("A-1".to_string()))
+        );
+        assert_eq!(flagged.payload.get("score"), Some(&Value::Integer(170)));
+        assert_eq!(
+            flagged.payload.get("source"),
+            Some(&Value::String("rules".to_string()))
+        );
+        assert_eq!(result.emitted_events[1].event_type, "Audited");
+        assert!(result.emitted_events[1].payload.is_empty());
+
+        // Events do not carry over into the next run
+        let result = engine.execute(&facts).unwrap();
+        assert!(result.emitted_events.is_empty());
+    }
+
+    #[test]
+    fn test_dispatch_runs_rules_reading_the_event_then_handlers() {
+        let kb = KnowledgeBase::new("Events");
+        kb.add_rules_from_grl(
+            r#"
+            rule "LargeOrder" no-loop {
+                when OrderPlaced.Amount > 100
+                then
+                    OrderPlaced.Review = true;
+                    emit("ReviewRequested", { "amount": OrderPlaced.Amount });
+            }
+            rule "Unrelated" {
+                when Customer.Tier == "gold"
+                then Customer.Seen = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let on_order = seen.clone();
+        engine.on_event("OrderPlaced", move |event, facts| {
+            let review = facts.get_nested("OrderPlaced.Review");
+            on_order
+                .lock()
+                .unwrap()
+                .push(format!("{}:{:?}", event.event_type, review));
+            Ok(())
+        });
+        let on_review = seen.clone();
+        engine.on_event("ReviewRequested", move |event, _| {
+            on_review.lock().unwrap().push(format!(
+                "{}:{:?}",
+                event.event_type,
+                event.payload.get("amount")
+            ));
+            Ok(())
+        });
+
+        let context = Facts::new();
+        context
+            .add_value(
+                "Customer",
+                Facts::create_object(vec![(
+                    "Tier".to_string(),
+                    Value::String("gold".to_string()),
+                )]),
+            )
+            .unwrap();
+
+        let event = Event::new("OrderPlaced").with_field("Amount", Value::Integer(120));
+        let result = engine.dispatch_with_facts(event, &context).unwrap();
+
+        assert_eq!(result.rules_fired, 1);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                "OrderPlaced:Some(Boolean(true))".to_string(),
+                "ReviewRequested:Some(Integer(120))".to_string(),
+            ]
+        );
+        // The event fact is removed again; the unrelated rule did not run
+        assert!(context.get("OrderPlaced").is_none());
+        assert_eq!(context.get_nested("Customer.Seen"), None);
+
+        let small = Event::new("OrderPlaced").with_field("Amount", Value::Integer(5));
+        assert_eq!(engine.dispatch(small).unwrap().rules_fired, 0);
+    }
+
+    #[test]
+    fn test_dispatching_the_same_event_twice_fires_rules_once_each_time() {
+        let kb = KnowledgeBase::new("RepeatedEvents");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Audit" no-loop {
+                when Login.User != ""
+                then Login.Audited = true;
+            }
+            rule "Count" {
+                when Login.User != ""
+                then Login.Seen = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        for _ in 0..2 {
+            let event = Event::new("Login").with_field("User", Value::String("alice".to_string()));
+            let result = engine.dispatch(event).unwrap();
+            assert_eq!(result.rules_fired, 2);
+            assert_eq!(result.cycle_count, 1);
+        }
+    }
+
+    #[test]
+    fn test_max_actions_per_run_stops_runaway_rules() {
+        let kb = KnowledgeBase::new("Runaway");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Grow" {
+                when Counter.Value < 1000000
+                then
+                    Counter.Value = Counter.Value + 1;
+                    Counter.Touched = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let config = EngineConfig {
+            max_cycles: 1000,
+            max_actions_per_run: Some(10),
+            ..Default::default()
+        };
+        let mut engine = RustRuleEngine::with_config(kb, config);
+
+        let counter = |start: i64| {
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Counter",
+                    Facts::create_object(vec![("Value".to_string(), Value::Integer(start))]),
+                )
+                .unwrap();
+            facts
+        };
+
+        let facts = counter(0);
+        let err = engine.execute(&facts).unwrap_err();
+        assert!(matches!(
+            err,
+            RuleEngineError::ActionLimitExceeded { limit: 10 }
+        ));
+        // Five fires ran both actions; the sixth stopped before its first
+        assert_eq!(facts.get_nested("Counter.Value"), Some(Value::Integer(5)));
+
+        // The count starts over with every run
+        let facts = counter(999_996);
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 4);
+        let result = engine.execute(&counter(999_996)).unwrap();
+        assert_eq!(result.rules_fired, 4);
+    }
+
+    #[test]
+    fn test_object_conditions_compare_structurally() {
+        let kb = KnowledgeBase::new("Preferences");
+        kb.add_rules_from_grl(
+            r#"
+            rule "DarkDashboard" no-loop {
+                when User.Preferences == { layout: { widgets: ["clock", "mail"], sidebar: true }, theme: "dark" }
+                then User.Matched = true;
+            }
+            rule "CustomLayout" no-loop {
+                when User.Preferences.layout != { sidebar: true, widgets: ["clock"] }
+                then User.Custom = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let layout = Facts::create_object(vec![
+            ("sidebar".to_string(), Value::Boolean(true)),
+            (
+                "widgets".to_string(),
+                Value::Array(vec![
+                    Value::String("clock".to_string()),
+                    Value::String("mail".to_string()),
+                ]),
+            ),
+        ]);
+        let preferences = Facts::create_object(vec![
+            ("theme".to_string(), Value::String("dark".to_string())),
+            ("layout".to_string(), layout),
+        ]);
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![("Preferences".to_string(), preferences)]),
+            )
+            .unwrap();
+
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 2);
+        assert_eq!(facts.get_nested("User.Matched"), Some(Value::Boolean(true)));
+        assert_eq!(facts.get_nested("User.Custom"), Some(Value::Boolean(true)));
+
+        // A nested difference breaks equality
+        let mut other = facts.get_nested("User.Preferences").unwrap();
+        other
+            .set_property("theme", Value::String("light".to_string()))
+            .unwrap();
+        let stored = facts.get_nested("User.Preferences").unwrap();
+        assert!(!Operator::Equal.evaluate(&other, &stored));
+        assert!(Operator::NotEqual.evaluate(&other, &stored));
+
+        // Objects have no ordering
+        let kb = KnowledgeBase::new("Ordering");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Bigger" {
+                when User.Preferences > { theme: "dark" }
+                then User.Bigger = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let err = RustRuleEngine::new(kb).execute(&facts).unwrap_err();
+        assert!(matches!(err, RuleEngineError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn test_dry_run_plans_actions_without_side_effects() {
+        let kb = KnowledgeBase::new("Approval");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Discount" salience 10 no-loop {
+                when Order.Total > 100
+                then
+                    Order.Discount = Order.Total * 0.1;
+                    Notify("sales");
+            }
+            rule "Review" no-loop {
+                when Order.Discount > 15
+                then Order.NeedsReview = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        let notified = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = notified.clone();
+        engine.register_action_handler("Notify", move |_, _| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        });
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(200))]),
+            )
+            .unwrap();
+        let before = facts.get("Order");
+
+        let planned = engine.dry_run(&facts).unwrap();
+
+        let summary: Vec<(&str, Option<Value>)> = planned
+            .iter()
+            .map(|p| (p.rule.as_str(), p.resolved_value.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Discount", Some(Value::Number(20.0))),
+                ("Discount", None),
+                ("Review", Some(Value::Boolean(true))),
+            ]
+        );
+        assert!(matches!(
+            planned[1].action,
+            ActionType::Custom { ref action_type, .. } if action_type == "Notify"
+        ));
+        assert_eq!(facts.get("Order"), before);
+        assert_eq!(notified.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        // A real run afterwards is unaffected by the dry run's no-loop state
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 2);
+        assert_eq!(notified.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_agenda_fallthrough_returns_to_main() {
+        let grl = r#"
+            rule "Stage" agenda-group "review" no-loop {
+                when Order.Flagged == true
+                then Order.Reviewed = true;
+            }
+
+            rule "Default" no-loop {
+                when Order.Total > 0
+                then Order.Priced = true;
+            }
+        "#;
+        let order = || {
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Order",
+                    Facts::create_object(vec![("Total".to_string(), Value::Number(50.0))]),
+                )
+                .unwrap();
+            facts
+        };
+
+        // Without fall-through the empty "review" stage ends the run
+        let kb = KnowledgeBase::new("Fallthrough");
+        kb.add_rules_from_grl(grl).unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.set_agenda_focus("review");
+        let facts = order();
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 0);
+        assert_eq!(engine.get_active_agenda_group(), "review");
+
+        let kb = KnowledgeBase::new("Fallthrough");
+        kb.add_rules_from_grl(grl).unwrap();
+        let config = EngineConfig {
+            agenda_fallthrough: true,
+            ..Default::default()
+        };
+        let mut engine = RustRuleEngine::with_config(kb, config);
+        engine.set_agenda_focus("review");
+        let facts = order();
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 1);
+        assert_eq!(engine.get_active_agenda_group(), "MAIN");
+        assert_eq!(facts.get_nested("Order.Priced"), Some(Value::Boolean(true)));
+        assert_eq!(facts.get_nested("Order.Reviewed"), None);
+    }
+
+    #[test]
+    fn test_bound_variables_are_rule_local() {
+        let kb = KnowledgeBase::new("Bindings");
+        kb.add_rules_from_grl(
+            r#"
+            rule "AgeGate" salience 10 no-loop {
+                when $age : User.Age && $age > 18 && Order.MinAge <= $age
+                then
+                    Order.ApprovedAge = $age;
+                    Order.AgeInMonths = $age * 12;
+            }
+
+            rule "Leak" no-loop {
+                when $age > 0
+                then Order.Leaked = true;
+            }
+
+            rule "MissingField" no-loop {
+                when $tier : User.Tier
+                then Order.Tier = $tier;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![("Age".to_string(), Value::Integer(30))]),
+            )
+            .unwrap();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("MinAge".to_string(), Value::Integer(21))]),
+            )
+            .unwrap();
+
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 1);
+        assert_eq!(
+            facts.get_nested("Order.ApprovedAge"),
+            Some(Value::Integer(30))
+        );
+        assert_eq!(
+            facts.get_nested("Order.AgeInMonths"),
+            Some(Value::Integer(360))
+        );
+        assert_eq!(facts.get_nested("Order.Leaked"), None);
+        assert_eq!(facts.get_nested("Order.Tier"), None);
+        assert_eq!(facts.get("$age"), None);
+    }
+
+    fn failing_bound_rule_engine() -> (RustRuleEngine, Facts) {
+        let kb = KnowledgeBase::new("FailingBindings");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Notify" no-loop {
+                when $age : User.Age && $age > 18
+                then Notify($age);
+            }
+            "#,
+        )
+        .unwrap();
+        let engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![("Age".to_string(), Value::Integer(30))]),
+            )
+            .unwrap();
+        (engine, facts)
+    }
+
+    #[test]
+    fn test_failed_action_clears_rule_bindings() {
+        let (mut engine, facts) = failing_bound_rule_engine();
+        engine.register_action_handler("Notify", |_, _| {
+            Err(RuleEngineError::EvaluationError {
+                message: "notification service down".to_string(),
+            })
+        });
+
+        assert!(engine.execute(&facts).is_err());
+        assert_eq!(facts.get("$age"), None);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_execute_async_failed_action_clears_rule_bindings() {
+        let (mut engine, facts) = failing_bound_rule_engine();
+        engine.register_async_action_handler("Notify", |_| async {
+            Err(RuleEngineError::EvaluationError {
+                message: "notification service down".to_string(),
+            })
+        });
+
+        assert!(engine.execute_async(&facts).await.is_err());
+        assert_eq!(facts.get("$age"), None);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_execute_async_awaits_actions_in_firing_order() {
+        use std::sync::Mutex;
+
+        let kb = KnowledgeBase::new("Async");
+        kb.add_rules_from_grl(
+            r#"
+            rule "First" salience 10 no-loop {
+                when Order.Total > 0
+                then
+                    Fetch("first");
+                    Mark("first");
+            }
+
+            rule "Second" no-loop {
+                when Order.Total > 0
+                then Fetch("second");
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.enable_analytics(RuleAnalytics::new(AnalyticsConfig::default()));
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let log = calls.clone();
+        engine.register_async_action_handler("Fetch", move |params| {
+            let log = log.clone();
+            async move {
+                tokio::task::yield_now().await;
+                log.lock().unwrap().push(format!("fetch {}", params["0"]));
+                Ok(())
+            }
+        });
+        let log = calls.clone();
+        engine.register_action_handler("Mark", move |params, _facts| {
+            log.lock().unwrap().push(format!("mark {}", params["0"]));
+            Ok(())
+        });
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Number(10.0))]),
+            )
+            .unwrap();
+
+        let result = engine.execute_async(&facts).await.unwrap();
+        assert_eq!(result.rules_fired, 2);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["fetch first", "mark first", "fetch second"]
+        );
+        // Async runs share the cycle helpers, so analytics are recorded too
+        let metrics = engine.analytics().unwrap();
+        assert_eq!(metrics.get_rule_metrics("First").unwrap().total_fires, 1);
+        assert_eq!(metrics.get_rule_metrics("Second").unwrap().total_fires, 1);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_execute_async_enforces_rule_timeout() {
+        let kb = KnowledgeBase::new("AsyncTimeouts");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Slow" salience 10 timeout 10ms {
+                when Order.Total > 0
+                then
+                    Order.Started = true;
+                    SlowQuote(Order.Total);
+            }
+            rule "Fast" no-loop {
+                when Order.Total > 0
+                then Order.Checked = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        engine.register_async_action_handler("SlowQuote", |_| async {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            Ok(())
+        });
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(5))]),
+            )
+            .unwrap();
+
+        let result = engine.execute_async(&facts).await.unwrap();
+
+        assert_eq!(result.rules_fired, 1);
+        assert!(result
+            .timed_out_rules
+            .iter()
+            .all(|timed_out| timed_out.rule_name == "Slow"));
+        assert!(!result.timed_out_rules.is_empty());
+        assert_eq!(facts.get_nested("Order.Started"), None);
+        assert_eq!(
+            facts.get_nested("Order.Checked"),
+            Some(Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn test_contains_depends_on_left_hand_type() {
+        let kb = KnowledgeBase::new("Contains");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Substring" no-loop {
+                when User.Email contains "@example"
+                then User.Internal = true;
+            }
+
+            rule "Element" no-loop {
+                when Order.Tags contains "vip"
+                then Order.Vip = true;
+            }
+
+            rule "DeepElement" no-loop {
+                when Order.Items contains {sku: "A1", qty: 2}
+                then Order.HasA1 = true;
+            }
+
+            rule "Key" no-loop {
+                when User.Preferences contains "theme"
+                then User.Themed = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let item = |sku: &str, qty: i64| {
+            Facts::create_object(vec![
+                ("sku".to_string(), Value::String(sku.to_string())),
+                ("qty".to_string(), Value::Integer(qty)),
+            ])
+        };
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![
+                    (
+                        "Email".to_string(),
+                        Value::String("ann@example.com".to_string()),
+                    ),
+                    (
+                        "Preferences".to_string(),
+                        Facts::create_object(vec![(
+                            "theme".to_string(),
+                            Value::String("dark".to_string()),
+                        )]),
+                    ),
+                ]),
+            )
+            .unwrap();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![
+                    (
+                        "Tags".to_string(),
+                        Value::Array(vec![
+                            Value::String("new".to_string()),
+                            Value::String("vip".to_string()),
+                        ]),
+                    ),
+                    (
+                        "Items".to_string(),
+                        Value::Array(vec![item("B2", 1), item("A1", 2)]),
+                    ),
+                ]),
+            )
+            .unwrap();
+
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 4);
+
+        // An element must match as a whole, not as a substring or a partial object
+        let tags = facts.get_nested("Order.Tags").unwrap();
+        assert!(!Operator::Contains.evaluate(&tags, &Value::String("vi".to_string())));
+        assert!(Operator::NotContains.evaluate(&tags, &Value::String("vi".to_string())));
+        let items = facts.get_nested("Order.Items").unwrap();
+        assert!(!Operator::Contains.evaluate(&items, &item("A1", 3)));
+
+        // Objects contain keys, not values
+        let preferences = facts.get_nested("User.Preferences").unwrap();
+        assert!(!Operator::Contains.evaluate(&preferences, &Value::String("dark".to_string())));
+        assert!(Operator::NotContains.evaluate(&preferences, &Value::String("font".to_string())));
+
+        // Integers and floats are the same element, as they are for `==`
+        let numbers = Value::Array(vec![Value::Integer(1), Value::Integer(2)]);
+        assert!(Operator::Equal.evaluate(&Value::Integer(2), &Value::Number(2.0)));
+        assert!(Operator::Contains.evaluate(&numbers, &Value::Number(2.0)));
+        assert!(!Operator::NotContains.evaluate(&numbers, &Value::Number(2.0)));
+        assert!(Operator::NotContains.evaluate(&numbers, &Value::Number(2.5)));
+        let floats = Value::Array(vec![Value::Number(1.0)]);
+        assert!(Operator::Contains.evaluate(&floats, &Value::Integer(1)));
+
+        // Scalars contain nothing, and a string never contains a number
+        let email = facts.get_nested("User.Email").unwrap();
+        assert!(!Operator::Contains.evaluate(&Value::Integer(12), &Value::Integer(1)));
+        assert!(!Operator::NotContains.evaluate(&Value::Integer(12), &Value::Integer(1)));
+        assert!(!Operator::Contains.evaluate(&email, &Value::Integer(1)));
+        assert!(!Operator::NotContains.evaluate(&email, &Value::Integer(1)));
+    }
+
+    #[test]
+    fn test_execute_with_cycle_callback_reports_each_cycle() {
+        let kb = KnowledgeBase::new("Cycles");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Ship" salience 10 no-loop {
+                when Order.Paid == true
+                then Order.Shipped = true;
+            }
+
+            rule "Pay" no-loop {
+                when Order.Total > 0
+                then Order.Paid = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Number(10.0))]),
+            )
+            .unwrap();
+
+        let mut cycles = Vec::new();
+        let result = engine
+            .execute_with_cycle_callback(&facts, |cycle, fired| {
+                cycles.push((cycle, fired.to_vec()));
+            })
+            .unwrap();
+
+        assert_eq!(result.cycle_count, 3);
+        assert_eq!(
+            cycles,
+            vec![
+                (1, vec!["Pay".to_string()]),
+                (2, vec!["Ship".to_string()]),
+                (3, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_null_checks_and_null_safe_ordering() {
+        let kb = KnowledgeBase::new("Nulls");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Missing" no-loop {
+                when Input.Value is null
+                then Input.Missing = true;
+            }
+
+            rule "Present" no-loop {
+                when Input.Value is not null
+                then Input.Present = true;
+            }
+
+            rule "Positive" no-loop {
+                when Input.Value > 0
+                then Input.Positive = true;
+            }
+            "#,
+        )
+        .unwrap();
+
+        // A fresh engine per scenario, since no-loop state outlives a run
+        let run = |value: Option<Value>| {
+            let mut engine = RustRuleEngine::new(kb.clone());
+            let facts = Facts::new();
+            let mut fields = vec![];
+            if let Some(value) = value {
+                fields.push(("Value".to_string(), value));
+            }
+            facts
+                .add_value("Input", Facts::create_object(fields))
+                .unwrap();
+            engine.execute(&facts).unwrap();
+            let flag = |name: &str| facts.get_nested(&format!("Input.{}", name)).is_some();
+            (flag("Missing"), flag("Present"), flag("Positive"))
+        };
+
+        // An absent field and an explicit null are both null, and neither orders against 0
+        assert_eq!(run(None), (true, false, false));
+        assert_eq!(run(Some(Value::Null)), (true, false, false));
+        assert_eq!(run(Some(Value::Integer(5))), (false, true, true));
+
+        assert!(!Operator::LessThan.evaluate(&Value::Null, &Value::Integer(0)));
+        assert!(!Operator::GreaterThanOrEqual.evaluate(&Value::Integer(0), &Value::Null));
+        assert!(Operator::Equal.evaluate(&Value::Null, &Value::Null));
+    }
+
+    #[test]
+    fn test_add_score_accumulates_weights_of_matching_rules() {
+        let kb = KnowledgeBase::new("Scorecard");
+        kb.add_rules_from_grl(
+            r#"
+            rule "YoungDriver" no-loop {
+                when Applicant.Age < 25
+                then AddScore("Applicant.Risk", 30);
+            }
+
+            rule "PriorClaims" no-loop {
+                when Applicant.Claims > 0
+                then AddScore("Applicant.Risk", 20.5);
+            }
+
+            rule "LongTenure" no-loop {
+                when Applicant.Years >= 5
+                then add_score("Applicant.Risk", -10);
+                     AddScore("Applicant.Loyalty", 1);
+            }
+
+            rule "HighRisk" no-loop {
+                when Applicant.Risk > 40
+                then Applicant.Review = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Applicant",
+                Facts::create_object(vec![
+                    ("Age".to_string(), Value::Integer(22)),
+                    ("Claims".to_string(), Value::Integer(2)),
+                    ("Years".to_string(), Value::Integer(6)),
+                ]),
+            )
+            .unwrap();
+
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.scores.len(), 2);
+        assert_eq!(result.scores["Applicant.Risk"], 40.5);
+        assert_eq!(result.scores["Applicant.Loyalty"], 1.0);
+
+        // Totals are written back to facts, so later rules can test them
+        assert_eq!(
+            facts.get_nested("Applicant.Risk"),
+            Some(Value::Number(40.5))
+        );
+        assert_eq!(
+            facts.get_nested("Applicant.Review"),
+            Some(Value::Boolean(true))
+        );
+
+        assert!(crate::parser::grl::GRLParser::parse_rules(
+            r#"rule "Bad" { when A.B == 1 then AddScore("A.Score", "ten"); }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_simplify_folds_constants_and_flattens_groups() {
+        let positive = |field: &str| {
+            ConditionGroup::single(Condition::new(
+                field.to_string(),
+                Operator::GreaterThan,
+                Value::Integer(0),
+            ))
+        };
+        let (a, b) = (positive("A.X"), positive("B.X"));
+        let a_and_b = ConditionGroup::and(a.clone(), b.clone());
+
+        let nested = ConditionGroup::and(
+            ConditionGroup::Constant(true),
+            ConditionGroup::and(a.clone(), ConditionGroup::and(a.clone(), b.clone())),
+        );
+        assert_eq!(nested.simplify(), a_and_b);
+        assert_eq!(
+            ConditionGroup::and(a.clone(), ConditionGroup::Constant(false)).simplify(),
+            ConditionGroup::Constant(false)
+        );
+        assert_eq!(
+            ConditionGroup::or(ConditionGroup::or(a.clone(), b.clone()), a.clone()).simplify(),
+            ConditionGroup::or(a.clone(), b.clone())
+        );
+        assert_eq!(
+            ConditionGroup::or(a.clone(), ConditionGroup::Constant(true)).simplify(),
+            ConditionGroup::Constant(true)
+        );
+        assert_eq!(
+            ConditionGroup::not(ConditionGroup::not(a.clone())).simplify(),
+            a
+        );
+        assert_eq!(
+            ConditionGroup::xor(a.clone(), ConditionGroup::Constant(true)).simplify(),
+            ConditionGroup::not(a.clone())
+        );
+        // Different operators are not merged
+        let mixed = ConditionGroup::or(a_and_b.clone(), b.clone());
+        assert_eq!(mixed.clone().simplify(), mixed);
+
+        let kb = KnowledgeBase::new("Generated").with_condition_simplification(true);
+        kb.add_rules_from_grl(
+            r#"
+            rule "Big" no-loop {
+                when true && Order.Total > 100 && Order.Total > 100
+                then Order.Big = true;
+            }
+
+            rule "Never" no-loop {
+                when false && Order.Total > 0
+                then Order.Never = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let big = kb.get_rule("Big").unwrap();
+        assert!(matches!(big.conditions, ConditionGroup::Single(_)));
+        assert!(kb.export_to_grl().contains("when\n        false"));
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(150))]),
+            )
+            .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        let result = engine.execute(&facts).unwrap();
+        assert_eq!(result.rules_fired, 1);
+        assert_eq!(facts.get_nested("Order.Big"), Some(Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_group_salience_orders_rules_across_groups() {
+        let grl = r#"
+            rule "Audit" salience 100 agenda-group "low" {
+                when Order.Total > 0
+                then Order.Last = "Audit";
+            }
+
+            rule "Stage" salience 1 agenda-group "high" {
+                when Order.Total > 0
+                then Order.Last = "Stage";
+            }
+        "#;
+        // Both rules fire; the one that fires last leaves its name behind
+        let last_fired = |base: i32| {
+            let kb = KnowledgeBase::new("Stages");
+            kb.add_rules_from_grl(grl).unwrap();
+            let mut engine = RustRuleEngine::new(kb);
+            engine.set_group_salience("high", base);
+            engine.ingest("Order.Total", Value::Integer(10)).unwrap();
+            assert_eq!(engine.run_pending().unwrap().rules_fired, 2);
+            engine.session_facts().get_nested("Order.Last")
+        };
+
+        assert_eq!(last_fired(0), Some(Value::String("Stage".to_string())));
+        assert_eq!(last_fired(1000), Some(Value::String("Audit".to_string())));
+    }
+
+    #[test]
+    fn test_execute_until_stops_once_predicate_holds() {
+        let kb = KnowledgeBase::new("Converge");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Double" {
+                when Num.X < 1000
+                then Num.X = Num.X * 2;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        let facts_with_x = || {
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Num",
+                    Facts::create_object(vec![("X".to_string(), Value::Integer(1))]),
+                )
+                .unwrap();
+            facts
+        };
+        let reached_16 =
+            |facts: &Facts| matches!(facts.get_nested("Num.X"), Some(Value::Integer(x)) if x >= 16);
+
+        let facts = facts_with_x();
+        let (result, met) = engine.execute_until(&facts, reached_16, 50).unwrap();
+        assert!(met);
+        assert_eq!(result.cycle_count, 4);
+        assert_eq!(facts.get_nested("Num.X"), Some(Value::Integer(16)));
+
+        // The cycle cap still applies and is reported as not converged
+        let facts = facts_with_x();
+        let (result, met) = engine.execute_until(&facts, reached_16, 2).unwrap();
+        assert!(!met);
+        assert_eq!(result.cycle_count, 2);
+        assert_eq!(engine.config.max_cycles, 100);
+    }
+
+    #[test]
+    fn test_registered_comparator_orders_tagged_fields() {
+        let kb = KnowledgeBase::new("Versions");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Upgrade" no-loop {
+                when App.Version < "1.2.10"
+                then App.Outdated = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let app = |version: &str| {
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "App",
+                    Facts::create_object(vec![(
+                        "Version".to_string(),
+                        Value::String(version.to_string()),
+                    )]),
+                )
+                .unwrap();
+            facts
+        };
+        let semver = |value: &Value| -> Vec<u64> {
+            value
+                .to_string()
+                .split('.')
+                .map(|part| part.parse().unwrap_or(0))
+                .collect()
+        };
+
+        // Without a comparator version strings have no ordering at all
+        let mut engine = RustRuleEngine::new(kb.clone());
+        assert_eq!(engine.execute(&app("1.2.9")).unwrap().rules_fired, 0);
+
+        for (version, outdated) in [("1.2.9", true), ("1.2.10", false), ("1.10.0", false)] {
+            let mut engine = RustRuleEngine::new(kb.clone());
+            let facts = app(version);
+            facts.register_comparator(
+                "semver",
+                Box::new(move |a: &Value, b: &Value| semver(a).cmp(&semver(b))),
+            );
+            facts.tag_field("App.Version", "semver");
+            assert_eq!(
+                engine.execute(&facts).unwrap().rules_fired,
+                usize::from(outdated)
+            );
+        }
+    }
+
+    #[test]
+    fn test_assignment_stores_function_result() {
+        let kb = KnowledgeBase::new("Scoring");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Score" {
+                when Order.Ready == true
+                then
+                    Order.Score = calculateScore(Order.Items, 2);
+                    Order.Ready = false;
+            }
+            "#,
+        )
+        .unwrap();
+        let rule = kb.get_rule("Score").unwrap();
+        assert!(matches!(
+            &rule.actions[0],
+            ActionType::CallAndBind { field, function, args }
+                if field == "Order.Score" && function == "calculateScore" && args.len() == 2
+        ));
+
+        let mut engine = RustRuleEngine::new(kb);
+        engine.register_function("calculateScore", |args, _facts| {
+            let total: f64 = match &args[0] {
+                Value::Array(items) => items.iter().filter_map(Value::to_number).sum(),
+                _ => 0.0,
+            };
+            Ok(Value::Number(total * args[1].to_number().unwrap_or(1.0)))
+        });
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![
+                    ("Ready".to_string(), Value::Boolean(true)),
+                    (
+                        "Items".to_string(),
+                        Value::Array(vec![Value::Integer(3), Value::Number(4.5)]),
+                    ),
+                ]),
+            )
+            .unwrap();
+        engine.execute(&facts).unwrap();
+        assert_eq!(facts.get_nested("Order.Score"), Some(Value::Number(15.0)));
+
+        // An unregistered function is an error rather than a silent no-op
+        let kb = KnowledgeBase::new("Unregistered");
+        kb.add_rule(rule).unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        facts
+            .set_nested("Order.Ready", Value::Boolean(true))
+            .unwrap();
+        assert!(engine.execute(&facts).is_err());
+    }
+
+    #[test]
+    fn test_disable_rules_by_tag_and_name_at_runtime() {
+        let kb = KnowledgeBase::new("Flags");
+        kb.add_rules_from_grl(
+            r#"
+            rule "BasePrice" tags ["pricing"] no-loop {
+                when Order.Total > 0
+                then Order.Priced = true;
+            }
+
+            rule "SummerPromo" tags ["pricing", "promo"] no-loop {
+                when Order.Total > 0
+                then Order.Promo = true;
+            }
+
+            rule "FraudCheck" tags ["risk"] no-loop {
+                when Order.Total > 0
+                then Order.Checked = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+        // Each phase runs against fresh facts, so no-loop state is reset too
+        let fired = |engine: &mut RustRuleEngine| {
+            engine.reset_no_loop_tracking();
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Order",
+                    Facts::create_object(vec![("Total".to_string(), Value::Integer(50))]),
+                )
+                .unwrap();
+            engine.execute(&facts).unwrap().rules_fired
+        };
+
+        assert_eq!(fired(&mut engine), 3);
+
+        assert_eq!(engine.disable_rules_by_tag("promo"), 1);
+        assert_eq!(engine.disable_rules_by_tag("unknown"), 0);
+        assert_eq!(fired(&mut engine), 2);
+
+        assert!(engine.set_rule_enabled("FraudCheck", false).unwrap());
+        assert!(!engine.set_rule_enabled("Missing", false).unwrap());
+        assert_eq!(fired(&mut engine), 1);
+
+        assert_eq!(engine.enable_rules_by_tag("pricing"), 2);
+        assert!(
+            engine
+                .knowledge_base()
+                .get_rule("SummerPromo")
+                .unwrap()
+                .enabled
+        );
+        assert_eq!(fired(&mut engine), 2);
+    }
+
+    #[test]
+    fn test_failing_custom_action_trips_circuit_breaker() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let grl = r#"
+            rule "Sync" {
+                when Batch.Left > 0
+                then
+                    CallApi(Batch.Left);
+                    Batch.Left = Batch.Left - 1;
+            }
+        "#;
+        let run = |threshold: Option<usize>| {
+            let kb = KnowledgeBase::new("Breaker");
+            kb.add_rules_from_grl(grl).unwrap();
+            let config = EngineConfig {
+                action_failure_threshold: threshold,
+                ..Default::default()
+            };
+            let mut engine = RustRuleEngine::with_config(kb, config);
+            let calls = Arc::new(AtomicUsize::new(0));
+            let counter = calls.clone();
+            engine.register_action_handler("CallApi", move |_, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err(RuleEngineError::EvaluationError {
+                    message: "service unavailable".to_string(),
+                })
+            });
+            let facts = Facts::new();
+            facts
+                .add_value(
+                    "Batch",
+                    Facts::create_object(vec![("Left".to_string(), Value::Integer(5))]),
+                )
+                .unwrap();
+            let result = engine.execute(&facts);
+            (result, calls.load(Ordering::SeqCst), facts)
+        };
+
+        // Without a threshold the first failure aborts the run
+        let (result, calls, _) = run(None);
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        let (result, calls, facts) = run(Some(3));
+        let result = result.unwrap();
+        assert_eq!(calls, 3);
+        assert_eq!(result.rules_fired, 5);
+        assert_eq!(result.tripped_actions, vec!["CallApi".to_string()]);
+        assert_eq!(facts.get_nested("Batch.Left"), Some(Value::Integer(0)));
+    }
+
+    #[test]
+    fn test_conditions_and_actions_index_arrays() {
+        let kb = KnowledgeBase::new("Indexed");
+        kb.add_rules_from_grl(
+            r#"
+            rule "PricyLast" no-loop {
+                when Order.Items[-1].price > 30 && Order.Items[0].price < 20
+                then Order.Items[-1].flagged = true;
+            }
+            "#,
+        )
+        .unwrap();
+        let mut engine = RustRuleEngine::new(kb);
+
+        let item =
+            |price: i64| Facts::create_object(vec![("price".to_string(), Value::Integer(price))]);
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![(
+                    "Items".to_string(),
+                    Value::Array(vec![item(10), item(25), item(40)]),
+                )]),
+            )
+            .unwrap();
+
+        assert_eq!(engine.execute(&facts).unwrap().rules_fired, 1);
+        assert_eq!(
+            facts.get_nested("Order.Items[2].flagged"),
+            Some(Value::Boolean(true))
+        );
+        assert_eq!(facts.get_nested("Order.Items[1].flagged"), None);
+    }
+}
@@ -0,0 +1,103 @@
+//! Synchronous event dispatch
+//!
+//! A lightweight middle ground between batch `execute` and the `streaming`
+//! feature: [`RustRuleEngine::dispatch`](crate::engine::RustRuleEngine::dispatch)
+//! runs the rules that read an event, then the handlers registered for its
+//! type with [`RustRuleEngine::on_event`](crate::engine::RustRuleEngine::on_event).
+//! There is no async runtime, windowing or buffering.
+
+use crate::engine::engine::EmittedEvent;
+use crate::engine::facts::Facts;
+use crate::errors::Result;
+use crate::types::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Event delivered through the event bus
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// Event type name; rules see the payload as a fact of this name
+    pub event_type: String,
+    /// Event fields
+    pub payload: HashMap<String, Value>,
+}
+
+impl Event {
+    /// Create an event with an empty payload
+    pub fn new(event_type: impl Into<String>) -> Self {
+        Self {
+            event_type: event_type.into(),
+            payload: HashMap::new(),
+        }
+    }
+
+    /// Add a payload field
+    pub fn with_field(mut self, key: &str, value: Value) -> Self {
+        self.payload.insert(key.to_string(), value);
+        self
+    }
+
+    /// The payload as a fact object
+    pub(crate) fn to_fact(&self) -> Value {
+        Value::Object(self.payload.clone())
+    }
+}
+
+impl From<EmittedEvent> for Event {
+    fn from(event: EmittedEvent) -> Self {
+        Self {
+            event_type: event.event_type,
+            payload: event.payload,
+        }
+    }
+}
+
+/// Callback receiving an event and the facts the rules ran against
+pub type EventHandler = Box<dyn Fn(&Event, &Facts) -> Result<()> + Send + Sync>;
+
+/// Event handlers grouped by event type
+#[derive(Default)]
+pub struct EventBus {
+    handlers: HashMap<String, Vec<EventHandler>>,
+}
+
+impl EventBus {
+    /// Create an empty event bus
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `handler` for events of `event_type`
+    pub fn subscribe(&mut self, event_type: &str, handler: EventHandler) {
+        self.handlers
+            .entry(event_type.to_string())
+            .or_default()
+            .push(handler);
+    }
+
+    /// Whether any handler is registered for `event_type`
+    pub fn has_handlers(&self, event_type: &str) -> bool {
+        self.handlers
+            .get(event_type)
+            .is_some_and(|handlers| !handlers.is_empty())
+    }
+
+    /// Call the handlers for `event` in registration order, stopping at the
+    /// first error
+    pub fn publish(&self, event: &Event, facts: &Facts) -> Result<()> {
+        for handler in self.handlers.get(&event.event_type).into_iter().flatten() {
+            handler(event, facts)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut types: Vec<_> = self.handlers.keys().collect();
+        types.sort();
+        f.debug_struct("EventBus")
+            .field("event_types", &types)
+            .finish()
+    }
+}
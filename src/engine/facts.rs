@@ -1,7 +1,10 @@
+use crate::engine::schema::FactSchema;
 use crate::errors::{Result, RuleEngineError};
-use crate::types::{Context, Value};
+use crate::rete::accumulate::AccumulateFunctionRegistry;
+use crate::types::{Context, Operator, Value};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
 /// Facts - represents the working memory of data objects
@@ -14,6 +17,80 @@ pub struct Facts {
     /// Each frame records per-key previous values so rollback can restore only
     /// changed keys instead of cloning the whole facts map.
     undo_frames: Arc<RwLock<Vec<Vec<UndoEntry>>>>,
+    /// Optional per-object schemas checked on `add_value`/`set_nested`
+    schemas: Arc<RwLock<HashMap<String, FactSchema>>>,
+    /// Computed aggregates, evaluated on read
+    aggregates: Arc<RwLock<HashMap<String, AggregateDefinition>>>,
+    /// Observers notified when a watched path is written
+    watchers: Arc<RwLock<Watchers>>,
+    /// Custom orderings for fields tagged with a type
+    comparators: Arc<RwLock<Comparators>>,
+}
+
+/// Callback invoked with the new value of a watched fact path
+pub type FactWatcher = Box<dyn Fn(&Value) + Send + Sync>;
+
+/// Handle returned by [`Facts::watch`], used to remove the watcher again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WatchId(u64);
+
+#[derive(Default)]
+struct Watchers {
+    next_id: u64,
+    entries: Vec<(WatchId, String, Arc<FactWatcher>)>,
+}
+
+impl Watchers {
+    /// Callbacks whose pattern matches `path`: exact, or `Prefix.*`
+    fn matching(&self, path: &str) -> Vec<Arc<FactWatcher>> {
+        self.entries
+            .iter()
+            .filter(|(_, pattern, _)| match pattern.strip_suffix('*') {
+                Some(prefix) if prefix.is_empty() || prefix.ends_with('.') => {
+                    path.starts_with(prefix)
+                }
+                _ => pattern == path,
+            })
+            .map(|(_, _, callback)| callback.clone())
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for Watchers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let patterns: Vec<&String> = self.entries.iter().map(|(_, pattern, _)| pattern).collect();
+        f.debug_struct("Watchers")
+            .field("patterns", &patterns)
+            .finish()
+    }
+}
+
+/// Orders two values of a field type registered with [`Facts::register_comparator`]
+pub type ValueComparator = Box<dyn Fn(&Value, &Value) -> Ordering + Send + Sync>;
+
+#[derive(Default)]
+struct Comparators {
+    /// Type tag of each tagged field path
+    field_types: HashMap<String, String>,
+    /// Comparator registered for each type tag
+    by_type: HashMap<String, Arc<ValueComparator>>,
+}
+
+impl std::fmt::Debug for Comparators {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let types: Vec<&String> = self.by_type.keys().collect();
+        f.debug_struct("Comparators")
+            .field("field_types", &self.field_types)
+            .field("types", &types)
+            .finish()
+    }
+}
+
+/// A computed fact: an accumulate function over all values matching a path pattern
+#[derive(Debug, Clone)]
+struct AggregateDefinition {
+    function: String,
+    pattern: Vec<String>,
 }
 
 impl Facts {
@@ -32,9 +109,182 @@ impl Facts {
             data: Arc::new(RwLock::new(HashMap::new())),
             fact_types: Arc::new(RwLock::new(HashMap::new())),
             undo_frames: Arc::new(RwLock::new(Vec::new())),
+            schemas: Arc::new(RwLock::new(HashMap::new())),
+            aggregates: Arc::new(RwLock::new(HashMap::new())),
+            watchers: Arc::new(RwLock::new(Watchers::default())),
+            comparators: Arc::new(RwLock::new(Comparators::default())),
         }
     }
 
+    /// Call `callback` with the new value whenever `path` is written
+    ///
+    /// `path` is either an exact path (`User.Name`) or a prefix glob
+    /// (`User.*`) matching every path below it. Watchers run synchronously
+    /// after `set`, `set_nested` or `set_many` has stored the value, on the
+    /// writing thread and without any lock held, so they may read the facts.
+    pub fn watch(&self, path: &str, callback: FactWatcher) -> WatchId {
+        let mut watchers = self.watchers.write().unwrap();
+        let id = WatchId(watchers.next_id);
+        watchers.next_id += 1;
+        watchers
+            .entries
+            .push((id, path.to_string(), Arc::new(callback)));
+        id
+    }
+
+    /// Remove a watcher, returning whether it was registered
+    pub fn unwatch(&self, id: WatchId) -> bool {
+        let mut watchers = self.watchers.write().unwrap();
+        let before = watchers.entries.len();
+        watchers.entries.retain(|(entry, _, _)| *entry != id);
+        watchers.entries.len() != before
+    }
+
+    fn notify_watchers(&self, path: &str, value: &Value) {
+        let callbacks = self.watchers.read().unwrap().matching(path);
+        for callback in callbacks {
+            callback(value);
+        }
+    }
+
+    /// Register how values of `type_tag` are ordered
+    ///
+    /// Fields tagged with `type_tag` through [`tag_field`](Self::tag_field)
+    /// are compared with `comparator` by `==`, `!=`, `>`, `>=`, `<` and `<=`,
+    /// e.g. to order version strings so that `"1.2.10" > "1.2.9"`.
+    pub fn register_comparator(&self, type_tag: &str, comparator: ValueComparator) {
+        let mut comparators = self.comparators.write().unwrap();
+        comparators
+            .by_type
+            .insert(type_tag.to_string(), Arc::new(comparator));
+    }
+
+    /// Tag the field at `path` with a type registered through
+    /// [`register_comparator`](Self::register_comparator)
+    pub fn tag_field(&self, path: &str, type_tag: &str) {
+        let mut comparators = self.comparators.write().unwrap();
+        comparators
+            .field_types
+            .insert(path.to_string(), type_tag.to_string());
+    }
+
+    /// Evaluate `operator` for a condition on `path`, using the comparator of
+    /// the field's type tag when one is registered
+    pub fn evaluate_operator(
+        &self,
+        path: &str,
+        operator: &Operator,
+        left: &Value,
+        right: &Value,
+    ) -> bool {
+        let comparator = {
+            let comparators = self.comparators.read().unwrap();
+            comparators
+                .field_types
+                .get(path)
+                .and_then(|type_tag| comparators.by_type.get(type_tag))
+                .cloned()
+        };
+        match comparator {
+            Some(compare) => operator.evaluate_with(left, right, &**compare),
+            None => operator.evaluate(left, right),
+        }
+    }
+
+    /// Register a schema for an object fact
+    ///
+    /// Subsequent `add_value`, `set_nested` and `set_many` writes to `name`
+    /// are validated and fail with [`RuleEngineError::SchemaViolation`] on a
+    /// kind mismatch. Facts without a schema stay fully dynamic.
+    pub fn register_schema(&self, name: &str, schema: FactSchema) {
+        let mut schemas = self.schemas.write().unwrap();
+        schemas.insert(name.to_string(), schema);
+    }
+
+    /// Check a write of `value` to `path` against any registered schema
+    fn validate_schema(&self, path: &str, value: &Value) -> Result<()> {
+        let schemas = self.schemas.read().unwrap();
+        let parts: Vec<&str> = path.split('.').collect();
+        let Some(schema) = schemas.get(parts[0]) else {
+            return Ok(());
+        };
+
+        match parts.len() {
+            1 => schema.validate(parts[0], value),
+            // Only direct fields are declared; deeper paths are not checked
+            2 => schema.validate_field(parts[0], parts[1], value),
+            _ => Ok(()),
+        }
+    }
+
+    /// Define a computed fact that aggregates over instance-keyed facts
+    ///
+    /// `pattern` is a dotted path where `*` matches any single segment, e.g.
+    /// `Order.*.amount` over `Order.1.amount`, `Order.2.amount`, ... It may also
+    /// reach into object facts (`Order.1` holding `{ amount: .. }`). `function`
+    /// is any built-in accumulate function (`sum`, `count`, `average`, `min`,
+    /// `max`, `count_distinct`, `median`, `stddev`).
+    ///
+    /// Nothing is stored: every `get`/`get_nested` of `name` recomputes the
+    /// aggregate from the current facts.
+    ///
+    /// ```rust
+    /// # use rust_rule_engine::{Facts, Value};
+    /// let facts = Facts::new();
+    /// facts.define_aggregate("Orders.total", "sum", "Order.*.amount").unwrap();
+    /// facts.set("Order.1.amount", Value::Integer(40));
+    /// facts.set("Order.2.amount", Value::Integer(60));
+    /// assert_eq!(facts.get_nested("Orders.total"), Some(Value::Number(100.0)));
+    /// ```
+    pub fn define_aggregate(&self, name: &str, function: &str, pattern: &str) -> Result<()> {
+        let function = if function == "avg" {
+            "average"
+        } else {
+            function
+        };
+        if AccumulateFunctionRegistry::new().get(function).is_none() {
+            return Err(RuleEngineError::EvaluationError {
+                message: format!("Unknown accumulate function: {}", function),
+            });
+        }
+
+        let mut aggregates = self.aggregates.write().unwrap();
+        aggregates.insert(
+            name.to_string(),
+            AggregateDefinition {
+                function: function.to_string(),
+                pattern: pattern.split('.').map(str::to_string).collect(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Compute the aggregate registered under `name`, if any
+    fn computed_aggregate(&self, data: &HashMap<String, Value>, name: &str) -> Option<Value> {
+        let aggregates = self.aggregates.read().unwrap();
+        let definition = aggregates.get(name)?;
+        let function = AccumulateFunctionRegistry::new().get(&definition.function)?;
+
+        let mut state = function.init();
+        for (key, value) in data {
+            let segments: Vec<&str> = key.split('.').collect();
+            if segments.len() > definition.pattern.len() {
+                continue;
+            }
+            let prefix_matches = segments
+                .iter()
+                .zip(&definition.pattern)
+                .all(|(segment, pattern)| pattern == "*" || pattern == segment);
+            if prefix_matches {
+                for_each_match(value, &definition.pattern[segments.len()..], &mut |v| {
+                    state.accumulate(&v.clone().into())
+                });
+            }
+        }
+
+        Some(state.get_result().into())
+    }
+
     /// Add a fact object to the working memory
     pub fn add<T>(&self, name: &str, fact: T) -> Result<()>
     where
@@ -58,6 +308,9 @@ impl Facts {
 
     /// Add a simple value fact
     pub fn add_value(&self, name: &str, value: Value) -> Result<()> {
+        self.validate_schema(name, &value)?;
+        self.record_undo_for_key(name);
+
         let mut data = self.data.write().unwrap();
         let mut types = self.fact_types.write().unwrap();
 
@@ -67,10 +320,100 @@ impl Facts {
         Ok(())
     }
 
+    /// Add a serializable struct as an object fact
+    ///
+    /// Fields keep their serialized names, so `add_struct("User", &user)` makes
+    /// `user.age` readable as `User.age`. Registered schemas are checked as
+    /// for [`add_value`](Self::add_value).
+    pub fn add_struct<T: Serialize>(&self, name: &str, value: &T) -> Result<()> {
+        let value = FactHelper::from_serialize(value)?;
+        self.validate_schema(name, &value)?;
+        self.record_undo_for_key(name);
+
+        let mut data = self.data.write().unwrap();
+        let mut types = self.fact_types.write().unwrap();
+
+        data.insert(name.to_string(), value);
+        types.insert(name.to_string(), std::any::type_name::<T>().to_string());
+
+        Ok(())
+    }
+
+    /// Add one instance of a fact type and return its id
+    ///
+    /// Fields of an object are stored as flat `Type.<id>.<field>` keys, the
+    /// layout accumulate and `get_instances` read. Any other value is stored as
+    /// `Type.<id>.value`. Ids are sequential integers starting at 1.
+    pub fn add_instance(&self, type_name: &str, obj: Value) -> String {
+        let mut data = self.data.write().unwrap();
+        let mut types = self.fact_types.write().unwrap();
+
+        let prefix = format!("{}.", type_name);
+        let next_id = data
+            .keys()
+            .filter_map(|key| {
+                key.strip_prefix(&prefix)?
+                    .split('.')
+                    .next()?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let id = next_id.to_string();
+
+        let fields = match obj {
+            Value::Object(fields) => fields.into_iter().collect(),
+            other => vec![("value".to_string(), other)],
+        };
+        for (field, value) in fields {
+            let key = format!("{}{}.{}", prefix, id, field);
+            types.insert(key.clone(), type_name.to_string());
+            data.insert(key, value);
+        }
+
+        id
+    }
+
+    /// All instances of a fact type as `(id, object)` pairs
+    ///
+    /// Reads flat `Type.<id>.<field>` keys, whether written by `add_instance` or
+    /// set directly; retracted instances are skipped. Numeric ids sort numerically.
+    pub fn get_instances(&self, type_name: &str) -> Vec<(String, Value)> {
+        let prefix = format!("{}.", type_name);
+        let mut instances: HashMap<String, HashMap<String, Value>> = HashMap::new();
+        for (key, value) in self.get_all_facts() {
+            let Some((id, field)) = key
+                .strip_prefix(&prefix)
+                .and_then(|rest| rest.split_once('.'))
+            else {
+                continue;
+            };
+            instances
+                .entry(id.to_string())
+                .or_default()
+                .insert(field.to_string(), value);
+        }
+
+        let mut instances: Vec<(String, Value)> = instances
+            .into_iter()
+            .map(|(id, fields)| (id, Value::Object(fields)))
+            .collect();
+        instances.sort_by(
+            |(a, _), (b, _)| match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                _ => a.cmp(b),
+            },
+        );
+        instances
+    }
+
     /// Get a fact by name
     pub fn get(&self, name: &str) -> Option<Value> {
         let data = self.data.read().unwrap();
-        data.get(name).cloned()
+        self.computed_aggregate(&data, name)
+            .or_else(|| data.get(name).cloned())
     }
 
     /// Access a fact value by reference via a callback, avoiding clone
@@ -83,34 +426,61 @@ impl Facts {
     }
 
     /// Get a nested fact property (e.g., "User.Profile.Age")
+    ///
+    /// Safe-navigation segments (`Order?.Customer?.Email`) are accepted; any
+    /// missing intermediate simply yields `None`.
     pub fn get_nested(&self, path: &str) -> Option<Value> {
-        let parts: Vec<&str> = path.split('.').collect();
-        if parts.is_empty() {
-            return None;
-        }
-
         let data = self.data.read().unwrap();
-        let mut current = data.get(parts[0])?;
+        self.computed_aggregate(&data, path)
+            .or_else(|| Self::lookup_nested(&data, path))
+    }
 
-        for part in parts.iter().skip(1) {
-            match current {
-                Value::Object(ref obj) => {
-                    current = obj.get(*part)?;
-                }
+    /// Resolve a dotted path against already-locked fact data
+    ///
+    /// Segments may index arrays, as in `Order.Items[0].price`; `[-1]` is the
+    /// last element. An index out of bounds resolves to `None`.
+    fn lookup_nested(data: &HashMap<String, Value>, path: &str) -> Option<Value> {
+        let mut segments = path.split('.').map(|part| part.trim_end_matches('?'));
+        let (root, brackets) = split_segment(segments.next()?);
+        let mut current = index_value(data.get(root)?, brackets)?;
+
+        for segment in segments {
+            let (name, brackets) = split_segment(segment);
+            current = match current {
+                Value::Object(obj) => index_value(obj.get(name)?, brackets)?,
                 _ => return None,
-            }
+            };
         }
 
         Some(current.clone())
     }
 
+    /// Get several facts at once under a single read lock
+    ///
+    /// Each path is resolved as a nested path first, then as a flat key.
+    /// The result has one entry per path, `None` for misses.
+    pub fn get_many(&self, paths: &[&str]) -> Vec<Option<Value>> {
+        let data = self.data.read().unwrap();
+        paths
+            .iter()
+            .map(|path| {
+                self.computed_aggregate(&data, path)
+                    .or_else(|| Self::lookup_nested(&data, path))
+                    .or_else(|| data.get(*path).cloned())
+            })
+            .collect()
+    }
+
     /// Set a fact value
     pub fn set(&self, name: &str, value: Value) {
         // Record previous value for undo if an undo frame is active
         self.record_undo_for_key(name);
 
-        let mut data = self.data.write().unwrap();
-        data.insert(name.to_string(), value);
+        self.data
+            .write()
+            .unwrap()
+            .insert(name.to_string(), value.clone());
+        self.notify_watchers(name, &value);
     }
 
     /// Set a nested fact property
@@ -122,63 +492,151 @@ impl Facts {
             });
         }
 
+        self.validate_schema(path, &value)?;
+
         // Record previous top-level key for undo semantics
-        self.record_undo_for_key(parts[0]);
+        self.record_undo_for_key(split_segment(parts[0]).0);
 
-        let mut data = self.data.write().unwrap();
+        {
+            let mut data = self.data.write().unwrap();
+            self.write_path(&mut data, path, value.clone())?;
+        }
+
+        self.notify_watchers(path, &value);
+        Ok(())
+    }
+
+    /// Set several facts at once under a single write lock
+    ///
+    /// Nested paths follow the same rules as [`Facts::set_nested`]. Updates are
+    /// applied in order and stop at the first path that cannot be set.
+    pub fn set_many(&self, updates: &[(&str, Value)]) -> Result<()> {
+        for (path, value) in updates {
+            self.validate_schema(path, value)?;
+        }
+
+        for (path, _) in updates {
+            let root_key = path.split('.').next().unwrap_or(path);
+            self.record_undo_for_key(split_segment(root_key).0);
+        }
 
-        if parts.len() == 1 {
-            data.insert(parts[0].to_string(), value);
+        let mut applied = 0;
+        let result = {
+            let mut data = self.data.write().unwrap();
+            updates.iter().try_for_each(|(path, value)| {
+                self.write_path(&mut data, path, value.clone())?;
+                applied += 1;
+                Ok(())
+            })
+        };
+
+        for (path, value) in &updates[..applied] {
+            self.notify_watchers(path, value);
+        }
+        result
+    }
+
+    /// Set several nested paths as one change: every path is written, or
+    /// none is when one of them cannot be set
+    ///
+    /// Runs under a single write lock like [`set_many`](Self::set_many), so
+    /// readers never see part of the change.
+    pub(crate) fn set_many_atomically(&self, updates: &[(String, Value)]) -> Result<()> {
+        for (path, value) in updates {
+            self.validate_schema(path, value)?;
+        }
+
+        let roots: HashSet<&str> = updates
+            .iter()
+            .map(|(path, _)| split_segment(path.split('.').next().unwrap_or(path)).0)
+            .collect();
+        for root in &roots {
+            self.record_undo_for_key(root);
+        }
+
+        {
+            let mut data = self.data.write().unwrap();
+            let previous: Vec<(&str, Option<Value>)> = roots
+                .iter()
+                .map(|root| (*root, data.get(*root).cloned()))
+                .collect();
+            let result = updates
+                .iter()
+                .try_for_each(|(path, value)| self.write_path(&mut data, path, value.clone()));
+            if let Err(e) = result {
+                for (root, value) in previous {
+                    match value {
+                        Some(value) => data.insert(root.to_string(), value),
+                        None => data.remove(root),
+                    };
+                }
+                return Err(e);
+            }
+        }
+
+        for (path, value) in updates {
+            self.notify_watchers(path, value);
+        }
+        Ok(())
+    }
+
+    /// Store `value` at a dotted, optionally indexed path in locked fact data
+    fn write_path(
+        &self,
+        data: &mut HashMap<String, Value>,
+        path: &str,
+        value: Value,
+    ) -> Result<()> {
+        let parts: Vec<&str> = path.split('.').collect();
+        let (root_key, brackets) = split_segment(parts[0]);
+        if parts.len() == 1 && brackets.is_empty() {
+            data.insert(root_key.to_string(), value);
             return Ok(());
         }
 
         // Navigate to parent and set the nested value
-        let root_key = parts[0];
         let root_value = data
             .get_mut(root_key)
             .ok_or_else(|| RuleEngineError::FieldNotFound {
                 field: root_key.to_string(),
             })?;
-
-        self.set_nested_in_value(root_value, &parts[1..], value)?;
-        Ok(())
+        let root_value = index_value_mut(root_value, brackets, parts[0])?;
+        self.set_nested_in_value(root_value, &parts[1..], value)
     }
 
+    /// Store `value` at `path` below `current`; an empty path replaces `current`
     #[allow(clippy::only_used_in_recursion)]
     fn set_nested_in_value(&self, current: &mut Value, path: &[&str], value: Value) -> Result<()> {
-        if path.is_empty() {
+        let Some((segment, rest)) = path.split_first() else {
+            *current = value;
             return Ok(());
-        }
+        };
+        let (name, brackets) = split_segment(segment);
 
-        if path.len() == 1 {
-            // We're at the target field
-            match current {
-                Value::Object(ref mut obj) => {
-                    obj.insert(path[0].to_string(), value);
-                    Ok(())
-                }
-                _ => Err(RuleEngineError::TypeMismatch {
+        let obj = match current {
+            Value::Object(obj) => obj,
+            other => {
+                return Err(RuleEngineError::TypeMismatch {
                     expected: "Object".to_string(),
-                    actual: format!("{:?}", current),
-                }),
-            }
-        } else {
-            // Continue navigating
-            match current {
-                Value::Object(ref mut obj) => {
-                    let next_value =
-                        obj.get_mut(path[0])
-                            .ok_or_else(|| RuleEngineError::FieldNotFound {
-                                field: path[0].to_string(),
-                            })?;
-                    self.set_nested_in_value(next_value, &path[1..], value)
-                }
-                _ => Err(RuleEngineError::TypeMismatch {
-                    expected: "Object".to_string(),
-                    actual: format!("{:?}", current),
-                }),
+                    actual: format!("{:?}", other),
+                })
             }
+        };
+
+        // We're at the target field
+        if rest.is_empty() && brackets.is_empty() {
+            obj.insert(name.to_string(), value);
+            return Ok(());
         }
+
+        // Continue navigating
+        let next_value = obj
+            .get_mut(name)
+            .ok_or_else(|| RuleEngineError::FieldNotFound {
+                field: name.to_string(),
+            })?;
+        let next_value = index_value_mut(next_value, brackets, segment)?;
+        self.set_nested_in_value(next_value, rest, value)
     }
 
     /// Remove a fact
@@ -221,9 +679,60 @@ impl Facts {
     }
 
     /// Get all facts as a HashMap (for pattern matching evaluation)
+    ///
+    /// Retracted objects are left out, together with any flat keys nested
+    /// under them (`Order.2.amount` once `Order.2` is retracted).
     pub fn get_all_facts(&self) -> HashMap<String, Value> {
         let data = self.data.read().unwrap();
-        data.clone()
+        let retracted = retracted_objects(&data);
+        if retracted.is_empty() {
+            return data.clone();
+        }
+
+        data.iter()
+            .filter(|(key, _)| !is_under_retracted(key, &retracted))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Names of the facts in working memory, sorted
+    ///
+    /// Built on [`get_all_facts`](Self::get_all_facts), so retracted objects
+    /// are left out, as are the engine's internal retraction markers.
+    pub fn keys(&self) -> Vec<String> {
+        self.iter().map(|(name, _)| name).collect()
+    }
+
+    /// Iterate over the facts in working memory by name
+    ///
+    /// Yields a snapshot of the same facts as [`keys`](Self::keys), in the same
+    /// order; later writes do not affect an iterator already created.
+    pub fn iter(&self) -> impl Iterator<Item = (String, Value)> {
+        let mut facts: Vec<(String, Value)> = self
+            .get_all_facts()
+            .into_iter()
+            .filter(|(name, _)| !name.starts_with("_retracted_"))
+            .collect();
+        facts.sort_by(|(a, _), (b, _)| a.cmp(b));
+        facts.into_iter()
+    }
+
+    /// Number of facts [`keys`](Self::keys) lists
+    ///
+    /// Unlike [`count`](Self::count) this skips retracted objects and markers.
+    pub fn len(&self) -> usize {
+        let data = self.data.read().unwrap();
+        let retracted = retracted_objects(&data);
+        data.keys()
+            .filter(|key| is_listed_key(key, &retracted))
+            .count()
+    }
+
+    /// Whether working memory holds no visible facts
+    pub fn is_empty(&self) -> bool {
+        let data = self.data.read().unwrap();
+        let retracted = retracted_objects(&data);
+        !data.keys().any(|key| is_listed_key(key, &retracted))
     }
 
     /// Get the type name of a fact
@@ -249,19 +758,38 @@ impl Facts {
     }
 
     /// Merge another Facts instance into this one
-    pub fn merge(&self, other: &Facts) {
-        let other_data = other.data.read().unwrap();
-        let other_types = other.fact_types.read().unwrap();
+    ///
+    /// `strategy` decides what happens to keys present in both: see
+    /// [`MergeStrategy`]. Fact types follow the values that were kept.
+    pub fn merge(&self, other: &Facts, strategy: MergeStrategy) {
+        let other_data = other.data.read().unwrap().clone();
+        let other_types = other.fact_types.read().unwrap().clone();
 
         let mut data = self.data.write().unwrap();
         let mut types = self.fact_types.write().unwrap();
 
-        for (key, value) in other_data.iter() {
-            data.insert(key.clone(), value.clone());
-        }
+        for (key, value) in other_data {
+            let existed = data.contains_key(&key);
+            match strategy {
+                MergeStrategy::Overwrite => {
+                    data.insert(key.clone(), value);
+                }
+                MergeStrategy::KeepExisting => {
+                    data.entry(key.clone()).or_insert(value);
+                }
+                MergeStrategy::DeepMerge => match data.get_mut(&key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => {
+                        data.insert(key.clone(), value);
+                    }
+                },
+            }
 
-        for (key, type_name) in other_types.iter() {
-            types.insert(key.clone(), type_name.clone());
+            if let Some(type_name) = other_types.get(&key) {
+                if !existed || strategy != MergeStrategy::KeepExisting {
+                    types.insert(key, type_name.clone());
+                }
+            }
         }
     }
 
@@ -276,6 +804,21 @@ impl Facts {
         }
     }
 
+    /// A private copy of the data that shares schemas, aggregates and
+    /// comparators; writes to it reach neither `self` nor its watchers
+    pub(crate) fn working_copy(&self) -> Facts {
+        let snapshot = self.snapshot();
+        Facts {
+            data: Arc::new(RwLock::new(snapshot.data)),
+            fact_types: Arc::new(RwLock::new(snapshot.fact_types)),
+            undo_frames: Arc::new(RwLock::new(Vec::new())),
+            schemas: Arc::clone(&self.schemas),
+            aggregates: Arc::clone(&self.aggregates),
+            watchers: Arc::new(RwLock::new(Watchers::default())),
+            comparators: Arc::clone(&self.comparators),
+        }
+    }
+
     /// Restore from a snapshot
     pub fn restore(&self, snapshot: FactsSnapshot) {
         let mut data = self.data.write().unwrap();
@@ -286,12 +829,158 @@ impl Facts {
     }
 }
 
+/// How [`Facts::merge`] resolves keys present in both working memories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// The incoming value replaces the existing one
+    #[default]
+    Overwrite,
+    /// The existing value is kept
+    KeepExisting,
+    /// Objects are merged key by key, recursively; any other incoming value
+    /// replaces the existing one
+    DeepMerge,
+}
+
+/// Objects hidden by a `_retracted_<object>` marker set to `true`
+fn retracted_objects(data: &HashMap<String, Value>) -> Vec<&str> {
+    data.iter()
+        .filter(|(_, value)| matches!(value, Value::Boolean(true)))
+        .filter_map(|(key, _)| key.strip_prefix("_retracted_"))
+        .collect()
+}
+
+/// Whether `key` is one of the `retracted` objects or a flat key nested under one
+fn is_under_retracted(key: &str, retracted: &[&str]) -> bool {
+    retracted.iter().any(|object| {
+        key.strip_prefix(object)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+/// Whether [`Facts::keys`] lists `key`: neither a retraction marker nor hidden
+/// by one
+fn is_listed_key(key: &str, retracted: &[&str]) -> bool {
+    !key.starts_with("_retracted_") && !is_under_retracted(key, retracted)
+}
+
+/// Split a path segment such as `Items[0][-1]` into its field name and the
+/// bracketed indices that follow it
+fn split_segment(segment: &str) -> (&str, &str) {
+    segment
+        .find('[')
+        .map_or((segment, ""), |open| segment.split_at(open))
+}
+
+/// Indices in the bracket part of a path segment, `None` for a malformed one
+fn segment_indices(brackets: &str) -> impl Iterator<Item = Option<i64>> + '_ {
+    brackets.split_inclusive(']').map(|index| {
+        index
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .trim()
+            .parse()
+            .ok()
+    })
+}
+
+/// Position of element `index` in an array of `len`, counting from the end
+/// when negative
+fn element_position(len: usize, index: i64) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)
+    } else {
+        usize::try_from(index)
+            .ok()
+            .filter(|&position| position < len)
+    }
+}
+
+/// Follow the bracketed indices of a path segment into nested arrays
+fn index_value<'a>(mut value: &'a Value, brackets: &str) -> Option<&'a Value> {
+    for index in segment_indices(brackets) {
+        let Value::Array(items) = value else {
+            return None;
+        };
+        value = items.get(element_position(items.len(), index?)?)?;
+    }
+    Some(value)
+}
+
+/// Mutable [`index_value`], failing on a non-array or an index out of bounds
+fn index_value_mut<'a>(
+    mut value: &'a mut Value,
+    brackets: &str,
+    segment: &str,
+) -> Result<&'a mut Value> {
+    for index in segment_indices(brackets) {
+        value = match value {
+            Value::Array(items) => {
+                let position = index.and_then(|index| element_position(items.len(), index));
+                match position {
+                    Some(position) => &mut items[position],
+                    None => {
+                        return Err(RuleEngineError::FieldNotFound {
+                            field: segment.to_string(),
+                        })
+                    }
+                }
+            }
+            other => {
+                return Err(RuleEngineError::TypeMismatch {
+                    expected: "Array".to_string(),
+                    actual: format!("{:?}", other),
+                })
+            }
+        };
+    }
+    Ok(value)
+}
+
+/// Merge `incoming` into `existing`, combining objects key by key
+fn deep_merge(existing: &mut Value, incoming: Value) {
+    match (existing, incoming) {
+        (Value::Object(existing), Value::Object(incoming)) => {
+            for (key, value) in incoming {
+                match existing.get_mut(&key) {
+                    Some(current) => deep_merge(current, value),
+                    None => {
+                        existing.insert(key, value);
+                    }
+                }
+            }
+        }
+        (existing, incoming) => *existing = incoming,
+    }
+}
+
 impl Default for Facts {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Visit every value reached by following `pattern` into `value`
+///
+/// `*` matches every field of an object; an empty pattern visits `value` itself.
+fn for_each_match(value: &Value, pattern: &[String], visit: &mut impl FnMut(&Value)) {
+    let Some((segment, rest)) = pattern.split_first() else {
+        visit(value);
+        return;
+    };
+    let Value::Object(obj) = value else {
+        return;
+    };
+
+    if segment == "*" {
+        for field in obj.values() {
+            for_each_match(field, rest, visit);
+        }
+    } else if let Some(field) = obj.get(segment) {
+        for_each_match(field, rest, visit);
+    }
+}
+
 /// A snapshot of Facts state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FactsSnapshot {
@@ -409,6 +1098,24 @@ impl FactHelper {
         Value::Object(object)
     }
 
+    /// Convert a serializable struct or map into a `Value::Object`
+    ///
+    /// Nested structs become nested objects and sequences become arrays.
+    /// Values that do not serialize to an object (numbers, strings, unit
+    /// enum variants) are rejected with a serialization error.
+    pub fn from_serialize<T: Serialize>(value: &T) -> Result<Value> {
+        let json =
+            serde_json::to_value(value).map_err(|e| RuleEngineError::SerializationError {
+                message: e.to_string(),
+            })?;
+        match Value::from(json) {
+            object @ Value::Object(_) => Ok(object),
+            other => Err(RuleEngineError::SerializationError {
+                message: format!("expected a struct or map, got {:?}", other),
+            }),
+        }
+    }
+
     /// Create a User fact from common fields
     pub fn create_user(name: &str, age: i64, email: &str, country: &str, is_vip: bool) -> Value {
         let mut user = HashMap::new();
@@ -570,4 +1277,420 @@ mod tests {
         assert_eq!(facts.count(), 1);
         assert_eq!(facts.get("test"), Some(Value::String("value".to_string())));
     }
+
+    #[test]
+    fn test_bulk_set_and_get() {
+        let facts = Facts::new();
+        let user = FactHelper::create_user("John", 25, "john@example.com", "US", false);
+        facts.add_value("User", user).unwrap();
+
+        facts
+            .set_many(&[
+                ("User.Age", Value::Integer(30)),
+                ("User.IsVIP", Value::Boolean(true)),
+                ("Score", Value::Integer(99)),
+            ])
+            .unwrap();
+
+        assert_eq!(facts.get_nested("User.Age"), Some(Value::Integer(30)));
+        assert_eq!(facts.get_nested("User.IsVIP"), Some(Value::Boolean(true)));
+        assert_eq!(facts.get("Score"), Some(Value::Integer(99)));
+
+        let values = facts.get_many(&["User.Age", "Score", "User.Missing", "Nope"]);
+        assert_eq!(
+            values,
+            vec![
+                Some(Value::Integer(30)),
+                Some(Value::Integer(99)),
+                None,
+                None
+            ]
+        );
+
+        // Nested writes into an unknown root fail like set_nested
+        assert!(facts
+            .set_many(&[("Order.Total", Value::Integer(1))])
+            .is_err());
+    }
+
+    #[test]
+    fn test_schema_validation() {
+        use crate::engine::schema::ValueKind;
+
+        let facts = Facts::new();
+        facts.register_schema(
+            "User",
+            FactSchema::new()
+                .with_required_field("Age", ValueKind::Number)
+                .with_field("Name", ValueKind::String),
+        );
+
+        let user = |age: Value| {
+            Facts::create_object(vec![
+                ("Age".to_string(), age),
+                ("Nickname".to_string(), Value::Integer(7)),
+            ])
+        };
+
+        assert!(facts.add_value("User", user(Value::Integer(30))).is_ok());
+        assert!(matches!(
+            facts.add_value("User", user(Value::String("30".to_string()))),
+            Err(RuleEngineError::SchemaViolation { ref field, .. }) if field == "Age"
+        ));
+        assert!(facts
+            .add_value("User", Facts::create_object(vec![]))
+            .is_err());
+
+        assert!(facts.set_nested("User.Age", Value::Number(31.5)).is_ok());
+        assert!(facts.set_nested("User.Name", Value::Boolean(true)).is_err());
+        assert!(facts
+            .set_many(&[("User.Age", Value::String("old".to_string()))])
+            .is_err());
+        assert_eq!(facts.get_nested("User.Age"), Some(Value::Number(31.5)));
+
+        // Objects without a schema stay dynamic
+        assert!(facts
+            .add_value("Order", Value::String("anything".to_string()))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_computed_aggregate_tracks_instances() {
+        let facts = Facts::new();
+        facts
+            .define_aggregate("Orders.total", "sum", "Order.*.amount")
+            .unwrap();
+        facts
+            .define_aggregate("Orders.count", "count", "Order.*.amount")
+            .unwrap();
+
+        assert_eq!(facts.get_nested("Orders.total"), Some(Value::Number(0.0)));
+
+        facts.set("Order.1.amount", Value::Integer(40));
+        facts.set("Order.2.amount", Value::Number(12.5));
+        facts.set("Order.2.status", Value::String("open".to_string()));
+        assert_eq!(facts.get_nested("Orders.total"), Some(Value::Number(52.5)));
+
+        // Instances stored as objects are matched too
+        facts
+            .add_value(
+                "Order.3",
+                Facts::create_object(vec![("amount".to_string(), Value::Integer(7))]),
+            )
+            .unwrap();
+        assert_eq!(facts.get("Orders.total"), Some(Value::Number(59.5)));
+        assert_eq!(facts.get("Orders.count"), Some(Value::Integer(3)));
+
+        assert!(facts
+            .define_aggregate("Orders.bogus", "product", "Order.*.amount")
+            .is_err());
+    }
+
+    #[test]
+    fn test_watchers_fire_on_matching_writes() {
+        use std::sync::Mutex;
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![("Name".to_string(), Value::String("a".into()))]),
+            )
+            .unwrap();
+
+        let seen: Arc<Mutex<Vec<String>>> = Arc::default();
+        let exact = {
+            let seen = seen.clone();
+            facts.watch(
+                "User.Name",
+                Box::new(move |v| seen.lock().unwrap().push(format!("name={}", v.to_string()))),
+            )
+        };
+        {
+            let seen = seen.clone();
+            let reader = facts.clone();
+            facts.watch(
+                "User.*",
+                Box::new(move |v| {
+                    // Watchers run without locks held, so reading back is safe
+                    let stored = reader.get_nested("User.Name").unwrap();
+                    seen.lock().unwrap().push(format!(
+                        "user={}/{}",
+                        v.to_string(),
+                        stored.to_string()
+                    ));
+                }),
+            );
+        }
+
+        facts
+            .set_nested("User.Name", Value::String("b".into()))
+            .unwrap();
+        facts.set_nested("User.Age", Value::Integer(30)).unwrap();
+        facts.set("Order.Total", Value::Integer(10));
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["name=b", "user=b/b", "user=30/b"]
+        );
+
+        assert!(facts.unwatch(exact));
+        assert!(!facts.unwatch(exact));
+        facts
+            .set_many(&[("User.Name", Value::String("c".into()))])
+            .unwrap();
+        assert_eq!(seen.lock().unwrap().last().unwrap(), "user=c/c");
+        assert_eq!(seen.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_add_and_get_instances() {
+        let facts = Facts::new();
+        facts.set("Order.1.amount", Value::Integer(10));
+        facts.set("Order.sum", Value::Integer(10));
+
+        let id = facts.add_instance(
+            "Order",
+            Facts::create_object(vec![
+                ("amount".to_string(), Value::Integer(250)),
+                ("region".to_string(), Value::String("EU".to_string())),
+            ]),
+        );
+        assert_eq!(id, "2");
+        assert_eq!(facts.get("Order.2.amount"), Some(Value::Integer(250)));
+        assert_eq!(
+            facts.get_fact_type("Order.2.region"),
+            Some("Order".to_string())
+        );
+
+        for _ in 0..8 {
+            facts.add_instance("Order", Value::Integer(1));
+        }
+        assert_eq!(facts.get("Order.10.value"), Some(Value::Integer(1)));
+
+        let instances = facts.get_instances("Order");
+        let ids: Vec<&str> = instances.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2", "3", "4", "5", "6", "7", "8", "9", "10"]);
+        assert_eq!(
+            instances[1].1,
+            Facts::create_object(vec![
+                ("amount".to_string(), Value::Integer(250)),
+                ("region".to_string(), Value::String("EU".to_string())),
+            ])
+        );
+
+        facts.set("_retracted_Order.2", Value::Boolean(true));
+        assert_eq!(facts.get_instances("Order").len(), 9);
+        assert!(facts.get_instances("Customer").is_empty());
+    }
+
+    #[test]
+    fn test_merge_strategies() {
+        let object = |pairs: Vec<(&str, Value)>| FactHelper::create_object(pairs);
+        let build = || {
+            let facts = Facts::new();
+            facts.set("Score", Value::Integer(1));
+            facts.set(
+                "User",
+                object(vec![
+                    ("Name", Value::String("Ada".to_string())),
+                    (
+                        "Address",
+                        object(vec![("City", Value::String("London".to_string()))]),
+                    ),
+                ]),
+            );
+            facts
+        };
+        let other = Facts::new();
+        other.set("Score", Value::Integer(2));
+        other.set("Region", Value::String("EU".to_string()));
+        other.set(
+            "User",
+            object(vec![
+                ("Age", Value::Integer(36)),
+                (
+                    "Address",
+                    object(vec![("Zip", Value::String("N1".to_string()))]),
+                ),
+            ]),
+        );
+
+        let facts = build();
+        facts.merge(&other, MergeStrategy::Overwrite);
+        assert_eq!(facts.get("Score"), Some(Value::Integer(2)));
+        assert_eq!(facts.get_nested("User.Name"), None);
+        assert_eq!(facts.get_nested("User.Age"), Some(Value::Integer(36)));
+
+        let facts = build();
+        facts.merge(&other, MergeStrategy::KeepExisting);
+        assert_eq!(facts.get("Score"), Some(Value::Integer(1)));
+        assert_eq!(facts.get("Region"), Some(Value::String("EU".to_string())));
+        assert_eq!(facts.get_nested("User.Age"), None);
+
+        let facts = build();
+        facts.merge(&other, MergeStrategy::DeepMerge);
+        assert_eq!(facts.get("Score"), Some(Value::Integer(2)));
+        assert_eq!(
+            facts.get_nested("User.Name"),
+            Some(Value::String("Ada".to_string()))
+        );
+        assert_eq!(facts.get_nested("User.Age"), Some(Value::Integer(36)));
+        assert_eq!(
+            facts.get_nested("User.Address.City"),
+            Some(Value::String("London".to_string()))
+        );
+        assert_eq!(
+            facts.get_nested("User.Address.Zip"),
+            Some(Value::String("N1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_add_struct_converts_nested_fields() {
+        #[derive(Serialize)]
+        struct Address {
+            city: String,
+        }
+
+        #[derive(Serialize)]
+        struct User {
+            name: String,
+            age: u32,
+            vip: bool,
+            tags: Vec<String>,
+            address: Address,
+            manager: Option<String>,
+        }
+
+        let user = User {
+            name: "Ada".to_string(),
+            age: 36,
+            vip: true,
+            tags: vec!["beta".to_string()],
+            address: Address {
+                city: "London".to_string(),
+            },
+            manager: None,
+        };
+
+        let facts = Facts::new();
+        facts.add_struct("User", &user).unwrap();
+        assert_eq!(facts.get_nested("User.age"), Some(Value::Integer(36)));
+        assert_eq!(facts.get_nested("User.vip"), Some(Value::Boolean(true)));
+        assert_eq!(
+            facts.get_nested("User.address.city"),
+            Some(Value::String("London".to_string()))
+        );
+        assert_eq!(
+            facts.get_nested("User.tags"),
+            Some(Value::Array(vec![Value::String("beta".to_string())]))
+        );
+        assert_eq!(facts.get_nested("User.manager"), Some(Value::Null));
+        assert!(facts.get_fact_type("User").unwrap().ends_with("User"));
+
+        assert!(matches!(
+            FactHelper::from_serialize(&42),
+            Err(RuleEngineError::SerializationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_keys_and_iter_skip_retracted_facts() {
+        let facts = Facts::new();
+        assert!(facts.is_empty());
+
+        facts.set("User", Value::String("Ada".to_string()));
+        facts.set("Order.1", Value::Integer(10));
+        facts.set("Order.2", Value::Integer(20));
+        facts.set("_retracted_Order.2", Value::Boolean(true));
+
+        assert_eq!(
+            facts.keys(),
+            vec!["Order.1".to_string(), "User".to_string()]
+        );
+        assert_eq!(facts.len(), 2);
+        assert_eq!(facts.count(), 4);
+        assert_eq!(
+            facts.iter().collect::<Vec<_>>(),
+            vec![
+                ("Order.1".to_string(), Value::Integer(10)),
+                ("User".to_string(), Value::String("Ada".to_string())),
+            ]
+        );
+
+        let retracted_only = Facts::new();
+        retracted_only.set("Order.3", Value::Integer(30));
+        retracted_only.set("_retracted_Order.3", Value::Boolean(true));
+        assert!(retracted_only.is_empty());
+        assert_eq!(retracted_only.len(), 0);
+    }
+
+    #[test]
+    fn test_nested_paths_index_arrays() {
+        let item = |sku: &str, price: i64| {
+            Facts::create_object(vec![
+                ("sku".to_string(), Value::String(sku.to_string())),
+                ("price".to_string(), Value::Integer(price)),
+            ])
+        };
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![
+                    (
+                        "Items".to_string(),
+                        Value::Array(vec![item("A1", 10), item("B2", 25), item("C3", 40)]),
+                    ),
+                    (
+                        "Grid".to_string(),
+                        Value::Array(vec![Value::Array(vec![
+                            Value::Integer(1),
+                            Value::Integer(2),
+                        ])]),
+                    ),
+                ]),
+            )
+            .unwrap();
+
+        // In bounds, from the end, and into nested arrays
+        assert_eq!(
+            facts.get_nested("Order.Items[1].price"),
+            Some(Value::Integer(25))
+        );
+        assert_eq!(
+            facts.get_nested("Order.Items[-1].sku"),
+            Some(Value::String("C3".to_string()))
+        );
+        assert_eq!(facts.get_nested("Order.Items[0]"), Some(item("A1", 10)));
+        assert_eq!(
+            facts.get_nested("Order.Grid[0][-1]"),
+            Some(Value::Integer(2))
+        );
+
+        // Out of bounds, malformed, or not an array
+        assert_eq!(facts.get_nested("Order.Items[3].price"), None);
+        assert_eq!(facts.get_nested("Order.Items[-4]"), None);
+        assert_eq!(facts.get_nested("Order.Items[x]"), None);
+        assert_eq!(facts.get_nested("Order.Grid[0][0][0]"), None);
+
+        facts
+            .set_nested("Order.Items[-1].price", Value::Integer(35))
+            .unwrap();
+        assert_eq!(
+            facts.get_nested("Order.Items[2].price"),
+            Some(Value::Integer(35))
+        );
+        facts
+            .set_nested("Order.Grid[0][0]", Value::Integer(9))
+            .unwrap();
+        assert_eq!(
+            facts.get_nested("Order.Grid[0][0]"),
+            Some(Value::Integer(9))
+        );
+        assert!(matches!(
+            facts.set_nested("Order.Items[5].price", Value::Integer(1)),
+            Err(RuleEngineError::FieldNotFound { .. })
+        ));
+    }
 }
@@ -0,0 +1,91 @@
+//! Engine-level global variables (CLIPS `defglobal`)
+//!
+//! Globals are declared in GRL outside of rule blocks:
+//!
+//! ```grl
+//! defglobal ?*MAX_SPEED* = 120;
+//!
+//! rule "Speeding" {
+//!     when Car.Speed > ?*MAX_SPEED*
+//!     then Car.Flagged = true;
+//! }
+//! ```
+//!
+//! They live in a [`GlobalStore`] shared by the knowledge base and the engine,
+//! are resolved wherever a condition or `Set` action references `?*NAME*`, and
+//! can be reassigned with `?*NAME* = value;`.
+//!
+//! This is the forward-chaining counterpart of
+//! [`GlobalsRegistry`](crate::rete::globals::GlobalsRegistry) and holds full
+//! [`Value`]s rather than RETE fact values.
+
+use crate::errors::{Result, RuleEngineError};
+use crate::types::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Thread-safe store of global variables; clones share the same storage
+#[derive(Debug, Clone, Default)]
+pub struct GlobalStore {
+    values: Arc<RwLock<HashMap<String, Value>>>,
+}
+
+impl GlobalStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define a global, replacing any previous value
+    pub fn define(&self, name: &str, value: Value) {
+        self.values.write().unwrap().insert(name.to_string(), value);
+    }
+
+    /// Current value of a global
+    pub fn get(&self, name: &str) -> Option<Value> {
+        self.values.read().unwrap().get(name).cloned()
+    }
+
+    /// Update an already defined global
+    pub fn set(&self, name: &str, value: Value) -> Result<()> {
+        match self.values.write().unwrap().get_mut(name) {
+            Some(slot) => {
+                *slot = value;
+                Ok(())
+            }
+            None => Err(undefined(name)),
+        }
+    }
+
+    /// Check whether a global is defined
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.read().unwrap().contains_key(name)
+    }
+
+    /// Names of all defined globals
+    pub fn names(&self) -> Vec<String> {
+        self.values.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Value of a `?*NAME*` reference, failing if it is not defined
+    pub(crate) fn resolve(&self, reference: &str) -> Result<Value> {
+        let name = global_name(reference).unwrap_or(reference);
+        self.get(name).ok_or_else(|| undefined(name))
+    }
+}
+
+/// Extract `NAME` from a `?*NAME*` reference
+pub(crate) fn global_name(text: &str) -> Option<&str> {
+    let name = text.trim().strip_prefix("?*")?.strip_suffix('*')?;
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    valid.then_some(name)
+}
+
+fn undefined(name: &str) -> RuleEngineError {
+    RuleEngineError::EvaluationError {
+        message: format!("Undefined global '?*{}*'", name),
+    }
+}
@@ -1,20 +1,49 @@
 #![allow(deprecated)]
 
-use crate::engine::rule::Rule;
+use crate::engine::agenda::ConflictStrategy;
+use crate::engine::facts::Facts;
+use crate::engine::globals::GlobalStore;
+use crate::engine::rule::{Rule, Salience};
 use crate::errors::{Result, RuleEngineError};
 use crate::parser::grl::GRLParser;
 use crate::types::Value;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
+/// Type for lazy rule loaders, invoked the first time their namespace is focused
+pub type LazyRuleLoader = Box<dyn Fn() -> Result<Vec<Rule>> + Send + Sync>;
+
 /// Knowledge Base - manages collections of rules and facts
 /// Similar to Grule's KnowledgeBase concept
-#[derive(Debug)]
 pub struct KnowledgeBase {
     name: String,
     rules: Arc<RwLock<Vec<Rule>>>,
     rule_index: Arc<RwLock<HashMap<String, usize>>>,
     version: Arc<RwLock<u64>>,
+    /// Loaders for namespaces whose rules have not been loaded yet
+    lazy_loaders: Arc<RwLock<HashMap<String, LazyRuleLoader>>>,
+    /// Globals declared with `defglobal`
+    globals: GlobalStore,
+    /// Simplify rule conditions as rules are added
+    simplify_conditions: bool,
+}
+
+impl std::fmt::Debug for KnowledgeBase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pending: Vec<String> = self.lazy_loaders.read().unwrap().keys().cloned().collect();
+        f.debug_struct("KnowledgeBase")
+            .field("name", &self.name)
+            .field("rules", &self.rules)
+            .field("rule_index", &self.rule_index)
+            .field("version", &self.version)
+            .field("lazy_namespaces", &pending)
+            .field("globals", &self.globals)
+            .field("simplify_conditions", &self.simplify_conditions)
+            .finish()
+    }
 }
 
 impl KnowledgeBase {
@@ -25,9 +54,21 @@ impl KnowledgeBase {
             rules: Arc::new(RwLock::new(Vec::new())),
             rule_index: Arc::new(RwLock::new(HashMap::new())),
             version: Arc::new(RwLock::new(0)),
+            lazy_loaders: Arc::new(RwLock::new(HashMap::new())),
+            globals: GlobalStore::new(),
+            simplify_conditions: false,
         }
     }
 
+    /// Simplify each rule's conditions with [`ConditionGroup::simplify`] when
+    /// it is added, which keeps machine-generated rules small
+    ///
+    /// [`ConditionGroup::simplify`]: crate::engine::rule::ConditionGroup::simplify
+    pub fn with_condition_simplification(mut self, enabled: bool) -> Self {
+        self.simplify_conditions = enabled;
+        self
+    }
+
     /// Get the knowledge base name
     pub fn name(&self) -> &str {
         &self.name
@@ -39,7 +80,11 @@ impl KnowledgeBase {
     }
 
     /// Add a rule to the knowledge base
-    pub fn add_rule(&self, rule: Rule) -> Result<()> {
+    pub fn add_rule(&self, mut rule: Rule) -> Result<()> {
+        if self.simplify_conditions {
+            rule.conditions = rule.conditions.simplify();
+        }
+
         let mut rules = self.rules.write().unwrap();
         let mut index = self.rule_index.write().unwrap();
         let mut version = self.version.write().unwrap();
@@ -69,11 +114,23 @@ impl KnowledgeBase {
         Ok(())
     }
 
+    /// Globals declared with `defglobal`, shared with engines built on this knowledge base
+    pub fn globals(&self) -> &GlobalStore {
+        &self.globals
+    }
+
     /// Add multiple rules from GRL text
+    ///
+    /// `defglobal` declarations in the text are added to [`Self::globals`].
     pub fn add_rules_from_grl(&self, grl_text: &str) -> Result<usize> {
+        let globals = GRLParser::parse_globals(grl_text)?;
         let rules = GRLParser::parse_rules(grl_text)?;
         let count = rules.len();
 
+        for (name, value) in globals {
+            self.globals.define(&name, value);
+        }
+
         for rule in rules {
             self.add_rule(rule)?;
         }
@@ -81,6 +138,71 @@ impl KnowledgeBase {
         Ok(count)
     }
 
+    /// Add rules from a GRL file, loading its `include "other.grl";` files first
+    ///
+    /// Include paths are resolved relative to the including file. A file reached
+    /// through several includes is loaded once, and an include cycle is an
+    /// error. Every file is read and parsed before any rule is added, so
+    /// `defglobal`s declared in one file are visible to the others.
+    pub fn add_rules_from_file<P: AsRef<Path>>(&self, path: P) -> Result<usize> {
+        let mut grl_text = String::new();
+        collect_grl_sources(
+            path.as_ref(),
+            &mut Vec::new(),
+            &mut HashSet::new(),
+            &mut grl_text,
+        )?;
+        self.add_rules_from_grl(&grl_text)
+    }
+
+    /// Register a loader that supplies the rules of `namespace` on demand
+    ///
+    /// The loader runs the first time the engine focuses the agenda group named
+    /// `namespace`. Loaded rules without an agenda group are placed in
+    /// `namespace`, so they only fire while it has focus.
+    pub fn register_lazy_loader(&self, namespace: &str, loader: LazyRuleLoader) {
+        self.lazy_loaders
+            .write()
+            .unwrap()
+            .insert(namespace.to_string(), loader);
+    }
+
+    /// Check whether `namespace` still has a loader waiting to run
+    pub fn has_lazy_loader(&self, namespace: &str) -> bool {
+        self.lazy_loaders.read().unwrap().contains_key(namespace)
+    }
+
+    /// Run the pending loader for `namespace`, if any, and add its rules
+    ///
+    /// Returns the number of rules added. A loader runs at most once; if it
+    /// fails it stays registered so the load can be retried.
+    pub fn load_namespace(&self, namespace: &str) -> Result<usize> {
+        let Some(loader) = self.lazy_loaders.write().unwrap().remove(namespace) else {
+            return Ok(0);
+        };
+
+        let rules = match loader() {
+            Ok(rules) => rules,
+            Err(e) => {
+                self.lazy_loaders
+                    .write()
+                    .unwrap()
+                    .insert(namespace.to_string(), loader);
+                return Err(e);
+            }
+        };
+
+        let count = rules.len();
+        for mut rule in rules {
+            if rule.agenda_group.is_none() {
+                rule.agenda_group = Some(namespace.to_string());
+            }
+            self.add_rule(rule)?;
+        }
+
+        Ok(count)
+    }
+
     /// Remove a rule by name
     pub fn remove_rule(&self, rule_name: &str) -> Result<bool> {
         let mut rules = self.rules.write().unwrap();
@@ -103,6 +225,30 @@ impl KnowledgeBase {
         }
     }
 
+    /// Replace a rule with a new definition of the same name
+    ///
+    /// Returns `false` if no rule with that name exists.
+    pub fn replace_rule(&self, rule: Rule) -> Result<bool> {
+        let mut rules = self.rules.write().unwrap();
+        let mut index = self.rule_index.write().unwrap();
+        let mut version = self.version.write().unwrap();
+
+        let Some(&position) = index.get(&rule.name) else {
+            return Ok(false);
+        };
+        rules[position] = rule;
+
+        // Salience may have changed
+        rules.sort_by_key(|b| std::cmp::Reverse(b.salience));
+        index.clear();
+        for (pos, rule) in rules.iter().enumerate() {
+            index.insert(rule.name.clone(), pos);
+        }
+
+        *version += 1;
+        Ok(true)
+    }
+
     /// Get a rule by name
     pub fn get_rule(&self, rule_name: &str) -> Option<Rule> {
         let rules = self.rules.read().unwrap();
@@ -130,6 +276,45 @@ impl KnowledgeBase {
         indices
     }
 
+    /// Get rule indices sorted by effective salience for the current facts
+    ///
+    /// Dynamic salience expressions are evaluated on every call, so the
+    /// ordering reflects the latest fact values.
+    pub fn get_rules_by_effective_salience(&self, facts: &Facts) -> Vec<usize> {
+        self.get_rules_by_strategy(facts, ConflictStrategy::default())
+    }
+
+    /// Get rule indices ordered by effective salience, breaking ties with the
+    /// given conflict resolution strategy
+    pub fn get_rules_by_strategy(&self, facts: &Facts, strategy: ConflictStrategy) -> Vec<usize> {
+        self.get_rules_by_strategy_with_base(facts, strategy, |_| 0)
+    }
+
+    /// Like [`get_rules_by_strategy`](Self::get_rules_by_strategy), with
+    /// `base_salience(rule)` added to each rule's effective salience
+    pub fn get_rules_by_strategy_with_base<F>(
+        &self,
+        facts: &Facts,
+        strategy: ConflictStrategy,
+        base_salience: F,
+    ) -> Vec<usize>
+    where
+        F: Fn(&Rule) -> i32,
+    {
+        let rules = self.rules.read().unwrap();
+        let saliences: Vec<i32> = rules
+            .iter()
+            .map(|r| r.effective_salience(facts).saturating_add(base_salience(r)))
+            .collect();
+        let mut indices: Vec<usize> = (0..rules.len()).collect();
+        indices.sort_by(|&a, &b| {
+            saliences[b]
+                .cmp(&saliences[a])
+                .then_with(|| strategy.compare(&rules[a], a, &rules[b], b))
+        });
+        indices
+    }
+
     /// Get rule by index - avoids cloning
     pub fn get_rule_by_index(&self, index: usize) -> Option<Rule> {
         let rules = self.rules.read().unwrap();
@@ -142,6 +327,12 @@ impl KnowledgeBase {
         index.keys().cloned().collect()
     }
 
+    /// Get rule names in execution order (highest salience first)
+    pub fn rule_names(&self) -> Vec<String> {
+        let rules = self.rules.read().unwrap();
+        rules.iter().map(|rule| rule.name.clone()).collect()
+    }
+
     /// Get rule count
     pub fn rule_count(&self) -> usize {
         let rules = self.rules.read().unwrap();
@@ -167,6 +358,27 @@ impl KnowledgeBase {
         }
     }
 
+    /// Enable or disable every rule tagged with `tag`
+    ///
+    /// Returns the number of rules carrying the tag.
+    pub fn set_enabled_by_tag(&self, tag: &str, enabled: bool) -> usize {
+        let mut rules = self.rules.write().unwrap();
+        let mut version = self.version.write().unwrap();
+
+        let mut matched = 0;
+        for rule in rules
+            .iter_mut()
+            .filter(|rule| rule.tags.iter().any(|t| t == tag))
+        {
+            rule.enabled = enabled;
+            matched += 1;
+        }
+        if matched > 0 {
+            *version += 1;
+        }
+        matched
+    }
+
     /// Clear all rules
     pub fn clear(&self) {
         let mut rules = self.rules.write().unwrap();
@@ -227,7 +439,8 @@ impl KnowledgeBase {
 impl Clone for KnowledgeBase {
     fn clone(&self) -> Self {
         let rules = self.rules.read().unwrap();
-        let new_kb = KnowledgeBase::new(&self.name);
+        let new_kb =
+            KnowledgeBase::new(&self.name).with_condition_simplification(self.simplify_conditions);
 
         for rule in rules.iter() {
             let _ = new_kb.add_rule(rule.clone());
@@ -274,24 +487,81 @@ impl std::fmt::Display for KnowledgeBaseStats {
     }
 }
 
-/// Extension trait to add GRL export functionality to Rule
-trait RuleGRLExport {
-    fn to_grl(&self) -> String;
+/// Content hash of a rule, used to detect changed definitions on reload
+pub(crate) fn rule_fingerprint(rule: &Rule) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    rule.to_grl().hash(&mut hasher);
+    hasher.finish()
 }
 
-impl RuleGRLExport for Rule {
-    fn to_grl(&self) -> String {
+impl Rule {
+    /// Render the rule as canonical GRL text
+    ///
+    /// Parsing the output with `GRLParser::parse_rule` yields an equivalent rule.
+    /// A disabled rule is prefixed with a `// DISABLED` comment, which the parser
+    /// ignores.
+    pub fn to_grl(&self) -> String {
         let mut grl = String::new();
 
+        if !self.metadata.is_empty() {
+            let mut entries: Vec<(&String, &String)> = self.metadata.iter().collect();
+            entries.sort();
+            let entries: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, value))
+                .collect();
+            grl.push_str(&format!("@metadata({})\n", entries.join(", ")));
+        }
+
         // Rule declaration
-        grl.push_str(&format!("rule {}", self.name));
+        if is_grl_identifier(&self.name) {
+            grl.push_str(&format!("rule {}", self.name));
+        } else {
+            grl.push_str(&format!("rule \"{}\"", self.name));
+        }
 
         if let Some(ref description) = self.description {
             grl.push_str(&format!(" \"{}\"", description));
         }
 
-        if self.salience != 0 {
-            grl.push_str(&format!(" salience {}", self.salience));
+        match &self.salience_source {
+            Salience::Dynamic(expression) => {
+                grl.push_str(&format!(" salience ({})", expression));
+            }
+            Salience::Static(_) if self.salience != 0 => {
+                grl.push_str(&format!(" salience {}", self.salience));
+            }
+            Salience::Static(_) => {}
+        }
+
+        if self.no_loop {
+            grl.push_str(" no-loop");
+        }
+        if self.lock_on_active {
+            grl.push_str(" lock-on-active");
+        }
+        if let Some(max_fires) = self.max_fires {
+            grl.push_str(&format!(" max-fires {}", max_fires));
+        }
+        if let Some(timeout) = self.timeout {
+            grl.push_str(&format!(" timeout {}ms", timeout.as_millis()));
+        }
+        if let Some(ref group) = self.agenda_group {
+            grl.push_str(&format!(" agenda-group \"{}\"", group));
+        }
+        if let Some(ref group) = self.activation_group {
+            grl.push_str(&format!(" activation-group \"{}\"", group));
+        }
+        if let Some(date) = self.date_effective {
+            grl.push_str(&format!(" date-effective \"{}\"", date.to_rfc3339()));
+        }
+        if let Some(date) = self.date_expires {
+            grl.push_str(&format!(" date-expires \"{}\"", date.to_rfc3339()));
+        }
+
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|t| format!("\"{}\"", t)).collect();
+            grl.push_str(&format!(" tags [{}]", tags.join(", ")));
         }
 
         grl.push_str(" {\n");
@@ -316,6 +586,32 @@ impl RuleGRLExport for Rule {
     }
 }
 
+/// Whether `name` can be written without quotes in a rule header
+fn is_grl_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Quote a string literal, escaping characters the parser unescapes
+fn quote_grl(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 /// Extension trait for ConditionGroup GRL export
 trait ConditionGroupGRLExport {
     fn to_grl(&self) -> String;
@@ -324,14 +620,7 @@ trait ConditionGroupGRLExport {
 impl ConditionGroupGRLExport for crate::engine::rule::ConditionGroup {
     fn to_grl(&self) -> String {
         match self {
-            crate::engine::rule::ConditionGroup::Single(condition) => {
-                format!(
-                    "{} {} {}",
-                    condition.field,
-                    condition.operator.to_grl(),
-                    condition.value.to_grl()
-                )
-            }
+            crate::engine::rule::ConditionGroup::Single(condition) => condition_to_grl(condition),
             crate::engine::rule::ConditionGroup::Compound {
                 left,
                 operator,
@@ -340,13 +629,35 @@ impl ConditionGroupGRLExport for crate::engine::rule::ConditionGroup {
                 let op_str = match operator {
                     crate::types::LogicalOperator::And => "&&",
                     crate::types::LogicalOperator::Or => "||",
+                    crate::types::LogicalOperator::Xor => "^^",
                     crate::types::LogicalOperator::Not => "!",
                 };
-                format!("{} {} {}", left.to_grl(), op_str, right.to_grl())
-            }
-            crate::engine::rule::ConditionGroup::Not(condition) => {
-                format!("!{}", condition.to_grl())
+                // The parser folds `a && b && c` to the left, so only a looser
+                // operand, or an equally tight one on the right, needs parentheses
+                let precedence = logical_precedence(operator);
+                let operand = |group: &Self, on_right: bool| match group {
+                    crate::engine::rule::ConditionGroup::Compound { operator, .. }
+                        if logical_precedence(operator) < precedence
+                            || (on_right && logical_precedence(operator) == precedence) =>
+                    {
+                        format!("({})", group.to_grl())
+                    }
+                    _ => group.to_grl(),
+                };
+                format!(
+                    "{} {} {}",
+                    operand(left.as_ref(), false),
+                    op_str,
+                    operand(right.as_ref(), true)
+                )
             }
+            crate::engine::rule::ConditionGroup::Not(condition) => match **condition {
+                crate::engine::rule::ConditionGroup::Compound { .. } => {
+                    format!("!({})", condition.to_grl())
+                }
+                _ => format!("!{}", condition.to_grl()),
+            },
+            crate::engine::rule::ConditionGroup::Constant(value) => value.to_string(),
             crate::engine::rule::ConditionGroup::Exists(condition) => {
                 format!("exists({})", condition.to_grl())
             }
@@ -420,6 +731,102 @@ impl ConditionGroupGRLExport for crate::engine::rule::ConditionGroup {
     }
 }
 
+/// Append a GRL file to `out` after the files it includes, depth first
+///
+/// `stack` holds the chain of files currently being included, for cycle
+/// detection; `loaded` holds every file already appended.
+fn collect_grl_sources(
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+    loaded: &mut HashSet<PathBuf>,
+    out: &mut String,
+) -> Result<()> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| RuleEngineError::io(path, e))?;
+    if stack.contains(&path) {
+        let chain: Vec<String> = stack
+            .iter()
+            .chain(std::iter::once(&path))
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(RuleEngineError::ParseError {
+            message: format!("Include cycle: {}", chain.join(" -> ")),
+        });
+    }
+    if !loaded.insert(path.clone()) {
+        return Ok(());
+    }
+
+    let grl_text = std::fs::read_to_string(&path).map_err(|e| RuleEngineError::io(&path, e))?;
+    let (includes, body) = GRLParser::parse_includes(&grl_text)?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    stack.push(path.clone());
+    for include in includes {
+        collect_grl_sources(&dir.join(include), stack, loaded, out)?;
+    }
+    stack.pop();
+
+    out.push_str(body);
+    out.push('\n');
+    Ok(())
+}
+
+/// Binding strength of a logical operator in the GRL parser (`||` loosest)
+fn logical_precedence(operator: &crate::types::LogicalOperator) -> u8 {
+    match operator {
+        crate::types::LogicalOperator::Or => 0,
+        crate::types::LogicalOperator::Xor => 1,
+        crate::types::LogicalOperator::And => 2,
+        crate::types::LogicalOperator::Not => 3,
+    }
+}
+
+/// Render a single condition in the form the parser produced it from
+fn condition_to_grl(condition: &crate::engine::rule::Condition) -> String {
+    use crate::engine::rule::ConditionExpression;
+
+    let operator = condition.operator.to_grl();
+    match &condition.expression {
+        ConditionExpression::Field(field) if condition.operator.is_unary() => {
+            format!("{} {}", field, operator)
+        }
+        ConditionExpression::Field(field) => {
+            format!("{} {} {}", field, operator, condition.value.to_grl())
+        }
+        ConditionExpression::FunctionCall { name, args } => format!(
+            "{}({}) {} {}",
+            name,
+            args.join(", "),
+            operator,
+            condition.value.to_grl()
+        ),
+        // Arithmetic and literal-membership tests keep the whole clause as the name
+        ConditionExpression::Test { name, args }
+            if args.is_empty()
+                && !name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '.') =>
+        {
+            name.clone()
+        }
+        ConditionExpression::Test { name, args } => {
+            format!("test({}({}))", name, args.join(", "))
+        }
+        ConditionExpression::Bind { variable, field } => format!("{} : {}", variable, field),
+        ConditionExpression::MultiField {
+            field,
+            operation,
+            variable,
+        } => match (operation.as_str(), variable) {
+            ("count", _) => format!("{} count {} {}", field, operator, condition.value.to_grl()),
+            ("collect", Some(variable)) => format!("{} {}", field, variable),
+            (operation, Some(variable)) => format!("{} {} {}", field, operation, variable),
+            (operation, None) => format!("{} {}", field, operation),
+        },
+    }
+}
+
 /// Extension trait for Operator GRL export
 trait OperatorGRLExport {
     fn to_grl(&self) -> &'static str;
@@ -440,6 +847,16 @@ impl OperatorGRLExport for crate::types::Operator {
             crate::types::Operator::EndsWith => "endsWith",
             crate::types::Operator::Matches => "matches",
             crate::types::Operator::In => "in",
+            crate::types::Operator::NotIn => "not in",
+            crate::types::Operator::ContainsAll => "contains_all",
+            crate::types::Operator::ContainsAny => "contains_any",
+            crate::types::Operator::IsNumber => "is_number",
+            crate::types::Operator::IsString => "is_string",
+            crate::types::Operator::IsBoolean => "is_boolean",
+            crate::types::Operator::IsArray => "is_array",
+            crate::types::Operator::IsObject => "is_object",
+            crate::types::Operator::IsNull => "is null",
+            crate::types::Operator::IsNotNull => "is not null",
         }
     }
 }
@@ -452,14 +869,37 @@ trait ValueGRLExport {
 impl ValueGRLExport for Value {
     fn to_grl(&self) -> String {
         match self {
-            Value::String(s) => format!("\"{}\"", s),
-            Value::Number(n) => n.to_string(),
+            Value::String(s) => quote_grl(s),
+            // Debug keeps the fraction (`100.0`), so the value parses back as a float
+            Value::Number(n) => format!("{:?}", n),
             Value::Integer(i) => i.to_string(),
             Value::Boolean(b) => b.to_string(),
             Value::Null => "null".to_string(),
-            Value::Array(_) => "[array]".to_string(),
-            Value::Object(_) => "{object}".to_string(),
+            Value::Array(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_grl()).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Value::Object(fields) => {
+                let mut fields: Vec<String> = fields
+                    .iter()
+                    .map(|(key, value)| {
+                        if is_grl_identifier(key) {
+                            format!("{}: {}", key, value.to_grl())
+                        } else {
+                            format!("{}: {}", quote_grl(key), value.to_grl())
+                        }
+                    })
+                    .collect();
+                fields.sort();
+                format!("{{{}}}", fields.join(", "))
+            }
             Value::Expression(expr) => expr.clone(), // Export as-is
+            Value::Range { .. } => self.to_string(),
+            Value::DateTime(dt) => dt.to_rfc3339(),
+            Value::Duration(_) => self.to_string(),
+            Value::Bytes(_) => self.to_string(),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => format!("{}d", d),
         }
     }
 }
@@ -476,7 +916,7 @@ impl ActionTypeGRLExport for crate::types::ActionType {
                 format!("{} = {}", field, value.to_grl())
             }
             crate::types::ActionType::Log { message } => {
-                format!("Log(\"{}\")", message)
+                format!("Log({})", quote_grl(message))
             }
             crate::types::ActionType::MethodCall {
                 object,
@@ -488,25 +928,38 @@ impl ActionTypeGRLExport for crate::types::ActionType {
                     .map(|arg| arg.to_grl())
                     .collect::<Vec<_>>()
                     .join(", ");
-                format!("{}.{}({})", object, method, args_str)
+                format!("${}.{}({})", object, method, args_str)
             }
             crate::types::ActionType::Retract { object } => {
                 format!("retract(${})", object)
             }
-            crate::types::ActionType::Custom { action_type, .. } => {
-                format!("Custom(\"{}\")", action_type)
+            crate::types::ActionType::Custom {
+                action_type,
+                params,
+            } => {
+                // Statements the parser did not recognise are kept verbatim
+                if action_type == "statement" {
+                    if let Some(Value::String(statement)) = params.get("statement") {
+                        return statement.clone();
+                    }
+                }
+                // Positional arguments are keyed "0", "1", ...
+                let mut args: Vec<(&String, &Value)> = params.iter().collect();
+                args.sort_by_key(|(key, _)| (key.parse::<usize>().ok(), *key));
+                let args: Vec<String> = args.into_iter().map(|(_, value)| value.to_grl()).collect();
+                format!("{}({})", action_type, args.join(", "))
             }
             crate::types::ActionType::ActivateAgendaGroup { group } => {
-                format!("ActivateAgendaGroup(\"{}\")", group)
+                format!("ActivateAgendaGroup({})", quote_grl(group))
             }
             crate::types::ActionType::ScheduleRule {
                 rule_name,
                 delay_ms,
             } => {
-                format!("ScheduleRule({}, \"{}\")", delay_ms, rule_name)
+                format!("ScheduleRule({}, {})", delay_ms, quote_grl(rule_name))
             }
             crate::types::ActionType::CompleteWorkflow { workflow_name } => {
-                format!("CompleteWorkflow(\"{}\")", workflow_name)
+                format!("CompleteWorkflow({})", quote_grl(workflow_name))
             }
             crate::types::ActionType::SetWorkflowData { key, value } => {
                 format!("SetWorkflowData(\"{}={}\")", key, value.to_grl())
@@ -514,6 +967,32 @@ impl ActionTypeGRLExport for crate::types::ActionType {
             crate::types::ActionType::Append { field, value } => {
                 format!("{} += {}", field, value.to_grl())
             }
+            crate::types::ActionType::Halt => "halt()".to_string(),
+            crate::types::ActionType::Emit {
+                event_type,
+                payload,
+            } => {
+                if payload.is_empty() {
+                    return format!("emit(\"{}\")", event_type);
+                }
+                let mut fields: Vec<String> = payload
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value.to_grl()))
+                    .collect();
+                fields.sort();
+                format!("emit(\"{}\", {{ {} }})", event_type, fields.join(", "))
+            }
+            crate::types::ActionType::AddScore { target, amount } => {
+                format!("AddScore({}, {:?})", quote_grl(target), amount)
+            }
+            crate::types::ActionType::CallAndBind {
+                field,
+                function,
+                args,
+            } => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_grl()).collect();
+                format!("{} = {}({})", field, function, args.join(", "))
+            }
         }
     }
 }
@@ -2,6 +2,8 @@
 pub mod agenda;
 /// Advanced analytics and performance monitoring
 pub mod analytics;
+/// Injectable time source for deterministic runs
+pub mod clock;
 /// Shared condition evaluation logic for both forward and backward chaining
 pub mod condition_evaluator;
 pub mod coverage; // Adding coverage module
@@ -10,34 +12,59 @@ pub mod dependency;
 /// Main rule execution engine
 #[allow(clippy::module_inception)]
 pub mod engine;
+/// Synchronous event dispatch without the streaming stack
+pub mod event_bus;
 /// Facts (working memory) for rule execution
 pub mod facts;
+/// Global variables declared with `defglobal`
+pub mod globals;
 /// Knowledge base for rule storage and management
 pub mod knowledge_base;
 /// Module system for namespace isolation (CLIPS-inspired defmodule)
 pub mod module;
+/// OpenTelemetry export of rule execution traces
+#[cfg(feature = "otel")]
+pub mod otel;
 /// Parallel rule execution engine
 pub mod parallel;
 /// Pattern matching for complex conditions
 pub mod pattern_matcher;
 /// Plugin system for extensibility
 pub mod plugin;
+/// Static checks of rules against registered functions and handlers
+pub mod preflight;
+/// Provenance chains explaining how facts got their values
+pub mod provenance;
 /// Rule execution engine and core functionality
 pub mod rule;
+/// Typed fact schemas validated on write
+pub mod schema;
 /// Rule templates for dynamic rule generation
 pub mod template;
 /// Workflow engine for rule chaining and sequential execution
 pub mod workflow;
 
 // Re-export main components for easy access
-pub use agenda::{ActivationGroupManager, AgendaManager};
+pub use agenda::{ActivationGroupManager, AgendaManager, ConflictStrategy};
 pub use analytics::{AnalyticsConfig, ExecutionEvent, OverallStats, RuleAnalytics, RuleMetrics};
+pub use clock::{Clock, MockClock, SystemClock};
 pub use condition_evaluator::ConditionEvaluator;
 pub use dependency::{
-    DependencyAnalysisResult, DependencyAnalyzer, ExecutionGroup, ExecutionMode, ExecutionStrategy,
+    DependencyAnalysisResult, DependencyAnalyzer, DependencyEdge, ExecutionGroup, ExecutionMode,
+    ExecutionStrategy, RuleFieldAccess,
 };
-pub use engine::{EngineConfig, GruleExecutionResult, RustRuleEngine};
+#[cfg(feature = "async")]
+pub use engine::AsyncActionHandler;
+pub use engine::{
+    CycleResult, EmittedEvent, EngineConfig, GruleExecutionResult, MissingHandler, PlannedAction,
+    ReloadReport, RuleTimedOut, RustRuleEngine, WhatIfResult,
+};
+pub use event_bus::{Event, EventBus, EventHandler};
+pub use globals::GlobalStore;
 pub use parallel::{ParallelConfig, ParallelExecutionResult, ParallelRuleEngine};
+pub use preflight::ValidationIssue;
+pub use provenance::ProvenanceChain;
+pub use schema::{FactSchema, ValueKind};
 pub use template::{ParameterType, RuleTemplate, TemplateManager};
 pub use workflow::{
     ScheduledTask, WorkflowEngine, WorkflowResult, WorkflowState, WorkflowStats, WorkflowStatus,
@@ -0,0 +1,233 @@
+//! OpenTelemetry export of rule execution
+//!
+//! With the `otel` feature enabled, [`RustRuleEngine::set_otel_tracer`] makes
+//! every `execute` produce a `rule_engine.execute` span with one child span
+//! per fired rule. Rule spans carry `rule.name`, `rule.salience`,
+//! `rule.duration_us` and `rule.fact_keys` (facts written by the rule's
+//! actions).
+//!
+//! Timings are collected during the run and exported once it completes, so
+//! the engine loop itself never touches the tracer.
+//!
+//! [`RustRuleEngine::set_otel_tracer`]: crate::engine::RustRuleEngine::set_otel_tracer
+
+use crate::engine::facts::Facts;
+use crate::engine::rule::Rule;
+use crate::types::ActionType;
+use opentelemetry::trace::{Span, TraceContextExt, Tracer};
+use opentelemetry::{Array, Context, KeyValue, StringValue};
+use std::time::{Duration, SystemTime};
+
+/// Sink for a finished execution trace
+pub(crate) type TraceExporter = Box<dyn Fn(&ExecutionTrace) + Send + Sync>;
+
+/// Timing of a single fired rule
+pub(crate) struct RuleSpan {
+    name: String,
+    salience: i32,
+    start: SystemTime,
+    end: SystemTime,
+    fact_keys: Vec<String>,
+}
+
+/// Everything recorded for one `execute` call
+pub(crate) struct ExecutionTrace {
+    start: SystemTime,
+    end: SystemTime,
+    cycles: usize,
+    rules: Vec<RuleSpan>,
+}
+
+impl ExecutionTrace {
+    pub(crate) fn new() -> Self {
+        let now = SystemTime::now();
+        Self {
+            start: now,
+            end: now,
+            cycles: 0,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Record a rule that just finished firing after `duration`
+    pub(crate) fn record_rule(&mut self, rule: &Rule, facts: &Facts, duration: Duration) {
+        let end = SystemTime::now();
+        self.rules.push(RuleSpan {
+            name: rule.name.clone(),
+            salience: rule.effective_salience(facts),
+            start: end.checked_sub(duration).unwrap_or(end),
+            end,
+            fact_keys: action_fact_keys(&rule.actions),
+        });
+    }
+
+    pub(crate) fn finish(&mut self, cycles: usize) {
+        self.end = SystemTime::now();
+        self.cycles = cycles;
+    }
+}
+
+/// Build an exporter that replays traces onto `tracer`
+pub(crate) fn exporter<T>(tracer: T) -> TraceExporter
+where
+    T: Tracer + Send + Sync + 'static,
+    T::Span: Send + Sync + 'static,
+{
+    Box::new(move |trace| export(&tracer, trace))
+}
+
+fn export<T>(tracer: &T, trace: &ExecutionTrace)
+where
+    T: Tracer,
+    T::Span: Send + Sync + 'static,
+{
+    // Parented to the caller's current span, if any
+    let root = tracer
+        .span_builder("rule_engine.execute")
+        .with_start_time(trace.start)
+        .with_attributes(vec![
+            KeyValue::new("rule_engine.cycles", trace.cycles as i64),
+            KeyValue::new("rule_engine.rules_fired", trace.rules.len() as i64),
+        ])
+        .start(tracer);
+    let cx = Context::current_with_span(root);
+
+    for rule in &trace.rules {
+        let duration = rule.end.duration_since(rule.start).unwrap_or_default();
+        let fact_keys: Vec<StringValue> = rule.fact_keys.iter().cloned().map(Into::into).collect();
+
+        let mut span = tracer
+            .span_builder(format!("rule {}", rule.name))
+            .with_start_time(rule.start)
+            .with_attributes(vec![
+                KeyValue::new("rule.name", rule.name.clone()),
+                KeyValue::new("rule.salience", rule.salience as i64),
+                KeyValue::new("rule.duration_us", duration.as_micros() as i64),
+                KeyValue::new(
+                    "rule.fact_keys",
+                    opentelemetry::Value::Array(Array::String(fact_keys)),
+                ),
+            ])
+            .start_with_context(tracer, &cx);
+        span.end_with_timestamp(rule.end);
+    }
+
+    cx.span().end_with_timestamp(trace.end);
+}
+
+/// Facts written by a rule's actions
+fn action_fact_keys(actions: &[ActionType]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for action in actions {
+        let key = match action {
+            ActionType::Set { field, .. }
+            | ActionType::Append { field, .. }
+            | ActionType::CallAndBind { field, .. } => field,
+            ActionType::MethodCall { object, .. } | ActionType::Retract { object } => object,
+            _ => continue,
+        };
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::facts::Facts;
+    use crate::engine::knowledge_base::KnowledgeBase;
+    use crate::engine::RustRuleEngine;
+    use crate::types::Value;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry::{Array, KeyValue};
+    use opentelemetry_sdk::export::trace::SpanData;
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporterBuilder;
+    use opentelemetry_sdk::trace::TracerProvider;
+
+    fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a opentelemetry::Value> {
+        span.attributes
+            .iter()
+            .find(|kv: &&KeyValue| kv.key.as_str() == key)
+            .map(|kv| &kv.value)
+    }
+
+    #[test]
+    fn test_execute_exports_span_hierarchy() {
+        let kb = KnowledgeBase::new("Traced");
+        kb.add_rules_from_grl(
+            r#"
+            rule "FlagVip" salience 20 no-loop {
+                when
+                    User.Points > 100
+                then
+                    User.IsVIP = true;
+            }
+
+            rule "VipDiscount" salience 10 no-loop {
+                when
+                    User.IsVIP == true
+                then
+                    Order.Discount = 0.2;
+            }
+            "#,
+        )
+        .unwrap();
+
+        let exporter = InMemorySpanExporterBuilder::new().build();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+
+        let mut engine = RustRuleEngine::new(kb);
+        engine.set_otel_tracer(provider.tracer("rule-engine-test"));
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "User",
+                Facts::create_object(vec![
+                    ("Points".to_string(), Value::Integer(150)),
+                    ("IsVIP".to_string(), Value::Boolean(false)),
+                ]),
+            )
+            .unwrap();
+        facts
+            .add_value("Order", Facts::create_object(vec![]))
+            .unwrap();
+        engine.execute(&facts).unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 3);
+
+        let root = spans
+            .iter()
+            .find(|s| s.name == "rule_engine.execute")
+            .expect("root span");
+        let rules: Vec<&SpanData> = spans
+            .iter()
+            .filter(|s| s.name.starts_with("rule "))
+            .collect();
+        assert_eq!(rules.len(), 2);
+        for span in &rules {
+            assert_eq!(span.parent_span_id, root.span_context.span_id());
+            assert!(attribute(span, "rule.duration_us").is_some());
+        }
+
+        let vip = rules.iter().find(|s| s.name == "rule FlagVip").unwrap();
+        assert_eq!(
+            attribute(vip, "rule.name"),
+            Some(&opentelemetry::Value::from("FlagVip"))
+        );
+        assert_eq!(
+            attribute(vip, "rule.salience"),
+            Some(&opentelemetry::Value::I64(20))
+        );
+        assert_eq!(
+            attribute(vip, "rule.fact_keys"),
+            Some(&opentelemetry::Value::Array(Array::String(vec![
+                "User.IsVIP".into()
+            ])))
+        );
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::collapsible_match)]
 
+use crate::engine::dependency::{DependencyAnalyzer, ExecutionMode, ExecutionStrategy};
 use crate::engine::{facts::Facts, knowledge_base::KnowledgeBase, rule::Rule};
 use crate::errors::{Result, RuleEngineError};
 use crate::types::{ActionType, Value};
@@ -19,6 +20,14 @@ pub struct ParallelConfig {
     pub min_rules_per_thread: usize,
     /// Enable dependency analysis
     pub dependency_analysis: bool,
+    /// Keep running sibling rules when a rule fails, collecting the error
+    /// in `ParallelExecutionResult::errors` instead of aborting the batch
+    pub continue_on_error: bool,
+    /// How each salience group picks parallel or sequential execution
+    pub strategy: ExecutionStrategy,
+    /// Group size a conflict-free group must exceed to run in parallel
+    /// under `ExecutionStrategy::Auto`
+    pub auto_parallel_threshold: usize,
 }
 
 impl Default for ParallelConfig {
@@ -30,6 +39,9 @@ impl Default for ParallelConfig {
                 .unwrap_or(4),
             min_rules_per_thread: 2,
             dependency_analysis: true,
+            continue_on_error: true,
+            strategy: ExecutionStrategy::FullParallel,
+            auto_parallel_threshold: 8,
         }
     }
 }
@@ -38,6 +50,9 @@ impl Default for ParallelConfig {
 type CustomFunctionMap =
     HashMap<String, Box<dyn Fn(&[Value], &Facts) -> Result<Value> + Send + Sync>>;
 
+/// Execution context of a rule paired with the error it raised, if any
+type RuleOutcome = (RuleExecutionContext, Option<RuleEngineError>);
+
 /// Rule execution context for parallel processing
 #[derive(Debug, Clone)]
 pub struct RuleExecutionContext {
@@ -87,7 +102,7 @@ impl ParallelRuleEngine {
         if debug_mode {
             println!(
                 "🚀 Starting parallel rule execution with {} rules",
-                knowledge_base.get_rules().len()
+                knowledge_base.rule_count()
             );
         }
 
@@ -97,6 +112,8 @@ impl ParallelRuleEngine {
         let mut total_fired = 0;
         let mut total_evaluated = 0;
         let mut execution_contexts = Vec::new();
+        let mut errors = Vec::new();
+        let mut group_modes = Vec::new();
 
         // Execute rules by salience level (highest first)
         let mut salience_levels: Vec<_> = salience_groups.keys().copied().collect();
@@ -114,23 +131,32 @@ impl ParallelRuleEngine {
             }
 
             // Decide whether to use parallel execution for this level
-            let should_parallelize = self.should_parallelize(rules_at_level);
+            let mode = self.group_mode(rules_at_level);
+            group_modes.push((salience, mode));
 
-            let contexts = if should_parallelize {
-                self.execute_rules_parallel(rules_at_level, facts, debug_mode)?
-            } else {
-                self.execute_rules_sequential(rules_at_level, facts, debug_mode)?
+            let outcomes = match mode {
+                ExecutionMode::Parallel => {
+                    self.execute_rules_parallel(rules_at_level, facts, debug_mode)?
+                }
+                ExecutionMode::Sequential => {
+                    self.execute_rules_sequential(rules_at_level, facts, debug_mode)?
+                }
             };
 
             // Count results
-            for context in &contexts {
+            for (context, error) in outcomes {
                 total_evaluated += 1;
                 if context.fired {
                     total_fired += 1;
                 }
+                if let Some(error) = error {
+                    if !self.config.continue_on_error {
+                        return Err(error);
+                    }
+                    errors.push((context.rule.name.clone(), error));
+                }
+                execution_contexts.push(context);
             }
-
-            execution_contexts.extend(contexts);
         }
 
         Ok(ParallelExecutionResult {
@@ -139,6 +165,9 @@ impl ParallelRuleEngine {
             execution_time: start_time.elapsed(),
             parallel_speedup: self.calculate_speedup(&execution_contexts),
             execution_contexts,
+            errors,
+            strategy: self.resolved_strategy(&group_modes),
+            group_modes,
         })
     }
 
@@ -158,13 +187,57 @@ impl ParallelRuleEngine {
         self.config.enabled && rules.len() >= self.config.min_rules_per_thread && rules.len() >= 2
     }
 
+    /// Pick the execution mode for one salience group under the configured strategy
+    fn group_mode(&self, rules: &[Rule]) -> ExecutionMode {
+        let conflict_free = || {
+            DependencyAnalyzer::new()
+                .analyze(rules)
+                .can_parallelize_safely
+        };
+        let parallel = match self.config.strategy {
+            ExecutionStrategy::FullSequential | ExecutionStrategy::ForcedSequential => false,
+            ExecutionStrategy::FullParallel => self.should_parallelize(rules),
+            ExecutionStrategy::Hybrid => self.should_parallelize(rules) && conflict_free(),
+            ExecutionStrategy::Auto => {
+                self.config.enabled
+                    && rules.len() > self.config.auto_parallel_threshold
+                    && conflict_free()
+            }
+        };
+
+        if parallel {
+            ExecutionMode::Parallel
+        } else {
+            ExecutionMode::Sequential
+        }
+    }
+
+    /// Summarize the modes chosen per group as the strategy actually applied
+    fn resolved_strategy(&self, group_modes: &[(i32, ExecutionMode)]) -> ExecutionStrategy {
+        if !self.config.enabled || self.config.strategy == ExecutionStrategy::ForcedSequential {
+            return ExecutionStrategy::ForcedSequential;
+        }
+
+        let parallel = group_modes
+            .iter()
+            .filter(|(_, mode)| *mode == ExecutionMode::Parallel)
+            .count();
+        if parallel == 0 {
+            ExecutionStrategy::FullSequential
+        } else if parallel == group_modes.len() {
+            ExecutionStrategy::FullParallel
+        } else {
+            ExecutionStrategy::Hybrid
+        }
+    }
+
     /// Execute rules in parallel within the same salience level
     fn execute_rules_parallel(
         &self,
         rules: &[Rule],
         facts: &Facts,
         debug_mode: bool,
-    ) -> Result<Vec<RuleExecutionContext>> {
+    ) -> Result<Vec<RuleOutcome>> {
         let results = Arc::new(Mutex::new(Vec::new()));
         let facts_arc = Arc::new(facts.clone());
         let functions_arc = Arc::clone(&self.custom_functions);
@@ -187,39 +260,17 @@ impl ParallelRuleEngine {
                         println!("  🧵 Thread {} processing {} rules", thread_id, chunk.len());
                     }
 
-                    let mut thread_results = Vec::new();
-                    for rule in chunk {
-                        let start = Instant::now();
-                        // Pass functions to evaluator
-                        let fired =
-                            Self::evaluate_rule_conditions(&rule, &facts_clone, &functions_clone);
-
-                        if fired {
-                            if debug_mode {
-                                println!("    🔥 Rule '{}' fired", rule.name);
-                            }
-
-                            // Execute actions (simplified for demo)
-                            for action in &rule.actions {
-                                if let Err(e) = Self::execute_action_parallel(
-                                    action,
-                                    &facts_clone,
-                                    &functions_clone,
-                                ) {
-                                    if debug_mode {
-                                        println!("    ❌ Action failed: {}", e);
-                                    }
-                                }
-                            }
-                        }
-
-                        thread_results.push(RuleExecutionContext {
-                            rule: rule.clone(),
-                            fired,
-                            error: None,
-                            execution_time: start.elapsed(),
-                        });
-                    }
+                    let thread_results: Vec<RuleOutcome> = chunk
+                        .iter()
+                        .map(|rule| {
+                            Self::execute_single_rule(
+                                rule,
+                                &facts_clone,
+                                &functions_clone,
+                                debug_mode,
+                            )
+                        })
+                        .collect();
 
                     let mut results = results_clone.lock().unwrap();
                     results.extend(thread_results);
@@ -236,8 +287,8 @@ impl ParallelRuleEngine {
                 })?;
         }
 
-        let results = results.lock().unwrap();
-        Ok(results.clone())
+        let mut results = results.lock().unwrap();
+        Ok(std::mem::take(&mut *results))
     }
 
     /// Execute rules sequentially (fallback)
@@ -246,38 +297,100 @@ impl ParallelRuleEngine {
         rules: &[Rule],
         facts: &Facts,
         debug_mode: bool,
-    ) -> Result<Vec<RuleExecutionContext>> {
-        let mut contexts = Vec::new();
+    ) -> Result<Vec<RuleOutcome>> {
         let functions_arc = Arc::clone(&self.custom_functions);
 
-        for rule in rules {
-            let start = Instant::now();
-            let fired = Self::evaluate_rule_conditions(rule, facts, &functions_arc);
+        Ok(rules
+            .iter()
+            .map(|rule| Self::execute_single_rule(rule, facts, &functions_arc, debug_mode))
+            .collect())
+    }
 
-            if fired && debug_mode {
+    /// Evaluate a rule and, if it fires, run its actions in isolation
+    fn execute_single_rule(
+        rule: &Rule,
+        facts: &Facts,
+        functions: &Arc<RwLock<CustomFunctionMap>>,
+        debug_mode: bool,
+    ) -> RuleOutcome {
+        let start = Instant::now();
+        // `$var` bindings are made in a per-rule copy, so they never reach the
+        // shared facts or a sibling rule running on another thread
+        let local = (!rule.conditions.bound_variables().is_empty()).then(|| facts.working_copy());
+        let context = local.as_ref().unwrap_or(facts);
+        let fired = Self::evaluate_rule_conditions(rule, context, functions);
+
+        let error = if fired {
+            if debug_mode {
                 println!("    🔥 Rule '{}' fired", rule.name);
             }
+            Self::execute_rule_actions_isolated(rule, context, facts, functions).err()
+        } else {
+            None
+        };
 
-            if fired {
-                // Execute actions
-                for action in &rule.actions {
-                    if let Err(e) = Self::execute_action_parallel(action, facts, &functions_arc) {
-                        if debug_mode {
-                            println!("    ❌ Action failed: {}", e);
-                        }
-                    }
-                }
+        if let Some(e) = &error {
+            if debug_mode {
+                println!("    ❌ Action failed: {}", e);
             }
+        }
 
-            contexts.push(RuleExecutionContext {
-                rule: rule.clone(),
-                fired,
-                error: None,
-                execution_time: start.elapsed(),
-            });
+        let context = RuleExecutionContext {
+            rule: rule.clone(),
+            fired,
+            error: error.as_ref().map(|e| e.to_string()),
+            execution_time: start.elapsed(),
+        };
+        (context, error)
+    }
+
+    /// Run a rule's actions against a private copy of `context` and publish
+    /// the facts it changed to `facts` only if every action succeeds
+    ///
+    /// Changes are published per dotted path, so two rules writing
+    /// `Order.A` and `Order.B` do not overwrite each other's `Order`. The
+    /// paths are published together: if one cannot be set, none is.
+    fn execute_rule_actions_isolated(
+        rule: &Rule,
+        context: &Facts,
+        facts: &Facts,
+        functions: &Arc<RwLock<CustomFunctionMap>>,
+    ) -> Result<()> {
+        if rule.actions.is_empty() {
+            return Ok(());
+        }
+
+        // Bindings are in `context` both before and after, so they are never
+        // published
+        let before = context.snapshot();
+        let scratch = context.working_copy();
+
+        for action in &rule.actions {
+            Self::execute_action_parallel(action, &scratch, functions)?;
         }
 
-        Ok(contexts)
+        let after = scratch.snapshot().data;
+        let mut changes = Vec::new();
+        let mut flat_changes = Vec::new();
+        for (key, value) in &after {
+            if !key.contains(['.', '[']) {
+                changed_paths(key, before.data.get(key), value, &mut changes);
+            } else if before.data.get(key) != Some(value) {
+                // Flat keys such as "User.score" are not paths
+                flat_changes.push((key, value));
+            }
+        }
+        facts.set_many_atomically(&changes)?;
+        for (key, value) in flat_changes {
+            facts.set(key, value.clone());
+        }
+        for key in before.data.keys() {
+            if !after.contains_key(key) {
+                facts.remove(key);
+            }
+        }
+
+        Ok(())
     }
 
     /// Evaluate rule conditions for parallel execution - FULL FEATURED
@@ -312,32 +425,12 @@ impl ParallelRuleEngine {
             } => {
                 // Create temporary rules to evaluate sub-conditions
                 let left_rule = Rule {
-                    name: rule.name.clone(),
-                    description: rule.description.clone(),
                     conditions: (**left).clone(),
-                    actions: rule.actions.clone(),
-                    salience: rule.salience,
-                    enabled: rule.enabled,
-                    no_loop: rule.no_loop,
-                    lock_on_active: rule.lock_on_active,
-                    agenda_group: rule.agenda_group.clone(),
-                    activation_group: rule.activation_group.clone(),
-                    date_effective: rule.date_effective,
-                    date_expires: rule.date_expires,
+                    ..rule.clone()
                 };
                 let right_rule = Rule {
-                    name: rule.name.clone(),
-                    description: rule.description.clone(),
                     conditions: (**right).clone(),
-                    actions: rule.actions.clone(),
-                    salience: rule.salience,
-                    enabled: rule.enabled,
-                    no_loop: rule.no_loop,
-                    lock_on_active: rule.lock_on_active,
-                    agenda_group: rule.agenda_group.clone(),
-                    activation_group: rule.activation_group.clone(),
-                    date_effective: rule.date_effective,
-                    date_expires: rule.date_expires,
+                    ..rule.clone()
                 };
 
                 let left_result = Self::evaluate_rule_conditions(&left_rule, facts, functions);
@@ -346,23 +439,15 @@ impl ParallelRuleEngine {
                 match operator {
                     crate::types::LogicalOperator::And => left_result && right_result,
                     crate::types::LogicalOperator::Or => left_result || right_result,
+                    crate::types::LogicalOperator::Xor => left_result != right_result,
                     crate::types::LogicalOperator::Not => false, // Not handled in compound
                 }
             }
+            ConditionGroup::Constant(value) => *value,
             ConditionGroup::Not(condition) => {
                 let temp_rule = Rule {
-                    name: rule.name.clone(),
-                    description: rule.description.clone(),
                     conditions: (**condition).clone(),
-                    actions: rule.actions.clone(),
-                    salience: rule.salience,
-                    enabled: rule.enabled,
-                    no_loop: rule.no_loop,
-                    lock_on_active: rule.lock_on_active,
-                    agenda_group: rule.agenda_group.clone(),
-                    activation_group: rule.activation_group.clone(),
-                    date_effective: rule.date_effective,
-                    date_expires: rule.date_expires,
+                    ..rule.clone()
                 };
                 !Self::evaluate_rule_conditions(&temp_rule, facts, functions)
             }
@@ -436,9 +521,19 @@ impl ParallelRuleEngine {
                         }
                         _ => condition.value.clone(),
                     };
-                    condition.operator.evaluate(&value, &rhs)
+                    facts.evaluate_operator(field_name, &condition.operator, &value, &rhs)
                 } else {
-                    false
+                    // A missing field only satisfies `is null`
+                    condition.operator == crate::types::Operator::IsNull
+                }
+            }
+            ConditionExpression::Bind { variable, field } => {
+                match facts.get_nested(field).or_else(|| facts.get(field)) {
+                    Some(Value::Null) | None => false,
+                    Some(value) => {
+                        facts.set(variable, value);
+                        true
+                    }
                 }
             }
             ConditionExpression::FunctionCall { name, args } => {
@@ -729,6 +824,55 @@ impl ParallelRuleEngine {
                 // Simplified append handling
                 Ok(())
             }
+            ActionType::Halt => {
+                // Rules run independently in parallel, there is no loop to stop
+                Ok(())
+            }
+            ActionType::Emit { .. } => {
+                // Parallel results do not collect emitted events
+                Ok(())
+            }
+            ActionType::AddScore { target, amount } => {
+                // Parallel results do not collect scores, the fact is still updated
+                let current = facts
+                    .get_nested(target)
+                    .or_else(|| facts.get(target))
+                    .and_then(|value| value.to_number())
+                    .unwrap_or(0.0);
+                let total = Value::Number(current + amount);
+                if facts.set_nested(target, total.clone()).is_err() {
+                    facts.set(target, total);
+                }
+                Ok(())
+            }
+            ActionType::CallAndBind {
+                field,
+                function,
+                args,
+            } => {
+                let functions_guard = functions.read().unwrap();
+                let Some(func) = functions_guard.get(function) else {
+                    return Err(RuleEngineError::EvaluationError {
+                        message: format!("Function '{}' is not registered", function),
+                    });
+                };
+                // `$Order.Total` means `Order.Total`; literals pass through
+                let args: Vec<Value> = args
+                    .iter()
+                    .map(|arg| match arg {
+                        Value::Expression(text) => {
+                            crate::expression::evaluate_expression(&text.replace('$', ""), facts)
+                                .unwrap_or_else(|_| arg.clone())
+                        }
+                        _ => arg.clone(),
+                    })
+                    .collect();
+                let result = func(&args, facts)?;
+                if facts.set_nested(field, result.clone()).is_err() {
+                    facts.set(field, result);
+                }
+                Ok(())
+            }
         }
     }
 
@@ -753,6 +897,30 @@ impl ParallelRuleEngine {
     }
 }
 
+/// Collect the dotted paths at which `after` differs from `before`
+///
+/// Objects are compared field by field. An object that lost fields, or whose
+/// field names cannot be written as a path, is reported whole.
+fn changed_paths(
+    path: &str,
+    before: Option<&Value>,
+    after: &Value,
+    changes: &mut Vec<(String, Value)>,
+) {
+    match (before, after) {
+        (Some(before), after) if before == after => {}
+        (Some(Value::Object(old)), Value::Object(new))
+            if old.keys().all(|key| new.contains_key(key))
+                && new.keys().all(|key| !key.contains(['.', '['])) =>
+        {
+            for (key, value) in new {
+                changed_paths(&format!("{}.{}", path, key), old.get(key), value, changes);
+            }
+        }
+        _ => changes.push((path.to_string(), after.clone())),
+    }
+}
+
 /// Result of parallel rule execution
 #[derive(Debug)]
 pub struct ParallelExecutionResult {
@@ -766,6 +934,12 @@ pub struct ParallelExecutionResult {
     pub execution_contexts: Vec<RuleExecutionContext>,
     /// Parallel speedup factor
     pub parallel_speedup: f64,
+    /// Errors raised by individual rules, as `(rule_name, error)`
+    pub errors: Vec<(String, RuleEngineError)>,
+    /// Strategy actually applied, derived from the per-group decisions
+    pub strategy: ExecutionStrategy,
+    /// Mode chosen for each salience group, highest salience first
+    pub group_modes: Vec<(i32, ExecutionMode)>,
 }
 
 impl ParallelExecutionResult {
@@ -845,4 +1019,346 @@ mod tests {
         assert_eq!(groups[&10].len(), 2);
         assert_eq!(groups[&5].len(), 1);
     }
+
+    fn failing_batch() -> (ParallelRuleEngine, KnowledgeBase, Facts) {
+        let mut engine = ParallelRuleEngine::new(ParallelConfig {
+            min_rules_per_thread: 1,
+            ..Default::default()
+        });
+        engine.register_function("touch", |_, facts| {
+            facts.set("Touched", Value::Boolean(true));
+            Ok(Value::Null)
+        });
+        engine.register_function("boom", |_, _| {
+            Err(RuleEngineError::ActionError {
+                message: "boom".to_string(),
+            })
+        });
+        engine.register_function("approve", |_, facts| {
+            facts.set("Approved", Value::Boolean(true));
+            Ok(Value::Null)
+        });
+
+        let custom = |name: &str| ActionType::Custom {
+            action_type: name.to_string(),
+            params: HashMap::new(),
+        };
+        let always = || {
+            ConditionGroup::Single(Condition::new(
+                "test".to_string(),
+                Operator::Equal,
+                Value::Boolean(true),
+            ))
+        };
+
+        let kb = KnowledgeBase::new("ParallelErrors");
+        kb.add_rule(Rule::new(
+            "Failing".to_string(),
+            always(),
+            vec![custom("touch"), custom("boom")],
+        ))
+        .unwrap();
+        kb.add_rule(Rule::new(
+            "Healthy".to_string(),
+            always(),
+            vec![custom("approve")],
+        ))
+        .unwrap();
+
+        let facts = Facts::new();
+        facts.set("test", Value::Boolean(true));
+        (engine, kb, facts)
+    }
+
+    #[test]
+    fn test_errors_collected_without_aborting_siblings() {
+        let (engine, kb, facts) = failing_batch();
+
+        let result = engine.execute_parallel(&kb, &facts, false).unwrap();
+
+        assert_eq!(result.total_rules_fired, 2);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].0, "Failing");
+        assert_eq!(facts.get("Approved"), Some(Value::Boolean(true)));
+        // Partial writes of the failed rule are discarded
+        assert_eq!(facts.get("Touched"), None);
+    }
+
+    #[test]
+    fn test_abort_on_error_when_disabled() {
+        let (mut engine, kb, facts) = failing_batch();
+        engine.config.continue_on_error = false;
+
+        assert!(engine.execute_parallel(&kb, &facts, false).is_err());
+    }
+
+    #[test]
+    fn test_sibling_writes_to_one_object_are_merged() {
+        let mut engine = ParallelRuleEngine::new(ParallelConfig {
+            min_rules_per_thread: 1,
+            ..Default::default()
+        });
+        for field in ["A", "B"] {
+            let path = format!("Order.{}", field);
+            engine.register_function(&format!("mark{}", field), move |_, facts| {
+                facts.set_nested(&path, Value::Boolean(true))?;
+                Ok(Value::Null)
+            });
+        }
+
+        let kb = KnowledgeBase::new("SharedObject");
+        for field in ["A", "B"] {
+            kb.add_rule(Rule::new(
+                format!("Mark{}", field),
+                ConditionGroup::Single(Condition::new(
+                    "Order.Ready".to_string(),
+                    Operator::Equal,
+                    Value::Boolean(true),
+                )),
+                vec![ActionType::Custom {
+                    action_type: format!("mark{}", field),
+                    params: HashMap::new(),
+                }],
+            ))
+            .unwrap();
+        }
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Ready".to_string(), Value::Boolean(true))]),
+            )
+            .unwrap();
+        let result = engine.execute_parallel(&kb, &facts, false).unwrap();
+
+        assert_eq!(result.total_rules_fired, 2);
+        assert_eq!(facts.get_nested("Order.A"), Some(Value::Boolean(true)));
+        assert_eq!(facts.get_nested("Order.B"), Some(Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_failed_publish_leaves_facts_unchanged() {
+        let mut engine = ParallelRuleEngine::new(ParallelConfig::default());
+        for (name, path) in [("markCart", "Cart.Checked"), ("markOrder", "Order.Checked")] {
+            engine.register_function(name, move |_, facts| {
+                facts.set_nested(path, Value::Boolean(true))?;
+                Ok(Value::Null)
+            });
+        }
+        let rule = Rule::new(
+            "MarkBoth".to_string(),
+            ConditionGroup::Single(Condition::new(
+                "Cart.Ready".to_string(),
+                Operator::Equal,
+                Value::Boolean(true),
+            )),
+            ["markCart", "markOrder"]
+                .into_iter()
+                .map(|name| ActionType::Custom {
+                    action_type: name.to_string(),
+                    params: HashMap::new(),
+                })
+                .collect(),
+        );
+
+        let object = || {
+            Facts::create_object(vec![
+                ("Ready".to_string(), Value::Boolean(true)),
+                ("Checked".to_string(), Value::Boolean(false)),
+            ])
+        };
+        let context = Facts::new();
+        context.add_value("Cart", object()).unwrap();
+        context.add_value("Order", object()).unwrap();
+        // `Order` is no longer an object where the changes are published
+        let facts = Facts::new();
+        facts.add_value("Cart", object()).unwrap();
+        facts.add_value("Order", Value::Integer(5)).unwrap();
+
+        let result = ParallelRuleEngine::execute_rule_actions_isolated(
+            &rule,
+            &context,
+            &facts,
+            &engine.custom_functions,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            facts.get_nested("Cart.Checked"),
+            Some(Value::Boolean(false))
+        );
+        assert_eq!(facts.get("Order"), Some(Value::Integer(5)));
+    }
+
+    #[test]
+    fn test_add_score_accumulates_across_groups() {
+        let engine = ParallelRuleEngine::new(ParallelConfig::default());
+        let kb = KnowledgeBase::new("ParallelScores");
+        for (name, salience, amount) in [("Young", 10, 30.0), ("Claims", 5, 20.0)] {
+            kb.add_rule(
+                Rule::new(
+                    name.to_string(),
+                    ConditionGroup::Single(Condition::new(
+                        "Applicant.Ready".to_string(),
+                        Operator::Equal,
+                        Value::Boolean(true),
+                    )),
+                    vec![ActionType::AddScore {
+                        target: "Applicant.Risk".to_string(),
+                        amount,
+                    }],
+                )
+                .with_priority(salience),
+            )
+            .unwrap();
+        }
+
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Applicant",
+                Facts::create_object(vec![("Ready".to_string(), Value::Boolean(true))]),
+            )
+            .unwrap();
+        engine.execute_parallel(&kb, &facts, false).unwrap();
+
+        assert_eq!(
+            facts.get_nested("Applicant.Risk"),
+            Some(Value::Number(50.0))
+        );
+    }
+
+    #[test]
+    fn test_call_and_bind_stores_function_result() {
+        let mut engine = ParallelRuleEngine::new(ParallelConfig::default());
+        engine.register_function("double", |args, _| {
+            Ok(Value::Number(args[0].to_number().unwrap_or(0.0) * 2.0))
+        });
+
+        let bind = |function: &str| {
+            Rule::new(
+                "Quote".to_string(),
+                ConditionGroup::Single(Condition::new(
+                    "Order.Total".to_string(),
+                    Operator::GreaterThan,
+                    Value::Integer(0),
+                )),
+                vec![ActionType::CallAndBind {
+                    field: "Order.Quote".to_string(),
+                    function: function.to_string(),
+                    args: vec![Value::Expression("Order.Total".to_string())],
+                }],
+            )
+        };
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(21))]),
+            )
+            .unwrap();
+
+        let kb = KnowledgeBase::new("ParallelBind");
+        kb.add_rule(bind("double")).unwrap();
+        let result = engine.execute_parallel(&kb, &facts, false).unwrap();
+        assert!(result.errors.is_empty());
+        assert_eq!(facts.get_nested("Order.Quote"), Some(Value::Number(42.0)));
+
+        // An unregistered function is reported, not silently skipped
+        let kb = KnowledgeBase::new("ParallelMissing");
+        kb.add_rule(bind("missing")).unwrap();
+        let result = engine.execute_parallel(&kb, &facts, false).unwrap();
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_bindings_stay_local_to_their_rule() {
+        let mut engine = ParallelRuleEngine::new(ParallelConfig {
+            min_rules_per_thread: 1,
+            ..Default::default()
+        });
+        engine.register_function("copyTotal", |_, facts| {
+            let total = facts.get("$total").unwrap_or(Value::Null);
+            facts.set_nested("Order.Copy", total)?;
+            Ok(Value::Null)
+        });
+
+        let kb = KnowledgeBase::new("ParallelBindings");
+        kb.add_rules_from_grl(
+            r#"
+            rule "Copy" {
+                when $total : Order.Total && $total > 10
+                then copyTotal();
+            }
+            "#,
+        )
+        .unwrap();
+        let facts = Facts::new();
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Total".to_string(), Value::Integer(25))]),
+            )
+            .unwrap();
+
+        let result = engine.execute_parallel(&kb, &facts, false).unwrap();
+
+        assert_eq!(result.total_rules_fired, 1);
+        assert_eq!(facts.get_nested("Order.Copy"), Some(Value::Integer(25)));
+        assert_eq!(facts.get("$total"), None);
+    }
+
+    #[test]
+    fn test_auto_strategy_parallelizes_only_large_conflict_free_groups() {
+        let engine = ParallelRuleEngine::new(ParallelConfig {
+            strategy: ExecutionStrategy::Auto,
+            auto_parallel_threshold: 2,
+            ..Default::default()
+        });
+
+        let rule = |name: &str, salience: i32, field: &str| {
+            Rule::new(
+                name.to_string(),
+                ConditionGroup::Single(Condition::new(
+                    "test".to_string(),
+                    Operator::Equal,
+                    Value::Boolean(true),
+                )),
+                vec![ActionType::Set {
+                    field: field.to_string(),
+                    value: Value::Boolean(true),
+                }],
+            )
+            .with_priority(salience)
+        };
+
+        let kb = KnowledgeBase::new("AutoStrategy");
+        // Three independent writers: above the threshold, runs in parallel
+        for (i, field) in ["A", "B", "C"].iter().enumerate() {
+            kb.add_rule(rule(&format!("Wide{}", i), 10, field)).unwrap();
+        }
+        // Too small to be worth spawning threads
+        kb.add_rule(rule("Small1", 5, "D")).unwrap();
+        kb.add_rule(rule("Small2", 5, "E")).unwrap();
+        // Large enough, but every rule writes the same field
+        for i in 0..3 {
+            kb.add_rule(rule(&format!("Shared{}", i), 1, "F")).unwrap();
+        }
+
+        let facts = Facts::new();
+        facts.set("test", Value::Boolean(true));
+        let result = engine.execute_parallel(&kb, &facts, false).unwrap();
+
+        assert_eq!(
+            result.group_modes,
+            vec![
+                (10, ExecutionMode::Parallel),
+                (5, ExecutionMode::Sequential),
+                (1, ExecutionMode::Sequential),
+            ]
+        );
+        assert_eq!(result.strategy, ExecutionStrategy::Hybrid);
+        assert_eq!(result.total_rules_fired, 8);
+    }
 }
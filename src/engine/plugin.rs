@@ -49,6 +49,14 @@ pub trait RulePlugin: Send + Sync {
     /// Get plugin metadata
     fn get_metadata(&self) -> &PluginMetadata;
 
+    /// Namespace for the plugin's functions
+    ///
+    /// When set, functions are registered as `namespace.name` and called from
+    /// GRL as `namespace.name(...)`. Action names are not affected.
+    fn namespace(&self) -> Option<&str> {
+        None
+    }
+
     /// Register custom actions with the engine
     fn register_actions(&self, engine: &mut RustRuleEngine) -> Result<()>;
 
@@ -85,6 +93,8 @@ pub struct PluginConfig {
     pub enable_hot_reload: bool,
     pub plugin_timeout_ms: u64,
     pub safety_checks: bool,
+    /// Let a plugin replace actions or functions declared by an already loaded plugin
+    pub allow_override: bool,
 }
 
 impl Default for PluginConfig {
@@ -94,6 +104,7 @@ impl Default for PluginConfig {
             enable_hot_reload: true,
             plugin_timeout_ms: 5000,
             safety_checks: true,
+            allow_override: false,
         }
     }
 }
@@ -142,6 +153,11 @@ impl PluginManager {
             self.validate_dependencies(&metadata.dependencies)?;
         }
 
+        // Check for actions or functions another plugin already provides
+        if !self.config.allow_override {
+            self.check_conflicts(plugin.as_ref())?;
+        }
+
         // Store plugin
         self.plugins.insert(name.clone(), plugin);
         self.load_order.push(name.clone());
@@ -223,6 +239,33 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Reject a plugin declaring an action or function a loaded plugin already declares
+    ///
+    /// Function names are compared after applying each plugin's namespace.
+    fn check_conflicts(&self, plugin: &dyn RulePlugin) -> Result<()> {
+        let metadata = plugin.get_metadata();
+        let functions = qualified_functions(plugin);
+
+        for existing_plugin in self.load_order.iter().filter_map(|n| self.plugins.get(n)) {
+            let existing = existing_plugin.get_metadata();
+            let existing_functions = qualified_functions(existing_plugin.as_ref());
+            let conflict = metadata
+                .actions
+                .iter()
+                .find(|a| existing.actions.contains(a))
+                .or_else(|| functions.iter().find(|f| existing_functions.contains(f)));
+
+            if let Some(name) = conflict {
+                return Err(crate::errors::RuleEngineError::PluginConflict {
+                    name: name.clone(),
+                    existing_plugin: existing.name.clone(),
+                    new_plugin: metadata.name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Get plugin statistics
     pub fn get_stats(&self) -> PluginStats {
         let mut loaded_count = 0;
@@ -256,3 +299,15 @@ impl std::fmt::Display for PluginStats {
         )
     }
 }
+
+/// Function names a plugin declares, as registered with the engine
+fn qualified_functions(plugin: &dyn RulePlugin) -> Vec<String> {
+    let functions = &plugin.get_metadata().functions;
+    match plugin.namespace() {
+        Some(namespace) => functions
+            .iter()
+            .map(|f| format!("{}.{}", namespace, f))
+            .collect(),
+        None => functions.clone(),
+    }
+}
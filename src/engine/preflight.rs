@@ -0,0 +1,211 @@
+//! Pre-flight checks for rules
+//!
+//! [`RustRuleEngine::validate_rules`](crate::engine::RustRuleEngine::validate_rules)
+//! walks every rule once, without executing anything, and reports references
+//! that would only fail at runtime: functions that are not registered, custom
+//! actions without a handler and expressions that cannot be parsed.
+
+use crate::engine::rule::{ConditionExpression, ConditionGroup, Rule};
+use crate::expression::check_syntax;
+use crate::types::{ActionType, Value};
+use std::fmt;
+
+/// Problem found in a rule by a pre-flight check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// A condition calls a function that is not registered
+    UnknownFunction {
+        /// Rule containing the call
+        rule: String,
+        /// Function name
+        function: String,
+    },
+    /// A custom action has no registered handler
+    UnknownAction {
+        /// Rule containing the action
+        rule: String,
+        /// Action name
+        action: String,
+    },
+    /// An expression cannot be parsed
+    MalformedExpression {
+        /// Rule containing the expression
+        rule: String,
+        /// Expression text
+        expression: String,
+        /// Why the expression is malformed
+        reason: String,
+    },
+}
+
+impl ValidationIssue {
+    /// Name of the rule the issue was found in
+    pub fn rule(&self) -> &str {
+        match self {
+            ValidationIssue::UnknownFunction { rule, .. }
+            | ValidationIssue::UnknownAction { rule, .. }
+            | ValidationIssue::MalformedExpression { rule, .. } => rule,
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::UnknownFunction { rule, function } => {
+                write!(
+                    f,
+                    "rule '{}': function '{}' is not registered",
+                    rule, function
+                )
+            }
+            ValidationIssue::UnknownAction { rule, action } => {
+                write!(f, "rule '{}': no handler for action '{}'", rule, action)
+            }
+            ValidationIssue::MalformedExpression {
+                rule,
+                expression,
+                reason,
+            } => write!(
+                f,
+                "rule '{}': malformed expression '{}': {}",
+                rule, expression, reason
+            ),
+        }
+    }
+}
+
+/// Check one rule against the registered functions and action handlers
+pub(crate) fn validate_rule(
+    rule: &Rule,
+    has_function: &dyn Fn(&str) -> bool,
+    has_action: &dyn Fn(&str) -> bool,
+) -> Vec<ValidationIssue> {
+    let mut checker = RuleChecker {
+        rule,
+        has_function,
+        has_action,
+        issues: Vec::new(),
+    };
+    checker.check_group(&rule.conditions);
+    for action in &rule.actions {
+        checker.check_action(action);
+    }
+    checker.issues
+}
+
+struct RuleChecker<'a> {
+    rule: &'a Rule,
+    has_function: &'a dyn Fn(&str) -> bool,
+    has_action: &'a dyn Fn(&str) -> bool,
+    issues: Vec<ValidationIssue>,
+}
+
+impl RuleChecker<'_> {
+    fn check_group(&mut self, group: &ConditionGroup) {
+        match group {
+            ConditionGroup::Single(condition) => {
+                match &condition.expression {
+                    ConditionExpression::FunctionCall { name, .. } => self.check_function(name),
+                    // Arithmetic conditions are stored as a test whose name is the expression
+                    ConditionExpression::Test { name, args }
+                        if args.is_empty() && !is_identifier(name) =>
+                    {
+                        self.check_expression(name)
+                    }
+                    ConditionExpression::Test { name, .. } => self.check_function(name),
+                    ConditionExpression::Field(_)
+                    | ConditionExpression::MultiField { .. }
+                    | ConditionExpression::Bind { .. } => {}
+                }
+                self.check_value(&condition.value);
+            }
+            ConditionGroup::Compound { left, right, .. } => {
+                self.check_group(left);
+                self.check_group(right);
+            }
+            ConditionGroup::Not(inner)
+            | ConditionGroup::Exists(inner)
+            | ConditionGroup::Forall(inner) => self.check_group(inner),
+            _ => {}
+        }
+    }
+
+    fn check_action(&mut self, action: &ActionType) {
+        match action {
+            ActionType::Set { value, .. } | ActionType::Append { value, .. } => {
+                self.check_value(value)
+            }
+            ActionType::Emit { payload, .. } => {
+                for value in payload.values() {
+                    self.check_value(value);
+                }
+            }
+            ActionType::MethodCall { args, .. } => {
+                for arg in args {
+                    self.check_value(arg);
+                }
+            }
+            ActionType::CallAndBind { function, args, .. } => {
+                self.check_function(function);
+                for arg in args {
+                    self.check_value(arg);
+                }
+            }
+            ActionType::Custom {
+                action_type,
+                params,
+            } => {
+                if !(self.has_action)(action_type) {
+                    self.issues.push(ValidationIssue::UnknownAction {
+                        rule: self.rule.name.clone(),
+                        action: action_type.clone(),
+                    });
+                }
+                for value in params.values() {
+                    self.check_value(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_function(&mut self, name: &str) {
+        if !(self.has_function)(name) {
+            self.issues.push(ValidationIssue::UnknownFunction {
+                rule: self.rule.name.clone(),
+                function: name.to_string(),
+            });
+        }
+    }
+
+    fn check_value(&mut self, value: &Value) {
+        match value {
+            Value::Expression(expr) => self.check_expression(expr),
+            Value::Array(items) => {
+                for item in items {
+                    self.check_value(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_expression(&mut self, expr: &str) {
+        if let Err(reason) = check_syntax(expr) {
+            self.issues.push(ValidationIssue::MalformedExpression {
+                rule: self.rule.name.clone(),
+                expression: expr.to_string(),
+                reason,
+            });
+        }
+    }
+}
+
+/// Function name, optionally qualified with a plugin namespace
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+}
@@ -0,0 +1,143 @@
+//! Provenance of fact values
+//!
+//! With provenance enabled, every fact written by a rule remembers which rule
+//! wrote it and how the facts that rule read got their values, recursively.
+//! This answers audit questions such as "why is `Order.Discount` 0.15?":
+//!
+//! ```text
+//! Order.Discount = 0.15  <- rule "GoldDiscount"
+//!   Customer.Tier = "gold"  <- rule "PromoteToGold"
+//!     Customer.Spend = 1200  <- rule "TotalSpend"
+//! ```
+
+use crate::engine::dependency::DependencyAnalyzer;
+use crate::engine::facts::Facts;
+use crate::engine::rule::{ConditionGroup, Rule};
+use crate::types::{ActionType, Value};
+use std::collections::HashMap;
+
+/// How a fact came to hold its value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvenanceChain {
+    /// Fact path that was written
+    pub fact: String,
+    /// Value written by the rule
+    pub value: Value,
+    /// Rule that wrote the value
+    pub rule: String,
+    /// Provenance of the rule-derived facts the rule read when it fired
+    ///
+    /// Facts supplied by the caller have no provenance and do not appear here.
+    pub causes: Vec<ProvenanceChain>,
+}
+
+impl ProvenanceChain {
+    /// Every rule in the chain, causes first, without duplicates
+    pub fn rules(&self) -> Vec<String> {
+        let mut rules = Vec::new();
+        self.collect_rules(&mut rules);
+        rules
+    }
+
+    fn collect_rules(&self, rules: &mut Vec<String>) {
+        for cause in &self.causes {
+            cause.collect_rules(rules);
+        }
+        if !rules.contains(&self.rule) {
+            rules.push(self.rule.clone());
+        }
+    }
+
+    /// Depth of the chain; a fact derived only from input facts has depth 1
+    pub fn depth(&self) -> usize {
+        1 + self.causes.iter().map(|c| c.depth()).max().unwrap_or(0)
+    }
+}
+
+/// Latest provenance per written fact path
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProvenanceTracker {
+    chains: HashMap<String, ProvenanceChain>,
+}
+
+impl ProvenanceTracker {
+    pub(crate) fn clear(&mut self) {
+        self.chains.clear();
+    }
+
+    pub(crate) fn get(&self, fact: &str) -> Option<&ProvenanceChain> {
+        self.chains.get(fact)
+    }
+
+    /// Record the writes of a rule that just fired
+    ///
+    /// Causes are resolved against the provenance recorded before this rule
+    /// fired, so a rule that reads and writes the same fact links to the
+    /// previous write rather than to itself.
+    pub(crate) fn record_rule(&mut self, rule: &Rule, facts: &Facts) {
+        let causes: Vec<ProvenanceChain> = rule_reads(&rule.conditions)
+            .iter()
+            .filter_map(|read| self.chains.get(read).cloned())
+            .collect();
+
+        for fact in rule_writes(&rule.actions) {
+            let value = facts
+                .get_nested(&fact)
+                .or_else(|| facts.get(&fact))
+                .unwrap_or(Value::Null);
+            let chain = ProvenanceChain {
+                fact: fact.clone(),
+                value,
+                rule: rule.name.clone(),
+                causes: causes.clone(),
+            };
+            self.chains.insert(fact, chain);
+        }
+    }
+}
+
+/// Fact paths read by conditions, including dotted right-hand side references
+pub(crate) fn rule_reads(conditions: &ConditionGroup) -> Vec<String> {
+    let mut reads = Vec::new();
+    DependencyAnalyzer::extract_fields_from_condition_group(conditions, &mut reads);
+    collect_value_reads(conditions, &mut reads);
+    reads.sort();
+    reads.dedup();
+    reads
+}
+
+fn collect_value_reads(group: &ConditionGroup, reads: &mut Vec<String>) {
+    match group {
+        ConditionGroup::Single(condition) => {
+            if let Value::String(path) | Value::Expression(path) = &condition.value {
+                if path.contains('.') && !path.contains(' ') {
+                    reads.push(path.clone());
+                }
+            }
+        }
+        ConditionGroup::Compound { left, right, .. } => {
+            collect_value_reads(left, reads);
+            collect_value_reads(right, reads);
+        }
+        ConditionGroup::Not(inner)
+        | ConditionGroup::Exists(inner)
+        | ConditionGroup::Forall(inner) => collect_value_reads(inner, reads),
+        _ => {}
+    }
+}
+
+/// Fact paths written by actions
+pub(crate) fn rule_writes(actions: &[ActionType]) -> Vec<String> {
+    let mut writes: Vec<String> = Vec::new();
+    for action in actions {
+        if let ActionType::Set { field, .. }
+        | ActionType::Append { field, .. }
+        | ActionType::CallAndBind { field, .. } = action
+        {
+            if !writes.contains(field) {
+                writes.push(field.clone());
+            }
+        }
+    }
+    writes
+}
@@ -4,8 +4,6 @@
 use crate::types::{ActionType, LogicalOperator, Operator, Value};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
-
-#[cfg(feature = "streaming")]
 use std::time::Duration;
 
 /// Window specification for stream patterns
@@ -31,7 +29,7 @@ pub enum StreamWindowType {
 }
 
 /// Expression in a condition - can be a field reference or function call
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ConditionExpression {
     /// Direct field reference (e.g., User.age)
     Field(String),
@@ -66,10 +64,20 @@ pub enum ConditionExpression {
         /// Optional variable for binding (e.g., "$?all_items")
         variable: Option<String>,
     },
+    /// Variable binding (e.g., `$age : User.Age`)
+    ///
+    /// Matches when the field exists and stores its value under the variable,
+    /// so later conditions and the rule's actions can refer to `$age`.
+    Bind {
+        /// Variable name, including the leading `$`
+        variable: String,
+        /// Field whose value is bound
+        field: String,
+    },
 }
 
 /// Represents a single condition in a rule
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Condition {
     /// The expression to evaluate (field or function call)
     pub expression: ConditionExpression,
@@ -127,6 +135,23 @@ impl Condition {
         }
     }
 
+    /// Create a binding condition: `$age : User.Age`
+    ///
+    /// Bindings are rule-local. They are visible to conditions to the right of
+    /// the binding and to the rule's actions, and are cleared after every
+    /// evaluation of the rule.
+    pub fn with_bind(variable: String, field: String) -> Self {
+        Self {
+            expression: ConditionExpression::Bind {
+                variable: variable.clone(),
+                field: field.clone(),
+            },
+            operator: Operator::Equal,   // Not used for bindings
+            value: Value::Boolean(true), // Not used for bindings
+            field: variable,             // For backward compat
+        }
+    }
+
     /// Create multi-field collect condition
     /// Example: Order.items $?all_items
     pub fn with_multifield_collect(field: String, variable: String) -> Self {
@@ -228,6 +253,10 @@ impl Condition {
 
                 self.operator.evaluate(&field_value, &self.value)
             }
+            ConditionExpression::Bind { field, .. } => {
+                // Without an engine there is nowhere to store the value
+                get_nested_value(facts, field).is_some_and(|value| *value != Value::Null)
+            }
             ConditionExpression::FunctionCall { .. }
             | ConditionExpression::Test { .. }
             | ConditionExpression::MultiField { .. } => {
@@ -261,6 +290,9 @@ impl Condition {
 
                 self.operator.evaluate(&field_value, &self.value)
             }
+            ConditionExpression::Bind { field, .. } => {
+                get_nested_value(facts, field).is_some_and(|value| *value != Value::Null)
+            }
             ConditionExpression::FunctionCall { name, args } => {
                 // Call the function with arguments
                 if let Some(function) = function_registry.get(name) {
@@ -386,10 +418,12 @@ impl Condition {
 }
 
 /// Group of conditions with logical operators
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ConditionGroup {
     /// A single condition
     Single(Condition),
+    /// A condition that always (`true`) or never (`false`) holds
+    Constant(bool),
     /// A compound condition with two sub-conditions and a logical operator
     Compound {
         /// The left side condition
@@ -460,6 +494,132 @@ impl ConditionGroup {
         }
     }
 
+    /// Create a compound condition using logical XOR operator
+    pub fn xor(left: ConditionGroup, right: ConditionGroup) -> Self {
+        ConditionGroup::Compound {
+            left: Box::new(left),
+            operator: LogicalOperator::Xor,
+            right: Box::new(right),
+        }
+    }
+
+    /// Number of leaf conditions in this group (rule specificity)
+    pub fn condition_count(&self) -> usize {
+        match self {
+            ConditionGroup::Compound { left, right, .. } => {
+                left.condition_count() + right.condition_count()
+            }
+            ConditionGroup::Not(inner)
+            | ConditionGroup::Exists(inner)
+            | ConditionGroup::Forall(inner) => inner.condition_count(),
+            ConditionGroup::Single(_) | ConditionGroup::Accumulate { .. } => 1,
+            ConditionGroup::Constant(_) => 0,
+            #[cfg(feature = "streaming")]
+            ConditionGroup::StreamPattern { .. } => 1,
+        }
+    }
+
+    /// Variables bound by `$name : Field` conditions in this group
+    pub fn bound_variables(&self) -> Vec<&str> {
+        match self {
+            ConditionGroup::Single(condition) => match &condition.expression {
+                ConditionExpression::Bind { variable, .. } => vec![variable.as_str()],
+                _ => Vec::new(),
+            },
+            ConditionGroup::Compound { left, right, .. } => {
+                let mut variables = left.bound_variables();
+                variables.extend(right.bound_variables());
+                variables
+            }
+            ConditionGroup::Not(inner)
+            | ConditionGroup::Exists(inner)
+            | ConditionGroup::Forall(inner) => inner.bound_variables(),
+            ConditionGroup::Constant(_) | ConditionGroup::Accumulate { .. } => Vec::new(),
+            #[cfg(feature = "streaming")]
+            ConditionGroup::StreamPattern { .. } => Vec::new(),
+        }
+    }
+
+    /// Rewrite the group into an equivalent, smaller one
+    ///
+    /// Constant operands are folded (`true && X` becomes `X`, `false && X`
+    /// becomes `false`), nested groups with the same operator are flattened,
+    /// repeated operands of AND and OR are dropped and double negations are
+    /// removed. Operand order is kept, so variable bindings still run before
+    /// the conditions that use them.
+    pub fn simplify(self) -> ConditionGroup {
+        match self {
+            ConditionGroup::Compound {
+                left,
+                operator: operator @ (LogicalOperator::And | LogicalOperator::Or),
+                right,
+            } => {
+                // The value that decides the whole group on its own
+                let absorbing = operator == LogicalOperator::Or;
+                let mut operands = Vec::new();
+                let mut pending = vec![*right, *left];
+                while let Some(group) = pending.pop() {
+                    match group.simplify() {
+                        ConditionGroup::Compound {
+                            left,
+                            operator: inner,
+                            right,
+                        } if inner == operator => {
+                            pending.push(*right);
+                            pending.push(*left);
+                        }
+                        ConditionGroup::Constant(value) if value == absorbing => {
+                            return ConditionGroup::Constant(absorbing);
+                        }
+                        ConditionGroup::Constant(_) => {}
+                        operand if !operands.contains(&operand) => operands.push(operand),
+                        _ => {}
+                    }
+                }
+
+                operands
+                    .into_iter()
+                    .reduce(|left, right| ConditionGroup::Compound {
+                        left: Box::new(left),
+                        operator: operator.clone(),
+                        right: Box::new(right),
+                    })
+                    .unwrap_or(ConditionGroup::Constant(!absorbing))
+            }
+            ConditionGroup::Compound {
+                left,
+                operator: LogicalOperator::Xor,
+                right,
+            } => match (left.simplify(), right.simplify()) {
+                (ConditionGroup::Constant(a), ConditionGroup::Constant(b)) => {
+                    ConditionGroup::Constant(a != b)
+                }
+                (ConditionGroup::Constant(flip), other)
+                | (other, ConditionGroup::Constant(flip)) => {
+                    if flip {
+                        ConditionGroup::Not(Box::new(other)).simplify()
+                    } else {
+                        other
+                    }
+                }
+                (left, right) => ConditionGroup::xor(left, right),
+            },
+            ConditionGroup::Compound {
+                left,
+                operator: LogicalOperator::Not,
+                ..
+            } => ConditionGroup::Not(left).simplify(),
+            ConditionGroup::Not(inner) => match inner.simplify() {
+                ConditionGroup::Constant(value) => ConditionGroup::Constant(!value),
+                ConditionGroup::Not(inner) => *inner,
+                inner => ConditionGroup::Not(Box::new(inner)),
+            },
+            ConditionGroup::Exists(inner) => ConditionGroup::Exists(Box::new(inner.simplify())),
+            ConditionGroup::Forall(inner) => ConditionGroup::Forall(Box::new(inner.simplify())),
+            other => other,
+        }
+    }
+
     /// Create a negated condition using logical NOT operator
     #[allow(clippy::should_implement_trait)]
     pub fn not(condition: ConditionGroup) -> Self {
@@ -515,6 +675,7 @@ impl ConditionGroup {
     pub fn evaluate(&self, facts: &HashMap<String, Value>) -> bool {
         match self {
             ConditionGroup::Single(condition) => condition.evaluate(facts),
+            ConditionGroup::Constant(value) => *value,
             ConditionGroup::Compound {
                 left,
                 operator,
@@ -525,6 +686,7 @@ impl ConditionGroup {
                 match operator {
                     LogicalOperator::And => left_result && right_result,
                     LogicalOperator::Or => left_result || right_result,
+                    LogicalOperator::Xor => left_result != right_result,
                     LogicalOperator::Not => !left_result, // For Not, we ignore right side
                 }
             }
@@ -554,6 +716,7 @@ impl ConditionGroup {
                 let fact_map = facts.get_all_facts();
                 condition.evaluate(&fact_map)
             }
+            ConditionGroup::Constant(value) => *value,
             ConditionGroup::Compound {
                 left,
                 operator,
@@ -564,6 +727,7 @@ impl ConditionGroup {
                 match operator {
                     LogicalOperator::And => left_result && right_result,
                     LogicalOperator::Or => left_result || right_result,
+                    LogicalOperator::Xor => left_result != right_result,
                     LogicalOperator::Not => !left_result,
                 }
             }
@@ -587,6 +751,22 @@ impl ConditionGroup {
     }
 }
 
+/// How the salience (priority) of a rule is determined
+#[derive(Debug, Clone, PartialEq)]
+pub enum Salience {
+    /// Fixed priority parsed at load time
+    Static(i32),
+    /// Arithmetic expression over facts (e.g. `Order.Total / 100`),
+    /// re-evaluated at the start of every cycle
+    Dynamic(String),
+}
+
+impl Default for Salience {
+    fn default() -> Self {
+        Salience::Static(0)
+    }
+}
+
 /// A rule with conditions and actions
 #[derive(Debug, Clone)]
 pub struct Rule {
@@ -596,12 +776,18 @@ pub struct Rule {
     pub description: Option<String>,
     /// Priority of the rule (higher values execute first)
     pub salience: i32,
+    /// Source of the rule's salience; `salience` mirrors the static value
+    pub salience_source: Salience,
     /// Whether the rule is enabled for execution
     pub enabled: bool,
     /// Prevents the rule from activating itself in the same cycle
     pub no_loop: bool,
     /// Prevents the rule from firing again until agenda group changes
     pub lock_on_active: bool,
+    /// Maximum number of times the rule may fire in a single run
+    pub max_fires: Option<usize>,
+    /// Time the rule may spend on its conditions and actions before it is skipped
+    pub timeout: Option<Duration>,
     /// Agenda group this rule belongs to (for workflow control)
     pub agenda_group: Option<String>,
     /// Activation group - only one rule in group can fire
@@ -610,6 +796,10 @@ pub struct Rule {
     pub date_effective: Option<DateTime<Utc>>,
     /// Rule expires after this date
     pub date_expires: Option<DateTime<Utc>>,
+    /// Free-form labels for selecting subsets of rules (see `execute_tagged`)
+    pub tags: Vec<String>,
+    /// Key/value annotations for tooling, from `@metadata(key="value")`
+    pub metadata: HashMap<String, String>,
     /// The conditions that must be met for the rule to fire
     pub conditions: ConditionGroup,
     /// The actions to execute when the rule fires
@@ -623,36 +813,95 @@ impl Rule {
             name,
             description: None,
             salience: 0,
+            salience_source: Salience::default(),
             enabled: true,
             no_loop: false,
             lock_on_active: false,
+            max_fires: None,
+            timeout: None,
             agenda_group: None,
             activation_group: None,
             date_effective: None,
             date_expires: None,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
             conditions,
             actions,
         }
     }
 
+    /// Set the rule's tags
+    pub fn with_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Check whether the rule carries at least one of `tags`
+    pub fn has_any_tag(&self, tags: &[&str]) -> bool {
+        self.tags.iter().any(|tag| tags.contains(&tag.as_str()))
+    }
+
     /// Add a description to the rule
     pub fn with_description(mut self, description: String) -> Self {
         self.description = Some(description);
         self
     }
 
+    /// Add a metadata annotation to the rule
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
     /// Set the salience (priority) of the rule
     pub fn with_salience(mut self, salience: i32) -> Self {
         self.salience = salience;
+        self.salience_source = Salience::Static(salience);
         self
     }
 
     /// Set the priority of the rule (alias for salience)
-    pub fn with_priority(mut self, priority: i32) -> Self {
-        self.salience = priority;
+    pub fn with_priority(self, priority: i32) -> Self {
+        self.with_salience(priority)
+    }
+
+    /// Use an expression over facts as the rule's salience
+    ///
+    /// The expression is re-evaluated at the start of every cycle and the
+    /// agenda is re-sorted, so higher-valued facts can be prioritized.
+    pub fn with_dynamic_salience<S: Into<String>>(mut self, expression: S) -> Self {
+        self.salience = 0;
+        self.salience_source = Salience::Dynamic(expression.into());
         self
     }
 
+    /// Check whether the rule's salience depends on facts
+    pub fn has_dynamic_salience(&self) -> bool {
+        matches!(self.salience_source, Salience::Dynamic(_))
+    }
+
+    /// Resolve the salience of the rule against the current facts
+    ///
+    /// Dynamic salience is truncated to `i32`; if the expression cannot be
+    /// evaluated the static `salience` value is used instead.
+    pub fn effective_salience(&self, facts: &crate::engine::facts::Facts) -> i32 {
+        match &self.salience_source {
+            // `salience` is authoritative for static rules, even if set directly
+            Salience::Static(_) => self.salience,
+            Salience::Dynamic(expression) => {
+                crate::expression::evaluate_expression(expression, facts)
+                    .ok()
+                    .and_then(|value| value.to_number())
+                    .map(|n| n as i32)
+                    .unwrap_or(self.salience)
+            }
+        }
+    }
+
     /// Enable or disable no-loop behavior for this rule
     pub fn with_no_loop(mut self, no_loop: bool) -> Self {
         self.no_loop = no_loop;
@@ -665,6 +914,18 @@ impl Rule {
         self
     }
 
+    /// Cap how many times this rule may fire in a single run
+    pub fn with_max_fires(mut self, max_fires: usize) -> Self {
+        self.max_fires = Some(max_fires);
+        self
+    }
+
+    /// Skip this rule when evaluating and firing it takes longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Set the agenda group for this rule
     pub fn with_agenda_group(mut self, agenda_group: String) -> Self {
         self.agenda_group = Some(agenda_group);
@@ -0,0 +1,118 @@
+//! Typed fact schemas
+//!
+//! A [`FactSchema`] declares the expected [`ValueKind`] of fields on one
+//! object fact. Once registered with [`Facts::register_schema`], writes through
+//! `add_value`, `set_nested` and `set_many` are checked against it, so a
+//! `User.Age` accidentally stored as a string is rejected up front instead of
+//! making numeric rules silently miss.
+//!
+//! [`Facts::register_schema`]: crate::engine::facts::Facts::register_schema
+
+use crate::errors::{Result, RuleEngineError};
+use crate::types::Value;
+use std::collections::HashMap;
+
+/// Expected kind of a fact field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// `Value::String`
+    String,
+    /// Any numeric value (`Value::Number` or `Value::Integer`)
+    Number,
+    /// `Value::Integer` only
+    Integer,
+    /// `Value::Boolean`
+    Boolean,
+    /// `Value::Array`
+    Array,
+    /// `Value::Object`
+    Object,
+    /// Any value, including null
+    Any,
+}
+
+impl ValueKind {
+    /// Check whether a value is of this kind
+    pub fn matches(&self, value: &Value) -> bool {
+        match self {
+            ValueKind::String => matches!(value, Value::String(_)),
+            ValueKind::Number => matches!(value, Value::Number(_) | Value::Integer(_)),
+            ValueKind::Integer => matches!(value, Value::Integer(_)),
+            ValueKind::Boolean => matches!(value, Value::Boolean(_)),
+            ValueKind::Array => matches!(value, Value::Array(_)),
+            ValueKind::Object => matches!(value, Value::Object(_)),
+            ValueKind::Any => true,
+        }
+    }
+}
+
+/// Field declarations for one object fact type
+#[derive(Debug, Clone, Default)]
+pub struct FactSchema {
+    fields: HashMap<String, (ValueKind, bool)>,
+}
+
+impl FactSchema {
+    /// Create an empty schema
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare an optional field; it is only checked when present
+    pub fn with_field(mut self, name: &str, kind: ValueKind) -> Self {
+        self.fields.insert(name.to_string(), (kind, false));
+        self
+    }
+
+    /// Declare a field that must be present whenever the whole object is set
+    pub fn with_required_field(mut self, name: &str, kind: ValueKind) -> Self {
+        self.fields.insert(name.to_string(), (kind, true));
+        self
+    }
+
+    /// Expected kind of a declared field
+    pub fn field_kind(&self, name: &str) -> Option<ValueKind> {
+        self.fields.get(name).map(|(kind, _)| *kind)
+    }
+
+    /// Validate a complete object value for `fact`
+    ///
+    /// Undeclared fields are allowed and left untouched.
+    pub fn validate(&self, fact: &str, value: &Value) -> Result<()> {
+        let Value::Object(obj) = value else {
+            return Err(violation(fact, "", ValueKind::Object, value));
+        };
+
+        for (name, (kind, required)) in &self.fields {
+            match obj.get(name) {
+                Some(field_value) => self.validate_field(fact, name, field_value)?,
+                None if *required => {
+                    return Err(RuleEngineError::SchemaViolation {
+                        fact: fact.to_string(),
+                        field: name.clone(),
+                        message: format!("required {:?} field is missing", kind),
+                    })
+                }
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate a single field write; undeclared fields always pass
+    pub fn validate_field(&self, fact: &str, field: &str, value: &Value) -> Result<()> {
+        match self.field_kind(field) {
+            Some(kind) if !kind.matches(value) => Err(violation(fact, field, kind, value)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn violation(fact: &str, field: &str, expected: ValueKind, value: &Value) -> RuleEngineError {
+    RuleEngineError::SchemaViolation {
+        fact: fact.to_string(),
+        field: field.to_string(),
+        message: format!("expected {:?}, got {:?}", expected, value),
+    }
+}
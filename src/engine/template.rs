@@ -252,10 +252,12 @@ impl TemplateManager {
 
     /// Load templates from JSON file
     pub fn load_from_json(&mut self, json_content: &str) -> Result<()> {
-        let templates: Vec<RuleTemplate> =
-            serde_json::from_str(json_content).map_err(|e| RuleEngineError::ParseError {
-                message: format!("Failed to parse template JSON: {}", e),
-            })?;
+        let templates: Vec<RuleTemplate> = serde_json::from_str(json_content).map_err(|e| {
+            RuleEngineError::ParseErrorWithSource {
+                message: "Failed to parse template JSON".to_string(),
+                source: Box::new(e),
+            }
+        })?;
 
         for template in templates {
             self.register_template(template);
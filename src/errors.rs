@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Error types that can occur in the rule engine
@@ -10,6 +11,27 @@ pub enum RuleEngineError {
         message: String,
     },
 
+    /// Parse error at a known position in the GRL source
+    #[error("Parse error at line {line}, col {column}: {message}")]
+    ParseErrorAt {
+        /// Error message
+        message: String,
+        /// 1-based line number
+        line: usize,
+        /// 1-based column, counted in characters
+        column: usize,
+    },
+
+    /// Parse error caused by an underlying error, such as malformed JSON
+    #[error("Parse error: {message}: {source}")]
+    ParseErrorWithSource {
+        /// Error message
+        message: String,
+        /// Underlying error
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
     /// Error during rule evaluation
     #[error("Evaluation error: {message}")]
     EvaluationError {
@@ -28,6 +50,16 @@ pub enum RuleEngineError {
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
+    /// IO error on a specific file
+    #[error("IO error on {}: {source}", path.display())]
+    Io {
+        /// File that could not be read or written
+        path: PathBuf,
+        /// Underlying IO error
+        #[source]
+        source: std::io::Error,
+    },
+
     /// Type mismatch error
     #[error("Type mismatch: expected {expected}, got {actual}")]
     TypeMismatch {
@@ -83,6 +115,17 @@ pub enum RuleEngineError {
         message: String,
     },
 
+    /// Two plugins declare the same action or function name
+    #[error("Plugin conflict: '{name}' is already provided by plugin '{existing_plugin}', cannot load '{new_plugin}'")]
+    PluginConflict {
+        /// Conflicting action or function name
+        name: String,
+        /// Plugin that already provides the name
+        existing_plugin: String,
+        /// Plugin being loaded
+        new_plugin: String,
+    },
+
     /// Feature not enabled error
     #[error("Feature not enabled: {feature} - {message}")]
     FeatureNotEnabled {
@@ -98,6 +141,34 @@ pub enum RuleEngineError {
         /// Error message
         message: String,
     },
+
+    /// A run executed more actions than `EngineConfig::max_actions_per_run`
+    #[error("Action limit exceeded: more than {limit} actions in one run")]
+    ActionLimitExceeded {
+        /// Configured maximum
+        limit: usize,
+    },
+
+    /// Fact write rejected by a registered schema
+    #[error("Schema violation on {fact}.{field}: {message}")]
+    SchemaViolation {
+        /// Fact (object type) name
+        fact: String,
+        /// Offending field (empty when the whole value has the wrong shape)
+        field: String,
+        /// Error message
+        message: String,
+    },
+}
+
+impl RuleEngineError {
+    /// Wrap an IO error with the path of the file it concerns
+    pub fn io(path: impl AsRef<Path>, source: std::io::Error) -> Self {
+        RuleEngineError::Io {
+            path: path.as_ref().to_path_buf(),
+            source,
+        }
+    }
 }
 
 /// Convenient Result type alias for rule engine operations
@@ -1,11 +1,14 @@
 //! Expression Evaluator
 //!
 //! This module provides runtime evaluation of arithmetic expressions
-//! similar to CLIPS (bind ?total (* ?quantity ?price))
+//! similar to CLIPS (bind ?total (* ?quantity ?price)), plus conditional
+//! expressions such as `Customer.vip ? 0.2 : 0.05`.
 
+use crate::engine::clock;
 use crate::engine::facts::Facts;
 use crate::errors::{Result, RuleEngineError};
-use crate::types::Value;
+use crate::types::{Operator, Value};
+use chrono::{Local, NaiveTime};
 
 /// Evaluate an arithmetic expression with field references
 /// Example: "Order.quantity * Order.price" with facts containing Order.quantity=10, Order.price=100
@@ -13,6 +16,20 @@ use crate::types::Value;
 pub fn evaluate_expression(expr: &str, facts: &Facts) -> Result<Value> {
     let expr = expr.trim();
 
+    // Conditional binds loosest; only the chosen branch is evaluated
+    if let Some((condition, then_branch, else_branch)) = split_ternary(expr) {
+        let branch = if evaluate_condition(condition, facts)? {
+            then_branch
+        } else {
+            else_branch
+        };
+        return evaluate_expression(branch, facts);
+    }
+
+    if expr.starts_with('(') && expr.ends_with(')') && top_level_chars(expr).is_empty() {
+        return evaluate_expression(&expr[1..expr.len() - 1], facts);
+    }
+
     // Try to evaluate as simple arithmetic expression
     // Support: +, -, *, /, %
 
@@ -55,39 +72,291 @@ pub fn evaluate_expression(expr: &str, facts: &Facts) -> Result<Value> {
         return Ok(Value::Number(float_val));
     }
 
+    #[cfg(feature = "decimal")]
+    if let Some(decimal) = Value::parse_decimal(expr) {
+        return Ok(Value::Decimal(decimal));
+    }
+
+    if expr.len() >= 2
+        && (expr.starts_with('"') || expr.starts_with('\''))
+        && top_level_chars(expr).is_empty()
+    {
+        return Ok(Value::String(expr[1..expr.len() - 1].to_string()));
+    }
+
+    match expr {
+        "true" => return Ok(Value::Boolean(true)),
+        "false" => return Ok(Value::Boolean(false)),
+        "null" => return Ok(Value::Null),
+        "now()" => return Ok(Value::DateTime(clock::now())),
+        "today()" => {
+            return Ok(Value::DateTime(
+                clock::now()
+                    .with_timezone(&Local)
+                    .date_naive()
+                    .and_time(NaiveTime::MIN)
+                    .and_utc(),
+            ))
+        }
+        _ => {}
+    }
+
+    // Duration literal: 30m, 24h, 1h30m
+    if let Some(duration) = Value::parse_duration(expr) {
+        return Ok(Value::Duration(duration));
+    }
+
     // Must be a field reference - get from facts
     if let Some(value) = facts.get(expr) {
         return Ok(value.clone());
     }
 
+    if let Some(value) = facts.get_nested(expr) {
+        return Ok(value);
+    }
+
+    // Safe navigation (Order?.Customer?.Email) short-circuits to null
+    if expr.contains("?.") {
+        return Ok(Value::Null);
+    }
+
     // Field not found - return error
     Err(RuleEngineError::EvaluationError {
         message: format!("Field '{}' not found in facts", expr),
     })
 }
 
-/// Find position of operator, skipping parentheses
+/// Check that an expression is well formed without evaluating it
+///
+/// Catches unbalanced parentheses or quotes and operators missing an operand,
+/// such as `Order.total *` or `a + * b`. Field references are not resolved.
+pub(crate) fn check_syntax(expr: &str) -> std::result::Result<(), String> {
+    if expr.trim().is_empty() {
+        return Err("empty expression".to_string());
+    }
+
+    let chars: Vec<char> = expr.chars().collect();
+    let mut depth = 0usize;
+    let mut expect_operand = true;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        match ch {
+            _ if ch.is_whitespace() => {}
+            '"' | '\'' => {
+                i += 1;
+                while i < chars.len() && chars[i] != ch {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err("unterminated string literal".to_string());
+                }
+                expect_operand = false;
+            }
+            '(' | '[' => {
+                depth += 1;
+                expect_operand = true;
+            }
+            ')' | ']' => {
+                let empty = matches!(
+                    chars[..i].iter().rev().find(|c| !c.is_whitespace()),
+                    Some('(' | '[')
+                );
+                if expect_operand && !empty {
+                    return Err(format!("missing operand before '{}'", ch));
+                }
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| format!("unbalanced '{}'", ch))?;
+                expect_operand = false;
+            }
+            ',' if depth > 0 => {
+                if expect_operand {
+                    return Err("missing argument before ','".to_string());
+                }
+                expect_operand = true;
+            }
+            // Globals (?*MAX*), safe navigation (a?.b) and variables (?x) are operands
+            '?' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && chars[i] != '*' {
+                    i += 1;
+                }
+                expect_operand = false;
+            }
+            '?' if !chars.get(i + 1).is_some_and(|c| c.is_whitespace()) => {}
+            '+' | '-' | '!' if expect_operand => {}
+            '+' | '-' | '*' | '/' | '%' | '?' | ':' | '=' | '<' | '>' | '&' | '|' | '!' => {
+                if expect_operand {
+                    return Err(format!("missing operand before '{}'", ch));
+                }
+                // Multi-character operators such as >=, == and &&
+                while chars
+                    .get(i + 1)
+                    .is_some_and(|c| matches!(c, '=' | '&' | '|'))
+                {
+                    i += 1;
+                }
+                expect_operand = true;
+            }
+            _ => expect_operand = false,
+        }
+        i += 1;
+    }
+
+    if depth > 0 {
+        return Err("unbalanced '('".to_string());
+    }
+    if expect_operand {
+        return Err("expression ends with an operator".to_string());
+    }
+    Ok(())
+}
+
+/// Find position of operator, skipping parentheses and string literals
 /// Returns rightmost occurrence for left-to-right evaluation
 fn find_operator(expr: &str, operators: &[char]) -> Option<usize> {
-    let mut paren_depth = 0;
-    let mut last_pos = None;
+    top_level_chars(expr)
+        .into_iter()
+        .rev()
+        .find(|(_, ch)| operators.contains(ch))
+        .map(|(pos, _)| pos)
+}
 
-    for (i, ch) in expr.chars().enumerate() {
-        match ch {
-            '(' => paren_depth += 1,
-            ')' => paren_depth -= 1,
-            _ if paren_depth == 0 && operators.contains(&ch) => {
-                last_pos = Some(i);
+/// Characters outside parentheses, array indices and string literals, with
+/// their byte offsets
+fn top_level_chars(expr: &str) -> Vec<(usize, char)> {
+    let mut chars = Vec::new();
+    let mut paren_depth = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    for (i, ch) in expr.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == q {
+                quote = None;
             }
+            continue;
+        }
+        match ch {
+            '"' | '\'' => quote = Some(ch),
+            '(' | '[' => paren_depth += 1,
+            ')' | ']' => paren_depth -= 1,
+            _ if paren_depth == 0 => chars.push((i, ch)),
             _ => {}
         }
     }
 
-    last_pos
+    chars
+}
+
+/// Split `cond ? a : b` into its condition and branches
+///
+/// A `?` only starts a conditional when followed by whitespace, which keeps
+/// safe navigation (`a?.b`), globals (`?*X*`) and variables (`?x`) intact.
+/// Conditionals nest to the right: `a ? b : c ? d : e`.
+pub(crate) fn split_ternary(expr: &str) -> Option<(&str, &str, &str)> {
+    let chars = top_level_chars(expr);
+    let is_question =
+        |(pos, ch): (usize, char)| ch == '?' && expr[pos + 1..].starts_with(char::is_whitespace);
+
+    let question = chars.iter().position(|&c| is_question(c))?;
+    let mut depth = 0;
+    for &(pos, ch) in &chars[question + 1..] {
+        if is_question((pos, ch)) {
+            depth += 1;
+        } else if ch == ':' {
+            if depth == 0 {
+                let q = chars[question].0;
+                return Some((
+                    expr[..q].trim(),
+                    expr[q + 1..pos].trim(),
+                    expr[pos + 1..].trim(),
+                ));
+            }
+            depth -= 1;
+        }
+    }
+
+    None
+}
+
+/// Evaluate the condition of a conditional expression
+///
+/// Supports `||`, `&&`, `!`, comparisons and any expression whose value is
+/// truthy.
+fn evaluate_condition(expr: &str, facts: &Facts) -> Result<bool> {
+    let expr = expr.trim();
+
+    if let Some(pos) = find_top_level(expr, "||") {
+        return Ok(evaluate_condition(&expr[..pos], facts)?
+            || evaluate_condition(&expr[pos + 2..], facts)?);
+    }
+    if let Some(pos) = find_top_level(expr, "&&") {
+        return Ok(evaluate_condition(&expr[..pos], facts)?
+            && evaluate_condition(&expr[pos + 2..], facts)?);
+    }
+
+    // Two-character operators first so `>=` is not read as `>`
+    let comparisons = [
+        ("==", Operator::Equal),
+        ("!=", Operator::NotEqual),
+        (">=", Operator::GreaterThanOrEqual),
+        ("<=", Operator::LessThanOrEqual),
+        (">", Operator::GreaterThan),
+        ("<", Operator::LessThan),
+    ];
+    for (symbol, operator) in comparisons {
+        if let Some(pos) = find_top_level(expr, symbol) {
+            let left = evaluate_expression(&expr[..pos], facts)?;
+            let right = evaluate_expression(&expr[pos + symbol.len()..], facts)?;
+            return Ok(operator.evaluate(&left, &right));
+        }
+    }
+
+    if expr.starts_with('(') && expr.ends_with(')') && top_level_chars(expr).is_empty() {
+        return evaluate_condition(&expr[1..expr.len() - 1], facts);
+    }
+    if let Some(inner) = expr.strip_prefix('!') {
+        return Ok(!evaluate_condition(inner, facts)?);
+    }
+
+    Ok(evaluate_expression(expr, facts)?.to_bool())
+}
+
+/// Leftmost occurrence of `pattern` outside parentheses and string literals
+fn find_top_level(expr: &str, pattern: &str) -> Option<usize> {
+    top_level_chars(expr)
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .find(|&pos| expr[pos..].starts_with(pattern))
 }
 
 /// Apply arithmetic operator to two values
+///
+/// `+` concatenates when either operand is a string.
 fn apply_operator(left: &Value, op: &str, right: &Value) -> Result<Value> {
+    if let Some(result) = apply_temporal_operator(left, op, right) {
+        return result;
+    }
+
+    #[cfg(feature = "decimal")]
+    if let Some(result) = apply_decimal_operator(left, op, right) {
+        return result;
+    }
+
+    if op == "+" && (matches!(left, Value::String(_)) || matches!(right, Value::String(_))) {
+        return Ok(Value::String(left.to_string() + &right.to_string()));
+    }
+
     // Convert to numbers
     let left_num = value_to_number(left)?;
     let right_num = value_to_number(right)?;
@@ -120,11 +389,104 @@ fn apply_operator(left: &Value, op: &str, right: &Value) -> Result<Value> {
     }
 }
 
+/// Date and duration arithmetic
+///
+/// Subtracting datetimes yields a duration; durations shift datetimes and add
+/// up. A string next to a temporal operand is parsed as a date or duration.
+/// Returns `None` when neither operand is temporal.
+fn apply_temporal_operator(left: &Value, op: &str, right: &Value) -> Option<Result<Value>> {
+    let is_temporal = |value: &Value| matches!(value, Value::DateTime(_) | Value::Duration(_));
+    if !is_temporal(left) && !is_temporal(right) {
+        return None;
+    }
+
+    let coerce = |value: &Value| match value {
+        Value::String(s) => Value::parse_datetime(s)
+            .map(Value::DateTime)
+            .or_else(|| Value::parse_duration(s).map(Value::Duration))
+            .unwrap_or_else(|| value.clone()),
+        _ => value.clone(),
+    };
+
+    let result = match (coerce(left), op, coerce(right)) {
+        (Value::DateTime(l), "-", Value::DateTime(r)) => Some(Value::Duration(l - r)),
+        (Value::DateTime(dt), "+", Value::Duration(d))
+        | (Value::Duration(d), "+", Value::DateTime(dt)) => {
+            dt.checked_add_signed(d).map(Value::DateTime)
+        }
+        (Value::DateTime(dt), "-", Value::Duration(d)) => {
+            dt.checked_sub_signed(d).map(Value::DateTime)
+        }
+        (Value::Duration(l), "+", Value::Duration(r)) => l.checked_add(&r).map(Value::Duration),
+        (Value::Duration(l), "-", Value::Duration(r)) => l.checked_sub(&r).map(Value::Duration),
+        // Leave "Age: " + Order.Age to string concatenation
+        (Value::String(_), "+", _) | (_, "+", Value::String(_)) => return None,
+        _ => {
+            return Some(Err(RuleEngineError::EvaluationError {
+                message: format!("Unsupported date arithmetic: {} {} {}", left, op, right),
+            }));
+        }
+    };
+
+    Some(result.ok_or_else(|| RuleEngineError::EvaluationError {
+        message: format!("Date arithmetic overflow: {} {} {}", left, op, right),
+    }))
+}
+
+/// Exact arithmetic between two decimals, or a decimal and an integer
+///
+/// Returns `None` for any other pair, so a decimal next to a float falls back
+/// to `f64` arithmetic.
+#[cfg(feature = "decimal")]
+fn apply_decimal_operator(left: &Value, op: &str, right: &Value) -> Option<Result<Value>> {
+    let exact = |value: &Value| match value {
+        Value::Decimal(d) => Some(*d),
+        Value::Integer(i) => Some(rust_decimal::Decimal::from(*i)),
+        _ => None,
+    };
+    if !matches!(left, Value::Decimal(_)) && !matches!(right, Value::Decimal(_)) {
+        return None;
+    }
+    let (l, r) = (exact(left)?, exact(right)?);
+
+    let result = match op {
+        "/" | "%" if r.is_zero() => {
+            return Some(Err(RuleEngineError::EvaluationError {
+                message: "Division by zero".to_string(),
+            }));
+        }
+        "+" => l.checked_add(r),
+        "-" => l.checked_sub(r),
+        "*" => l.checked_mul(r),
+        "/" => l.checked_div(r),
+        "%" => l.checked_rem(r),
+        _ => {
+            return Some(Err(RuleEngineError::EvaluationError {
+                message: format!("Unknown operator: {}", op),
+            }));
+        }
+    };
+
+    Some(
+        result
+            .map(Value::Decimal)
+            .ok_or_else(|| RuleEngineError::EvaluationError {
+                message: format!("Decimal overflow: {} {} {}", left, op, right),
+            }),
+    )
+}
+
 /// Convert Value to f64 for arithmetic
 fn value_to_number(value: &Value) -> Result<f64> {
     match value {
         Value::Integer(i) => Ok(*i as f64),
         Value::Number(n) => Ok(*n),
+        #[cfg(feature = "decimal")]
+        Value::Decimal(d) => rust_decimal::prelude::ToPrimitive::to_f64(d).ok_or_else(|| {
+            RuleEngineError::EvaluationError {
+                message: format!("Cannot convert {} to number", d),
+            }
+        }),
         Value::String(s) => s
             .parse::<f64>()
             .map_err(|_| RuleEngineError::EvaluationError {
@@ -195,4 +557,209 @@ mod tests {
             Value::Integer(20)
         );
     }
+
+    #[test]
+    fn test_safe_navigation() {
+        let facts = Facts::new();
+        let customer = Facts::create_object(vec![(
+            "Email".to_string(),
+            Value::String("a@example.com".to_string()),
+        )]);
+        facts
+            .add_value(
+                "Order",
+                Facts::create_object(vec![("Customer".to_string(), customer)]),
+            )
+            .unwrap();
+        facts
+            .add_value("Guest", Facts::create_object(vec![]))
+            .unwrap();
+
+        // Full path exists
+        assert_eq!(
+            evaluate_expression("Order?.Customer?.Email", &facts).unwrap(),
+            Value::String("a@example.com".to_string())
+        );
+
+        // Intermediate is missing
+        assert_eq!(
+            evaluate_expression("Guest?.Customer?.Email", &facts).unwrap(),
+            Value::Null
+        );
+        assert_eq!(
+            evaluate_expression("Missing?.Customer?.Email", &facts).unwrap(),
+            Value::Null
+        );
+
+        // Final field is missing
+        assert_eq!(
+            evaluate_expression("Order?.Customer?.Phone", &facts).unwrap(),
+            Value::Null
+        );
+
+        // Plain navigation still reports missing fields
+        assert!(evaluate_expression("Guest.Customer.Email", &facts).is_err());
+    }
+
+    #[test]
+    fn test_conditional_expression() {
+        let facts = Facts::new();
+        facts.set("Customer.vip", Value::Boolean(true));
+        facts.set("Customer.tier", Value::String("silver".to_string()));
+        facts.set("Order.total", Value::Integer(250));
+
+        assert_eq!(
+            evaluate_expression("Customer.vip ? 0.2 : 0.05", &facts).unwrap(),
+            Value::Number(0.2)
+        );
+        assert_eq!(
+            evaluate_expression("Order.total > 500 ? 0.1 : Order.total * 2", &facts).unwrap(),
+            Value::Integer(500)
+        );
+
+        // Nested to the right, with branches of different types
+        let tiered = r#"Customer.tier == "gold" ? 3 : Customer.tier == "silver" ? "two" : false"#;
+        assert_eq!(
+            evaluate_expression(tiered, &facts).unwrap(),
+            Value::String("two".to_string())
+        );
+        assert_eq!(
+            evaluate_expression("!Customer.vip ? 1 : (Order.total >= 250 ? 2 : 3)", &facts)
+                .unwrap(),
+            Value::Integer(2)
+        );
+
+        // The branch not taken is never evaluated
+        assert_eq!(
+            evaluate_expression("Customer.vip || Missing.flag ? 1 : Missing.value", &facts)
+                .unwrap(),
+            Value::Integer(1)
+        );
+
+        assert_eq!(
+            evaluate_expression("1 + (Customer.vip ? 1 : 0)", &facts).unwrap(),
+            Value::Integer(2)
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_decimal_arithmetic_is_exact() {
+        let decimal = |s: &str| Value::Decimal(s.parse().unwrap());
+        let facts = Facts::new();
+        facts.set("Order.Subtotal", decimal("0.1"));
+
+        assert_eq!(
+            evaluate_expression("0.1d + 0.2d", &facts).unwrap(),
+            decimal("0.3")
+        );
+        assert_eq!(
+            evaluate_expression("Order.Subtotal * 3", &facts).unwrap(),
+            decimal("0.3")
+        );
+        assert_eq!(
+            evaluate_expression("10.00d / 4", &facts).unwrap(),
+            decimal("2.5")
+        );
+        assert!(evaluate_expression("1.00d / 0", &facts).is_err());
+
+        // A float operand falls back to f64, and `30d` is still a duration
+        assert!(matches!(
+            evaluate_expression("0.1d + 0.5", &facts).unwrap(),
+            Value::Number(_)
+        ));
+        assert!(matches!(
+            evaluate_expression("30d", &facts).unwrap(),
+            Value::Duration(_)
+        ));
+    }
+
+    #[test]
+    fn test_string_concatenation() {
+        let facts = Facts::new();
+        facts.set("User.FirstName", Value::String("Ada".to_string()));
+        facts.set("User.LastName", Value::String("Lovelace".to_string()));
+        facts.set("User.Score", Value::Integer(42));
+        facts.set("User.Ratio", Value::Number(0.5));
+
+        assert_eq!(
+            evaluate_expression(r#"User.FirstName + " " + User.LastName"#, &facts).unwrap(),
+            Value::String("Ada Lovelace".to_string())
+        );
+        assert_eq!(
+            evaluate_expression(r#""score: " + User.Score"#, &facts).unwrap(),
+            Value::String("score: 42".to_string())
+        );
+        assert_eq!(
+            evaluate_expression(r#"User.Ratio + "x""#, &facts).unwrap(),
+            Value::String("0.5x".to_string())
+        );
+
+        // Numeric addition is unchanged
+        assert_eq!(
+            evaluate_expression("User.Score + 8", &facts).unwrap(),
+            Value::Integer(50)
+        );
+        assert_eq!(
+            evaluate_expression(r#""a" + User.Score + 1"#, &facts).unwrap(),
+            Value::String("a421".to_string())
+        );
+    }
+
+    #[test]
+    fn test_date_and_duration_arithmetic() {
+        let facts = Facts::new();
+        facts.set(
+            "Session.Start",
+            Value::DateTime(Value::parse_datetime("2024-03-01T10:00:00Z").unwrap()),
+        );
+        facts.set(
+            "Session.LastSeen",
+            Value::String("2024-03-01T11:30:00Z".to_string()),
+        );
+
+        let elapsed = evaluate_expression("Session.LastSeen - Session.Start", &facts).unwrap();
+        assert_eq!(
+            elapsed,
+            Value::Duration(Value::parse_duration("1h30m").unwrap())
+        );
+        assert_eq!(elapsed.to_string(), "1h30m");
+        assert!(Operator::GreaterThan.evaluate(&elapsed, &Value::String("1h".to_string())));
+
+        assert_eq!(
+            evaluate_expression("Session.Start + 2d - 30m", &facts).unwrap(),
+            Value::DateTime(Value::parse_datetime("2024-03-03T09:30:00Z").unwrap())
+        );
+        assert!(matches!(
+            evaluate_expression("now() - Session.Start", &facts).unwrap(),
+            Value::Duration(d) if d > Value::parse_duration("7d").unwrap()
+        ));
+        assert!(evaluate_expression("Session.Start * 2", &facts).is_err());
+    }
+
+    #[test]
+    fn test_check_syntax() {
+        for valid in [
+            "Order.quantity * Order.price",
+            "-Order.total + (Order.fee * 2)",
+            "Customer.vip ? 0.2 : 0.05",
+            r#"User.FirstName + " (" + User.LastName + ")""#,
+            "now() - Session.LastSeen > 30m",
+            "Order?.Customer?.Email == null && ?*MAX_SPEED* >= 10",
+        ] {
+            assert_eq!(check_syntax(valid), Ok(()), "{}", valid);
+        }
+
+        for invalid in [
+            "",
+            "Order.total *",
+            "Order.total + * 2",
+            "(Order.total + 1",
+            "Order.total + 1)",
+            r#""unterminated + 1"#,
+            "max(Order.a, )",
+        ] {
+            assert!(check_syntax(invalid).is_err(), "{}", invalid);
+        }
+    }
 }
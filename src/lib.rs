@@ -162,10 +162,19 @@ pub use errors::{Result, RuleEngineError};
 pub use types::{ActionType, LogicalOperator, Operator, Value};
 
 // Re-export Grule-style components
-pub use engine::engine::{EngineConfig, GruleExecutionResult, RustRuleEngine};
-pub use engine::facts::{FactHelper, Facts};
+pub use engine::clock::{Clock, MockClock, SystemClock};
+pub use engine::engine::{
+    CycleResult, EmittedEvent, EngineConfig, GruleExecutionResult, MissingHandler, PlannedAction,
+    ReloadReport, RuleTimedOut, RustRuleEngine, WhatIfResult,
+};
+pub use engine::event_bus::{Event, EventBus, EventHandler};
+pub use engine::facts::{FactHelper, FactWatcher, Facts, MergeStrategy, WatchId};
+pub use engine::globals::GlobalStore;
 pub use engine::knowledge_base::KnowledgeBase;
-pub use engine::rule::{Condition, ConditionGroup, Rule};
+pub use engine::preflight::ValidationIssue;
+pub use engine::provenance::ProvenanceChain;
+pub use engine::rule::{Condition, ConditionGroup, Rule, Salience};
+pub use engine::schema::{FactSchema, ValueKind};
 
 // Re-export parsers
 pub use parser::grl::GRLParser;
@@ -194,6 +203,7 @@ pub use parser::grl::GRLParser;
 pub struct RuleEngineBuilder {
     kb: KnowledgeBase,
     config: EngineConfig,
+    plugins: Vec<std::sync::Arc<dyn engine::plugin::RulePlugin>>,
 }
 
 impl RuleEngineBuilder {
@@ -204,6 +214,7 @@ impl RuleEngineBuilder {
         Self {
             kb: KnowledgeBase::new("DefaultKB"),
             config: EngineConfig::default(),
+            plugins: Vec::new(),
         }
     }
 
@@ -215,7 +226,7 @@ impl RuleEngineBuilder {
     ///
     /// Returns an error if the file cannot be read or if the GRL syntax is invalid.
     pub fn with_rule_file<P: AsRef<std::path::Path>>(self, path: P) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
+        let content = std::fs::read_to_string(&path).map_err(|e| RuleEngineError::io(&path, e))?;
         let rules = GRLParser::parse_rules(&content)?;
 
         for rule in rules {
@@ -250,11 +261,67 @@ impl RuleEngineBuilder {
         self
     }
 
+    /// Add a plugin to load when the engine is built.
+    ///
+    /// Plugins are loaded in the order they were added, so a plugin's
+    /// dependencies must be added before it. Adding a plugin with the same
+    /// name as one already added replaces the earlier one.
+    pub fn with_plugin(mut self, plugin: std::sync::Arc<dyn engine::plugin::RulePlugin>) -> Self {
+        let name = plugin.get_metadata().name.clone();
+        self.plugins.retain(|p| p.get_metadata().name != name);
+        self.plugins.push(plugin);
+        self
+    }
+
+    /// Add all built-in plugins.
+    ///
+    /// Registers the string, math, date, validation and collection plugins so
+    /// their actions and functions are available as soon as the engine is built.
+    ///
+    /// ```rust
+    /// use rust_rule_engine::RuleEngineBuilder;
+    ///
+    /// let engine = RuleEngineBuilder::new().with_default_plugins().build();
+    /// assert!(engine.has_function("template"));
+    /// assert!(engine.has_action_handler("ValidateEmail"));
+    /// ```
+    pub fn with_default_plugins(self) -> Self {
+        use std::sync::Arc;
+
+        self.with_plugin(Arc::new(plugins::StringUtilsPlugin::new()))
+            .with_plugin(Arc::new(plugins::MathUtilsPlugin::new()))
+            .with_plugin(Arc::new(plugins::DateUtilsPlugin::new()))
+            .with_plugin(Arc::new(plugins::ValidationPlugin::new()))
+            .with_plugin(Arc::new(plugins::CollectionUtilsPlugin::new()))
+    }
+
     /// Build the RustRuleEngine.
     ///
     /// Consumes the builder and creates a configured rule engine instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a plugin fails to load. Use [`try_build`](Self::try_build)
+    /// to handle plugin errors instead.
     pub fn build(self) -> RustRuleEngine {
-        RustRuleEngine::with_config(self.kb, self.config)
+        match self.try_build() {
+            Ok(engine) => engine,
+            Err(e) => panic!("failed to build rule engine: {}", e),
+        }
+    }
+
+    /// Build the RustRuleEngine, loading every added plugin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a plugin fails to register or its dependencies are
+    /// not loaded before it.
+    pub fn try_build(self) -> Result<RustRuleEngine> {
+        let mut engine = RustRuleEngine::with_config(self.kb, self.config);
+        for plugin in self.plugins {
+            engine.load_plugin(plugin)?;
+        }
+        Ok(engine)
     }
 }
 
@@ -201,6 +201,7 @@ fn demo_advanced_grule_rules() -> std::result::Result<(), Box<dyn std::error::Er
         timeout: None,
         enable_stats: true,
         debug_mode: false,
+        ..Default::default()
     };
     let mut engine = RustRuleEngine::with_config(kb, config);
 
@@ -1,10 +1,10 @@
 use crate::engine::module::{ExportItem, ExportList, ImportType, ItemType, ModuleManager};
-use crate::engine::rule::{Condition, ConditionGroup, Rule};
+use crate::engine::rule::{Condition, ConditionGroup, Rule, Salience};
 use crate::errors::{Result, RuleEngineError};
 use crate::types::{ActionType, Operator, Value};
 use chrono::{DateTime, Utc};
 use rexile::Pattern;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::OnceLock;
 
 // Stream syntax parser module
@@ -13,13 +13,15 @@ pub mod stream_syntax;
 
 // Cached main regexes - compiled once at startup
 static RULE_REGEX: OnceLock<Pattern> = OnceLock::new();
-static RULE_SPLIT_REGEX: OnceLock<Pattern> = OnceLock::new();
 static DEFMODULE_REGEX: OnceLock<Pattern> = OnceLock::new();
 static DEFMODULE_SPLIT_REGEX: OnceLock<Pattern> = OnceLock::new();
 static WHEN_THEN_REGEX: OnceLock<Pattern> = OnceLock::new();
 static SALIENCE_REGEX: OnceLock<Pattern> = OnceLock::new();
+static DYNAMIC_SALIENCE_REGEX: OnceLock<Pattern> = OnceLock::new();
+static GLOBAL_SALIENCE_REGEX: OnceLock<Pattern> = OnceLock::new();
 static TEST_CONDITION_REGEX: OnceLock<Pattern> = OnceLock::new();
 static TYPED_TEST_CONDITION_REGEX: OnceLock<Pattern> = OnceLock::new();
+static BINDING_REGEX: OnceLock<Pattern> = OnceLock::new();
 static FUNCTION_CALL_REGEX: OnceLock<Pattern> = OnceLock::new();
 static CONDITION_REGEX: OnceLock<Pattern> = OnceLock::new();
 static METHOD_CALL_REGEX: OnceLock<Pattern> = OnceLock::new();
@@ -31,6 +33,12 @@ static MULTIFIELD_LAST_REGEX: OnceLock<Pattern> = OnceLock::new();
 static MULTIFIELD_EMPTY_REGEX: OnceLock<Pattern> = OnceLock::new();
 static MULTIFIELD_NOT_EMPTY_REGEX: OnceLock<Pattern> = OnceLock::new();
 static SIMPLE_CONDITION_REGEX: OnceLock<Pattern> = OnceLock::new();
+static TYPE_CHECK_REGEX: OnceLock<Pattern> = OnceLock::new();
+static DEFGLOBAL_REGEX: OnceLock<Pattern> = OnceLock::new();
+static TAGS_REGEX: OnceLock<Pattern> = OnceLock::new();
+
+/// Opening of a rule metadata annotation
+const METADATA_ANNOTATION: &str = "@metadata(";
 
 // Helper functions to get or initialize regexes
 fn rule_regex() -> &'static Pattern {
@@ -40,13 +48,391 @@ fn rule_regex() -> &'static Pattern {
     })
 }
 
-fn rule_split_regex() -> &'static Pattern {
-    RULE_SPLIT_REGEX.get_or_init(|| {
-        Pattern::new(r#"(?s)rule\s+(?:"[^"]+"|[a-zA-Z_]\w*).*?\}"#)
-            .expect("Invalid rule split regex pattern")
+/// Split GRL text into `rule ... { ... }` blocks.
+///
+/// Braces are matched by depth, ignoring any inside string literals or `//`
+/// comments, so rule bodies may contain templates such as `"{User.Name}"`.
+///
+/// A rule whose braces never close is reported at the position where it starts.
+fn split_rule_blocks(grl_text: &str) -> Result<Vec<&str>> {
+    let bytes = grl_text.as_bytes();
+    let mut blocks = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            match b {
+                b'\\' => i += 1,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            i += 1;
+            continue;
+        }
+
+        match b {
+            b'"' => in_string = true,
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'{' if start.is_some() => depth += 1,
+            b'}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(begin) = start.take() {
+                        blocks.push(&grl_text[begin..=i]);
+                    }
+                }
+            }
+            b'r' if start.is_none() && is_rule_keyword(bytes, i) => {
+                start = Some(annotations_start(grl_text, i))
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    match start {
+        Some(begin) => Err(error_at(grl_text, begin, "Missing closing brace")),
+        None => Ok(blocks),
+    }
+}
+
+/// Convert a byte offset into a 1-based (line, column) pair, counting
+/// columns in characters
+fn offset_to_line_col(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Parse error located at a byte offset in `text`
+fn error_at(text: &str, offset: usize, message: impl Into<String>) -> RuleEngineError {
+    let (line, column) = offset_to_line_col(text, offset);
+    RuleEngineError::ParseErrorAt {
+        message: message.into(),
+        line,
+        column,
+    }
+}
+
+/// Check whether a standalone `rule` keyword starts at `pos`
+fn is_rule_keyword(bytes: &[u8], pos: usize) -> bool {
+    let preceded_by_word =
+        pos > 0 && (bytes[pos - 1].is_ascii_alphanumeric() || bytes[pos - 1] == b'_');
+    bytes[pos..].starts_with(b"rule")
+        && !preceded_by_word
+        && bytes.get(pos + 4).is_some_and(|c| c.is_ascii_whitespace())
+}
+
+/// Start of the `@metadata(...)` annotations directly preceding the `rule`
+/// keyword at `rule_pos`, or `rule_pos` itself when there are none
+fn annotations_start(text: &str, rule_pos: usize) -> usize {
+    let mut start = rule_pos;
+    loop {
+        let before = text[..start].trim_end();
+        let Some(pos) = before.rfind(METADATA_ANNOTATION) else {
+            return start;
+        };
+        let args_start = pos + METADATA_ANNOTATION.len();
+        match find_outside_strings(&before[args_start..], ")") {
+            Some(close) if args_start + close + 1 == before.len() => start = pos,
+            _ => return start,
+        }
+    }
+}
+
+/// Remove the `@metadata(key="value", ...)` annotations from a rule's header,
+/// returning the remaining text and the annotations
+fn take_metadata_annotations(rule_text: &str) -> Result<(String, HashMap<String, String>)> {
+    let header_end = find_outside_strings(rule_text, "{").unwrap_or(rule_text.len());
+    let mut header = &rule_text[..header_end];
+    let mut remaining = String::with_capacity(rule_text.len());
+    let mut metadata = HashMap::new();
+
+    while let Some(pos) = find_outside_strings(header, METADATA_ANNOTATION) {
+        remaining.push_str(&header[..pos]);
+        let args = &header[pos + METADATA_ANNOTATION.len()..];
+        let close = find_outside_strings(args, ")").ok_or_else(|| RuleEngineError::ParseError {
+            message: "Unclosed @metadata annotation".to_string(),
+        })?;
+
+        for entry in split_top_level(&args[..close], b',') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (key, value) = entry
+                .split_once('=')
+                .filter(|(key, _)| !key.trim().is_empty())
+                .ok_or_else(|| RuleEngineError::ParseError {
+                    message: format!(
+                        "Invalid @metadata entry '{}', expected key=\"value\"",
+                        entry
+                    ),
+                })?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .map(unescape_string)
+                .unwrap_or_else(|| value.to_string());
+            metadata.insert(key.trim().to_string(), value);
+        }
+
+        header = &args[close + 1..];
+    }
+
+    remaining.push_str(header);
+    remaining.push_str(&rule_text[header_end..]);
+    Ok((remaining, metadata))
+}
+
+/// Arguments of an `emit(...)` action statement
+fn emit_arguments(statement: &str) -> Option<&str> {
+    let rest = statement
+        .get(..4)
+        .filter(|keyword| keyword.eq_ignore_ascii_case("emit"))
+        .map(|_| statement[4..].trim_start())?;
+    rest.strip_prefix('(')?.strip_suffix(')')
+}
+
+/// Leading quoted string of a rule header, as in `rule Name "description" {`
+fn leading_description(header: &str) -> Option<String> {
+    let rest = header.trim_start().strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(rest[..end].to_string())
+}
+
+/// Parse a numeric range literal such as `1..31` (end excluded) or `1..=31`
+/// (end included)
+fn parse_range_literal(text: &str) -> Option<Value> {
+    let (start, end, inclusive) = if let Some((start, end)) = text.split_once("..=") {
+        (start, end, true)
+    } else {
+        let (start, end) = text.split_once("..")?;
+        (start, end, false)
+    };
+
+    Some(Value::Range {
+        start: start.trim().parse().ok()?,
+        end: end.trim().parse().ok()?,
+        inclusive,
     })
 }
 
+/// Split an argument list on top-level commas, ignoring one trailing comma
+///
+/// `f(1, 2,)` has the same two arguments as `f(1, 2)`.
+fn split_arguments(text: &str) -> Vec<&str> {
+    let mut args = split_top_level(text, b',');
+    if args.len() > 1 && args.last().is_some_and(|arg| arg.trim().is_empty()) {
+        args.pop();
+    }
+    args
+}
+
+/// Split `text` on `separator`, ignoring separators inside string literals
+/// (with backslash escapes) and inside `()`, `[]` or `{}`
+fn split_top_level(text: &str, separator: u8) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut depth = 0i32;
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if let Some(q) = quote {
+            if b == b'\\' {
+                i += 1;
+            } else if b == q {
+                quote = None;
+            }
+            i += 1;
+            continue;
+        }
+
+        match b {
+            b'"' | b'\'' => quote = Some(b),
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            _ if b == separator && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Byte offset of the first `needle` outside string literals
+fn find_outside_strings(text: &str, needle: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        match quote {
+            Some(_) if b == b'\\' => i += 1,
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None if bytes[i..].starts_with(needle.as_bytes()) => return Some(i),
+            None => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Whether `chars` continues with `keyword` (any case) followed by whitespace
+fn keyword_follows(chars: &std::iter::Peekable<std::str::Chars<'_>>, keyword: &str) -> bool {
+    let mut ahead = chars.clone();
+    keyword
+        .chars()
+        .all(|k| ahead.next().is_some_and(|c| c.eq_ignore_ascii_case(&k)))
+        && ahead.next().is_some_and(char::is_whitespace)
+}
+
+/// Whether a literal is tested for membership in a fact, as in
+/// `"premium" in Customer.Tags`
+fn is_literal_membership(clause: &str) -> bool {
+    let starts_with_literal = clause
+        .chars()
+        .next()
+        .is_some_and(|c| c == '"' || c == '\'' || c.is_ascii_digit());
+
+    starts_with_literal
+        && find_outside_strings(clause, " in ").is_some()
+        && ["==", "!=", ">=", "<=", ">", "<"]
+            .iter()
+            .all(|op| find_outside_strings(clause, op).is_none())
+}
+
+/// Whether the left side of a comparison does arithmetic on a function call,
+/// as in `now() - Session.LastSeen > 30m`
+fn is_call_arithmetic(clause: &str) -> bool {
+    let Some(op_pos) = ["==", "!=", ">=", "<=", ">", "<"]
+        .iter()
+        .filter_map(|op| find_outside_strings(clause, op))
+        .min()
+    else {
+        return false;
+    };
+
+    let left = &clause[..op_pos];
+    let mut depth = 0;
+    left.contains('(')
+        && left.chars().any(|c| match c {
+            '(' => {
+                depth += 1;
+                false
+            }
+            ')' => {
+                depth -= 1;
+                false
+            }
+            '+' | '-' | '*' | '/' | '%' => depth == 0,
+            _ => false,
+        })
+}
+
+/// Split `Object.setField(args)` into object, method and argument text
+///
+/// Only setters (`set` followed by an uppercase letter or `_`) qualify, so
+/// other `Object.method(...)` statements keep reaching custom action handlers.
+fn plain_setter_call(statement: &str) -> Option<(&str, &str, &str)> {
+    let body = statement.strip_suffix(')')?;
+    let open = body.find('(')?;
+    let (object, method) = body[..open].trim_end().split_once('.')?;
+
+    let is_ident =
+        |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let is_setter = method
+        .strip_prefix("set")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_uppercase() || c == '_');
+
+    (is_ident(object) && is_ident(method) && is_setter).then(|| (object, method, &body[open + 1..]))
+}
+
+/// Split a call such as `calculateScore(Order.Items)` into the function name
+/// and its argument text
+///
+/// Anything else, including `f(a) * g(b)` or a method call `Order.total()`,
+/// yields `None`.
+fn function_call_parts(text: &str) -> Option<(&str, &str)> {
+    let body = text.strip_suffix(')')?;
+    let open = body.find('(')?;
+    let name = body[..open].trim_end();
+    let args = &body[open + 1..];
+
+    let is_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_name {
+        return None;
+    }
+
+    // The opening paren must be the one closed at the very end
+    let mut depth = 0usize;
+    let mut quote = None;
+    for c in args.chars() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+    (depth == 0).then_some((name, args))
+}
+
+/// Resolve `\n`, `\t`, `\r`, `\"`, `\'` and `\\` in a string literal body.
+/// Unknown escapes such as `\d` are kept verbatim so regex patterns survive.
+fn unescape_string(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some(escaped @ ('"' | '\'' | '\\')) => out.push(escaped),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
 fn defmodule_regex() -> &'static Pattern {
     DEFMODULE_REGEX.get_or_init(|| {
         Pattern::new(r#"defmodule\s+([A-Z_]\w*)\s*\{([^}]*)\}"#)
@@ -72,13 +458,36 @@ fn salience_regex() -> &'static Pattern {
         .get_or_init(|| Pattern::new(r"salience\s+(\d+)").expect("Invalid salience regex pattern"))
 }
 
+fn global_salience_regex() -> &'static Pattern {
+    GLOBAL_SALIENCE_REGEX.get_or_init(|| {
+        Pattern::new(r"salience\s+\$([a-zA-Z_][a-zA-Z0-9_]*)")
+            .expect("Invalid global salience regex pattern")
+    })
+}
+
+fn dynamic_salience_regex() -> &'static Pattern {
+    DYNAMIC_SALIENCE_REGEX.get_or_init(|| {
+        Pattern::new(
+            r"salience\s+(?:\(([^)]+)\)|([a-zA-Z_][a-zA-Z0-9_]*(?:\.[a-zA-Z_][a-zA-Z0-9_]*)*))",
+        )
+        .expect("Invalid dynamic salience regex pattern")
+    })
+}
+
 fn test_condition_regex() -> &'static Pattern {
     TEST_CONDITION_REGEX.get_or_init(|| {
-        Pattern::new(r#"^test\s*\(\s*([a-zA-Z_]\w*)\s*\(([^)]*)\)\s*\)$"#)
+        Pattern::new(r#"^test\s*\(\s*([a-zA-Z_]\w*(?:\.[a-zA-Z_]\w*)?)\s*\(([^)]*)\)\s*\)$"#)
             .expect("Invalid test condition regex")
     })
 }
 
+fn binding_regex() -> &'static Pattern {
+    BINDING_REGEX.get_or_init(|| {
+        Pattern::new(r#"^(\$[a-zA-Z_]\w*)\s*:\s*([a-zA-Z_]\w*(?:\??\.[a-zA-Z_]\w*)*)$"#)
+            .expect("Invalid binding regex")
+    })
+}
+
 fn typed_test_condition_regex() -> &'static Pattern {
     TYPED_TEST_CONDITION_REGEX.get_or_init(|| {
         Pattern::new(r#"\$(\w+)\s*:\s*(\w+)\s*\(\s*(.+?)\s*\)"#)
@@ -88,14 +497,14 @@ fn typed_test_condition_regex() -> &'static Pattern {
 
 fn function_call_regex() -> &'static Pattern {
     FUNCTION_CALL_REGEX.get_or_init(|| {
-        Pattern::new(r#"([a-zA-Z_]\w*)\s*\(([^)]*)\)\s*(>=|<=|==|!=|>|<|contains|startsWith|endsWith|matches|in)\s*(.+)"#)
+        Pattern::new(r#"([a-zA-Z_]\w*(?:\.[a-zA-Z_]\w*)?)\s*\(([^)]*)\)\s*(>=|<=|==|!=|>|<|contains_all|contains_any|contains|startsWith|endsWith|matches|not\s+in|in)\s*(.+)"#)
             .expect("Invalid function call regex")
     })
 }
 
 fn condition_regex() -> &'static Pattern {
     CONDITION_REGEX.get_or_init(|| {
-        Pattern::new(r#"([a-zA-Z_][a-zA-Z0-9_]*(?:\.[a-zA-Z_][a-zA-Z0-9_]*)*(?:\s*[+\-*/%]\s*[a-zA-Z0-9_\.]+)*)\s*(>=|<=|==|!=|>|<|contains|startsWith|endsWith|matches|in)\s*(.+)"#)
+        Pattern::new(r#"(\$?[a-zA-Z_][a-zA-Z0-9_]*(?:\[-?[0-9]+\])*(?:\??\.[a-zA-Z_][a-zA-Z0-9_]*(?:\[-?[0-9]+\])*)*(?:\s*[+\-*/%]\s*[a-zA-Z0-9_\.]+)*)\s*(>=|<=|==|!=|>|<|contains_all|contains_any|contains|startsWith|endsWith|matches|not\s+in|in)\s*(.+)"#)
             .expect("Invalid condition regex")
     })
 }
@@ -154,6 +563,26 @@ fn multifield_not_empty_regex() -> &'static Pattern {
     })
 }
 
+fn type_check_regex() -> &'static Pattern {
+    TYPE_CHECK_REGEX.get_or_init(|| {
+        Pattern::new(r#"^([a-zA-Z_][a-zA-Z0-9_]*(?:\??\.[a-zA-Z_][a-zA-Z0-9_]*)*)\s+(is_number|is_string|is_boolean|is_array|is_object|is_not_null|is_null|is\s+not\s+null|is\s+null)$"#)
+            .expect("Invalid type check regex")
+    })
+}
+
+fn defglobal_regex() -> &'static Pattern {
+    DEFGLOBAL_REGEX.get_or_init(|| {
+        Pattern::new(r#"defglobal\s+\?\*([a-zA-Z_][a-zA-Z0-9_\-]*)\*\s*=\s*([^;]+);"#)
+            .expect("Invalid defglobal regex")
+    })
+}
+
+fn tags_regex() -> &'static Pattern {
+    TAGS_REGEX.get_or_init(|| {
+        Pattern::new(r#"\btags\s*\[([^\]]*)\]"#).expect("Invalid tags regex pattern")
+    })
+}
+
 fn simple_condition_regex() -> &'static Pattern {
     SIMPLE_CONDITION_REGEX.get_or_init(|| {
         Pattern::new(r#"(\w+)\s*(>=|<=|==|!=|>|<)\s*(.+)"#).expect("Invalid simple condition regex")
@@ -164,15 +593,27 @@ fn simple_condition_regex() -> &'static Pattern {
 /// Parses Grule-like syntax into Rule objects
 pub struct GRLParser;
 
+/// Per-parse state behind the `GRLParser` entry points
+#[derive(Debug, Default)]
+struct ParserState {
+    /// Globals available for parse-time resolution (e.g. `salience $HighPriority`)
+    globals: HashMap<String, Value>,
+    /// Custom action names accepted in strict mode; `None` accepts any name
+    known_actions: Option<HashSet<String>>,
+}
+
 /// Parsed rule attributes from GRL header
 #[derive(Debug, Default)]
 struct RuleAttributes {
     pub no_loop: bool,
     pub lock_on_active: bool,
+    pub max_fires: Option<usize>,
+    pub timeout: Option<std::time::Duration>,
     pub agenda_group: Option<String>,
     pub activation_group: Option<String>,
     pub date_effective: Option<DateTime<Utc>>,
     pub date_expires: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
 }
 
 /// Result from parsing GRL with modules
@@ -216,16 +657,114 @@ impl GRLParser {
     /// }
     /// ```
     pub fn parse_rule(grl_text: &str) -> Result<Rule> {
-        let mut parser = GRLParser;
+        let mut parser = ParserState::default();
         parser.parse_single_rule(grl_text)
     }
 
     /// Parse multiple rules from GRL text
     pub fn parse_rules(grl_text: &str) -> Result<Vec<Rule>> {
-        let mut parser = GRLParser;
+        let mut parser = ParserState::default();
+        parser.parse_multiple_rules(grl_text)
+    }
+
+    /// Parse multiple rules, resolving `$name` references in rule headers
+    /// against the given globals at parse time
+    ///
+    /// ```grl
+    /// rule Discount salience $HighPriority {
+    ///     when Order.Total > 100
+    ///     then Order.Discount = 0.1;
+    /// }
+    /// ```
+    ///
+    /// Returns a parse error if a referenced global is undefined or not an integer.
+    pub fn parse_rules_with_globals(
+        grl_text: &str,
+        globals: &HashMap<String, Value>,
+    ) -> Result<Vec<Rule>> {
+        let mut parser = ParserState {
+            globals: globals.clone(),
+            ..Default::default()
+        };
+        parser.parse_multiple_rules(grl_text)
+    }
+
+    /// Parse multiple rules, rejecting function-style actions that are neither
+    /// built in nor listed in `known_names`
+    ///
+    /// In the default (lenient) mode an unknown call such as `SendEmial("x")`
+    /// becomes an [`ActionType::Custom`] and only fails when executed. Strict
+    /// mode reports it as a parse error instead, so typos are caught when the
+    /// rules are loaded. Built-in actions (`Retract`, `Log`, `halt`, ...) are
+    /// always accepted.
+    pub fn parse_rules_strict(grl_text: &str, known_names: &[&str]) -> Result<Vec<Rule>> {
+        let mut parser = ParserState {
+            known_actions: Some(known_names.iter().map(|name| name.to_string()).collect()),
+            ..Default::default()
+        };
         parser.parse_multiple_rules(grl_text)
     }
 
+    /// Parse the `defglobal` declarations in GRL text
+    ///
+    /// ```grl
+    /// defglobal ?*MAX_SPEED* = 120;
+    /// ```
+    ///
+    /// Returns `(name, value)` pairs in declaration order; rule blocks are ignored.
+    pub fn parse_globals(grl_text: &str) -> Result<Vec<(String, Value)>> {
+        let parser = ParserState::default();
+        defglobal_regex()
+            .captures_iter(grl_text)
+            .map(|captures| {
+                let name = captures.get(1).unwrap().to_string();
+                let value = parser.parse_value(captures.get(2).unwrap())?;
+                Ok((name, value))
+            })
+            .collect()
+    }
+
+    /// Split the `include "path";` directives off the top of GRL text
+    ///
+    /// ```grl
+    /// include "common.grl";
+    /// include "shared/pricing.grl";
+    /// ```
+    ///
+    /// Includes may only be preceded by blank lines and `//` comments. Returns
+    /// the included paths in order and the remaining text.
+    pub fn parse_includes(grl_text: &str) -> Result<(Vec<String>, &str)> {
+        let mut includes = Vec::new();
+        let mut offset = 0;
+
+        for line in grl_text.split_inclusive('\n') {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with("//") {
+                offset += line.len();
+                continue;
+            }
+            let Some(rest) = trimmed.strip_prefix("include") else {
+                break;
+            };
+            if !rest.starts_with([' ', '\t', '"']) {
+                break;
+            }
+
+            let target = rest.trim().trim_end_matches(';').trim_end();
+            let path = target
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .filter(|path| !path.is_empty() && !path.contains('"'))
+                .ok_or_else(|| RuleEngineError::ParseError {
+                    message: format!("Invalid include directive: {}", trimmed),
+                })?;
+            includes.push(path.to_string());
+            offset += line.len();
+        }
+
+        Ok((includes, &grl_text[offset..]))
+    }
+
     /// Parse GRL text with module support
     ///
     /// Example:
@@ -244,10 +783,12 @@ impl GRLParser {
     /// }
     /// ```
     pub fn parse_with_modules(grl_text: &str) -> Result<ParsedGRL> {
-        let mut parser = GRLParser;
+        let mut parser = ParserState::default();
         parser.parse_grl_with_modules(grl_text)
     }
+}
 
+impl ParserState {
     fn parse_grl_with_modules(&mut self, grl_text: &str) -> Result<ParsedGRL> {
         let mut result = ParsedGRL::new();
 
@@ -402,6 +943,7 @@ impl GRLParser {
 
     fn parse_single_rule(&mut self, grl_text: &str) -> Result<Rule> {
         let cleaned = self.clean_text(grl_text);
+        let (cleaned, metadata) = take_metadata_annotations(&cleaned)?;
 
         // Extract rule components using cached regex
         let captures =
@@ -451,7 +993,10 @@ impl GRLParser {
 
         // Build rule
         let mut rule = Rule::new(rule_name, conditions, actions);
-        rule = rule.with_priority(salience);
+        rule = match salience {
+            Salience::Static(value) => rule.with_priority(value),
+            Salience::Dynamic(expression) => rule.with_dynamic_salience(expression),
+        };
 
         // Apply parsed attributes
         if attributes.no_loop {
@@ -460,6 +1005,12 @@ impl GRLParser {
         if attributes.lock_on_active {
             rule = rule.with_lock_on_active(true);
         }
+        if let Some(max_fires) = attributes.max_fires {
+            rule = rule.with_max_fires(max_fires);
+        }
+        if let Some(timeout) = attributes.timeout {
+            rule = rule.with_timeout(timeout);
+        }
         if let Some(agenda_group) = attributes.agenda_group {
             rule = rule.with_agenda_group(agenda_group);
         }
@@ -472,6 +1023,13 @@ impl GRLParser {
         if let Some(date_expires) = attributes.date_expires {
             rule = rule.with_date_expires(date_expires);
         }
+        if !attributes.tags.is_empty() {
+            rule = rule.with_tags(attributes.tags);
+        }
+        if let Some(description) = leading_description(attributes_section) {
+            rule = rule.with_description(description);
+        }
+        rule.metadata = metadata;
 
         Ok(rule)
     }
@@ -481,9 +1039,13 @@ impl GRLParser {
         // Use DOTALL flag to match newlines in rule body
         let mut rules = Vec::new();
 
-        for rule_match in rule_split_regex().find_iter(grl_text) {
-            let rule_text = rule_match.as_str();
-            let rule = self.parse_single_rule(rule_text)?;
+        for rule_text in split_rule_blocks(grl_text)? {
+            // Errors inside a rule are reported at the start of that rule
+            let offset = rule_text.as_ptr() as usize - grl_text.as_ptr() as usize;
+            let rule = self.parse_single_rule(rule_text).map_err(|err| match err {
+                RuleEngineError::ParseError { message } => error_at(grl_text, offset, message),
+                other => other,
+            })?;
             rules.push(rule);
         }
 
@@ -513,10 +1075,13 @@ impl GRLParser {
                 .find("salience")
                 .or_else(|| after_rule.find("no-loop"))
                 .or_else(|| after_rule.find("lock-on-active"))
+                .or_else(|| after_rule.find("max-fires"))
+                .or_else(|| after_rule.find("timeout"))
                 .or_else(|| after_rule.find("agenda-group"))
                 .or_else(|| after_rule.find("activation-group"))
                 .or_else(|| after_rule.find("date-effective"))
                 .or_else(|| after_rule.find("date-expires"))
+                .or_else(|| after_rule.find("tags"))
             {
                 attrs_section = after_rule[first_keyword..].to_string();
             }
@@ -539,6 +1104,36 @@ impl GRLParser {
             attributes.lock_on_active = true;
         }
 
+        // Parse max-fires attribute: max-fires 3
+        let max_fires_regex =
+            Pattern::new(r"\bmax-fires\s+(\d+)").map_err(|e| RuleEngineError::ParseError {
+                message: format!("Invalid max-fires regex: {}", e),
+            })?;
+        if let Some(captures) = max_fires_regex.captures(&attrs_section) {
+            let count = captures.get(1).unwrap();
+            attributes.max_fires =
+                Some(count.parse().map_err(|_| RuleEngineError::ParseError {
+                    message: format!("Invalid max-fires value: {}", count),
+                })?);
+        }
+
+        // Parse timeout attribute: timeout 100ms
+        let timeout_regex = Pattern::new(r"\btimeout\s+([0-9][0-9a-z]*)").map_err(|e| {
+            RuleEngineError::ParseError {
+                message: format!("Invalid timeout regex: {}", e),
+            }
+        })?;
+        if let Some(captures) = timeout_regex.captures(&attrs_section) {
+            let literal = captures.get(1).unwrap();
+            attributes.timeout = Some(
+                Value::parse_duration(literal)
+                    .and_then(|d| d.to_std().ok())
+                    .ok_or_else(|| RuleEngineError::ParseError {
+                        message: format!("Invalid timeout value: {}", literal),
+                    })?,
+            );
+        }
+
         // Parse agenda-group attribute
         if let Some(agenda_group) = self.extract_quoted_attribute(rule_header, "agenda-group")? {
             attributes.agenda_group = Some(agenda_group);
@@ -561,6 +1156,15 @@ impl GRLParser {
             attributes.date_expires = Some(self.parse_date_string(&date_str)?);
         }
 
+        // Parse tags attribute: tags ["pricing", "promo"]
+        if let Some(captures) = tags_regex().captures(rule_header) {
+            attributes.tags = split_top_level(captures.get(1).unwrap_or(""), b',')
+                .into_iter()
+                .map(|tag| tag.trim().trim_matches('"').trim_matches('\'').to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+        }
+
         Ok(attributes)
     }
 
@@ -611,26 +1215,88 @@ impl GRLParser {
     }
 
     /// Extract salience value from attributes section
-    fn extract_salience(&self, attributes_section: &str) -> Result<i32> {
+    fn extract_salience(&self, attributes_section: &str) -> Result<Salience> {
+        // Global reference: `salience $HighPriority`, resolved now
+        if let Some(captures) = global_salience_regex().captures(attributes_section) {
+            if let Some(name) = captures.get(1) {
+                return match self.globals.get(name) {
+                    Some(Value::Integer(value)) => i32::try_from(*value)
+                        .map(Salience::Static)
+                        .map_err(|_| RuleEngineError::ParseError {
+                            message: format!("Global '${}' is out of range for salience", name),
+                        }),
+                    Some(other) => Err(RuleEngineError::ParseError {
+                        message: format!(
+                            "Global '${}' used as salience must be an integer, got {:?}",
+                            name, other
+                        ),
+                    }),
+                    None => Err(RuleEngineError::ParseError {
+                        message: format!("Undefined global '${}' in salience", name),
+                    }),
+                };
+            }
+        }
+
         if let Some(captures) = salience_regex().captures(attributes_section) {
             if let Some(salience_match) = captures.get(1) {
                 return salience_match
                     .parse::<i32>()
+                    .map(Salience::Static)
                     .map_err(|e| RuleEngineError::ParseError {
                         message: format!("Invalid salience value: {}", e),
                     });
             }
         }
 
-        Ok(0) // Default salience
+        // Dynamic salience: `salience Order.Total` or `salience (Order.Total / 100)`
+        if let Some(captures) = dynamic_salience_regex().captures(attributes_section) {
+            if let Some(expression) = captures.get(1).or_else(|| captures.get(2)) {
+                return Ok(Salience::Dynamic(expression.trim().to_string()));
+            }
+        }
+
+        Ok(Salience::default())
     }
 
+    /// Collapse the rule text onto one line, dropping blank and `//` lines.
+    ///
+    /// A string literal that spans several lines keeps its line breaks as
+    /// `\n` escapes, which `parse_value` turns back into newlines.
     fn clean_text(&self, text: &str) -> String {
-        text.lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty() && !line.starts_with("//"))
-            .collect::<Vec<_>>()
-            .join(" ")
+        let mut cleaned = String::with_capacity(text.len());
+        let mut in_string = false;
+
+        for line in text.lines() {
+            let line = if in_string {
+                cleaned.push_str("\\n");
+                line
+            } else {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with("//") {
+                    continue;
+                }
+                if !cleaned.is_empty() {
+                    cleaned.push(' ');
+                }
+                line
+            };
+
+            let mut escaped = false;
+            for ch in line.chars() {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' && in_string {
+                    escaped = true;
+                } else if ch == '"' {
+                    in_string = !in_string;
+                }
+            }
+
+            cleaned.push_str(if in_string { line } else { line.trim_end() });
+        }
+
+        cleaned
     }
 
     fn parse_when_clause(&self, when_clause: &str) -> Result<ConditionGroup> {
@@ -655,6 +1321,11 @@ impl GRLParser {
             return self.parse_or_parts(parts);
         }
 
+        // Parse XOR (binds tighter than OR, looser than AND)
+        if let Some(parts) = self.split_logical_operator(clause, "^^") {
+            return self.parse_xor_parts(parts);
+        }
+
         // Parse AND (higher precedence)
         if let Some(parts) = self.split_logical_operator(clause, "&&") {
             return self.parse_and_parts(parts);
@@ -705,10 +1376,25 @@ impl GRLParser {
         let mut parts = Vec::new();
         let mut current_part = String::new();
         let mut paren_count = 0;
+        let mut in_string = false;
         let mut chars = clause.chars().peekable();
 
         while let Some(ch) = chars.next() {
+            if in_string {
+                current_part.push(ch);
+                match ch {
+                    '\\' => current_part.extend(chars.next()),
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+
             match ch {
+                '"' => {
+                    in_string = true;
+                    current_part.push(ch);
+                }
                 '(' => {
                     paren_count += 1;
                     current_part.push(ch);
@@ -727,6 +1413,21 @@ impl GRLParser {
                     parts.push(current_part.trim().to_string());
                     current_part.clear();
                 }
+                '^' if operator == "^^" && paren_count == 0 && chars.peek() == Some(&'^') => {
+                    chars.next(); // consume second ^
+                    parts.push(current_part.trim().to_string());
+                    current_part.clear();
+                }
+                // Keyword form: a xor b
+                c if c.is_whitespace()
+                    && operator == "^^"
+                    && paren_count == 0
+                    && keyword_follows(&chars, "xor") =>
+                {
+                    chars.nth(2); // consume "xor"
+                    parts.push(current_part.trim().to_string());
+                    current_part.clear();
+                }
                 _ => {
                     current_part.push(ch);
                 }
@@ -792,6 +1493,31 @@ impl GRLParser {
         Ok(result)
     }
 
+    fn parse_xor_parts(&self, parts: Vec<String>) -> Result<ConditionGroup> {
+        let mut conditions = Vec::new();
+        for part in parts {
+            let condition = self.parse_when_clause(&part)?;
+            conditions.push(condition);
+        }
+
+        if conditions.is_empty() {
+            return Err(RuleEngineError::ParseError {
+                message: "No conditions found in XOR".to_string(),
+            });
+        }
+
+        // Left-associative: a ^^ b ^^ c is (a ^^ b) ^^ c
+        let mut iter = conditions.into_iter();
+        let mut result = iter
+            .next()
+            .expect("Iterator cannot be empty after empty check");
+        for condition in iter {
+            result = ConditionGroup::xor(result, condition);
+        }
+
+        Ok(result)
+    }
+
     fn parse_not_condition(&self, clause: &str) -> Result<ConditionGroup> {
         let inner_clause = clause
             .strip_prefix('!')
@@ -1051,6 +1777,14 @@ impl GRLParser {
             trimmed_clause
         };
 
+        // Constant condition: true or false
+        if clause_to_parse.eq_ignore_ascii_case("true") {
+            return Ok(ConditionGroup::Constant(true));
+        }
+        if clause_to_parse.eq_ignore_ascii_case("false") {
+            return Ok(ConditionGroup::Constant(false));
+        }
+
         // === STREAM PATTERNS ===
         // Check for stream pattern syntax: "var: Type from stream(...)"
         #[cfg(feature = "streaming")]
@@ -1137,6 +1871,21 @@ impl GRLParser {
 
         // === END MULTI-FIELD PATTERNS ===
 
+        // Unary type tests: Input.Value is_number
+        if let Some(captures) = type_check_regex().captures(clause_to_parse) {
+            let field = captures.get(1).unwrap().to_string();
+            let operator_str = captures.get(2).unwrap();
+
+            let operator = Operator::from_str(operator_str).ok_or_else(|| {
+                RuleEngineError::InvalidOperator {
+                    operator: operator_str.to_string(),
+                }
+            })?;
+
+            let condition = Condition::new(field, operator, Value::Null);
+            return Ok(ConditionGroup::single(condition));
+        }
+
         // Handle Test CE: test(functionName(args...))
         // This is a CLIPS-inspired feature for arbitrary boolean expressions
         if let Some(captures) = test_condition_regex().captures(clause_to_parse) {
@@ -1157,6 +1906,15 @@ impl GRLParser {
             return Ok(ConditionGroup::single(condition));
         }
 
+        // Variable binding: $age : User.Age
+        if let Some(captures) = binding_regex().captures(clause_to_parse) {
+            let variable = captures.get(1).unwrap().to_string();
+            let field = captures.get(2).unwrap().to_string();
+
+            let condition = Condition::with_bind(variable, field);
+            return Ok(ConditionGroup::single(condition));
+        }
+
         // Handle typed object conditions like: $TestCar : TestCarClass( speedUp == true && speed < maxSpeed )
         if let Some(captures) = typed_test_condition_regex().captures(clause_to_parse) {
             let _object_name = captures.get(1).unwrap();
@@ -1167,6 +1925,13 @@ impl GRLParser {
             return self.parse_conditions_within_object(conditions_str);
         }
 
+        // Literal on the left of `in`: "premium" in Customer.Tags
+        // Arithmetic over a function call: now() - Session.LastSeen > 30m
+        if is_literal_membership(clause_to_parse) || is_call_arithmetic(clause_to_parse) {
+            let condition = Condition::with_test(clause_to_parse.to_string(), vec![]);
+            return Ok(ConditionGroup::single(condition));
+        }
+
         // Try to parse function call pattern: functionName(arg1, arg2, ...) operator value
         if let Some(captures) = function_call_regex().captures(clause_to_parse) {
             let function_name = captures.get(1).unwrap().to_string();
@@ -1216,13 +1981,13 @@ impl GRLParser {
 
         let value = self.parse_value(value_str)?;
 
-        // Check if left_side contains arithmetic operators - if yes, it's an expression
-        if left_side.contains('+')
-            || left_side.contains('-')
-            || left_side.contains('*')
-            || left_side.contains('/')
-            || left_side.contains('%')
-        {
+        // Check if left_side contains arithmetic operators - if yes, it's an expression.
+        // A `-` inside an array index such as `Items[-1]` is not one.
+        let unindexed: String = left_side
+            .split('[')
+            .map(|part| part.split_once(']').map_or(part, |(_, rest)| rest))
+            .collect();
+        if unindexed.contains(['+', '-', '*', '/', '%']) {
             // This is an arithmetic expression - use Test CE
             // Format: test(left_side operator value)
             let test_expr = format!("{} {} {}", left_side, operator_str, value_str);
@@ -1290,17 +2055,45 @@ impl GRLParser {
     fn parse_value(&self, value_str: &str) -> Result<Value> {
         let trimmed = value_str.trim();
 
+        // Conditional: Customer.vip ? 0.2 : 0.05, evaluated at runtime
+        if crate::expression::split_ternary(trimmed).is_some() {
+            return Ok(Value::Expression(trimmed.to_string()));
+        }
+
         // Array literal: ["value1", "value2", 123]
         if trimmed.starts_with('[') && trimmed.ends_with(']') {
             return self.parse_array_literal(trimmed);
         }
 
-        // String literal
-        if (trimmed.starts_with('"') && trimmed.ends_with('"'))
-            || (trimmed.starts_with('\'') && trimmed.ends_with('\''))
+        // Object literal: { theme: "dark", limits: { daily: 5 } }
+        if let Some(body) = trimmed
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+        {
+            return self.parse_object_fields(body).map(Value::Object);
+        }
+
+        // Base64 bytes literal: b64"SGVsbG8="
+        if trimmed.starts_with("b64\"") {
+            return Value::parse_bytes_literal(trimmed)
+                .map(Value::Bytes)
+                .ok_or_else(|| RuleEngineError::ParseError {
+                    message: format!("Invalid base64 literal: {}", trimmed),
+                });
+        }
+
+        // String literal (but not a concatenation such as "a" + "b")
+        if ((trimmed.starts_with('"') && trimmed.ends_with('"'))
+            || (trimmed.starts_with('\'') && trimmed.ends_with('\'')))
+            && find_outside_strings(trimmed, "+").is_none()
         {
             let unquoted = &trimmed[1..trimmed.len() - 1];
-            return Ok(Value::String(unquoted.to_string()));
+            return Ok(Value::String(unescape_string(unquoted)));
+        }
+
+        // Range literal: 1..31 or 1..=31
+        if let Some(range) = parse_range_literal(trimmed) {
+            return Ok(range);
         }
 
         // Boolean
@@ -1316,22 +2109,51 @@ impl GRLParser {
             return Ok(Value::Null);
         }
 
-        // Number (try integer first, then float)
-        if let Ok(int_val) = trimmed.parse::<i64>() {
-            return Ok(Value::Integer(int_val));
+        // Decimal literal: 19.99d
+        #[cfg(feature = "decimal")]
+        if let Some(decimal) = Value::parse_decimal(trimmed) {
+            return Ok(Value::Decimal(decimal));
         }
 
-        if let Ok(float_val) = trimmed.parse::<f64>() {
-            return Ok(Value::Number(float_val));
+        // Unquoted ISO-8601 literal (2024-01-01T10:00:00Z) or duration (30m, 1h30m)
+        if trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+            if let Some(dt) = Value::parse_datetime(trimmed) {
+                return Ok(Value::DateTime(dt));
+            }
+            if let Some(duration) = Value::parse_duration(trimmed) {
+                return Ok(Value::Duration(duration));
+            }
         }
 
-        // Expression with arithmetic operators (e.g., "Order.quantity * Order.price")
-        // Detect: contains operators AND (contains field reference OR multiple tokens)
-        if self.is_expression(trimmed) {
+        // Regex capture from a `matches` condition ($1) or a bound variable ($age),
+        // resolved by the engine at runtime
+        if trimmed.strip_prefix('$').is_some_and(|n| {
+            !n.is_empty() && n.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }) {
             return Ok(Value::Expression(trimmed.to_string()));
         }
 
-        // Field reference (like User.Name)
+        // Global reference: ?*MAX_SPEED*, resolved by the engine at runtime
+        if crate::engine::globals::global_name(trimmed).is_some() {
+            return Ok(Value::Expression(trimmed.to_string()));
+        }
+
+        // Number (try integer first, then float)
+        if let Ok(int_val) = trimmed.parse::<i64>() {
+            return Ok(Value::Integer(int_val));
+        }
+
+        if let Ok(float_val) = trimmed.parse::<f64>() {
+            return Ok(Value::Number(float_val));
+        }
+
+        // Expression with arithmetic operators (e.g., "Order.quantity * Order.price")
+        // Detect: contains operators AND (contains field reference OR multiple tokens)
+        if self.is_expression(trimmed) {
+            return Ok(Value::Expression(trimmed.to_string()));
+        }
+
+        // Field reference (like User.Name)
         if trimmed.contains('.') {
             return Ok(Value::String(trimmed.to_string()));
         }
@@ -1401,53 +2223,20 @@ impl GRLParser {
             return Ok(Value::Array(vec![]));
         }
 
-        // Split by comma, handling quoted strings
-        let mut elements = Vec::new();
-        let mut current_element = String::new();
-        let mut in_quotes = false;
-        let mut quote_char = ' ';
-
-        for ch in inner.chars() {
-            match ch {
-                '"' | '\'' if !in_quotes => {
-                    in_quotes = true;
-                    quote_char = ch;
-                    current_element.push(ch);
-                }
-                c if in_quotes && c == quote_char => {
-                    in_quotes = false;
-                    current_element.push(ch);
-                }
-                ',' if !in_quotes => {
-                    if !current_element.trim().is_empty() {
-                        elements.push(current_element.trim().to_string());
-                    }
-                    current_element.clear();
-                }
-                _ => {
-                    current_element.push(ch);
-                }
-            }
-        }
-
-        // Don't forget the last element
-        if !current_element.trim().is_empty() {
-            elements.push(current_element.trim().to_string());
-        }
-
-        // Parse each element
+        // Split by top-level commas, so nested arrays and objects stay whole
         let mut array_values = Vec::new();
-        for elem in elements {
-            let value = self.parse_value(&elem)?;
-            array_values.push(value);
+        for elem in split_top_level(inner, b',') {
+            if !elem.trim().is_empty() {
+                array_values.push(self.parse_value(elem)?);
+            }
         }
 
         Ok(Value::Array(array_values))
     }
 
     fn parse_then_clause(&self, then_clause: &str) -> Result<Vec<ActionType>> {
-        let statements: Vec<&str> = then_clause
-            .split(';')
+        let statements: Vec<&str> = split_top_level(then_clause, b';')
+            .into_iter()
             .map(|s| s.trim())
             .filter(|s| !s.is_empty())
             .collect();
@@ -1462,6 +2251,70 @@ impl GRLParser {
         Ok(actions)
     }
 
+    /// Parse the `key: value, ...` fields between the braces of an object literal
+    ///
+    /// Keys may be quoted; values are parsed like any other literal, so objects
+    /// and arrays nest.
+    fn parse_object_fields(&self, body: &str) -> Result<HashMap<String, Value>> {
+        let mut fields = HashMap::new();
+        for field in split_top_level(body, b',') {
+            let field = field.trim();
+            if field.is_empty() {
+                continue;
+            }
+            let colon =
+                find_outside_strings(field, ":").ok_or_else(|| RuleEngineError::ParseError {
+                    message: format!("Invalid object field '{}'", field),
+                })?;
+            let key = field[..colon]
+                .trim()
+                .trim_matches(|c| c == '"' || c == '\'');
+            let value = self.parse_value(&field[colon + 1..])?;
+            fields.insert(key.to_string(), value);
+        }
+        Ok(fields)
+    }
+
+    /// Parse the arguments of `emit("Type", { key: value, ... })`
+    fn parse_emit(&self, args_str: &str) -> Result<ActionType> {
+        let args = split_arguments(args_str);
+        let event_type = match self.parse_value(args[0])? {
+            Value::String(event_type) if !event_type.is_empty() => event_type,
+            _ => {
+                return Err(RuleEngineError::ParseError {
+                    message: "emit requires an event type string".to_string(),
+                })
+            }
+        };
+
+        let payload = match &args[1..] {
+            [] => HashMap::new(),
+            [object] => {
+                let body = object
+                    .trim()
+                    .strip_prefix('{')
+                    .and_then(|body| body.strip_suffix('}'))
+                    .ok_or_else(|| RuleEngineError::ParseError {
+                        message: format!(
+                            "emit payload must be an object literal such as {{ id: Order.Id }}, got '{}'",
+                            object.trim()
+                        ),
+                    })?;
+                self.parse_object_fields(body)?
+            }
+            _ => {
+                return Err(RuleEngineError::ParseError {
+                    message: "emit takes an event type and an optional payload".to_string(),
+                })
+            }
+        };
+
+        Ok(ActionType::Emit {
+            event_type,
+            payload,
+        })
+    }
+
     fn parse_action_statement(&self, statement: &str) -> Result<ActionType> {
         let trimmed = statement.trim();
 
@@ -1484,8 +2337,23 @@ impl GRLParser {
             });
         }
 
+        // Setter without the `$` prefix: Object.setField(value)
+        if let Some((object, method, args_str)) = plain_setter_call(trimmed) {
+            return Ok(ActionType::MethodCall {
+                object: object.to_string(),
+                method: method.to_string(),
+                args: self.parse_method_args(args_str)?,
+            });
+        }
+
+        // Event emission: emit("Type", { key: value, ... }); checked before
+        // assignments since payload values may contain comparisons
+        if let Some(args_str) = emit_arguments(trimmed) {
+            return self.parse_emit(args_str);
+        }
+
         // Check for compound assignment operators first (+=, -=, etc.)
-        if let Some(plus_eq_pos) = trimmed.find("+=") {
+        if let Some(plus_eq_pos) = find_outside_strings(trimmed, "+=") {
             // Append operator: Field += Value
             let field = trimmed[..plus_eq_pos].trim().to_string();
             let value_str = trimmed[plus_eq_pos + 2..].trim();
@@ -1495,9 +2363,27 @@ impl GRLParser {
         }
 
         // Assignment: Field = Value
-        if let Some(eq_pos) = trimmed.find('=') {
+        if let Some(eq_pos) = find_outside_strings(trimmed, "=") {
             let field = trimmed[..eq_pos].trim().to_string();
             let value_str = trimmed[eq_pos + 1..].trim();
+
+            // Field = function(args): the function's result is stored in the field.
+            // now() and today() read the engine clock and stay expressions.
+            if let Some((function, args_str)) = function_call_parts(value_str) {
+                let function_lower = function.to_lowercase();
+                if matches!(function_lower.as_str(), "now" | "today") && args_str.trim().is_empty()
+                {
+                    return Ok(ActionType::Set {
+                        field,
+                        value: Value::Expression(format!("{}()", function_lower)),
+                    });
+                }
+                return Ok(ActionType::CallAndBind {
+                    field,
+                    function: function.to_string(),
+                    args: self.parse_method_args(args_str)?,
+                });
+            }
             let value = self.parse_value(value_str)?;
 
             return Ok(ActionType::Set { field, value });
@@ -1506,20 +2392,28 @@ impl GRLParser {
         // Function calls: update($Object), retract($Object), etc.
         if let Some(captures) = function_binding_regex().captures(trimmed) {
             let function_name = captures.get(1).unwrap();
-            let args_str = captures.get(2).unwrap_or("");
+            // The lazy capture stops at the first ')', which may sit inside a
+            // string argument, so take everything up to the closing paren instead
+            let args_str = match (trimmed.find('('), trimmed.strip_suffix(')')) {
+                (Some(open), Some(body)) if trimmed[..open].trim_end() == function_name => {
+                    body[open + 1..].trim()
+                }
+                _ => captures.get(2).unwrap_or(""),
+            };
 
             match function_name.to_lowercase().as_str() {
                 "retract" => {
-                    // Extract object name from $Object
+                    // Extract object name from $Object, Object or "Object"
                     let object_name = if let Some(stripped) = args_str.strip_prefix('$') {
                         stripped.to_string()
                     } else {
-                        args_str.to_string()
+                        args_str.trim_matches(|c| c == '"' || c == '\'').to_string()
                     };
                     Ok(ActionType::Retract {
                         object: object_name,
                     })
                 }
+                "halt" => Ok(ActionType::Halt),
                 "log" => {
                     let message = if args_str.is_empty() {
                         "Log message".to_string()
@@ -1547,7 +2441,7 @@ impl GRLParser {
                 }
                 "schedulerule" | "schedule_rule" => {
                     // Parse delay and target rule: ScheduleRule(5000, "next-rule")
-                    let parts: Vec<&str> = args_str.split(',').collect();
+                    let parts = split_arguments(args_str);
                     if parts.len() != 2 {
                         return Err(RuleEngineError::ParseError {
                             message: "ScheduleRule requires delay_ms and rule_name".to_string(),
@@ -1611,7 +2505,40 @@ impl GRLParser {
 
                     Ok(ActionType::SetWorkflowData { key, value })
                 }
+                "addscore" | "add_score" => {
+                    // Target field and weight: AddScore("Applicant.Risk", 25)
+                    let parts = split_arguments(args_str);
+                    if parts.len() != 2 {
+                        return Err(RuleEngineError::ParseError {
+                            message: "AddScore requires a target field and an amount".to_string(),
+                        });
+                    }
+
+                    let target = match self.parse_value(parts[0].trim())? {
+                        Value::String(s) | Value::Expression(s) => s,
+                        other => other.to_string(),
+                    };
+                    let amount = match self.parse_value(parts[1].trim())? {
+                        Value::Integer(i) => i as f64,
+                        Value::Number(f) => f,
+                        _ => {
+                            return Err(RuleEngineError::ParseError {
+                                message: "AddScore amount must be a number".to_string(),
+                            })
+                        }
+                    };
+
+                    Ok(ActionType::AddScore { target, amount })
+                }
                 _ => {
+                    if let Some(known) = &self.known_actions {
+                        if !known.contains(function_name) {
+                            return Err(RuleEngineError::ParseError {
+                                message: format!("Unknown action '{}'", function_name),
+                            });
+                        }
+                    }
+
                     // All other functions become custom actions
                     let params = if args_str.is_empty() {
                         HashMap::new()
@@ -1645,21 +2572,27 @@ impl GRLParser {
 
         // Handle expressions like: $TestCar.Speed + $TestCar.SpeedIncrement
         let mut args = Vec::new();
-        let parts: Vec<&str> = args_str.split(',').collect();
 
-        for part in parts {
+        for part in split_arguments(args_str) {
             let trimmed = part.trim();
 
             // Handle arithmetic expressions
-            if trimmed.contains('+')
-                || trimmed.contains('-')
-                || trimmed.contains('*')
-                || trimmed.contains('/')
+            if !trimmed.starts_with('"')
+                && (trimmed.contains('+')
+                    || trimmed.contains('-')
+                    || trimmed.contains('*')
+                    || trimmed.contains('/'))
             {
-                // For now, store as string - the engine will evaluate
-                args.push(Value::String(trimmed.to_string()));
+                // Evaluated by the engine against the current facts
+                args.push(Value::Expression(trimmed.to_string()));
             } else {
-                args.push(self.parse_value(trimmed)?);
+                match self.parse_value(trimmed)? {
+                    // An unquoted dotted name is a fact reference, not a literal
+                    Value::String(text) if !trimmed.starts_with(['"', '\'']) => {
+                        args.push(Value::Expression(text))
+                    }
+                    value => args.push(value),
+                }
             }
         }
 
@@ -1675,7 +2608,7 @@ impl GRLParser {
         }
 
         // Parse positional parameters as numbered args
-        let parts: Vec<&str> = args_str.split(',').collect();
+        let parts = split_arguments(args_str);
         for (i, part) in parts.iter().enumerate() {
             let trimmed = part.trim();
             let value = self.parse_value(trimmed)?;
@@ -1706,7 +2639,11 @@ impl GRLParser {
         let window = pattern.source.window.map(|w| StreamWindow {
             duration: w.duration,
             window_type: match w.window_type {
-                crate::parser::grl::stream_syntax::WindowType::Sliding => StreamWindowType::Sliding,
+                // GRL stream syntax does not produce hopping windows
+                crate::parser::grl::stream_syntax::WindowType::Sliding
+                | crate::parser::grl::stream_syntax::WindowType::Hopping { .. } => {
+                    StreamWindowType::Sliding
+                }
                 crate::parser::grl::stream_syntax::WindowType::Tumbling => {
                     StreamWindowType::Tumbling
                 }
@@ -1727,7 +2664,11 @@ impl GRLParser {
 
 #[cfg(test)]
 mod tests {
-    use super::GRLParser;
+    use super::{function_call_parts, offset_to_line_col, GRLParser};
+    use crate::engine::rule::Salience;
+    use crate::errors::RuleEngineError;
+    use crate::types::{ActionType, Value};
+    use std::collections::HashMap;
 
     #[test]
     fn test_parse_simple_rule() {
@@ -2048,6 +2989,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_not_in_operator() {
+        let grl = r#"
+        rule "TestNotInOperator" salience 75 {
+            when
+                User.role not in ["banned", "suspended"]
+            then
+                User.access = "granted";
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        assert_eq!(rules.len(), 1);
+        let rule = &rules[0];
+        assert_eq!(rule.name, "TestNotInOperator");
+
+        match &rule.conditions {
+            crate::engine::rule::ConditionGroup::Single(cond) => {
+                assert!(matches!(
+                    &cond.expression,
+                    crate::engine::rule::ConditionExpression::Field(f) if f == "User.role"
+                ));
+                assert_eq!(cond.operator, crate::types::Operator::NotIn);
+
+                match &cond.value {
+                    crate::types::Value::Array(arr) => {
+                        assert_eq!(arr.len(), 2);
+                        assert_eq!(arr[0], crate::types::Value::String("banned".to_string()));
+                        assert_eq!(arr[1], crate::types::Value::String("suspended".to_string()));
+                    }
+                    _ => panic!("Expected Array value, got {:?}", cond.value),
+                }
+
+                let member = crate::types::Value::String("banned".to_string());
+                let other = crate::types::Value::String("admin".to_string());
+                assert!(!cond.operator.evaluate(&member, &cond.value));
+                assert!(cond.operator.evaluate(&other, &cond.value));
+            }
+            _ => panic!("Expected Single condition, got: {:?}", rule.conditions),
+        }
+    }
+
+    #[test]
+    fn test_parse_contains_all_and_any_operators() {
+        use crate::engine::rule::{ConditionExpression, ConditionGroup};
+        use crate::types::{Operator, Value};
+
+        let grl = r#"
+        rule "Permissions" {
+            when
+                User.Roles contains_all ["read", "write"] &&
+                User.Roles contains_any ["admin", "owner"]
+            then
+                User.CanEdit = true;
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        let ConditionGroup::Compound { left, right, .. } = &rules[0].conditions else {
+            panic!(
+                "Expected Compound condition, got: {:?}",
+                rules[0].conditions
+            );
+        };
+        for (group, operator) in [
+            (left, Operator::ContainsAll),
+            (right, Operator::ContainsAny),
+        ] {
+            match group.as_ref() {
+                ConditionGroup::Single(cond) => {
+                    assert!(matches!(
+                        &cond.expression,
+                        ConditionExpression::Field(f) if f == "User.Roles"
+                    ));
+                    assert_eq!(cond.operator, operator);
+                    assert!(matches!(&cond.value, Value::Array(arr) if arr.len() == 2));
+                }
+                other => panic!("Expected Single condition, got: {:?}", other),
+            }
+        }
+    }
+
     #[test]
     fn test_parse_startswith_endswith_operators() {
         let grl = r#"
@@ -2094,4 +3117,658 @@ mod tests {
             _ => panic!("Expected Compound condition, got: {:?}", rule.conditions),
         }
     }
+
+    #[test]
+    fn test_parse_log_template_with_braces() {
+        let grl = r#"
+        rule "TraceScore" salience 5 {
+            when
+                User.Score > 10
+            then
+                Log("User {User.Name} scored {User.Score}");
+        }
+
+        rule "Second" {
+            when
+                User.Score > 100
+            then
+                User.vip = true;
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        assert_eq!(rules.len(), 2);
+        match &rules[0].actions[0] {
+            crate::types::ActionType::Log { message } => {
+                assert_eq!(message, "User {User.Name} scored {User.Score}");
+            }
+            other => panic!("Expected Log action, got: {:?}", other),
+        }
+        assert_eq!(rules[1].name, "Second");
+    }
+
+    #[test]
+    fn test_parse_safe_navigation_field() {
+        let grl = r#"
+        rule "NotifyCustomer" {
+            when
+                Order?.Customer?.Email != null
+            then
+                Order.notify = true;
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        match &rules[0].conditions {
+            crate::engine::rule::ConditionGroup::Single(cond) => {
+                assert!(matches!(
+                    &cond.expression,
+                    crate::engine::rule::ConditionExpression::Field(f) if f == "Order?.Customer?.Email"
+                ));
+                assert_eq!(cond.operator, crate::types::Operator::NotEqual);
+                assert_eq!(cond.value, crate::types::Value::Null);
+            }
+            other => panic!("Expected Single condition, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_dynamic_salience() {
+        let grl = r#"
+        rule "BigOrdersFirst" salience Order.Total {
+            when
+                Order.status == "pending"
+            then
+                Order.reviewed = true;
+        }
+
+        rule "ScaledPriority" salience (Order.Total / 100) no-loop {
+            when
+                Order.status == "pending"
+            then
+                Order.flagged = true;
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(
+            rules[0].salience_source,
+            Salience::Dynamic("Order.Total".to_string())
+        );
+        assert_eq!(
+            rules[1].salience_source,
+            Salience::Dynamic("Order.Total / 100".to_string())
+        );
+        assert!(rules[1].no_loop);
+    }
+
+    #[test]
+    fn test_parse_salience_from_global() {
+        let grl = r#"
+        rule Discount salience $HighPriority {
+            when
+                Order.Total > 100
+            then
+                Order.Discount = 0.1;
+        }
+        "#;
+
+        let mut globals = HashMap::new();
+        globals.insert("HighPriority".to_string(), Value::Integer(90));
+        let rules = GRLParser::parse_rules_with_globals(grl, &globals).unwrap();
+        assert_eq!(rules[0].salience, 90);
+        assert_eq!(rules[0].salience_source, Salience::Static(90));
+
+        // Undefined global
+        assert!(GRLParser::parse_rules_with_globals(grl, &HashMap::new()).is_err());
+
+        // Non-integer global
+        globals.insert(
+            "HighPriority".to_string(),
+            Value::String("high".to_string()),
+        );
+        assert!(GRLParser::parse_rules_with_globals(grl, &globals).is_err());
+    }
+
+    #[test]
+    fn test_parse_range_literals() {
+        let grl = r#"
+        rule "MonthlyPromo" {
+            when
+                Order.Day in 1..=31
+            then
+                Promotion.ValidDays = 1..31;
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        let rule = &rules[0];
+
+        match &rule.conditions {
+            crate::engine::rule::ConditionGroup::Single(cond) => {
+                assert_eq!(cond.operator, crate::types::Operator::In);
+                assert_eq!(
+                    cond.value,
+                    Value::Range {
+                        start: 1.0,
+                        end: 31.0,
+                        inclusive: true
+                    }
+                );
+            }
+            other => panic!("Expected single condition, got {:?}", other),
+        }
+
+        match &rule.actions[0] {
+            crate::types::ActionType::Set { field, value } => {
+                assert_eq!(field, "Promotion.ValidDays");
+                assert_eq!(
+                    value,
+                    &Value::Range {
+                        start: 1.0,
+                        end: 31.0,
+                        inclusive: false
+                    }
+                );
+            }
+            other => panic!("Expected Set action, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_string_escapes() {
+        let grl = r#"
+        rule "Greeting" {
+            when
+                Message.Text == "say \"hi\" && bye"
+            then
+                Log("line1\nline2, (done) x=1");
+                notify("a, b", 2);
+                Log("first
+  second");
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        let rule = &rules[0];
+
+        match &rule.conditions {
+            crate::engine::rule::ConditionGroup::Single(cond) => {
+                assert_eq!(cond.value, Value::String("say \"hi\" && bye".to_string()));
+            }
+            other => panic!("Expected single condition, got {:?}", other),
+        }
+
+        assert_eq!(rule.actions.len(), 3);
+        match &rule.actions[0] {
+            crate::types::ActionType::Log { message } => {
+                assert_eq!(message, "line1\nline2, (done) x=1");
+            }
+            other => panic!("Expected Log action, got {:?}", other),
+        }
+        match &rule.actions[1] {
+            crate::types::ActionType::Custom { params, .. } => {
+                assert_eq!(params.get("0"), Some(&Value::String("a, b".to_string())));
+                assert_eq!(params.get("1"), Some(&Value::Integer(2)));
+            }
+            other => panic!("Expected Custom action, got {:?}", other),
+        }
+        match &rule.actions[2] {
+            crate::types::ActionType::Log { message } => {
+                assert_eq!(message, "first\n  second");
+            }
+            other => panic!("Expected Log action, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_quoted_method_args_with_operators() {
+        let grl = r#"
+        rule "Tag" {
+            when Release.Ready == true
+            then
+                $Release.setVersion("v1.2-beta", Release.Build + 1);
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        match &rules[0].actions[0] {
+            crate::types::ActionType::MethodCall { args, .. } => {
+                assert_eq!(args[0], Value::String("v1.2-beta".to_string()));
+                assert_eq!(args[1], Value::Expression("Release.Build + 1".to_string()));
+            }
+            other => panic!("Expected method call, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_type_check_operators() {
+        let cases = [
+            ("is_number", crate::types::Operator::IsNumber),
+            ("is_string", crate::types::Operator::IsString),
+            ("is_boolean", crate::types::Operator::IsBoolean),
+            ("is_array", crate::types::Operator::IsArray),
+            ("is_object", crate::types::Operator::IsObject),
+            ("is null", crate::types::Operator::IsNull),
+            ("is not null", crate::types::Operator::IsNotNull),
+            ("is_not_null", crate::types::Operator::IsNotNull),
+        ];
+
+        for (keyword, expected) in cases {
+            let grl = format!(
+                r#"
+                rule "TypeGuard" {{
+                    when
+                        Input.Value {}
+                    then
+                        Input.Checked = true;
+                }}
+                "#,
+                keyword
+            );
+
+            let rules = GRLParser::parse_rules(&grl).unwrap();
+            match &rules[0].conditions {
+                crate::engine::rule::ConditionGroup::Single(cond) => {
+                    assert!(matches!(
+                        &cond.expression,
+                        crate::engine::rule::ConditionExpression::Field(f) if f == "Input.Value"
+                    ));
+                    assert_eq!(cond.operator, expected);
+                }
+                other => panic!("Expected single condition, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_defglobals() {
+        let grl = r#"
+        defglobal ?*MAX_SPEED* = 120;
+        defglobal ?*ZONE* = "school";
+
+        rule "Speeding" {
+            when
+                Car.Speed > ?*MAX_SPEED*
+            then
+                ?*LAST_ZONE* = ?*ZONE*;
+        }
+        "#;
+
+        let globals = GRLParser::parse_globals(grl).unwrap();
+        assert_eq!(
+            globals,
+            vec![
+                ("MAX_SPEED".to_string(), Value::Integer(120)),
+                ("ZONE".to_string(), Value::String("school".to_string())),
+            ]
+        );
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        assert_eq!(rules.len(), 1);
+        match &rules[0].conditions {
+            crate::engine::rule::ConditionGroup::Single(cond) => {
+                assert_eq!(cond.value, Value::Expression("?*MAX_SPEED*".to_string()));
+            }
+            other => panic!("Expected single condition, got {:?}", other),
+        }
+        assert_eq!(
+            rules[0].actions[0],
+            crate::types::ActionType::Set {
+                field: "?*LAST_ZONE*".to_string(),
+                value: Value::Expression("?*ZONE*".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rules_strict_rejects_unknown_actions() {
+        let grl = r#"
+        rule "Notify" {
+            when
+                Order.Total > 100
+            then
+                SendEmial("sales@example.com");
+                Retract("Order");
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        assert!(matches!(
+            &rules[0].actions[0],
+            ActionType::Custom { action_type, .. } if action_type == "SendEmial"
+        ));
+
+        let err = GRLParser::parse_rules_strict(grl, &["SendEmail"]).unwrap_err();
+        assert!(err.to_string().contains("SendEmial"));
+
+        let fixed = grl.replace("SendEmial", "SendEmail");
+        let rules = GRLParser::parse_rules_strict(&fixed, &["SendEmail"]).unwrap();
+        assert_eq!(rules[0].actions.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_string_concatenation_action() {
+        let grl = r#"
+        rule "Greet" {
+            when
+                User.Active == true
+            then
+                User.Greeting = "Hello, " + User.Name;
+                User.Tag = "a" + "b";
+                User.Label = "a + b";
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        let values: Vec<&Value> = rules[0]
+            .actions
+            .iter()
+            .map(|action| match action {
+                ActionType::Set { value, .. } => value,
+                other => panic!("unexpected action {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                &Value::Expression(r#""Hello, " + User.Name"#.to_string()),
+                &Value::Expression(r#""a" + "b""#.to_string()),
+                &Value::String("a + b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_xor_precedence_and_associativity() {
+        use crate::engine::rule::ConditionGroup;
+        use crate::types::LogicalOperator;
+
+        let parse = |when: &str| {
+            let grl = format!(
+                "rule \"R\" {{\n    when\n        {}\n    then\n        X.done = true;\n}}",
+                when
+            );
+            GRLParser::parse_rules(&grl).unwrap().remove(0).conditions
+        };
+        let operator = |group: &ConditionGroup| match group {
+            ConditionGroup::Compound { operator, .. } => operator.clone(),
+            other => panic!("Expected compound condition, got {:?}", other),
+        };
+
+        // Chains of three nest to the left, in both spellings
+        for when in [
+            "F.a == true ^^ F.b == true ^^ F.c == true",
+            "F.a == true xor F.b == true XOR F.c == true",
+        ] {
+            let group = parse(when);
+            match &group {
+                ConditionGroup::Compound {
+                    left,
+                    operator: LogicalOperator::Xor,
+                    right,
+                } => {
+                    assert_eq!(operator(left), LogicalOperator::Xor);
+                    assert!(matches!(**right, ConditionGroup::Single(_)));
+                }
+                other => panic!("Expected XOR chain, got {:?}", other),
+            }
+
+            // (a ^^ b) ^^ c holds when an odd number of sides hold
+            for (a, b, c, expected) in [
+                (true, false, false, true),
+                (true, true, false, false),
+                (true, true, true, true),
+                (false, false, false, false),
+            ] {
+                let mut flags = HashMap::new();
+                flags.insert("a".to_string(), Value::Boolean(a));
+                flags.insert("b".to_string(), Value::Boolean(b));
+                flags.insert("c".to_string(), Value::Boolean(c));
+                let mut facts = HashMap::new();
+                facts.insert("F".to_string(), Value::Object(flags));
+                assert_eq!(group.evaluate(&facts), expected, "{} {} {}", a, b, c);
+            }
+        }
+
+        // AND binds tighter than XOR, which binds tighter than OR
+        let group = parse("F.a == 1 || F.b == 1 ^^ F.c == 1 && F.d == 1");
+        assert_eq!(operator(&group), LogicalOperator::Or);
+        if let ConditionGroup::Compound { right, .. } = &group {
+            assert_eq!(operator(right), LogicalOperator::Xor);
+            if let ConditionGroup::Compound { right, .. } = &**right {
+                assert_eq!(operator(right), LogicalOperator::And);
+            }
+        }
+
+        // "xor" inside a string or a field name is not an operator
+        let group = parse(r#"F.mode == "a xor b" && F.xor_flag == true"#);
+        assert_eq!(operator(&group), LogicalOperator::And);
+    }
+
+    #[test]
+    fn test_parse_description_and_metadata() {
+        let grl = r#"
+        @metadata(owner="pricing", version="2")
+        rule Discount "Ten percent off large orders" salience 10 @metadata(jira-ticket="PRC-42") {
+            when Order.Total > 100
+            then Order.Discount = 0.1;
+        }
+
+        rule "Plain" {
+            when Order.Total > 0
+            then Order.Seen = true;
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        assert_eq!(rules.len(), 2);
+
+        let discount = &rules[0];
+        assert_eq!(discount.name, "Discount");
+        assert_eq!(discount.salience, 10);
+        assert_eq!(
+            discount.description.as_deref(),
+            Some("Ten percent off large orders")
+        );
+        assert_eq!(discount.metadata.len(), 3);
+        assert_eq!(discount.metadata["owner"], "pricing");
+        assert_eq!(discount.metadata["version"], "2");
+        assert_eq!(discount.metadata["jira-ticket"], "PRC-42");
+
+        let plain = &rules[1];
+        assert_eq!(plain.description, None);
+        assert!(plain.metadata.is_empty());
+
+        assert!(GRLParser::parse_rules(
+            r#"rule Bad @metadata(owner) { when A.b == 1 then A.c = 2; }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_base64_bytes_literal() {
+        let grl = r#"
+        rule "KnownHash" {
+            when
+                Token.Hash == b64"SGVsbG8="
+            then
+                Token.Trusted = true;
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        let hello = crate::types::Value::Bytes(b"Hello".to_vec());
+        match &rules[0].conditions {
+            crate::engine::rule::ConditionGroup::Single(cond) => {
+                assert_eq!(cond.value, hello);
+                assert!(cond.operator.evaluate(&hello, &cond.value));
+                assert!(!cond
+                    .operator
+                    .evaluate(&crate::types::Value::Bytes(b"Hell".to_vec()), &cond.value));
+                // Bytes have no ordering
+                assert!(!crate::types::Operator::GreaterThan.evaluate(&hello, &cond.value));
+            }
+            _ => panic!("Expected single condition"),
+        }
+
+        // Display writes the literal back in parseable form
+        assert_eq!(hello.to_string(), r#"b64"SGVsbG8=""#);
+        assert_eq!(
+            crate::types::Value::parse_bytes_literal(r#"b64"SGk""#),
+            Some(b"Hi".to_vec())
+        );
+        assert!(GRLParser::parse_rules(
+            r#"rule "Bad" { when Token.Hash == b64"S" then Token.Trusted = true; }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_rule_to_grl_round_trips() {
+        let grl = r#"
+        rule "Big Order" "Flag large orders" salience 20 no-loop agenda-group "checks" tags ["orders"] {
+            when
+                (Order.Total > 1000.0 || Order.Items count >= 10) && !(Customer.Tier == "gold" && Customer.Years > 2) && Order.Region in ["EU", "UK"]
+            then
+                Order.Flagged = true;
+                Order.Note = "review: large";
+                Log("Order flagged");
+        }
+        "#;
+
+        let rule = GRLParser::parse_rule(grl).unwrap();
+        let exported = rule.to_grl();
+        let reparsed = GRLParser::parse_rule(&exported).unwrap();
+
+        assert_eq!(reparsed.to_grl(), exported);
+        assert_eq!(reparsed.name, "Big Order");
+        assert_eq!(reparsed.description.as_deref(), Some("Flag large orders"));
+        assert_eq!(reparsed.salience, 20);
+        assert!(reparsed.no_loop);
+        assert_eq!(reparsed.agenda_group.as_deref(), Some("checks"));
+        assert_eq!(reparsed.tags, vec!["orders".to_string()]);
+        assert_eq!(reparsed.actions.len(), 3);
+        assert!(exported.contains("(Order.Total > 1000.0 || Order.Items count >= 10) && "));
+        assert!(exported.contains("!(Customer.Tier == \"gold\" && Customer.Years > 2)"));
+
+        // The exported rule behaves like the original
+        let facts = crate::engine::facts::Facts::new();
+        facts.set("Order.Total", crate::types::Value::Number(1500.0));
+        facts.set("Order.Items", crate::types::Value::Array(vec![]));
+        facts.set(
+            "Order.Region",
+            crate::types::Value::String("EU".to_string()),
+        );
+        facts.set(
+            "Customer.Tier",
+            crate::types::Value::String("silver".to_string()),
+        );
+        facts.set("Customer.Years", crate::types::Value::Integer(5));
+        let evaluator =
+            crate::engine::condition_evaluator::ConditionEvaluator::with_builtin_functions();
+        assert!(evaluator
+            .evaluate_conditions(&rule.conditions, &facts)
+            .unwrap());
+        assert!(evaluator
+            .evaluate_conditions(&reparsed.conditions, &facts)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_trailing_commas_and_semicolons_are_ignored() {
+        let grl = r#"
+        rule "Sloppy" {
+            when Car.Speed > 0
+            then
+                $Car.setLimits(1, 2,);
+                setX(1, 2,);
+                ScheduleRule(1000, "Next",);
+                Car.Checked = true;;
+        }
+        "#;
+
+        let rules = GRLParser::parse_rules(grl).unwrap();
+        let actions = &rules[0].actions;
+        assert_eq!(actions.len(), 4);
+        match &actions[0] {
+            ActionType::MethodCall { method, args, .. } => {
+                assert_eq!(method, "setLimits");
+                assert_eq!(args, &vec![Value::Integer(1), Value::Integer(2)]);
+            }
+            other => panic!("Expected method call, got {:?}", other),
+        }
+        match &actions[1] {
+            ActionType::Custom {
+                action_type,
+                params,
+            } => {
+                assert_eq!(action_type, "setX");
+                let expected: HashMap<String, Value> = [
+                    ("0".to_string(), Value::Integer(1)),
+                    ("1".to_string(), Value::Integer(2)),
+                ]
+                .into_iter()
+                .collect();
+                assert_eq!(params, &expected);
+            }
+            other => panic!("Expected custom action, got {:?}", other),
+        }
+        assert!(matches!(
+            &actions[2],
+            ActionType::ScheduleRule { delay_ms: 1000, rule_name } if rule_name == "Next"
+        ));
+        assert!(matches!(&actions[3], ActionType::Set { field, .. } if field == "Car.Checked"));
+    }
+
+    #[test]
+    fn test_parse_errors_report_line_and_column() {
+        assert_eq!(offset_to_line_col("ab\ncd\néf", 0), (1, 1));
+        assert_eq!(offset_to_line_col("ab\ncd\néf", 4), (2, 2));
+        assert_eq!(offset_to_line_col("ab\ncd\néf", 8), (3, 2));
+
+        let grl = r#"
+rule "Valid" {
+    when A.X > 1
+    then A.Y = true;
+}
+
+    rule "Broken" {
+        when A.X > 1
+        then ScheduleRule(1000);
+    }
+"#;
+        let err = GRLParser::parse_rules(grl).unwrap_err();
+        match &err {
+            RuleEngineError::ParseErrorAt { line, column, .. } => {
+                assert_eq!((*line, *column), (7, 5));
+            }
+            other => panic!("Expected a located parse error, got {:?}", other),
+        }
+        assert!(err
+            .to_string()
+            .starts_with("Parse error at line 7, col 5: ScheduleRule"));
+
+        let unclosed = "rule \"Open\" {\n    when A.X > 1\n    then A.Y = true;\n";
+        assert_eq!(
+            GRLParser::parse_rules(unclosed).unwrap_err().to_string(),
+            "Parse error at line 1, col 1: Missing closing brace"
+        );
+    }
+
+    #[test]
+    fn test_function_call_parts_requires_a_single_call() {
+        assert_eq!(
+            function_call_parts("calculateScore(Order.Items, 2)"),
+            Some(("calculateScore", "Order.Items, 2"))
+        );
+        assert_eq!(
+            function_call_parts("f(g(x), \")\")"),
+            Some(("f", "g(x), \")\""))
+        );
+        assert_eq!(function_call_parts("f(a) * g(b)"), None);
+        assert_eq!(function_call_parts("Order.total()"), None);
+        assert_eq!(function_call_parts("(a + b)"), None);
+    }
 }
@@ -54,8 +54,12 @@ struct RuleAttributes {
     pub activation_group: Option<String>,
     pub date_effective: Option<DateTime<Utc>>,
     pub date_expires: Option<DateTime<Utc>>,
+    pub description: Option<String>,
 }
 
+/// Opening of a rule metadata annotation
+const METADATA_ANNOTATION: &str = "@metadata(";
+
 impl GRLParserNoRegex {
     /// Parse multiple rules from GRL text
     pub fn parse_rules(grl_text: &str) -> Result<Vec<Rule>> {
@@ -109,6 +113,7 @@ impl GRLParserNoRegex {
 
     fn parse_single_rule(grl_text: &str) -> Result<Rule> {
         let cleaned = clean_text(grl_text);
+        let (cleaned, metadata) = take_metadata_annotations(&cleaned)?;
 
         // Find "rule" keyword
         let rule_pos =
@@ -174,6 +179,10 @@ impl GRLParserNoRegex {
         if let Some(date_expires) = attributes.date_expires {
             rule = rule.with_date_expires(date_expires);
         }
+        if let Some(description) = attributes.description {
+            rule = rule.with_description(description);
+        }
+        rule.metadata = metadata;
 
         Ok(rule)
     }
@@ -272,7 +281,7 @@ fn split_into_rules(grl_text: &str) -> Vec<String> {
                 let brace_abs = abs_pos + brace_pos;
 
                 if let Some(close_pos) = literal_search::find_matching_brace(grl_text, brace_abs) {
-                    let rule_text = &grl_text[abs_pos..=close_pos];
+                    let rule_text = &grl_text[annotations_start(grl_text, abs_pos)..=close_pos];
                     rules.push(rule_text.to_string());
                     i = close_pos + 1;
                     continue;
@@ -285,6 +294,57 @@ fn split_into_rules(grl_text: &str) -> Vec<String> {
     rules
 }
 
+/// Start of the `@metadata(...)` annotations directly preceding the `rule`
+/// keyword at `rule_pos`, or `rule_pos` itself when there are none
+fn annotations_start(text: &str, rule_pos: usize) -> usize {
+    let mut start = rule_pos;
+    loop {
+        let before = text[..start].trim_end();
+        match before.rfind(METADATA_ANNOTATION) {
+            Some(pos) if before.ends_with(')') && !before[pos..].contains('\n') => start = pos,
+            _ => return start,
+        }
+    }
+}
+
+/// Remove the `@metadata(key="value", ...)` annotations from a rule's header,
+/// returning the remaining text and the annotations
+fn take_metadata_annotations(rule_text: &str) -> Result<(String, HashMap<String, String>)> {
+    let header_end = rule_text.find('{').unwrap_or(rule_text.len());
+    let mut header = &rule_text[..header_end];
+    let mut remaining = String::with_capacity(rule_text.len());
+    let mut metadata = HashMap::new();
+
+    while let Some(pos) = header.find(METADATA_ANNOTATION) {
+        remaining.push_str(&header[..pos]);
+        let args = &header[pos + METADATA_ANNOTATION.len()..];
+        let close = args.find(')').ok_or_else(|| RuleEngineError::ParseError {
+            message: "Unclosed @metadata annotation".to_string(),
+        })?;
+
+        for entry in split_top_level_comma(&args[..close])? {
+            let (key, value) = entry
+                .split_once('=')
+                .filter(|(key, _)| !key.trim().is_empty())
+                .ok_or_else(|| RuleEngineError::ParseError {
+                    message: format!("Invalid @metadata entry '{}', expected key=\"value\"", entry),
+                })?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            metadata.insert(key.trim().to_string(), value.to_string());
+        }
+
+        header = &args[close + 1..];
+    }
+
+    remaining.push_str(header);
+    remaining.push_str(&rule_text[header_end..]);
+    Ok((remaining, metadata))
+}
+
 /// Check if a position is inside a single-line comment
 fn is_inside_comment(text: &str, pos: usize) -> bool {
     // Find the start of the current line
@@ -433,6 +493,11 @@ fn parse_rule_attributes(attrs: &str) -> Result<RuleAttributes> {
     result.no_loop = has_keyword(&cleaned, "no-loop");
     result.lock_on_active = has_keyword(&cleaned, "lock-on-active");
 
+    // A quoted string right after the rule name is its description
+    if let Some(rest) = attrs.trim_start().strip_prefix('"') {
+        result.description = rest.find('"').map(|end| rest[..end].to_string());
+    }
+
     // Parse quoted attributes from original (not cleaned)
     result.agenda_group = extract_quoted_attribute(attrs, "agenda-group");
     result.activation_group = extract_quoted_attribute(attrs, "activation-group");
@@ -685,6 +750,11 @@ fn parse_when_clause(when_clause: &str) -> Result<ConditionGroup> {
         return parse_or_parts(parts);
     }
 
+    // Parse XOR (between OR and AND)
+    if let Some(parts) = split_logical_operator(clause, "^^") {
+        return parse_xor_parts(parts);
+    }
+
     // Parse AND
     if let Some(parts) = split_logical_operator(clause, "&&") {
         return parse_and_parts(parts);
@@ -787,6 +857,13 @@ fn split_logical_operator(clause: &str, operator: &str) -> Option<Vec<String>> {
                     current.clear();
                     continue;
                 }
+                // Keyword form of XOR: a xor b
+                if operator == "^^" && ch.is_whitespace() && keyword_follows(&chars, "xor") {
+                    chars.nth(2); // consume "xor"
+                    parts.push(current.trim().to_string());
+                    current.clear();
+                    continue;
+                }
                 current.push(ch);
             }
             _ => {
@@ -806,6 +883,39 @@ fn split_logical_operator(clause: &str, operator: &str) -> Option<Vec<String>> {
     }
 }
 
+/// Check whether `chars` continues with `keyword` (any case) followed by whitespace
+fn keyword_follows(chars: &std::iter::Peekable<std::str::Chars<'_>>, keyword: &str) -> bool {
+    let mut ahead = chars.clone();
+    keyword
+        .chars()
+        .all(|k| ahead.next().is_some_and(|c| c.eq_ignore_ascii_case(&k)))
+        && ahead.next().is_some_and(char::is_whitespace)
+}
+
+/// Parse XOR parts, left-associative
+fn parse_xor_parts(parts: Vec<String>) -> Result<ConditionGroup> {
+    let mut conditions = Vec::new();
+    for part in parts {
+        conditions.push(parse_when_clause(&part)?);
+    }
+
+    if conditions.is_empty() {
+        return Err(RuleEngineError::ParseError {
+            message: "No conditions in XOR".to_string(),
+        });
+    }
+
+    let mut iter = conditions.into_iter();
+    let mut result = iter
+        .next()
+        .expect("Iterator cannot be empty after empty check");
+    for condition in iter {
+        result = ConditionGroup::xor(result, condition);
+    }
+
+    Ok(result)
+}
+
 /// Parse OR parts
 fn parse_or_parts(parts: Vec<String>) -> Result<ConditionGroup> {
     let mut conditions = Vec::new();
@@ -1035,7 +1145,18 @@ fn find_matching_paren(text: &str, open_pos: usize) -> Option<usize> {
 /// Split condition into field, operator, value
 fn split_condition(clause: &str) -> Result<(&str, &str, &str)> {
     let operators = [
-        ">=", "<=", "==", "!=", ">", "<", "contains", "matches", "in",
+        ">=",
+        "<=",
+        "==",
+        "!=",
+        ">",
+        "<",
+        "contains_all",
+        "contains_any",
+        "contains",
+        "matches",
+        "not in",
+        "in",
     ];
 
     for op in &operators {
@@ -1324,6 +1445,15 @@ fn parse_value(value_str: &str) -> Result<Value> {
         return parse_array_literal(trimmed);
     }
 
+    // Base64 bytes literal: b64"SGVsbG8="
+    if trimmed.starts_with("b64\"") {
+        return Value::parse_bytes_literal(trimmed)
+            .map(Value::Bytes)
+            .ok_or_else(|| RuleEngineError::ParseError {
+                message: format!("Invalid base64 literal: {}", trimmed),
+            });
+    }
+
     // String literal
     if (trimmed.starts_with('"') && trimmed.ends_with('"'))
         || (trimmed.starts_with('\'') && trimmed.ends_with('\''))
@@ -1345,6 +1475,12 @@ fn parse_value(value_str: &str) -> Result<Value> {
         return Ok(Value::Null);
     }
 
+    // Decimal literal: 19.99d
+    #[cfg(feature = "decimal")]
+    if let Some(decimal) = Value::parse_decimal(trimmed) {
+        return Ok(Value::Decimal(decimal));
+    }
+
     // Integer
     if let Ok(int_val) = trimmed.parse::<i64>() {
         return Ok(Value::Integer(int_val));
@@ -1857,4 +1993,112 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parse_not_in_operator() {
+        let grl = r#"
+        rule "TestNotInOperator" {
+            when
+                User.role not in ["banned", "suspended"]
+            then
+                User.access = "granted";
+        }
+        "#;
+
+        let rules = GRLParserNoRegex::parse_rules(grl).unwrap();
+        assert_eq!(rules.len(), 1);
+        match &rules[0].conditions {
+            ConditionGroup::Single(cond) => {
+                assert_eq!(cond.field, "User.role");
+                assert_eq!(cond.operator, crate::types::Operator::NotIn);
+                match &cond.value {
+                    Value::Array(arr) => assert_eq!(arr.len(), 2),
+                    _ => panic!("Expected Array value, got {:?}", cond.value),
+                }
+            }
+            _ => panic!("Expected Single condition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_contains_all_and_any_operators() {
+        use crate::types::Operator;
+
+        let grl = r#"
+        rule "Permissions" {
+            when
+                User.Roles contains_all ["read", "write"] &&
+                User.Roles contains_any ["admin", "owner"]
+            then
+                User.CanEdit = true;
+        }
+        "#;
+
+        let rules = GRLParserNoRegex::parse_rules(grl).unwrap();
+        let ConditionGroup::Compound { left, right, .. } = &rules[0].conditions else {
+            panic!("Expected Compound condition, got: {:?}", rules[0].conditions);
+        };
+        for (group, operator) in [(left, Operator::ContainsAll), (right, Operator::ContainsAny)] {
+            match group.as_ref() {
+                ConditionGroup::Single(cond) => {
+                    assert_eq!(cond.field, "User.Roles");
+                    assert_eq!(cond.operator, operator);
+                    assert!(matches!(&cond.value, Value::Array(arr) if arr.len() == 2));
+                }
+                other => panic!("Expected Single condition, got: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_xor_chain() {
+        use crate::types::LogicalOperator;
+
+        let grl = r#"
+        rule "ExactlyOneFlag" {
+            when
+                F.a == true ^^ F.b == true xor F.c == true
+            then
+                F.ok = true;
+        }
+        "#;
+
+        let rules = GRLParserNoRegex::parse_rules(grl).unwrap();
+        match &rules[0].conditions {
+            ConditionGroup::Compound {
+                left,
+                operator: LogicalOperator::Xor,
+                right,
+            } => {
+                assert!(matches!(
+                    **left,
+                    ConditionGroup::Compound {
+                        operator: LogicalOperator::Xor,
+                        ..
+                    }
+                ));
+                assert!(matches!(**right, ConditionGroup::Single(_)));
+            }
+            other => panic!("Expected XOR chain, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_description_and_metadata() {
+        let grl = r#"
+        @metadata(owner="pricing")
+        rule Discount "Ten percent off large orders" salience 10 {
+            when Order.Total > 100
+            then Order.Discount = 0.1;
+        }
+        "#;
+
+        let rules = GRLParserNoRegex::parse_rules(grl).unwrap();
+        assert_eq!(
+            rules[0].description.as_deref(),
+            Some("Ten percent off large orders")
+        );
+        assert_eq!(rules[0].metadata["owner"], "pricing");
+        assert_eq!(rules[0].salience, 10);
+    }
 }
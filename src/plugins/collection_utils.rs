@@ -35,6 +35,7 @@ impl CollectionUtilsPlugin {
                     "ArrayFilter".to_string(),
                     "ArrayMap".to_string(),
                     "ArrayFind".to_string(),
+                    "ArrayReduce".to_string(),
                     "ObjectKeys".to_string(),
                     "ObjectValues".to_string(),
                     "ObjectMerge".to_string(),
@@ -47,6 +48,7 @@ impl CollectionUtilsPlugin {
                     "reverse".to_string(),
                     "join".to_string(),
                     "slice".to_string(),
+                    "reduce".to_string(),
                     "keys".to_string(),
                     "values".to_string(),
                 ],
@@ -179,6 +181,23 @@ impl RulePlugin for CollectionUtilsPlugin {
             Ok(())
         });
 
+        // ArrayReduce - Fold array into a single value
+        engine.register_action_handler("ArrayReduce", |params, facts| {
+            let input = get_string_param(params, "input", "0")?;
+            let initial = get_value_param(params, facts, "initial", "1")?;
+            let operation = get_string_param(params, "operation", "2")?;
+            let output = get_string_param(params, "output", "3")?;
+            let separator = get_string_param(params, "separator", "4").unwrap_or_default();
+
+            if let Some(value) = facts.get(&input) {
+                if let Value::Array(arr) = value {
+                    let reduced = reduce_values(&arr, initial, &operation, &separator)?;
+                    facts.set_nested(&output, reduced)?;
+                }
+            }
+            Ok(())
+        });
+
         // ObjectKeys - Get object keys
         engine.register_action_handler("ObjectKeys", |params, facts| {
             let input = get_string_param(params, "input", "0")?;
@@ -260,6 +279,7 @@ impl RulePlugin for CollectionUtilsPlugin {
                 Value::Array(arr) => arr.len(),
                 Value::String(s) => s.len(),
                 Value::Object(obj) => obj.len(),
+                Value::Bytes(bytes) => bytes.len(),
                 _ => 0,
             };
             Ok(Value::Integer(length as i64))
@@ -400,6 +420,30 @@ impl RulePlugin for CollectionUtilsPlugin {
             }
         });
 
+        // reduce - Fold array into a single value: reduce(array, initial, operation, [separator])
+        engine.register_function("reduce", |args, _facts| {
+            if args.len() < 3 || args.len() > 4 {
+                return Err(RuleEngineError::EvaluationError {
+                    message:
+                        "reduce requires 3-4 arguments: array, initial, operation, [separator]"
+                            .to_string(),
+                });
+            }
+
+            match (&args[0], &args[2]) {
+                (Value::Array(arr), Value::String(operation)) => {
+                    let separator = match args.get(3) {
+                        Some(sep) => value_to_string(sep)?,
+                        None => String::new(),
+                    };
+                    reduce_values(arr, args[1].clone(), operation, &separator)
+                }
+                _ => Err(RuleEngineError::EvaluationError {
+                    message: "reduce requires an array and an operation name".to_string(),
+                }),
+            }
+        });
+
         // keys - Get object keys
         engine.register_function("keys", |args, _facts| {
             if args.len() != 1 {
@@ -523,6 +567,63 @@ fn value_to_number(value: &Value) -> Result<f64> {
     }
 }
 
+/// Fold `items` into `initial` with a named accumulator
+///
+/// `operation` is `sum`, `product` or `concat`, optionally applied to a field
+/// of each element: `sum(price)`, like the functions of `accumulate`. Elements
+/// without the field are skipped. `concat` puts `separator` between elements.
+fn reduce_values(
+    items: &[Value],
+    initial: Value,
+    operation: &str,
+    separator: &str,
+) -> Result<Value> {
+    let operation = operation.trim();
+    let (name, field) = match operation
+        .strip_suffix(')')
+        .and_then(|op| op.split_once('('))
+    {
+        Some((name, field)) => (name.trim(), Some(field.trim())),
+        None => (operation, None),
+    };
+
+    let mut elements = items.iter().filter_map(|item| match field {
+        Some(path) => path
+            .split('.')
+            .try_fold(item.clone(), |value, key| value.get_property(key)),
+        None => Some(item.clone()),
+    });
+
+    match name {
+        "sum" | "product" => elements.try_fold(initial, |acc, element| {
+            let (a, b) = (value_to_number(&acc)?, value_to_number(&element)?);
+            let result = if name == "sum" { a + b } else { a * b };
+            Ok(match (&acc, &element) {
+                (Value::Integer(_), Value::Integer(_)) if result.fract() == 0.0 => {
+                    Value::Integer(result as i64)
+                }
+                _ => Value::Number(result),
+            })
+        }),
+        "concat" => {
+            let parts = elements
+                .map(|element| value_to_string(&element))
+                .collect::<Result<Vec<_>>>()?;
+            let prefix = match initial {
+                Value::Null => String::new(),
+                other => value_to_string(&other)?,
+            };
+            Ok(Value::String(prefix + &parts.join(separator)))
+        }
+        _ => Err(RuleEngineError::EvaluationError {
+            message: format!(
+                "Unknown reduce operation '{}' (expected sum, product or concat)",
+                name
+            ),
+        }),
+    }
+}
+
 fn compare_values(a: &Value, b: &Value) -> std::cmp::Ordering {
     use std::cmp::Ordering;
 
@@ -548,3 +649,43 @@ fn filter_predicate(item: &Value, field: &str, expected: &Value) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reduce_values() {
+        let line = |name: &str, price: i64| {
+            Value::Object(HashMap::from([
+                ("name".to_string(), Value::String(name.to_string())),
+                ("price".to_string(), Value::Integer(price)),
+            ]))
+        };
+        let items = vec![line("pen", 3), line("ink", 4), Value::Null];
+
+        assert_eq!(
+            reduce_values(&items, Value::Integer(0), "sum(price)", "").unwrap(),
+            Value::Integer(7)
+        );
+        assert_eq!(
+            reduce_values(&items, Value::Number(0.5), "sum(price)", "").unwrap(),
+            Value::Number(7.5)
+        );
+        assert_eq!(
+            reduce_values(&items, Value::Integer(1), "product(price)", "").unwrap(),
+            Value::Integer(12)
+        );
+        assert_eq!(
+            reduce_values(&items, Value::String(String::new()), "concat(name)", ", ").unwrap(),
+            Value::String("pen, ink".to_string())
+        );
+
+        let numbers = vec![Value::Integer(2), Value::Integer(5)];
+        assert_eq!(
+            reduce_values(&numbers, Value::Integer(10), "sum", "").unwrap(),
+            Value::Integer(17)
+        );
+        assert!(reduce_values(&numbers, Value::Integer(0), "median", "").is_err());
+    }
+}
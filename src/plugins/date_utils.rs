@@ -1,12 +1,15 @@
+use crate::engine::clock::{self, Clock};
 use crate::engine::plugin::{PluginHealth, PluginMetadata, PluginState, RulePlugin};
 use crate::engine::RustRuleEngine;
 use crate::errors::{Result, RuleEngineError};
 use crate::types::Value;
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, Utc};
+use std::sync::Arc;
 
 /// Built-in plugin for date and time operations
 pub struct DateUtilsPlugin {
     metadata: PluginMetadata,
+    clock: Option<Arc<dyn Clock>>,
 }
 
 impl Default for DateUtilsPlugin {
@@ -46,8 +49,22 @@ impl DateUtilsPlugin {
                 ],
                 dependencies: vec![],
             },
+            clock: None,
         }
     }
+
+    /// Read the current time from `clock` instead of the engine's clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Closure returning the current time from the plugin's clock, falling
+    /// back to the clock of the running engine
+    fn now_fn(&self) -> impl Fn() -> DateTime<Utc> + Send + Sync + 'static {
+        let fixed = self.clock.clone();
+        move || fixed.as_ref().map_or_else(clock::now, |fixed| fixed.now())
+    }
 }
 
 impl RulePlugin for DateUtilsPlugin {
@@ -57,18 +74,20 @@ impl RulePlugin for DateUtilsPlugin {
 
     fn register_actions(&self, engine: &mut RustRuleEngine) -> Result<()> {
         // CurrentDate - Get current date
-        engine.register_action_handler("CurrentDate", |params, facts| {
+        let now = self.now_fn();
+        engine.register_action_handler("CurrentDate", move |params, facts| {
             let output = get_string_param(params, "output", "0")?;
-            let now = Local::now();
+            let now = now().with_timezone(&Local);
             let date_str = now.format("%Y-%m-%d").to_string();
             facts.set_nested(&output, Value::String(date_str))?;
             Ok(())
         });
 
         // CurrentTime - Get current time
-        engine.register_action_handler("CurrentTime", |params, facts| {
+        let now = self.now_fn();
+        engine.register_action_handler("CurrentTime", move |params, facts| {
             let output = get_string_param(params, "output", "0")?;
-            let now = Local::now();
+            let now = now().with_timezone(&Local);
             let time_str = now.format("%H:%M:%S").to_string();
             facts.set_nested(&output, Value::String(time_str))?;
             Ok(())
@@ -81,10 +100,7 @@ impl RulePlugin for DateUtilsPlugin {
             let output = get_string_param(params, "output", "2")?;
 
             if let Some(value) = facts.get(&input) {
-                let date_str = value_to_string(&value)?;
-
-                // Try to parse the date
-                let dt = parse_date_string(&date_str)?;
+                let dt = value_to_datetime(&value)?;
                 let formatted = dt.format(&format).to_string();
                 facts.set_nested(&output, Value::String(formatted))?;
             }
@@ -98,11 +114,9 @@ impl RulePlugin for DateUtilsPlugin {
             let output = get_string_param(params, "output", "2")?;
 
             if let Some(value) = facts.get(&input) {
-                let date_str = value_to_string(&value)?;
-                let dt = parse_date_string(&date_str)?;
+                let dt = value_to_datetime(&value)?;
                 let new_dt = dt + Duration::days(days as i64);
-                let result = new_dt.format("%Y-%m-%d").to_string();
-                facts.set_nested(&output, Value::String(result))?;
+                facts.set_nested(&output, Value::DateTime(new_dt))?;
             }
             Ok(())
         });
@@ -113,8 +127,7 @@ impl RulePlugin for DateUtilsPlugin {
             let output = get_string_param(params, "output", "1")?;
 
             if let Some(value) = facts.get(&input) {
-                let date_str = value_to_string(&value)?;
-                let dt = parse_date_string(&date_str)?;
+                let dt = value_to_datetime(&value)?;
                 let weekday = dt.weekday();
                 let is_weekend = weekday == chrono::Weekday::Sat || weekday == chrono::Weekday::Sun;
                 facts.set_nested(&output, Value::Boolean(is_weekend))?;
@@ -127,15 +140,20 @@ impl RulePlugin for DateUtilsPlugin {
 
     fn register_functions(&self, engine: &mut RustRuleEngine) -> Result<()> {
         // now - Get current timestamp
-        engine.register_function("now", |_args, _facts| {
-            let now = Utc::now();
-            Ok(Value::String(now.to_rfc3339()))
-        });
-
-        // today - Get today's date
-        engine.register_function("today", |_args, _facts| {
-            let today = Local::now();
-            Ok(Value::String(today.format("%Y-%m-%d").to_string()))
+        let now = self.now_fn();
+        engine.register_function("now", move |_args, _facts| Ok(Value::DateTime(now())));
+
+        // today - Get today's date (midnight, matching plain date literals)
+        let now = self.now_fn();
+        engine.register_function("today", move |_args, _facts| {
+            let today = now().with_timezone(&Local).date_naive();
+            let midnight =
+                today
+                    .and_hms_opt(0, 0, 0)
+                    .ok_or_else(|| RuleEngineError::EvaluationError {
+                        message: "Invalid date".to_string(),
+                    })?;
+            Ok(Value::DateTime(midnight.and_utc()))
         });
 
         // dayOfWeek - Get day of week (1=Monday, 7=Sunday)
@@ -146,8 +164,7 @@ impl RulePlugin for DateUtilsPlugin {
                 });
             }
 
-            let date_str = value_to_string(&args[0])?;
-            let dt = parse_date_string(&date_str)?;
+            let dt = value_to_datetime(&args[0])?;
             let day_num = dt.weekday().number_from_monday();
             Ok(Value::Integer(day_num as i64))
         });
@@ -160,8 +177,7 @@ impl RulePlugin for DateUtilsPlugin {
                 });
             }
 
-            let date_str = value_to_string(&args[0])?;
-            let dt = parse_date_string(&date_str)?;
+            let dt = value_to_datetime(&args[0])?;
             Ok(Value::Integer(dt.year() as i64))
         });
 
@@ -173,8 +189,7 @@ impl RulePlugin for DateUtilsPlugin {
                 });
             }
 
-            let date_str = value_to_string(&args[0])?;
-            let dt = parse_date_string(&date_str)?;
+            let dt = value_to_datetime(&args[0])?;
             Ok(Value::Integer(dt.month() as i64))
         });
 
@@ -186,8 +201,7 @@ impl RulePlugin for DateUtilsPlugin {
                 });
             }
 
-            let date_str = value_to_string(&args[0])?;
-            let dt = parse_date_string(&date_str)?;
+            let dt = value_to_datetime(&args[0])?;
             Ok(Value::Integer(dt.day() as i64))
         });
 
@@ -254,14 +268,12 @@ fn get_number_param(
     value_to_number(value)
 }
 
-fn value_to_string(value: &Value) -> Result<String> {
+fn value_to_datetime(value: &Value) -> Result<DateTime<Utc>> {
     match value {
-        Value::String(s) => Ok(s.clone()),
-        Value::Integer(i) => Ok(i.to_string()),
-        Value::Number(f) => Ok(f.to_string()),
-        Value::Boolean(b) => Ok(b.to_string()),
+        Value::DateTime(dt) => Ok(*dt),
+        Value::String(s) => parse_date_string(s),
         _ => Err(RuleEngineError::ActionError {
-            message: "Value cannot be converted to string".to_string(),
+            message: "Value cannot be converted to date".to_string(),
         }),
     }
 }
@@ -279,24 +291,17 @@ fn value_to_number(value: &Value) -> Result<f64> {
     }
 }
 
-fn parse_date_string(date_str: &str) -> Result<DateTime<Local>> {
-    // Try various date formats
-    let formats = vec![
-        "%Y-%m-%d",
-        "%Y-%m-%d %H:%M:%S",
-        "%Y/%m/%d",
-        "%d/%m/%Y",
-        "%m/%d/%Y",
-    ];
+fn parse_date_string(date_str: &str) -> Result<DateTime<Utc>> {
+    if let Some(dt) = Value::parse_datetime(date_str) {
+        return Ok(dt);
+    }
+
+    // Fall back to common non-ISO formats, read as UTC
+    let formats = vec!["%Y/%m/%d", "%d/%m/%Y", "%m/%d/%Y"];
 
     for format in formats {
         if let Ok(naive_dt) = NaiveDateTime::parse_from_str(date_str, format) {
-            return Local
-                .from_local_datetime(&naive_dt)
-                .single()
-                .ok_or_else(|| RuleEngineError::ActionError {
-                    message: "Invalid datetime".to_string(),
-                });
+            return Ok(naive_dt.and_utc());
         }
 
         if let Ok(naive_date) = chrono::NaiveDate::parse_from_str(date_str, format) {
@@ -306,12 +311,7 @@ fn parse_date_string(date_str: &str) -> Result<DateTime<Local>> {
                     .ok_or_else(|| RuleEngineError::ActionError {
                         message: "Invalid date".to_string(),
                     })?;
-            return Local
-                .from_local_datetime(&naive_dt)
-                .single()
-                .ok_or_else(|| RuleEngineError::ActionError {
-                    message: "Invalid datetime".to_string(),
-                });
+            return Ok(naive_dt.and_utc());
         }
     }
 
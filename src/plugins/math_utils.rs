@@ -43,6 +43,9 @@ impl MathUtilsPlugin {
                     "random".to_string(),
                     "sum".to_string(),
                     "avg".to_string(),
+                    "clamp".to_string(),
+                    "lerp".to_string(),
+                    "percentile".to_string(),
                 ],
                 dependencies: vec![],
             },
@@ -212,6 +215,55 @@ impl RulePlugin for MathUtilsPlugin {
             Ok(Value::Number(total / args.len() as f64))
         });
 
+        // clamp - Limit value to [min, max]
+        engine.register_function("clamp", |args, _facts| {
+            if args.len() != 3 {
+                return Err(RuleEngineError::EvaluationError {
+                    message: "clamp requires exactly 3 arguments: value, min, max".to_string(),
+                });
+            }
+
+            let value = value_to_number(&args[0])?;
+            let min = value_to_number(&args[1])?;
+            let max = value_to_number(&args[2])?;
+            Ok(Value::Number(clamp(value, min, max)?))
+        });
+
+        // lerp - Linear interpolation between a and b
+        engine.register_function("lerp", |args, _facts| {
+            if args.len() != 3 {
+                return Err(RuleEngineError::EvaluationError {
+                    message: "lerp requires exactly 3 arguments: a, b, t".to_string(),
+                });
+            }
+
+            let a = value_to_number(&args[0])?;
+            let b = value_to_number(&args[1])?;
+            let t = value_to_number(&args[2])?;
+            Ok(Value::Number(lerp(a, b, t)))
+        });
+
+        // percentile - p-th percentile of an array, interpolating between ranks
+        engine.register_function("percentile", |args, _facts| {
+            if args.len() != 2 {
+                return Err(RuleEngineError::EvaluationError {
+                    message: "percentile requires exactly 2 arguments: array, p".to_string(),
+                });
+            }
+
+            let Value::Array(items) = &args[0] else {
+                return Err(RuleEngineError::EvaluationError {
+                    message: "percentile requires an array as first argument".to_string(),
+                });
+            };
+            let values = items
+                .iter()
+                .map(value_to_number)
+                .collect::<Result<Vec<_>>>()?;
+            let p = value_to_number(&args[1])?;
+            Ok(Value::Number(percentile(&values, p)?))
+        });
+
         Ok(())
     }
 
@@ -288,3 +340,75 @@ fn value_to_number(value: &Value) -> Result<f64> {
         }),
     }
 }
+
+fn clamp(value: f64, min: f64, max: f64) -> Result<f64> {
+    if min > max {
+        return Err(RuleEngineError::EvaluationError {
+            message: format!("clamp requires min <= max (got {} > {})", min, max),
+        });
+    }
+    Ok(value.max(min).min(max))
+}
+
+/// `a` at `t = 0`, `b` at `t = 1`; `t` outside [0, 1] extrapolates
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Percentile with linear interpolation between the closest ranks
+fn percentile(values: &[f64], p: f64) -> Result<f64> {
+    if !(0.0..=100.0).contains(&p) {
+        return Err(RuleEngineError::EvaluationError {
+            message: format!("percentile requires p in [0, 100] (got {})", p),
+        });
+    }
+    if values.is_empty() {
+        return Err(RuleEngineError::EvaluationError {
+            message: "percentile of an empty array is undefined".to_string(),
+        });
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    Ok(lerp(sorted[lower], sorted[upper], rank - lower as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(clamp(5.0, 0.0, 10.0).unwrap(), 5.0);
+        assert_eq!(clamp(-1.0, 0.0, 10.0).unwrap(), 0.0);
+        assert_eq!(clamp(11.0, 0.0, 10.0).unwrap(), 10.0);
+        assert_eq!(clamp(3.0, 4.0, 4.0).unwrap(), 4.0);
+        assert!(clamp(1.0, 10.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_lerp() {
+        assert_eq!(lerp(10.0, 20.0, 0.0), 10.0);
+        assert_eq!(lerp(10.0, 20.0, 1.0), 20.0);
+        assert_eq!(lerp(10.0, 20.0, 0.25), 12.5);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values = [15.0, 20.0, 35.0, 40.0, 50.0];
+        assert_eq!(percentile(&values, 0.0).unwrap(), 15.0);
+        assert_eq!(percentile(&values, 100.0).unwrap(), 50.0);
+        assert_eq!(percentile(&values, 50.0).unwrap(), 35.0);
+        // Rank 0.4 * 4 = 1.6 falls between 20 and 35
+        assert!((percentile(&values, 40.0).unwrap() - 29.0).abs() < 1e-9);
+        assert_eq!(percentile(&[7.0], 90.0).unwrap(), 7.0);
+
+        assert!(percentile(&[], 50.0).is_err());
+        assert!(percentile(&values, -1.0).is_err());
+        assert!(percentile(&values, 100.5).is_err());
+    }
+}
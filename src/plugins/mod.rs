@@ -7,5 +7,5 @@ pub mod validation;
 pub use collection_utils::CollectionUtilsPlugin;
 pub use date_utils::DateUtilsPlugin;
 pub use math_utils::MathUtilsPlugin;
-pub use string_utils::StringUtilsPlugin;
+pub use string_utils::{MissingPlaceholder, StringUtilsPlugin};
 pub use validation::ValidationPlugin;
@@ -3,9 +3,20 @@ use crate::engine::RustRuleEngine;
 use crate::errors::{Result, RuleEngineError};
 use crate::types::Value;
 
+/// What `template` does with a `{key}` placeholder that has no value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingPlaceholder {
+    /// Leave the `{key}` token in the output
+    #[default]
+    Keep,
+    /// Replace the token with an empty string
+    Blank,
+}
+
 /// Built-in plugin for string manipulation operations
 pub struct StringUtilsPlugin {
     metadata: PluginMetadata,
+    missing_placeholder: MissingPlaceholder,
 }
 
 impl Default for StringUtilsPlugin {
@@ -40,11 +51,19 @@ impl StringUtilsPlugin {
                     "substring".to_string(),
                     "padLeft".to_string(),
                     "padRight".to_string(),
+                    "template".to_string(),
                 ],
                 dependencies: vec![],
             },
+            missing_placeholder: MissingPlaceholder::default(),
         }
     }
+
+    /// Set the default handling of placeholders `template` cannot resolve
+    pub fn with_missing_placeholder(mut self, missing: MissingPlaceholder) -> Self {
+        self.missing_placeholder = missing;
+        self
+    }
 }
 
 impl RulePlugin for StringUtilsPlugin {
@@ -226,6 +245,56 @@ impl RulePlugin for StringUtilsPlugin {
             Ok(Value::String(result))
         });
 
+        // template - Substitute named {key} placeholders from an object or from facts
+        let default_missing = self.missing_placeholder;
+        engine.register_function("template", move |args, facts| {
+            if args.is_empty() || args.len() > 3 {
+                return Err(RuleEngineError::EvaluationError {
+                    message: "template requires 1-3 arguments: text, [values], [missing]"
+                        .to_string(),
+                });
+            }
+
+            let text = value_to_string(&args[0])?;
+            let missing = match args.get(2) {
+                None => default_missing,
+                Some(Value::String(s)) if s == "keep" => MissingPlaceholder::Keep,
+                Some(Value::String(s)) if s == "blank" => MissingPlaceholder::Blank,
+                Some(other) => {
+                    return Err(RuleEngineError::EvaluationError {
+                        message: format!(
+                            "template missing mode must be \"keep\" or \"blank\", got {}",
+                            other.to_string()
+                        ),
+                    })
+                }
+            };
+
+            let result = match args.get(1) {
+                Some(Value::Object(values)) => render_template(&text, missing, |key| {
+                    let mut parts = key.split('.');
+                    let mut current = values.get(parts.next()?)?;
+                    for part in parts {
+                        match current {
+                            Value::Object(obj) => current = obj.get(part)?,
+                            _ => return None,
+                        }
+                    }
+                    Some(current.clone())
+                }),
+                None => render_template(&text, missing, |key| {
+                    facts.get_nested(key).or_else(|| facts.get(key))
+                }),
+                Some(_) => {
+                    return Err(RuleEngineError::EvaluationError {
+                        message: "template values must be an object".to_string(),
+                    })
+                }
+            };
+
+            Ok(Value::String(result))
+        });
+
         Ok(())
     }
 
@@ -245,6 +314,35 @@ impl RulePlugin for StringUtilsPlugin {
 }
 
 // Helper functions
+
+/// Replace each `{key}` in `text` with the value `lookup` finds for it
+fn render_template(
+    text: &str,
+    missing: MissingPlaceholder,
+    lookup: impl Fn(&str) -> Option<Value>,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open + 1..].find('}').map(|i| open + 1 + i) else {
+            break;
+        };
+        result.push_str(&rest[..open]);
+
+        let key = rest[open + 1..close].trim();
+        match lookup(key) {
+            Some(value) if !key.is_empty() => result.push_str(&value.to_string()),
+            _ if missing == MissingPlaceholder::Blank => {}
+            _ => result.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
 fn get_string_param(
     params: &std::collections::HashMap<String, Value>,
     name: &str,
@@ -276,3 +374,38 @@ fn value_to_string(value: &Value) -> Result<String> {
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_render_template() {
+        let mut values = HashMap::new();
+        values.insert("name".to_string(), Value::String("Ada".to_string()));
+        values.insert("count".to_string(), Value::Integer(3));
+        let lookup = |key: &str| values.get(key).cloned();
+
+        assert_eq!(
+            render_template(
+                "Hello {name}, you have { count } orders",
+                MissingPlaceholder::Keep,
+                lookup
+            ),
+            "Hello Ada, you have 3 orders"
+        );
+        assert_eq!(
+            render_template("Hi {name} {title}{}", MissingPlaceholder::Keep, lookup),
+            "Hi Ada {title}{}"
+        );
+        assert_eq!(
+            render_template("Hi {name} {title}!", MissingPlaceholder::Blank, lookup),
+            "Hi Ada !"
+        );
+        assert_eq!(
+            render_template("Unclosed {name", MissingPlaceholder::Blank, lookup),
+            "Unclosed {name"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::engine::plugin::{PluginHealth, PluginMetadata, PluginState, RulePlugin};
+use crate::engine::schema::ValueKind;
 use crate::engine::RustRuleEngine;
 use crate::errors::{Result, RuleEngineError};
 use crate::types::Value;
@@ -45,6 +46,8 @@ impl ValidationPlugin {
                     "ValidateLength".to_string(),
                     "ValidateNotEmpty".to_string(),
                     "ValidateNumeric".to_string(),
+                    "ValidateJson".to_string(),
+                    "ValidateJsonSchema".to_string(),
                 ],
                 functions: vec![
                     "isEmail".to_string(),
@@ -53,6 +56,7 @@ impl ValidationPlugin {
                     "isNumeric".to_string(),
                     "isEmpty".to_string(),
                     "inRange".to_string(),
+                    "isJson".to_string(),
                 ],
                 dependencies: vec![],
             },
@@ -171,6 +175,32 @@ impl RulePlugin for ValidationPlugin {
             Ok(())
         });
 
+        // ValidateJson - Check that a string parses as JSON
+        engine.register_action_handler("ValidateJson", |params, facts| {
+            let input = get_string_param(params, "input", "0")?;
+            let output = get_string_param(params, "output", "1")?;
+
+            if let Some(value) = facts.get(&input) {
+                let is_valid = matches!(&value, Value::String(s) if is_json(s));
+                facts.set_nested(&output, Value::Boolean(is_valid))?;
+            }
+            Ok(())
+        });
+
+        // ValidateJsonSchema - Check an object against required keys and field types
+        engine.register_action_handler("ValidateJsonSchema", |params, facts| {
+            let value = get_value_param(params, facts, "value", "0")?;
+            let schema = get_value_param(params, facts, "schema", "1")?;
+            let output = get_string_param(params, "output", "2")?;
+
+            let errors = json_schema_errors(&value, &schema)?;
+            facts.set_nested(
+                &output,
+                Value::Array(errors.into_iter().map(Value::String).collect()),
+            )?;
+            Ok(())
+        });
+
         Ok(())
     }
 
@@ -258,6 +288,19 @@ impl RulePlugin for ValidationPlugin {
             Ok(Value::Boolean(in_range))
         });
 
+        // isJson - Check if string parses as JSON
+        engine.register_function("isJson", |args, _facts| {
+            if args.len() != 1 {
+                return Err(RuleEngineError::EvaluationError {
+                    message: "isJson requires exactly 1 argument".to_string(),
+                });
+            }
+
+            Ok(Value::Boolean(
+                matches!(&args[0], Value::String(s) if is_json(s)),
+            ))
+        });
+
         Ok(())
     }
 
@@ -321,6 +364,34 @@ fn get_number_param(
     value_to_number(value)
 }
 
+/// Parameter value, read from facts when it names one and parsed when it is JSON text
+fn get_value_param(
+    params: &std::collections::HashMap<String, Value>,
+    facts: &crate::Facts,
+    name: &str,
+    pos: &str,
+) -> Result<Value> {
+    let value = params
+        .get(name)
+        .or_else(|| params.get(pos))
+        .ok_or_else(|| RuleEngineError::ActionError {
+            message: format!("Missing parameter: {}", name),
+        })?;
+
+    match value {
+        Value::String(s) => Ok(facts
+            .get(s)
+            .or_else(|| facts.get_nested(s))
+            .or_else(|| {
+                serde_json::from_str::<serde_json::Value>(s)
+                    .ok()
+                    .map(Value::from)
+            })
+            .unwrap_or_else(|| value.clone())),
+        _ => Ok(value.clone()),
+    }
+}
+
 fn value_to_string(value: &Value) -> Result<String> {
     match value {
         Value::String(s) => Ok(s.clone()),
@@ -360,3 +431,109 @@ fn is_valid_phone(phone: &str) -> bool {
 fn is_valid_url(url: &str) -> bool {
     url.starts_with("http://") || url.starts_with("https://") || url.starts_with("ftp://")
 }
+
+fn is_json(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text).is_ok()
+}
+
+/// Problems with `value` against a minimal schema
+///
+/// The schema is an object with optional `required` (array of field names) and
+/// `properties` (field name to type: `string`, `number`, `integer`, `boolean`,
+/// `array`, `object` or `any`). Fields not listed in `properties` are allowed.
+fn json_schema_errors(value: &Value, schema: &Value) -> Result<Vec<String>> {
+    let Value::Object(schema) = schema else {
+        return Err(RuleEngineError::ActionError {
+            message: "JSON schema must be an object".to_string(),
+        });
+    };
+    let Value::Object(obj) = value else {
+        return Ok(vec![format!(
+            "expected an object, got {}",
+            value.to_string()
+        )]);
+    };
+
+    let mut errors = Vec::new();
+
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for field in required {
+            let field = value_to_string(field)?;
+            if !obj.contains_key(&field) {
+                errors.push(format!("missing required field '{}'", field));
+            }
+        }
+    }
+
+    if let Some(Value::Object(properties)) = schema.get("properties") {
+        let mut names: Vec<&String> = properties.keys().collect();
+        names.sort();
+        for name in names {
+            let type_name = value_to_string(&properties[name])?;
+            let kind = match type_name.as_str() {
+                "string" => ValueKind::String,
+                "number" => ValueKind::Number,
+                "integer" => ValueKind::Integer,
+                "boolean" => ValueKind::Boolean,
+                "array" => ValueKind::Array,
+                "object" => ValueKind::Object,
+                "any" => ValueKind::Any,
+                other => {
+                    return Err(RuleEngineError::ActionError {
+                        message: format!("Unknown type '{}' in JSON schema", other),
+                    })
+                }
+            };
+            if let Some(field_value) = obj.get(name) {
+                if !kind.matches(field_value) {
+                    errors.push(format!("field '{}' should be {}", name, type_name));
+                }
+            }
+        }
+    }
+
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_json() {
+        assert!(is_json(r#"{"id": 1, "tags": ["a"]}"#));
+        assert!(is_json("[1, 2]"));
+        assert!(!is_json("{id: 1}"));
+        assert!(!is_json(""));
+    }
+
+    #[test]
+    fn test_json_schema_errors() {
+        let schema = Value::from(serde_json::json!({
+            "required": ["id", "email"],
+            "properties": {"id": "integer", "email": "string", "tags": "array"}
+        }));
+
+        let valid = Value::from(serde_json::json!({"id": 7, "email": "a@b.co", "extra": true}));
+        assert!(json_schema_errors(&valid, &schema).unwrap().is_empty());
+
+        let invalid = Value::from(serde_json::json!({"id": "7", "tags": "x"}));
+        assert_eq!(
+            json_schema_errors(&invalid, &schema).unwrap(),
+            vec![
+                "missing required field 'email'".to_string(),
+                "field 'id' should be integer".to_string(),
+                "field 'tags' should be array".to_string(),
+            ]
+        );
+
+        assert_eq!(
+            json_schema_errors(&Value::Integer(1), &schema)
+                .unwrap()
+                .len(),
+            1
+        );
+        let bad_schema = Value::from(serde_json::json!({"properties": {"id": "uuid"}}));
+        assert!(json_schema_errors(&valid, &bad_schema).is_err());
+    }
+}
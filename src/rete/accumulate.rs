@@ -19,7 +19,7 @@
 //! ```
 
 use super::facts::FactValue;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Accumulate function trait - defines how to aggregate values
 pub trait AccumulateFunction: Send + Sync {
@@ -305,6 +305,172 @@ impl AccumulateState for MaxState {
     }
 }
 
+/// Count-distinct accumulator - counts unique extracted values
+#[derive(Debug, Clone)]
+pub struct CountDistinctFunction;
+
+impl AccumulateFunction for CountDistinctFunction {
+    fn init(&self) -> Box<dyn AccumulateState> {
+        Box::new(CountDistinctState {
+            seen: HashSet::new(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "count_distinct"
+    }
+
+    fn clone_box(&self) -> Box<dyn AccumulateFunction> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CountDistinctState {
+    // Debug form keeps Integer(1) and String("1") apart
+    seen: HashSet<String>,
+}
+
+impl AccumulateState for CountDistinctState {
+    fn accumulate(&mut self, value: &FactValue) {
+        self.seen.insert(format!("{:?}", value));
+    }
+
+    fn get_result(&self) -> FactValue {
+        FactValue::Integer(self.seen.len() as i64)
+    }
+
+    fn reset(&mut self) {
+        self.seen.clear();
+    }
+
+    fn clone_box(&self) -> Box<dyn AccumulateState> {
+        Box::new(self.clone())
+    }
+}
+
+/// Median accumulator - middle numeric value (mean of the two middle values
+/// for even-sized inputs), `Null` when nothing numeric was accumulated
+#[derive(Debug, Clone)]
+pub struct MedianFunction;
+
+impl AccumulateFunction for MedianFunction {
+    fn init(&self) -> Box<dyn AccumulateState> {
+        Box::new(MedianState { values: Vec::new() })
+    }
+
+    fn name(&self) -> &str {
+        "median"
+    }
+
+    fn clone_box(&self) -> Box<dyn AccumulateFunction> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MedianState {
+    values: Vec<f64>,
+}
+
+impl AccumulateState for MedianState {
+    fn accumulate(&mut self, value: &FactValue) {
+        match value {
+            FactValue::Integer(i) => self.values.push(*i as f64),
+            FactValue::Float(f) => self.values.push(*f),
+            _ => {} // Ignore non-numeric values
+        }
+    }
+
+    fn get_result(&self) -> FactValue {
+        if self.values.is_empty() {
+            return FactValue::Null;
+        }
+
+        let mut sorted = self.values.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let mid = sorted.len() / 2;
+        if sorted.len().is_multiple_of(2) {
+            FactValue::Float((sorted[mid - 1] + sorted[mid]) / 2.0)
+        } else {
+            FactValue::Float(sorted[mid])
+        }
+    }
+
+    fn reset(&mut self) {
+        self.values.clear();
+    }
+
+    fn clone_box(&self) -> Box<dyn AccumulateState> {
+        Box::new(self.clone())
+    }
+}
+
+/// Standard deviation accumulator - population standard deviation of numeric
+/// values, `Null` when nothing numeric was accumulated
+#[derive(Debug, Clone)]
+pub struct StdDevFunction;
+
+impl AccumulateFunction for StdDevFunction {
+    fn init(&self) -> Box<dyn AccumulateState> {
+        Box::new(StdDevState {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "stddev"
+    }
+
+    fn clone_box(&self) -> Box<dyn AccumulateFunction> {
+        Box::new(self.clone())
+    }
+}
+
+/// Welford's online algorithm
+#[derive(Debug, Clone)]
+struct StdDevState {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl AccumulateState for StdDevState {
+    fn accumulate(&mut self, value: &FactValue) {
+        let x = match value {
+            FactValue::Integer(i) => *i as f64,
+            FactValue::Float(f) => *f,
+            _ => return, // Ignore non-numeric values
+        };
+
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn get_result(&self) -> FactValue {
+        if self.count == 0 {
+            FactValue::Null
+        } else {
+            FactValue::Float((self.m2 / self.count as f64).sqrt())
+        }
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.mean = 0.0;
+        self.m2 = 0.0;
+    }
+
+    fn clone_box(&self) -> Box<dyn AccumulateState> {
+        Box::new(self.clone())
+    }
+}
+
 // ============================================================================
 // Accumulate Pattern - for use in RETE conditions
 // ============================================================================
@@ -397,6 +563,9 @@ impl AccumulateFunctionRegistry {
         registry.register(Box::new(AverageFunction));
         registry.register(Box::new(MinFunction));
         registry.register(Box::new(MaxFunction));
+        registry.register(Box::new(CountDistinctFunction));
+        registry.register(Box::new(MedianFunction));
+        registry.register(Box::new(StdDevFunction));
 
         registry
     }
@@ -513,9 +682,92 @@ mod tests {
         assert!(registry.get("average").is_some());
         assert!(registry.get("min").is_some());
         assert!(registry.get("max").is_some());
+        assert!(registry.get("count_distinct").is_some());
+        assert!(registry.get("median").is_some());
+        assert!(registry.get("stddev").is_some());
         assert!(registry.get("unknown").is_none());
 
         let functions = registry.available_functions();
-        assert_eq!(functions.len(), 5);
+        assert_eq!(functions.len(), 8);
+    }
+
+    #[test]
+    fn test_count_distinct_function() {
+        let mut state = CountDistinctFunction.init();
+        assert!(matches!(state.get_result(), FactValue::Integer(0)));
+
+        for value in &[
+            FactValue::String("EU".to_string()),
+            FactValue::String("US".to_string()),
+            FactValue::String("EU".to_string()),
+            FactValue::Integer(1),
+            FactValue::String("1".to_string()),
+        ] {
+            state.accumulate(value);
+        }
+
+        match state.get_result() {
+            FactValue::Integer(i) => assert_eq!(i, 4),
+            _ => panic!("Expected Integer"),
+        }
+    }
+
+    #[test]
+    fn test_median_function() {
+        let mut odd = MedianFunction.init();
+        for value in &[
+            FactValue::Integer(9),
+            FactValue::Integer(1),
+            FactValue::Float(4.0),
+        ] {
+            odd.accumulate(value);
+        }
+        match odd.get_result() {
+            FactValue::Float(f) => assert_eq!(f, 4.0),
+            _ => panic!("Expected Float"),
+        }
+
+        let mut even = MedianFunction.init();
+        for value in &[
+            FactValue::Integer(4),
+            FactValue::Integer(1),
+            FactValue::Integer(10),
+            FactValue::Integer(3),
+        ] {
+            even.accumulate(value);
+        }
+        match even.get_result() {
+            FactValue::Float(f) => assert_eq!(f, 3.5),
+            _ => panic!("Expected Float"),
+        }
+
+        assert!(matches!(
+            MedianFunction.init().get_result(),
+            FactValue::Null
+        ));
+    }
+
+    #[test]
+    fn test_stddev_function() {
+        let mut state = StdDevFunction.init();
+        for value in &[2, 4, 4, 4, 5, 5, 7, 9] {
+            state.accumulate(&FactValue::Integer(*value));
+        }
+        match state.get_result() {
+            FactValue::Float(f) => assert!((f - 2.0).abs() < 1e-9),
+            _ => panic!("Expected Float"),
+        }
+
+        let mut single = StdDevFunction.init();
+        single.accumulate(&FactValue::Float(42.0));
+        match single.get_result() {
+            FactValue::Float(f) => assert_eq!(f, 0.0),
+            _ => panic!("Expected Float"),
+        }
+
+        assert!(matches!(
+            StdDevFunction.init().get_result(),
+            FactValue::Null
+        ));
     }
 }
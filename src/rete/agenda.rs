@@ -174,6 +174,23 @@ impl Ord for Activation {
     }
 }
 
+/// Agenda changes caused by an incremental fact update
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgendaDelta {
+    /// Rules that started matching the updated fact
+    pub activated: Vec<String>,
+    /// Rules that stopped matching the updated fact; their pending
+    /// activations were removed
+    pub deactivated: Vec<String>,
+}
+
+impl AgendaDelta {
+    /// Whether the update changed the agenda
+    pub fn is_empty(&self) -> bool {
+        self.activated.is_empty() && self.deactivated.is_empty()
+    }
+}
+
 /// Advanced Agenda (Drools-style)
 pub struct AdvancedAgenda {
     /// All activations by agenda group
@@ -411,6 +428,20 @@ impl AdvancedAgenda {
         self.locked_groups.clear();
     }
 
+    /// Remove pending activations of `rule_name` matched on `handle`,
+    /// returning how many were removed
+    pub fn remove_activations(&mut self, rule_name: &str, handle: super::FactHandle) -> usize {
+        let mut removed = 0;
+        for heap in self.activations.values_mut() {
+            let before = heap.len();
+            heap.retain(|activation| {
+                activation.rule_name != rule_name || activation.matched_fact_handle != Some(handle)
+            });
+            removed += before - heap.len();
+        }
+        removed
+    }
+
     /// Reset fired flags (for re-evaluation)
     pub fn reset_fired_flags(&mut self) {
         self.fired_rules.clear();
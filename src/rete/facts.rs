@@ -116,6 +116,25 @@ impl FactValue {
             "endsWith" => self.ends_with(other),
             "matches" => self.matches_pattern(other),
             "in" => self.in_array(other),
+            "not_in" => matches!(other, FactValue::Array(_)) && !self.in_array(other),
+            "contains_all" => match (self, other) {
+                (FactValue::Array(have), FactValue::Array(wanted)) => {
+                    wanted.iter().all(|item| have.contains(item))
+                }
+                _ => false,
+            },
+            "contains_any" => match (self, other) {
+                (FactValue::Array(have), FactValue::Array(wanted)) => {
+                    wanted.iter().any(|item| have.contains(item))
+                }
+                _ => false,
+            },
+            "is_number" => matches!(self, FactValue::Integer(_) | FactValue::Float(_)),
+            "is_string" => matches!(self, FactValue::String(_)),
+            "is_boolean" => matches!(self, FactValue::Boolean(_)),
+            "is_array" => matches!(self, FactValue::Array(_)),
+            "is_null" => self.is_null(),
+            "is_not_null" => !self.is_null(),
             _ => false,
         }
     }
@@ -253,6 +272,29 @@ impl From<crate::types::Value> for FactValue {
             }
             crate::types::Value::Null => FactValue::Null,
             crate::types::Value::Expression(expr) => FactValue::String(expr),
+            range @ crate::types::Value::Range { .. } => FactValue::String(range.to_string()),
+            crate::types::Value::DateTime(dt) => FactValue::String(dt.to_rfc3339()),
+            duration @ crate::types::Value::Duration(_) => FactValue::String(duration.to_string()),
+            bytes @ crate::types::Value::Bytes(_) => FactValue::String(bytes.to_string()),
+            // Kept as text so the exact digits survive; numeric comparisons parse it
+            #[cfg(feature = "decimal")]
+            crate::types::Value::Decimal(d) => FactValue::String(d.to_string()),
+        }
+    }
+}
+
+/// Convert from FactValue to types::Value
+impl From<FactValue> for crate::types::Value {
+    fn from(value: FactValue) -> Self {
+        match value {
+            FactValue::String(s) => crate::types::Value::String(s),
+            FactValue::Integer(i) => crate::types::Value::Integer(i),
+            FactValue::Float(f) => crate::types::Value::Number(f),
+            FactValue::Boolean(b) => crate::types::Value::Boolean(b),
+            FactValue::Array(arr) => {
+                crate::types::Value::Array(arr.into_iter().map(|v| v.into()).collect())
+            }
+            FactValue::Null => crate::types::Value::Null,
         }
     }
 }
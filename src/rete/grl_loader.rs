@@ -31,9 +31,7 @@ impl GrlReteLoader {
         engine: &mut IncrementalEngine,
     ) -> Result<usize> {
         let grl_text =
-            fs::read_to_string(path.as_ref()).map_err(|e| RuleEngineError::ParseError {
-                message: format!("Failed to read GRL file: {}", e),
-            })?;
+            fs::read_to_string(path.as_ref()).map_err(|e| RuleEngineError::io(&path, e))?;
 
         Self::load_from_string(&grl_text, engine)
     }
@@ -96,12 +94,37 @@ impl GrlReteLoader {
                     crate::types::LogicalOperator::Or => {
                         Ok(ReteUlNode::UlOr(Box::new(left_node), Box::new(right_node)))
                     }
+                    // (left && !right) || (!left && right)
+                    crate::types::LogicalOperator::Xor => Ok(ReteUlNode::UlOr(
+                        Box::new(ReteUlNode::UlAnd(
+                            Box::new(left_node.clone()),
+                            Box::new(ReteUlNode::UlNot(Box::new(right_node.clone()))),
+                        )),
+                        Box::new(ReteUlNode::UlAnd(
+                            Box::new(ReteUlNode::UlNot(Box::new(left_node))),
+                            Box::new(right_node),
+                        )),
+                    )),
                     crate::types::LogicalOperator::Not => {
                         // For NOT, we only use left node
                         Ok(ReteUlNode::UlNot(Box::new(left_node)))
                     }
                 }
             }
+            ConditionGroup::Constant(value) => {
+                // RETE has no constant node: `x || !x` always holds, `x && !x` never does
+                let probe = ReteUlNode::UlAlpha(AlphaNode {
+                    field: "__constant__".to_string(),
+                    operator: "==".to_string(),
+                    value: "true".to_string(),
+                });
+                let negated = ReteUlNode::UlNot(Box::new(probe.clone()));
+                Ok(if *value {
+                    ReteUlNode::UlOr(Box::new(probe), Box::new(negated))
+                } else {
+                    ReteUlNode::UlAnd(Box::new(probe), Box::new(negated))
+                })
+            }
             ConditionGroup::Not(inner) => {
                 let inner_node = Self::convert_condition_group(inner)?;
                 Ok(ReteUlNode::UlNot(Box::new(inner_node)))
@@ -200,6 +223,12 @@ impl GrlReteLoader {
                     compare_value: if operation == "count" { cmp_val } else { None },
                 })
             }
+            // A binding matches whenever the bound field is present
+            ConditionExpression::Bind { field, .. } => Ok(ReteUlNode::UlAlpha(AlphaNode {
+                field: field.clone(),
+                operator: "!=".to_string(),
+                value: "null".to_string(),
+            })),
             ConditionExpression::FunctionCall { name, args } => Ok(ReteUlNode::UlFunctionCall {
                 name: name.clone(),
                 args: args.clone(),
@@ -237,6 +266,16 @@ impl GrlReteLoader {
             Operator::EndsWith => "endsWith".to_string(),
             Operator::Matches => "matches".to_string(),
             Operator::In => "in".to_string(),
+            Operator::NotIn => "not_in".to_string(),
+            Operator::ContainsAll => "contains_all".to_string(),
+            Operator::ContainsAny => "contains_any".to_string(),
+            Operator::IsNumber => "is_number".to_string(),
+            Operator::IsString => "is_string".to_string(),
+            Operator::IsBoolean => "is_boolean".to_string(),
+            Operator::IsArray => "is_array".to_string(),
+            Operator::IsObject => "is_object".to_string(),
+            Operator::IsNull => "is_null".to_string(),
+            Operator::IsNotNull => "is_not_null".to_string(),
         }
     }
 
@@ -261,6 +300,12 @@ impl GrlReteLoader {
                 // For expressions, return the expression string
                 expr.clone()
             }
+            Value::Range { .. } => value.to_string(),
+            Value::DateTime(dt) => dt.to_rfc3339(),
+            Value::Duration(_) => value.to_string(),
+            Value::Bytes(_) => value.to_string(),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => d.to_string(),
         }
     }
 
@@ -421,6 +466,34 @@ impl GrlReteLoader {
 
                 info!("➕ APPEND: {} += {:?}", field, evaluated_value);
             }
+            ActionType::Halt => {
+                // The RETE network has no run loop to stop
+                log::warn!("halt() is not supported by the RETE engine, ignoring");
+            }
+            ActionType::Emit { event_type, .. } => {
+                log::warn!(
+                    "emit(\"{}\") is not supported by the RETE engine, ignoring",
+                    event_type
+                );
+            }
+            ActionType::AddScore { target, amount } => {
+                let current = facts
+                    .get(target)
+                    .and_then(|value| value.as_number())
+                    .unwrap_or(0.0);
+                facts.set(target, FactValue::Float(current + amount));
+
+                info!("🎯 SCORE: {} += {}", target, amount);
+            }
+            ActionType::CallAndBind {
+                field, function, ..
+            } => {
+                log::warn!(
+                    "{} = {}(..) is not supported by the RETE engine, ignoring",
+                    field,
+                    function
+                );
+            }
         }
     }
 
@@ -451,6 +524,12 @@ impl GrlReteLoader {
                 // For expressions, store as string - will be evaluated at runtime
                 FactValue::String(format!("[EXPR: {}]", expr))
             }
+            Value::Range { .. } => FactValue::String(value.to_string()),
+            Value::DateTime(dt) => FactValue::String(dt.to_rfc3339()),
+            Value::Duration(_) => FactValue::String(value.to_string()),
+            Value::Bytes(_) => FactValue::String(value.to_string()),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => FactValue::String(d.to_string()),
         }
     }
 
@@ -17,6 +17,7 @@ pub mod network;
 pub mod optimization;
 pub mod pattern;
 pub mod propagation;
+pub mod stats;
 pub mod template;
 pub mod tms;
 pub mod working_memory;
@@ -47,6 +48,7 @@ pub use network::*;
 pub use optimization::*;
 pub use pattern::*;
 pub use propagation::*;
+pub use stats::*;
 pub use template::*;
 pub use tms::*;
 pub use working_memory::*;
@@ -287,8 +287,8 @@ pub fn evaluate_rete_ul_node(node: &ReteUlNode, facts: &HashMap<String, String>)
             let has_results = !matching_values.is_empty();
 
             match function.as_str() {
-                "count" => has_results, // Count passes if there are any matches
-                "sum" | "average" | "min" | "max" => {
+                "count" | "count_distinct" => has_results, // Count passes if there are any matches
+                "sum" | "average" | "min" | "max" | "median" | "stddev" => {
                     // These functions need at least one value
                     has_results
                 }
@@ -441,6 +441,42 @@ impl ReteUlNode {
     pub fn evaluate_typed(&self, facts: &super::facts::TypedFacts) -> bool {
         evaluate_rete_ul_node_typed(self, facts, &std::collections::HashMap::new())
     }
+
+    /// Whether evaluating this node may read `field` (e.g. `"Order.amount"`)
+    ///
+    /// Errs on the side of `true` for nodes whose inputs are not known up front.
+    pub fn references_field(&self, field: &str) -> bool {
+        match self {
+            ReteUlNode::UlAlpha(alpha) => {
+                mentions_path(&alpha.field, field) || mentions_path(&alpha.value, field)
+            }
+            ReteUlNode::UlAnd(left, right) | ReteUlNode::UlOr(left, right) => {
+                left.references_field(field) || right.references_field(field)
+            }
+            ReteUlNode::UlNot(inner)
+            | ReteUlNode::UlExists(inner)
+            | ReteUlNode::UlForall(inner) => inner.references_field(field),
+            ReteUlNode::UlMultiField { field: multi, .. } => mentions_path(multi, field),
+            ReteUlNode::UlFunctionCall { args, .. } => {
+                args.iter().any(|arg| mentions_path(arg, field))
+            }
+            ReteUlNode::UlAccumulate { .. } => true,
+            #[cfg(feature = "streaming")]
+            ReteUlNode::UlStream { .. } => true,
+            ReteUlNode::UlTerminal(_) => false,
+        }
+    }
+}
+
+/// Whether `text` mentions the dotted `path` as a whole: `Order.total` is
+/// found in `Order.total * 2` but not in `Order.totalTax` or `Sub.Order.total`
+fn mentions_path(text: &str, path: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(path).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + path.len()..].chars().next();
+        !before.is_some_and(|c| is_ident(c) || c == '.') && !after.is_some_and(is_ident)
+    })
 }
 
 /// RETE-UL Rule Struct
@@ -833,8 +869,8 @@ pub fn evaluate_rete_ul_node_typed(
             let has_results = !matching_values.is_empty();
 
             match function.as_str() {
-                "count" => has_results,
-                "sum" | "average" | "min" | "max" => has_results,
+                "count" | "count_distinct" => has_results,
+                "sum" | "average" | "min" | "max" | "median" | "stddev" => has_results,
                 _ => true,
             }
         }
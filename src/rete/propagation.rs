@@ -5,11 +5,12 @@
 //! - Track affected rules and activations
 //! - Efficient re-evaluation after updates
 
-use super::agenda::{Activation, AdvancedAgenda};
+use super::agenda::{Activation, AdvancedAgenda, AgendaDelta};
 use super::deffacts::DeffactsRegistry;
 use super::facts::{FactValue, TypedFacts};
 use super::globals::GlobalsRegistry;
 use super::network::TypedReteUlRule;
+use super::stats::{collect_node_stats, ReteStats};
 use super::template::TemplateRegistry;
 use super::tms::TruthMaintenanceSystem;
 use super::working_memory::{FactHandle, WorkingMemory};
@@ -164,6 +165,97 @@ impl IncrementalEngine {
         Ok(())
     }
 
+    /// Set one field of a fact and re-evaluate only the rules whose conditions
+    /// read that field
+    ///
+    /// Unlike [`update`](Self::update), which re-checks every fact of the type
+    /// against every dependent rule, this compares each affected rule's match
+    /// before and after the change. Both evaluations see all of working memory
+    /// with the changed fact on top, so rules that also read other fact types
+    /// are re-evaluated against them. New matches are added to the agenda,
+    /// lost matches have their pending activations removed, and both are
+    /// reported in the returned [`AgendaDelta`].
+    pub fn update_fact<V: Into<FactValue>>(
+        &mut self,
+        handle: FactHandle,
+        field: &str,
+        value: V,
+    ) -> Result<AgendaDelta> {
+        let fact =
+            self.working_memory
+                .get(&handle)
+                .ok_or_else(|| RuleEngineError::FieldNotFound {
+                    field: format!("FactHandle {} not found", handle),
+                })?;
+        let fact_type = fact.fact_type.clone();
+        let mut data = fact.data.clone();
+        data.set(field, value);
+
+        let before = self.snapshot_with_fact(handle);
+        self.working_memory
+            .update(handle, data)
+            .map_err(|e| RuleEngineError::EvaluationError { message: e })?;
+        let after = self.snapshot_with_fact(handle);
+
+        let qualified_field = format!("{}.{}", fact_type, field);
+        let mut affected_rules: Vec<usize> = self
+            .dependencies
+            .get_affected_rules(&fact_type)
+            .into_iter()
+            .collect();
+        affected_rules.sort_unstable();
+
+        let mut delta = AgendaDelta::default();
+        for rule_idx in affected_rules {
+            let rule = &self.rules[rule_idx];
+            if !rule.node.references_field(&qualified_field) {
+                continue;
+            }
+
+            let matched_before = super::network::evaluate_rete_ul_node_typed(
+                &rule.node,
+                &before,
+                &self.custom_functions,
+            );
+            let matches_now = super::network::evaluate_rete_ul_node_typed(
+                &rule.node,
+                &after,
+                &self.custom_functions,
+            );
+
+            match (matched_before, matches_now) {
+                (false, true) => {
+                    let activation = Activation::new(rule.name.clone(), rule.priority)
+                        .with_no_loop(rule.no_loop)
+                        .with_matched_fact(handle);
+                    self.agenda.add_activation(activation);
+                    delta.activated.push(rule.name.clone());
+                }
+                (true, false) => {
+                    self.agenda.remove_activations(&rule.name, handle);
+                    delta.deactivated.push(rule.name.clone());
+                }
+                _ => {}
+            }
+        }
+
+        Ok(delta)
+    }
+
+    /// All of working memory flattened as by
+    /// [`WorkingMemory::to_typed_facts`], with the fact behind `handle` owning
+    /// the unprefixed `Type.field` keys of its type
+    fn snapshot_with_fact(&self, handle: FactHandle) -> TypedFacts {
+        let mut data = self.working_memory.to_typed_facts();
+        if let Some(fact) = self.working_memory.get(&handle) {
+            for (key, value) in fact.data.get_all() {
+                data.set(format!("{}.{}", fact.fact_type, key), value.clone());
+            }
+            data.set_fact_handle(fact.fact_type.clone(), fact.handle);
+        }
+        data
+    }
+
     /// Retract fact from working memory
     pub fn retract(&mut self, handle: FactHandle) -> Result<()> {
         // Get fact type before retract
@@ -705,6 +797,38 @@ impl IncrementalEngine {
         }
     }
 
+    /// Match every rule against the current working memory and report the
+    /// size of each alpha and join node memory
+    ///
+    /// Each rule is checked against the facts of the types it depends on, one
+    /// fact at a time, as during propagation.
+    pub fn network_stats(&self) -> ReteStats {
+        let mut stats = ReteStats::default();
+        for (rule_idx, rule) in self.rules.iter().enumerate() {
+            let mut fact_types: Vec<String> = self
+                .dependencies
+                .get_rule_dependencies(rule_idx)
+                .into_iter()
+                .collect();
+            fact_types.sort();
+
+            let facts: Vec<TypedFacts> = fact_types
+                .iter()
+                .flat_map(|fact_type| self.working_memory.get_by_type(fact_type))
+                .map(Self::single_fact_data)
+                .collect();
+
+            collect_node_stats(
+                &rule.name,
+                &rule.node,
+                &facts,
+                &self.custom_functions,
+                &mut stats,
+            );
+        }
+        stats
+    }
+
     /// Clear fired flags and reset agenda
     pub fn reset(&mut self) {
         self.agenda.reset_fired_flags();
@@ -942,4 +1066,177 @@ mod tests {
 
         // Rule should be re-evaluated (incrementally)
     }
+
+    #[test]
+    fn test_update_fact_reports_agenda_delta() {
+        let mut engine = IncrementalEngine::new();
+
+        let alpha_rule = |name: &str, field: &str, operator: &str, value: &str| TypedReteUlRule {
+            name: name.to_string(),
+            node: ReteUlNode::UlAlpha(AlphaNode {
+                field: field.to_string(),
+                operator: operator.to_string(),
+                value: value.to_string(),
+            }),
+            priority: 0,
+            no_loop: true,
+            action: std::sync::Arc::new(|_, _| {}),
+        };
+
+        engine.add_rule(
+            alpha_rule("IsAdult", "Person.age", ">=", "18"),
+            vec!["Person".to_string()],
+        );
+        engine.add_rule(
+            alpha_rule("IsVip", "Person.tier", "==", "gold"),
+            vec!["Person".to_string()],
+        );
+
+        let mut person = TypedFacts::new();
+        person.set("age", 25i64);
+        person.set("tier", "silver");
+        let handle = engine.insert("Person".to_string(), person);
+        assert_eq!(engine.stats().agenda.total_activations, 1);
+
+        // Only IsAdult reads age, so IsVip is not re-evaluated
+        let delta = engine.update_fact(handle, "age", 15i64).unwrap();
+        assert_eq!(delta.deactivated, vec!["IsAdult".to_string()]);
+        assert!(delta.activated.is_empty());
+        assert_eq!(engine.stats().agenda.total_activations, 0);
+
+        let delta = engine.update_fact(handle, "tier", "gold").unwrap();
+        assert_eq!(delta.activated, vec!["IsVip".to_string()]);
+        assert!(delta.deactivated.is_empty());
+
+        // Unchanged match: no delta
+        let delta = engine.update_fact(handle, "tier", "gold").unwrap();
+        assert!(delta.is_empty());
+
+        assert_eq!(engine.fire_all(), vec!["IsVip".to_string()]);
+        assert_eq!(
+            engine
+                .working_memory()
+                .get(&handle)
+                .and_then(|f| f.data.get("age"))
+                .and_then(|v| v.as_integer()),
+            Some(15)
+        );
+
+        assert!(engine
+            .update_fact(FactHandle::new(999), "age", 1i64)
+            .is_err());
+    }
+
+    #[test]
+    fn test_update_fact_re_evaluates_rules_joining_other_fact_types() {
+        let mut engine = IncrementalEngine::new();
+
+        let alpha = |field: &str, operator: &str, value: &str| {
+            Box::new(ReteUlNode::UlAlpha(AlphaNode {
+                field: field.to_string(),
+                operator: operator.to_string(),
+                value: value.to_string(),
+            }))
+        };
+        engine.add_rule(
+            TypedReteUlRule {
+                name: "GoldBigOrder".to_string(),
+                node: ReteUlNode::UlAnd(
+                    alpha("Order.total", ">", "100"),
+                    alpha("Customer.tier", "==", "gold"),
+                ),
+                priority: 0,
+                no_loop: true,
+                action: std::sync::Arc::new(|_, _| {}),
+            },
+            vec!["Order".to_string(), "Customer".to_string()],
+        );
+        engine.add_rule(
+            TypedReteUlRule {
+                name: "TaxedOrder".to_string(),
+                node: *alpha("Order.totalTax", ">", "0"),
+                priority: 0,
+                no_loop: true,
+                action: std::sync::Arc::new(|_, _| {}),
+            },
+            vec!["Order".to_string()],
+        );
+
+        let mut order = TypedFacts::new();
+        order.set("total", 150i64);
+        order.set("totalTax", 0i64);
+        engine.insert("Order".to_string(), order);
+        let mut customer = TypedFacts::new();
+        customer.set("tier", "silver");
+        let customer = engine.insert("Customer".to_string(), customer);
+        engine.agenda_mut().clear();
+
+        // The order in working memory joins the changed customer
+        let delta = engine.update_fact(customer, "tier", "gold").unwrap();
+        assert_eq!(delta.activated, vec!["GoldBigOrder".to_string()]);
+        assert!(delta.deactivated.is_empty());
+
+        let delta = engine.update_fact(customer, "tier", "silver").unwrap();
+        assert_eq!(delta.deactivated, vec!["GoldBigOrder".to_string()]);
+        assert_eq!(engine.stats().agenda.total_activations, 0);
+    }
+
+    #[test]
+    fn test_references_field_matches_whole_paths() {
+        let alpha = |field: &str, value: &str| {
+            ReteUlNode::UlAlpha(AlphaNode {
+                field: field.to_string(),
+                operator: ">".to_string(),
+                value: value.to_string(),
+            })
+        };
+
+        assert!(alpha("Order.total", "0").references_field("Order.total"));
+        assert!(alpha("Order.limit", "Order.total * 2").references_field("Order.total"));
+        assert!(!alpha("Order.totalTax", "0").references_field("Order.total"));
+        assert!(!alpha("SubOrder.total", "0").references_field("Order.total"));
+        assert!(!alpha("Sub.Order.total", "0").references_field("Order.total"));
+    }
+
+    #[test]
+    fn test_network_stats_counts_node_memories() {
+        let mut engine = IncrementalEngine::new();
+        let node = ReteUlNode::UlAnd(
+            Box::new(ReteUlNode::UlAlpha(AlphaNode {
+                field: "Person.age".to_string(),
+                operator: ">".to_string(),
+                value: "18".to_string(),
+            })),
+            Box::new(ReteUlNode::UlAlpha(AlphaNode {
+                field: "Person.country".to_string(),
+                operator: "==".to_string(),
+                value: "VN".to_string(),
+            })),
+        );
+        engine.add_rule(
+            TypedReteUlRule {
+                name: "AdultInVietnam".to_string(),
+                node,
+                priority: 0,
+                no_loop: true,
+                action: std::sync::Arc::new(|_, _| {}),
+            },
+            vec!["Person".to_string()],
+        );
+
+        for (age, country) in [(25i64, "VN"), (30, "US"), (12, "VN")] {
+            let mut person = TypedFacts::new();
+            person.set("age", age);
+            person.set("country", country);
+            engine.insert("Person".to_string(), person);
+        }
+
+        let stats = engine.network_stats();
+        assert_eq!(stats.alpha_nodes.len(), 2);
+        assert_eq!(stats.alpha_nodes[0].field, "Person.age");
+        assert_eq!(stats.alpha_nodes[0].matches, 2);
+        assert_eq!(stats.alpha_nodes[1].matches, 2);
+        assert_eq!(stats.beta_nodes[0].output_size, 1);
+        assert_eq!(stats.total_tokens, 5);
+    }
 }
@@ -0,0 +1,187 @@
+//! Node-level statistics for the RETE-UL network
+//!
+//! [`IncrementalEngine::network_stats`](super::IncrementalEngine::network_stats)
+//! matches every rule against the current working memory and reports how many
+//! facts each alpha node and each join holds, which shows the patterns that
+//! match too broadly or make joins explode.
+
+use super::facts::TypedFacts;
+use super::network::{evaluate_rete_ul_node_typed, ReteUlNode};
+use super::propagation::ReteCustomFunction;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Memory of one alpha node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlphaNodeStats {
+    /// Rule owning the node
+    pub rule: String,
+    /// Tested field, e.g. `Order.amount`
+    pub field: String,
+    /// Comparison operator
+    pub operator: String,
+    /// Compared value
+    pub value: String,
+    /// Facts currently matching the node
+    pub matches: usize,
+}
+
+/// Memory of one join (`&&` / `||`) node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BetaNodeStats {
+    /// Rule owning the node
+    pub rule: String,
+    /// `"and"` or `"or"`
+    pub kind: String,
+    /// Facts matching the left input
+    pub left_size: usize,
+    /// Facts matching the right input
+    pub right_size: usize,
+    /// Facts passing the join
+    pub output_size: usize,
+}
+
+/// Snapshot of the RETE network's node memories
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReteStats {
+    /// Every alpha node, in rule order
+    pub alpha_nodes: Vec<AlphaNodeStats>,
+    /// Every join node, in rule order
+    pub beta_nodes: Vec<BetaNodeStats>,
+    /// Facts held across all alpha and join memories
+    pub total_tokens: usize,
+}
+
+impl ReteStats {
+    /// Alpha nodes ordered by match count, largest first
+    pub fn busiest_alpha_nodes(&self) -> Vec<&AlphaNodeStats> {
+        let mut nodes: Vec<_> = self.alpha_nodes.iter().collect();
+        nodes.sort_by_key(|s| std::cmp::Reverse(s.matches));
+        nodes
+    }
+
+    /// Join nodes ordered by output size, largest first
+    pub fn largest_joins(&self) -> Vec<&BetaNodeStats> {
+        let mut nodes: Vec<_> = self.beta_nodes.iter().collect();
+        nodes.sort_by_key(|s| std::cmp::Reverse(s.output_size));
+        nodes
+    }
+}
+
+impl fmt::Display for ReteStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RETE Stats: {} alpha nodes, {} join nodes, {} tokens",
+            self.alpha_nodes.len(),
+            self.beta_nodes.len(),
+            self.total_tokens
+        )
+    }
+}
+
+/// Match `node` of `rule` against each fact, recording node memories in `stats`
+///
+/// Returns whether each fact matches the node.
+pub(crate) fn collect_node_stats(
+    rule: &str,
+    node: &ReteUlNode,
+    facts: &[TypedFacts],
+    custom_fns: &HashMap<String, ReteCustomFunction>,
+    stats: &mut ReteStats,
+) -> Vec<bool> {
+    match node {
+        ReteUlNode::UlAlpha(alpha) => {
+            let results: Vec<bool> = facts.iter().map(|f| alpha.matches_typed(f)).collect();
+            let matches = results.iter().filter(|m| **m).count();
+            stats.total_tokens += matches;
+            stats.alpha_nodes.push(AlphaNodeStats {
+                rule: rule.to_string(),
+                field: alpha.field.clone(),
+                operator: alpha.operator.clone(),
+                value: alpha.value.clone(),
+                matches,
+            });
+            results
+        }
+        ReteUlNode::UlAnd(left, right) | ReteUlNode::UlOr(left, right) => {
+            let is_and = matches!(node, ReteUlNode::UlAnd(..));
+            let left_results = collect_node_stats(rule, left, facts, custom_fns, stats);
+            let right_results = collect_node_stats(rule, right, facts, custom_fns, stats);
+            let results: Vec<bool> = left_results
+                .iter()
+                .zip(&right_results)
+                .map(|(l, r)| if is_and { *l && *r } else { *l || *r })
+                .collect();
+
+            let output_size = results.iter().filter(|m| **m).count();
+            stats.total_tokens += output_size;
+            stats.beta_nodes.push(BetaNodeStats {
+                rule: rule.to_string(),
+                kind: if is_and { "and" } else { "or" }.to_string(),
+                left_size: left_results.iter().filter(|m| **m).count(),
+                right_size: right_results.iter().filter(|m| **m).count(),
+                output_size,
+            });
+            results
+        }
+        ReteUlNode::UlNot(inner) => collect_node_stats(rule, inner, facts, custom_fns, stats)
+            .into_iter()
+            .map(|m| !m)
+            .collect(),
+        _ => facts
+            .iter()
+            .map(|f| evaluate_rete_ul_node_typed(node, f, custom_fns))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rete::alpha::AlphaNode;
+
+    fn alpha(field: &str, operator: &str, value: &str) -> Box<ReteUlNode> {
+        Box::new(ReteUlNode::UlAlpha(AlphaNode {
+            field: field.to_string(),
+            operator: operator.to_string(),
+            value: value.to_string(),
+        }))
+    }
+
+    #[test]
+    fn test_collect_node_stats() {
+        let node = ReteUlNode::UlAnd(
+            alpha("Order.amount", ">", "100"),
+            alpha("Order.status", "==", "open"),
+        );
+
+        let facts: Vec<TypedFacts> = [(50i64, "open"), (150, "open"), (200, "closed")]
+            .iter()
+            .map(|(amount, status)| {
+                let mut facts = TypedFacts::new();
+                facts.set("Order.amount", *amount);
+                facts.set("Order.status", *status);
+                facts
+            })
+            .collect();
+
+        let mut stats = ReteStats::default();
+        let results = collect_node_stats("Big", &node, &facts, &HashMap::new(), &mut stats);
+
+        assert_eq!(results, vec![false, true, false]);
+        assert_eq!(
+            stats
+                .alpha_nodes
+                .iter()
+                .map(|n| n.matches)
+                .collect::<Vec<_>>(),
+            vec![2, 2]
+        );
+        assert_eq!(stats.beta_nodes.len(), 1);
+        assert_eq!(stats.beta_nodes[0].left_size, 2);
+        assert_eq!(stats.beta_nodes[0].output_size, 1);
+        assert_eq!(stats.total_tokens, 5);
+        assert_eq!(stats.busiest_alpha_nodes()[0].rule, "Big");
+    }
+}
@@ -6,7 +6,7 @@
 #![allow(missing_docs)]
 
 use crate::streaming::event::StreamEvent;
-use crate::streaming::window::WindowType;
+use crate::streaming::window::{hopping_window_starts, WindowType};
 use std::collections::VecDeque;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -184,6 +184,11 @@ impl StreamAlphaNode {
                         timestamp >= current_time.saturating_sub(window_duration_ms)
                             && timestamp <= current_time
                     }
+                    WindowType::Hopping { size, slide } => {
+                        // Oldest hopping window still open at `current_time`
+                        let window_start = Self::hopping_window_start(current_time, size, slide);
+                        timestamp >= window_start && timestamp <= current_time
+                    }
                     WindowType::Tumbling => {
                         // Calculate window boundaries
                         let window_start = (current_time / window_duration_ms) * window_duration_ms;
@@ -238,6 +243,17 @@ impl StreamAlphaNode {
                         }
                     }
                 }
+                WindowType::Hopping { size, slide } => {
+                    let cutoff_time = Self::hopping_window_start(current_time, size, slide);
+
+                    while let Some(event) = self.events.front() {
+                        if event.metadata.timestamp < cutoff_time {
+                            self.events.pop_front();
+                        } else {
+                            break;
+                        }
+                    }
+                }
                 WindowType::Tumbling => {
                     let window_start = (current_time / window_duration_ms) * window_duration_ms;
 
@@ -292,6 +308,14 @@ impl StreamAlphaNode {
         self.events.len()
     }
 
+    /// Start of the oldest hopping window that still covers `current_time`
+    fn hopping_window_start(current_time: u64, size: Duration, slide: Duration) -> u64 {
+        hopping_window_starts(current_time, size, slide)
+            .first()
+            .copied()
+            .unwrap_or(current_time)
+    }
+
     /// Get current time in milliseconds since epoch
     fn current_time_ms() -> u64 {
         SystemTime::now()
@@ -10,12 +10,14 @@ use crate::engine::RustRuleEngine;
 use crate::parser::grl::GRLParser;
 use crate::streaming::aggregator::StreamAnalytics;
 use crate::streaming::event::StreamEvent;
-use crate::streaming::window::{TimeWindow, WindowManager, WindowType};
+use crate::streaming::watermark::Watermark;
+use crate::streaming::window::{TimeSemantics, TimeWindow, WindowManager, WindowType};
 use crate::types::Value;
 use crate::{Result, RuleEngineError};
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::interval;
@@ -25,6 +27,9 @@ use tokio::time::interval;
 pub struct StreamConfig {
     /// Buffer size for incoming events
     pub buffer_size: usize,
+    /// Maximum number of events sent but not yet processed; beyond it
+    /// `send_event` reports backpressure instead of queueing
+    pub max_buffered_events: usize,
     /// Window duration for aggregations
     pub window_duration: Duration,
     /// Maximum events per window
@@ -33,30 +38,53 @@ pub struct StreamConfig {
     pub max_windows: usize,
     /// Window type (sliding, tumbling, etc.)
     pub window_type: WindowType,
+    /// Whether windows follow event time or processing time
+    pub time_semantics: TimeSemantics,
     /// Cache TTL for analytics
     pub analytics_cache_ttl_ms: u64,
     /// Processing interval for rule evaluation
     pub processing_interval: Duration,
+    /// Maximum number of times derived events may re-enter the pipeline
+    /// during a single `execute_rules` call
+    pub max_derivation_depth: usize,
 }
 
 impl Default for StreamConfig {
     fn default() -> Self {
         Self {
             buffer_size: 10000,
+            max_buffered_events: 10000,
             window_duration: Duration::from_secs(60),
             max_events_per_window: 1000,
             max_windows: 100,
             window_type: WindowType::Sliding,
+            time_semantics: TimeSemantics::default(),
             analytics_cache_ttl_ms: 30000,
             processing_interval: Duration::from_millis(100),
+            max_derivation_depth: 8,
         }
     }
 }
 
+/// Whether `send_event` queued an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendOutcome {
+    /// The event was queued for processing
+    Accepted,
+    /// The engine is overloaded and the event was not queued; retry later
+    Backpressure {
+        /// Events waiting to be processed
+        queue_depth: usize,
+    },
+    /// The engine has not been started, or has been stopped; the event was
+    /// not queued
+    NotRunning,
+}
+
 /// Result of stream rule execution
 #[derive(Debug, Clone)]
 pub struct StreamExecutionResult {
-    /// Number of rules that fired
+    /// Number of rules that fired, counting each rule once per window
     pub rules_fired: usize,
     /// Number of events processed
     pub events_processed: usize,
@@ -66,6 +94,8 @@ pub struct StreamExecutionResult {
     pub actions: Vec<StreamAction>,
     /// Analytics results
     pub analytics: HashMap<String, Value>,
+    /// Events emitted by rules via `emit_event(...)` and fed back into the stream
+    pub derived_events: Vec<StreamEvent>,
 }
 
 /// Action triggered by stream rules
@@ -81,6 +111,25 @@ pub struct StreamAction {
     pub rule_name: String,
 }
 
+/// Final aggregates of a closed window, delivered to window sinks
+#[derive(Debug, Clone)]
+pub struct WindowResult {
+    /// Window start (milliseconds since epoch, inclusive)
+    pub start_time: u64,
+    /// Window end (milliseconds since epoch, exclusive)
+    pub end_time: u64,
+    /// Key of a keyed session window
+    pub key: Option<String>,
+    /// Number of events in the window
+    pub event_count: usize,
+    /// `<field>Sum`, `<field>Average`, `<field>Min` and `<field>Max` for every
+    /// numeric field, as exposed to stream rules
+    pub aggregates: HashMap<String, Value>,
+}
+
+/// Callback receiving closed windows
+pub type WindowSink = Box<dyn Fn(WindowResult) + Send + Sync>;
+
 /// Main streaming rule engine
 pub struct StreamRuleEngine {
     /// Configuration
@@ -93,52 +142,41 @@ pub struct StreamRuleEngine {
     analytics: Arc<RwLock<StreamAnalytics>>,
     /// Event sender
     event_sender: Option<mpsc::Sender<StreamEvent>>,
+    /// Events sent but not yet processed
+    queued_events: Arc<AtomicUsize>,
     /// Action callbacks
     action_handlers: Arc<RwLock<HashMap<String, Box<dyn Fn(&StreamAction) + Send + Sync>>>>,
+    /// Callbacks invoked when a window closes
+    window_sinks: Arc<RwLock<Vec<WindowSink>>>,
     /// Running state
     is_running: Arc<RwLock<bool>>,
+    /// Events emitted by rule actions that have not yet re-entered the stream
+    pending_derived: Arc<Mutex<Vec<StreamEvent>>>,
 }
 
 impl StreamRuleEngine {
     /// Create a new stream rule engine
     pub fn new() -> Self {
-        let config = StreamConfig::default();
-        let kb = KnowledgeBase::new("StreamKB");
-        let rule_engine = RustRuleEngine::new(kb);
-
-        let window_manager = Arc::new(RwLock::new(WindowManager::new(
-            config.window_type.clone(),
-            config.window_duration,
-            config.max_events_per_window,
-            config.max_windows,
-        )));
-
-        let analytics = Arc::new(RwLock::new(StreamAnalytics::new(
-            config.analytics_cache_ttl_ms,
-        )));
-
-        Self {
-            config,
-            rule_engine,
-            window_manager,
-            analytics,
-            event_sender: None,
-            action_handlers: Arc::new(RwLock::new(HashMap::new())),
-            is_running: Arc::new(RwLock::new(false)),
-        }
+        Self::with_config(StreamConfig::default())
     }
 
     /// Create with custom configuration
     pub fn with_config(config: StreamConfig) -> Self {
         let kb = KnowledgeBase::new("StreamKB");
-        let rule_engine = RustRuleEngine::new(kb);
+        let mut rule_engine = RustRuleEngine::new(kb);
 
-        let window_manager = Arc::new(RwLock::new(WindowManager::new(
-            config.window_type.clone(),
-            config.window_duration,
-            config.max_events_per_window,
-            config.max_windows,
-        )));
+        let pending_derived = Arc::new(Mutex::new(Vec::new()));
+        Self::register_emit_event_handler(&mut rule_engine, Arc::clone(&pending_derived));
+
+        let window_manager = Arc::new(RwLock::new(
+            WindowManager::new(
+                config.window_type.clone(),
+                config.window_duration,
+                config.max_events_per_window,
+                config.max_windows,
+            )
+            .with_time_semantics(config.time_semantics.clone()),
+        ));
 
         let analytics = Arc::new(RwLock::new(StreamAnalytics::new(
             config.analytics_cache_ttl_ms,
@@ -150,11 +188,52 @@ impl StreamRuleEngine {
             window_manager,
             analytics,
             event_sender: None,
+            queued_events: Arc::new(AtomicUsize::new(0)),
             action_handlers: Arc::new(RwLock::new(HashMap::new())),
+            window_sinks: Arc::new(RwLock::new(Vec::new())),
             is_running: Arc::new(RwLock::new(false)),
+            pending_derived,
         }
     }
 
+    /// Register the `emit_event` action used by rules to derive new events.
+    ///
+    /// `emit_event("Type", "key1", value1, "key2", value2, ...)` queues a
+    /// `StreamEvent` of the given type; unquoted values such as
+    /// `WindowEventCount` are evaluated against the window facts.
+    fn register_emit_event_handler(
+        rule_engine: &mut RustRuleEngine,
+        pending: Arc<Mutex<Vec<StreamEvent>>>,
+    ) {
+        rule_engine.register_action_handler("emit_event", move |params, facts| {
+            let event_type = params.get("0").map(|v| v.to_string()).ok_or_else(|| {
+                RuleEngineError::ActionError {
+                    message: "emit_event requires an event type".to_string(),
+                }
+            })?;
+
+            let mut data = HashMap::new();
+            let mut index = 1;
+            while let Some(key) = params.get(&index.to_string()) {
+                let value = match params.get(&(index + 1).to_string()) {
+                    Some(Value::Expression(expr)) => {
+                        crate::expression::evaluate_expression(expr, facts)?
+                    }
+                    Some(value) => value.clone(),
+                    None => Value::Null,
+                };
+                data.insert(key.to_string(), value);
+                index += 2;
+            }
+
+            pending
+                .lock()
+                .unwrap()
+                .push(StreamEvent::new(event_type, data, "derived"));
+            Ok(())
+        });
+    }
+
     /// Add streaming rule from GRL string
     pub async fn add_rule(&mut self, grl_rule: &str) -> Result<()> {
         let rules = GRLParser::parse_rules(grl_rule)?;
@@ -168,7 +247,7 @@ impl StreamRuleEngine {
 
     /// Add streaming rule from file
     pub async fn add_rule_file<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<()> {
-        let content = std::fs::read_to_string(path)?;
+        let content = std::fs::read_to_string(&path).map_err(|e| RuleEngineError::io(&path, e))?;
         self.add_rule(&content).await
     }
 
@@ -181,10 +260,34 @@ impl StreamRuleEngine {
         handlers.insert(action_type.to_string(), Box::new(handler));
     }
 
+    /// Register a sink that receives each window's results once it closes
+    ///
+    /// A window closes when event time passes its end, either through a
+    /// later event or [`advance_watermark`](Self::advance_watermark); sinks
+    /// are not called for individual events.
+    pub async fn register_window_sink<F>(&self, sink: F)
+    where
+        F: Fn(WindowResult) + Send + Sync + 'static,
+    {
+        self.window_sinks.write().await.push(Box::new(sink));
+    }
+
+    /// Advance event time to `watermark`, closing and emitting every window
+    /// that ends at or before it
+    pub async fn advance_watermark(&self, watermark: Watermark) {
+        let closed = {
+            let mut manager = self.window_manager.write().await;
+            manager.advance_watermark(watermark);
+            manager.take_closed_windows()
+        };
+        Self::emit_closed_windows(&self.window_sinks, closed).await;
+    }
+
     /// Start the streaming engine
     pub async fn start(&mut self) -> Result<()> {
         let (tx, mut rx) = mpsc::channel::<StreamEvent>(self.config.buffer_size);
         self.event_sender = Some(tx);
+        self.queued_events.store(0, Ordering::SeqCst);
 
         // Set running state
         {
@@ -194,9 +297,11 @@ impl StreamRuleEngine {
 
         // Clone shared components for the processing task
         let window_manager = Arc::clone(&self.window_manager);
+        let window_sinks = Arc::clone(&self.window_sinks);
         let _analytics = Arc::clone(&self.analytics);
         let _action_handlers = Arc::clone(&self.action_handlers);
         let is_running = Arc::clone(&self.is_running);
+        let queued_events = Arc::clone(&self.queued_events);
         let processing_interval = self.config.processing_interval;
 
         // Start event processing task
@@ -214,7 +319,8 @@ impl StreamRuleEngine {
 
                                 // Process batch when full or on timer
                                 if event_batch.len() >= 100 {
-                                    Self::process_event_batch(&window_manager, &event_batch).await;
+                                    Self::process_event_batch(&window_manager, &window_sinks, &event_batch).await;
+                                    queued_events.fetch_sub(event_batch.len(), Ordering::SeqCst);
                                     event_batch.clear();
                                 }
                             }
@@ -225,7 +331,8 @@ impl StreamRuleEngine {
                     // Timer tick for processing
                     _ = interval_timer.tick() => {
                         if !event_batch.is_empty() {
-                            Self::process_event_batch(&window_manager, &event_batch).await;
+                            Self::process_event_batch(&window_manager, &window_sinks, &event_batch).await;
+                            queued_events.fetch_sub(event_batch.len(), Ordering::SeqCst);
                             event_batch.clear();
                         }
 
@@ -249,78 +356,209 @@ impl StreamRuleEngine {
     }
 
     /// Send event to stream for processing
-    pub async fn send_event(&self, event: StreamEvent) -> Result<()> {
-        if let Some(ref sender) = self.event_sender {
-            sender.send(event).await.map_err(|_| {
-                RuleEngineError::ExecutionError("Failed to send event to stream".to_string())
-            })?;
+    ///
+    /// Once `StreamConfig::max_buffered_events` events are waiting to be
+    /// processed, the event is not queued and
+    /// [`SendOutcome::Backpressure`] is returned so the producer can slow down.
+    /// Before `start` or after `stop` the result is [`SendOutcome::NotRunning`].
+    pub async fn send_event(&self, event: StreamEvent) -> Result<SendOutcome> {
+        let Some(sender) = self.event_sender.as_ref() else {
+            return Ok(SendOutcome::NotRunning);
+        };
+        if !*self.is_running.read().await {
+            return Ok(SendOutcome::NotRunning);
         }
-        Ok(())
+
+        // Check the limit and claim a slot in one atomic step, so concurrent
+        // senders cannot both pass the check and overfill the queue
+        let max = self.config.max_buffered_events;
+        if let Err(queue_depth) =
+            self.queued_events
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |depth| {
+                    (depth < max).then_some(depth + 1)
+                })
+        {
+            return Ok(SendOutcome::Backpressure { queue_depth });
+        }
+
+        if sender.send(event).await.is_err() {
+            self.queued_events.fetch_sub(1, Ordering::SeqCst);
+            return Err(RuleEngineError::ExecutionError(
+                "Failed to send event to stream".to_string(),
+            ));
+        }
+        Ok(SendOutcome::Accepted)
+    }
+
+    /// Number of events sent but not yet processed
+    pub fn queue_depth(&self) -> usize {
+        self.queued_events.load(Ordering::SeqCst)
     }
 
     /// Process a batch of events
     async fn process_event_batch(
         window_manager: &Arc<RwLock<WindowManager>>,
+        window_sinks: &Arc<RwLock<Vec<WindowSink>>>,
         events: &[StreamEvent],
     ) {
-        let mut manager = window_manager.write().await;
-        for event in events {
-            manager.process_event(event.clone());
+        let closed = {
+            let mut manager = window_manager.write().await;
+            for event in events {
+                manager.process_event(event.clone());
+            }
+            manager.take_closed_windows()
+        };
+        Self::emit_closed_windows(window_sinks, closed).await;
+    }
+
+    /// Deliver closed windows to every registered sink
+    async fn emit_closed_windows(
+        window_sinks: &Arc<RwLock<Vec<WindowSink>>>,
+        closed: Vec<TimeWindow>,
+    ) {
+        if closed.is_empty() {
+            return;
+        }
+
+        let sinks = window_sinks.read().await;
+        for window in &closed {
+            let result = Self::window_result(window);
+            for sink in sinks.iter() {
+                sink(result.clone());
+            }
+        }
+    }
+
+    /// Final aggregates of a window
+    fn window_result(window: &TimeWindow) -> WindowResult {
+        let mut aggregates = HashMap::new();
+        for field in Self::detect_numeric_fields(window) {
+            aggregates.insert(format!("{}Sum", field), Value::Number(window.sum(&field)));
+            if let Some(avg) = window.average(&field) {
+                aggregates.insert(format!("{}Average", field), Value::Number(avg));
+            }
+            if let Some(min) = window.min(&field) {
+                aggregates.insert(format!("{}Min", field), Value::Number(min));
+            }
+            if let Some(max) = window.max(&field) {
+                aggregates.insert(format!("{}Max", field), Value::Number(max));
+            }
+        }
+
+        WindowResult {
+            start_time: window.start_time,
+            end_time: window.end_time,
+            key: window.key.clone(),
+            event_count: window.count(),
+            aggregates,
         }
     }
 
     /// Execute rules against current window state
+    ///
+    /// Events emitted by rules through `emit_event(...)` are stamped into the
+    /// window that produced them and the rules are re-run, so higher-level
+    /// rules can react to derived events. Identical derived events are only
+    /// emitted once per call, and re-entry stops after
+    /// `StreamConfig::max_derivation_depth` rounds.
     pub async fn execute_rules(&mut self) -> Result<StreamExecutionResult> {
         let start_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_millis() as u64;
 
-        let window_manager = self.window_manager.read().await;
-        let _analytics = self.analytics.read().await;
-
-        // Get current windows
-        let windows = window_manager.active_windows();
         let mut total_events_processed = 0;
         let mut rules_fired = 0;
         let actions = Vec::new();
         let mut analytics_results = HashMap::new();
+        let mut derived_events = Vec::new();
+        let mut seen_derived = HashSet::new();
+        let mut fired_in_window = HashSet::new();
+        let mut depth = 0;
+
+        loop {
+            let mut new_events = Vec::new();
+            {
+                let window_manager = self.window_manager.read().await;
+                let windows = window_manager.active_windows();
 
-        // Process each window
-        for window in windows {
-            total_events_processed += window.count();
+                // Process each window
+                for window in windows {
+                    if depth == 0 {
+                        total_events_processed += window.count();
+                    }
 
-            // Create facts from window data
-            let facts = Facts::new();
+                    // Create facts from window data
+                    let facts = Facts::new();
+
+                    // Add window aggregations to facts
+                    self.add_window_aggregations_to_facts(&facts, window)
+                        .await?;
+
+                    // Execute rules on this window. Every round re-runs all
+                    // windows, so a rule is counted once per window
+                    let mut fired = Vec::new();
+                    self.rule_engine
+                        .execute_with_callback(&facts, |name, _| fired.push(name.to_string()))?;
+                    for name in fired {
+                        if fired_in_window.insert((window.start_time, name)) {
+                            rules_fired += 1;
+                        }
+                    }
 
-            // Add window aggregations to facts
-            self.add_window_aggregations_to_facts(&facts, window)
-                .await?;
+                    // Derived events land in the window that produced them
+                    let emitted: Vec<StreamEvent> =
+                        std::mem::take(&mut *self.pending_derived.lock().unwrap());
+                    for mut event in emitted {
+                        if !seen_derived.insert(derived_event_signature(window, &event)) {
+                            continue;
+                        }
+                        if let Some(timestamp) = window.latest_timestamp() {
+                            event.metadata.timestamp = timestamp;
+                        }
+                        event.add_tag("derivation_depth", (depth + 1).to_string());
+                        new_events.push(event);
+                    }
+                }
 
-            // Execute rules on this window
-            let result = self.rule_engine.execute(&facts)?;
-            rules_fired += result.rules_fired;
+                // Calculate analytics
+                if depth == 0 && !windows.is_empty() {
+                    let latest_window = windows.last().unwrap();
+                    analytics_results.insert(
+                        "total_events".to_string(),
+                        Value::Number(total_events_processed as f64),
+                    );
+                    analytics_results.insert(
+                        "window_count".to_string(),
+                        Value::Number(windows.len() as f64),
+                    );
+                    analytics_results.insert(
+                        "latest_window_events".to_string(),
+                        Value::Number(latest_window.count() as f64),
+                    );
+                }
+            }
 
-            // Note: Traditional rule engine doesn't return actions,
-            // we'd need to extend it for streaming action capture
-            // For now, we create empty actions list
-        }
+            if new_events.is_empty() {
+                break;
+            }
 
-        // Calculate analytics
-        if !windows.is_empty() {
-            let latest_window = windows.last().unwrap();
-            analytics_results.insert(
-                "total_events".to_string(),
-                Value::Number(total_events_processed as f64),
-            );
-            analytics_results.insert(
-                "window_count".to_string(),
-                Value::Number(windows.len() as f64),
-            );
-            analytics_results.insert(
-                "latest_window_events".to_string(),
-                Value::Number(latest_window.count() as f64),
-            );
+            if depth >= self.config.max_derivation_depth {
+                log::warn!(
+                    "Dropping {} derived event(s): max derivation depth {} reached",
+                    new_events.len(),
+                    self.config.max_derivation_depth
+                );
+                break;
+            }
+
+            // Feed derived events back into the stream and re-evaluate
+            let mut window_manager = self.window_manager.write().await;
+            for event in &new_events {
+                window_manager.process_event(event.clone());
+            }
+            derived_events.extend(new_events);
+            depth += 1;
         }
 
         let end_time = SystemTime::now()
@@ -334,6 +572,7 @@ impl StreamRuleEngine {
             processing_time_ms: end_time - start_time,
             actions,
             analytics: analytics_results,
+            derived_events,
         })
     }
 
@@ -352,8 +591,20 @@ impl StreamRuleEngine {
             Value::Number(window.duration_ms() as f64),
         )?;
 
+        // Per-type event counts, e.g. `EventCounts.DerivedAlert`
+        let mut event_counts = HashMap::new();
+        for event in window.events() {
+            let count = event_counts
+                .entry(event.event_type.clone())
+                .or_insert(Value::Integer(0));
+            if let Value::Integer(n) = count {
+                *n += 1;
+            }
+        }
+        facts.add_value("EventCounts", Value::Object(event_counts))?;
+
         // Add common aggregations for numeric fields
-        let numeric_fields = self.detect_numeric_fields(window);
+        let numeric_fields = Self::detect_numeric_fields(window);
         for field in numeric_fields {
             if let Some(sum) = window
                 .events()
@@ -381,7 +632,7 @@ impl StreamRuleEngine {
     }
 
     /// Detect numeric fields in window events
-    fn detect_numeric_fields(&self, window: &TimeWindow) -> Vec<String> {
+    fn detect_numeric_fields(window: &TimeWindow) -> Vec<String> {
         let mut fields = std::collections::HashSet::new();
 
         for event in window.events() {
@@ -452,6 +703,14 @@ impl StreamRuleEngine {
     }
 }
 
+/// Identity of a derived event within one `execute_rules` call, used to stop
+/// a rule that keeps firing from emitting the same event over and over
+fn derived_event_signature(window: &TimeWindow, event: &StreamEvent) -> String {
+    let mut fields: Vec<_> = event.data.iter().collect();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+    format!("{}:{}:{:?}", window.start_time, event.event_type, fields)
+}
+
 impl Default for StreamRuleEngine {
     fn default() -> Self {
         Self::new()
@@ -499,4 +758,191 @@ mod tests {
 
         engine.stop().await;
     }
+
+    #[tokio::test]
+    async fn test_send_event_reports_backpressure() {
+        let mut engine = StreamRuleEngine::with_config(StreamConfig {
+            max_buffered_events: 2,
+            processing_interval: Duration::from_secs(60),
+            ..Default::default()
+        });
+        engine.start().await.unwrap();
+
+        assert_eq!(
+            engine.send_event(reading(1.0)).await.unwrap(),
+            SendOutcome::Accepted
+        );
+        assert_eq!(
+            engine.send_event(reading(2.0)).await.unwrap(),
+            SendOutcome::Accepted
+        );
+        assert_eq!(
+            engine.send_event(reading(3.0)).await.unwrap(),
+            SendOutcome::Backpressure { queue_depth: 2 }
+        );
+        assert_eq!(engine.queue_depth(), 2);
+
+        engine.stop().await;
+        assert_eq!(
+            engine.send_event(reading(4.0)).await.unwrap(),
+            SendOutcome::NotRunning
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_event_before_start_is_not_accepted() {
+        let engine = StreamRuleEngine::new();
+        assert_eq!(
+            engine.send_event(reading(1.0)).await.unwrap(),
+            SendOutcome::NotRunning
+        );
+        assert_eq!(engine.queue_depth(), 0);
+    }
+
+    fn reading(value: f64) -> StreamEvent {
+        let mut data = HashMap::new();
+        data.insert("value".to_string(), Value::Number(value));
+        StreamEvent::new("SensorReading", data, "sensor")
+    }
+
+    #[tokio::test]
+    async fn test_derived_event_triggers_higher_level_rule() {
+        let mut engine = StreamRuleEngine::new();
+        engine
+            .add_rule(
+                r#"
+        rule "DetectSpike" salience 10 {
+            when
+                valueMax > 100
+            then
+                emit_event("DerivedAlert", "severity", "high");
+        }
+
+        rule "EscalateAlerts" {
+            when
+                EventCounts.DerivedAlert >= 1
+            then
+                emit_event("Escalation", "alerts", 1);
+        }
+        "#,
+            )
+            .await
+            .unwrap();
+
+        {
+            let mut manager = engine.window_manager.write().await;
+            manager.process_event(reading(20.0));
+            manager.process_event(reading(150.0));
+        }
+
+        let result = engine.execute_rules().await.unwrap();
+        let types: Vec<&str> = result
+            .derived_events
+            .iter()
+            .map(|e| e.event_type.as_str())
+            .collect();
+
+        assert_eq!(types, vec!["DerivedAlert", "Escalation"]);
+        // Re-running the windows for derived events does not count fires again
+        assert_eq!(result.rules_fired, 2);
+        assert_eq!(
+            result.derived_events[0].data.get("severity"),
+            Some(&Value::String("high".to_string()))
+        );
+        assert_eq!(
+            result.derived_events[1]
+                .metadata
+                .tags
+                .get("derivation_depth")
+                .map(String::as_str),
+            Some("2")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_derived_events_stop_at_max_depth() {
+        let mut engine = StreamRuleEngine::with_config(StreamConfig {
+            max_derivation_depth: 3,
+            ..Default::default()
+        });
+        engine
+            .add_rule(
+                r#"
+        rule "Echo" {
+            when
+                WindowEventCount > 0
+            then
+                emit_event("Echo", "seen", WindowEventCount);
+        }
+        "#,
+            )
+            .await
+            .unwrap();
+
+        engine
+            .window_manager
+            .write()
+            .await
+            .process_event(reading(1.0));
+
+        let result = engine.execute_rules().await.unwrap();
+        assert_eq!(result.derived_events.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_window_sink_receives_closed_windows() {
+        let engine = StreamRuleEngine::with_config(StreamConfig {
+            window_type: WindowType::Tumbling,
+            window_duration: Duration::from_secs(60),
+            ..Default::default()
+        });
+
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let sink_results = Arc::clone(&results);
+        engine
+            .register_window_sink(move |result| sink_results.lock().unwrap().push(result))
+            .await;
+
+        let reading_at = |timestamp, value| {
+            let mut data = HashMap::new();
+            data.insert("value".to_string(), Value::Number(value));
+            StreamEvent::with_timestamp("SensorReading", data, "sensor", timestamp)
+        };
+
+        StreamRuleEngine::process_event_batch(
+            &engine.window_manager,
+            &engine.window_sinks,
+            &[reading_at(1_000, 1.0), reading_at(30_000, 2.0)],
+        )
+        .await;
+        assert!(results.lock().unwrap().is_empty());
+
+        // An event past the first window's end closes it
+        StreamRuleEngine::process_event_batch(
+            &engine.window_manager,
+            &engine.window_sinks,
+            &[reading_at(61_000, 5.0)],
+        )
+        .await;
+        {
+            let results = results.lock().unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!((results[0].start_time, results[0].end_time), (0, 60_000));
+            assert_eq!(results[0].event_count, 2);
+            assert_eq!(
+                results[0].aggregates.get("valueSum"),
+                Some(&Value::Number(3.0))
+            );
+        }
+
+        // With no further events, the watermark closes the second window
+        engine.advance_watermark(Watermark::new(120_000)).await;
+        let results = results.lock().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].start_time, 60_000);
+        assert_eq!(
+            results[1].aggregates.get("valueMax"),
+            Some(&Value::Number(5.0))
+        );
+    }
 }
@@ -1,7 +1,11 @@
-use crate::rete::stream_join_node::{JoinedEvent, StreamJoinNode};
+use crate::rete::stream_join_node::{JoinNodeStats, JoinedEvent, StreamJoinNode};
 use crate::streaming::event::StreamEvent;
-use std::collections::HashMap;
+use crate::streaming::watermark::{
+    LateDataHandler, LateDataStats, LateDataStrategy, LateEventDecision, Watermark,
+};
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Manages multiple stream joins and coordinates event routing
 pub struct StreamJoinManager {
@@ -9,16 +13,118 @@ pub struct StreamJoinManager {
     joins: HashMap<String, Arc<Mutex<StreamJoinNode>>>,
     /// Maps stream names to the join nodes that consume them
     stream_to_joins: HashMap<String, Vec<String>>,
+    /// Watermark-aware windowed joins, indexed by join ID
+    windowed_joins: HashMap<String, Mutex<WindowedJoin>>,
+    /// Counter used to name windowed joins
+    next_windowed_id: usize,
     /// Result handlers for each join
     result_handlers: HashMap<String, Box<dyn Fn(JoinedEvent) + Send + Sync>>,
 }
 
+/// Inner join of two streams on a key field, matching events whose timestamps
+/// are at most `window_ms` apart and tolerating events up to `lateness_ms`
+/// behind the watermark
+struct WindowedJoin {
+    left_stream: String,
+    right_stream: String,
+    on_key: String,
+    window_ms: u64,
+    lateness_ms: u64,
+    left_buffer: HashMap<String, VecDeque<StreamEvent>>,
+    right_buffer: HashMap<String, VecDeque<StreamEvent>>,
+    watermark: Watermark,
+    late_data: LateDataHandler,
+}
+
+impl WindowedJoin {
+    fn process(&mut self, event: StreamEvent, is_left: bool) -> Vec<JoinedEvent> {
+        let event_time = event.metadata.timestamp;
+
+        // Events behind the watermark are only joined while within the allowed lateness
+        let event = if self.watermark.is_late(event_time) {
+            match self.late_data.handle_late_event(event, &self.watermark) {
+                LateEventDecision::Process(e) | LateEventDecision::Recompute(e) => e,
+                LateEventDecision::Drop | LateEventDecision::SideOutput(_) => return Vec::new(),
+            }
+        } else {
+            self.watermark = Watermark::new(event_time);
+            event
+        };
+
+        let key = match event.data.get(&self.on_key) {
+            Some(value) => value.to_string(),
+            None => return Vec::new(),
+        };
+
+        let (own, other) = if is_left {
+            (&mut self.left_buffer, &self.right_buffer)
+        } else {
+            (&mut self.right_buffer, &self.left_buffer)
+        };
+
+        let mut results = Vec::new();
+        for candidate in other.get(&key).into_iter().flatten() {
+            let candidate_time = candidate.metadata.timestamp;
+            if event_time.abs_diff(candidate_time) <= self.window_ms {
+                let (left, right) = if is_left {
+                    (event.clone(), candidate.clone())
+                } else {
+                    (candidate.clone(), event.clone())
+                };
+                results.push(JoinedEvent {
+                    left: Some(left),
+                    right: Some(right),
+                    join_timestamp: event_time.max(candidate_time) as i64,
+                });
+            }
+        }
+
+        own.entry(key).or_default().push_back(event);
+        self.evict_expired();
+        results
+    }
+
+    fn advance_watermark(&mut self, watermark: u64) {
+        if watermark > self.watermark.timestamp {
+            self.watermark = Watermark::new(watermark);
+            self.evict_expired();
+        }
+    }
+
+    /// Drop buffered events that no event accepted from now on can match
+    fn evict_expired(&mut self) {
+        let horizon = self
+            .watermark
+            .timestamp
+            .saturating_sub(self.window_ms + self.lateness_ms);
+
+        for buffer in [&mut self.left_buffer, &mut self.right_buffer] {
+            for queue in buffer.values_mut() {
+                queue.retain(|event| event.metadata.timestamp >= horizon);
+            }
+            buffer.retain(|_, queue| !queue.is_empty());
+        }
+    }
+
+    fn stats(&self) -> JoinNodeStats {
+        JoinNodeStats {
+            left_buffer_size: self.left_buffer.values().map(|q| q.len()).sum(),
+            right_buffer_size: self.right_buffer.values().map(|q| q.len()).sum(),
+            left_partitions: self.left_buffer.len(),
+            right_partitions: self.right_buffer.len(),
+            watermark: self.watermark.timestamp as i64,
+        }
+    }
+}
+
 impl StreamJoinManager {
     /// Create a new stream join manager
     pub fn new() -> Self {
         Self {
             joins: HashMap::new(),
             stream_to_joins: HashMap::new(),
+            windowed_joins: HashMap::new(),
+            next_windowed_id: 0,
             result_handlers: HashMap::new(),
         }
     }
@@ -50,13 +156,99 @@ impl StreamJoinManager {
         self.result_handlers.insert(join_id, result_handler);
     }
 
+    /// Register an inner join of `left` and `right` on the `on_key` field that
+    /// tolerates out-of-order events
+    ///
+    /// Events match when their timestamps are at most `window` apart. The
+    /// watermark follows the latest event time seen on either stream; events
+    /// behind it are still joined while they are at most `allowed_lateness`
+    /// late, so buffered events are kept for `window + allowed_lateness`.
+    /// Later events go to the side output, see [`Self::take_late_events`].
+    ///
+    /// Returns the ID of the new join.
+    pub fn join_windowed(
+        &mut self,
+        left: &str,
+        right: &str,
+        on_key: &str,
+        window: Duration,
+        allowed_lateness: Duration,
+        result_handler: Box<dyn Fn(JoinedEvent) + Send + Sync>,
+    ) -> String {
+        let join_id = format!("{}_{}_windowed_{}", left, right, self.next_windowed_id);
+        self.next_windowed_id += 1;
+
+        let late_data = LateDataHandler::new(LateDataStrategy::AllowedLateness {
+            max_lateness: allowed_lateness,
+        })
+        .with_side_output();
+
+        let join = WindowedJoin {
+            left_stream: left.to_string(),
+            right_stream: right.to_string(),
+            on_key: on_key.to_string(),
+            window_ms: window.as_millis() as u64,
+            lateness_ms: allowed_lateness.as_millis() as u64,
+            left_buffer: HashMap::new(),
+            right_buffer: HashMap::new(),
+            watermark: Watermark::new(0),
+            late_data,
+        };
+
+        self.stream_to_joins
+            .entry(left.to_string())
+            .or_default()
+            .push(join_id.clone());
+        self.stream_to_joins
+            .entry(right.to_string())
+            .or_default()
+            .push(join_id.clone());
+
+        self.windowed_joins
+            .insert(join_id.clone(), Mutex::new(join));
+        self.result_handlers.insert(join_id.clone(), result_handler);
+        join_id
+    }
+
+    /// Take the events a windowed join rejected for arriving beyond the allowed lateness
+    pub fn take_late_events(&self, join_id: &str) -> Vec<StreamEvent> {
+        self.windowed_joins
+            .get(join_id)
+            .map(|join| {
+                let mut join_lock = join.lock().unwrap();
+                let events = join_lock.late_data.side_output().to_vec();
+                join_lock.late_data.clear_side_output();
+                events
+            })
+            .unwrap_or_default()
+    }
+
+    /// Get late data statistics for a windowed join
+    pub fn get_late_data_stats(&self, join_id: &str) -> Option<LateDataStats> {
+        self.windowed_joins
+            .get(join_id)
+            .map(|join| join.lock().unwrap().late_data.stats())
+    }
+
     /// Remove a stream join
     pub fn unregister_join(&mut self, join_id: &str) {
-        if let Some(join) = self.joins.get(join_id) {
+        let streams = if let Some(join) = self.joins.get(join_id) {
             let join_lock = join.lock().unwrap();
-            let left_stream = join_lock.left_stream.clone();
-            let right_stream = join_lock.right_stream.clone();
-
+            Some((
+                join_lock.left_stream.clone(),
+                join_lock.right_stream.clone(),
+            ))
+        } else {
+            self.windowed_joins.get(join_id).map(|join| {
+                let join_lock = join.lock().unwrap();
+                (
+                    join_lock.left_stream.clone(),
+                    join_lock.right_stream.clone(),
+                )
+            })
+        };
+
+        if let Some((left_stream, right_stream)) = streams {
             // Remove from stream indices
             if let Some(joins) = self.stream_to_joins.get_mut(&left_stream) {
                 joins.retain(|id| id != join_id);
@@ -67,6 +259,7 @@ impl StreamJoinManager {
         }
 
         self.joins.remove(join_id);
+        self.windowed_joins.remove(join_id);
         self.result_handlers.remove(join_id);
     }
 
@@ -89,6 +282,16 @@ impl StreamJoinManager {
                     };
 
                     // Process results
+                    if let Some(handler) = self.result_handlers.get(join_id) {
+                        for joined in results {
+                            handler(joined);
+                        }
+                    }
+                } else if let Some(join) = self.windowed_joins.get(join_id) {
+                    let mut join_lock = join.lock().unwrap();
+                    let is_left = join_lock.left_stream == stream_id;
+                    let results = join_lock.process(event.clone(), is_left);
+
                     if let Some(handler) = self.result_handlers.get(join_id) {
                         for joined in results {
                             handler(joined);
@@ -114,26 +317,33 @@ impl StreamJoinManager {
                             handler(joined);
                         }
                     }
+                } else if let Some(join) = self.windowed_joins.get(join_id) {
+                    join.lock()
+                        .unwrap()
+                        .advance_watermark(watermark.max(0) as u64);
                 }
             }
         }
     }
 
     /// Get statistics for all joins
-    pub fn get_all_stats(&self) -> HashMap<String, crate::rete::stream_join_node::JoinNodeStats> {
+    pub fn get_all_stats(&self) -> HashMap<String, JoinNodeStats> {
         let mut stats = HashMap::new();
         for (join_id, join) in &self.joins {
             let join_lock = join.lock().unwrap();
             stats.insert(join_id.clone(), join_lock.get_stats());
         }
+        for (join_id, join) in &self.windowed_joins {
+            stats.insert(join_id.clone(), join.lock().unwrap().stats());
+        }
         stats
     }
 
     /// Get statistics for a specific join
-    pub fn get_join_stats(
-        &self,
-        join_id: &str,
-    ) -> Option<crate::rete::stream_join_node::JoinNodeStats> {
+    pub fn get_join_stats(&self, join_id: &str) -> Option<JoinNodeStats> {
+        if let Some(join) = self.windowed_joins.get(join_id) {
+            return Some(join.lock().unwrap().stats());
+        }
         self.joins.get(join_id).map(|join| {
             let join_lock = join.lock().unwrap();
             join_lock.get_stats()
@@ -143,6 +353,7 @@ impl StreamJoinManager {
     /// Clear all joins (for testing or reset)
     pub fn clear(&mut self) {
         self.joins.clear();
+        self.windowed_joins.clear();
         self.stream_to_joins.clear();
         self.result_handlers.clear();
     }
@@ -363,4 +574,57 @@ mod tests {
         // Should still be 1 (event already emitted)
         assert_eq!(result_count.load(Ordering::SeqCst), 1);
     }
+
+    #[test]
+    fn test_join_windowed_with_out_of_order_events() {
+        let mut manager = StreamJoinManager::new();
+        let matches = Arc::new(Mutex::new(Vec::new()));
+        let matches_clone = matches.clone();
+
+        let join_id = manager.join_windowed(
+            "left",
+            "right",
+            "user_id",
+            Duration::from_millis(1000),
+            Duration::from_millis(500),
+            Box::new(move |joined| {
+                let left = joined.left.unwrap().metadata.timestamp;
+                let right = joined.right.unwrap().metadata.timestamp;
+                matches_clone.lock().unwrap().push((left, right));
+            }),
+        );
+
+        manager.process_event(create_test_event("left", 10_000, "user1"));
+        manager.process_event(create_test_event("right", 10_800, "user1"));
+        // 300ms behind the watermark: still joined
+        manager.process_event(create_test_event("left", 10_500, "user1"));
+        // 600ms behind the watermark: beyond the allowed lateness
+        manager.process_event(create_test_event("right", 10_200, "user1"));
+        manager.process_event(create_test_event("right", 12_000, "user2"));
+        manager.process_event(create_test_event("left", 9_000, "user1"));
+
+        assert_eq!(
+            *matches.lock().unwrap(),
+            vec![(10_000, 10_800), (10_500, 10_800)]
+        );
+
+        let stats = manager.get_late_data_stats(&join_id).unwrap();
+        assert_eq!(stats.total_late, 3);
+        assert_eq!(stats.allowed, 1);
+        assert_eq!(stats.side_output, 2);
+
+        let late: Vec<u64> = manager
+            .take_late_events(&join_id)
+            .iter()
+            .map(|e| e.metadata.timestamp)
+            .collect();
+        assert_eq!(late, vec![10_200, 9_000]);
+        assert!(manager.take_late_events(&join_id).is_empty());
+
+        // The left event at 10_000 fell out of window + lateness once the watermark hit 12_000
+        let join_stats = manager.get_join_stats(&join_id).unwrap();
+        assert_eq!(join_stats.watermark, 12_000);
+        assert_eq!(join_stats.left_buffer_size, 1);
+        assert_eq!(join_stats.right_buffer_size, 2);
+    }
 }
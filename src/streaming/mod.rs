@@ -56,7 +56,7 @@ pub mod window;
 #[cfg(feature = "streaming")]
 pub use aggregator::{AggregationType, Aggregator};
 #[cfg(feature = "streaming")]
-pub use engine::StreamRuleEngine;
+pub use engine::{SendOutcome, StreamRuleEngine, WindowResult};
 #[cfg(feature = "streaming")]
 pub use event::{EventMetadata, StreamEvent};
 #[cfg(feature = "streaming")]
@@ -78,7 +78,7 @@ pub use watermark::{
     WatermarkGenerator, WatermarkStrategy, WatermarkedStream,
 };
 #[cfg(feature = "streaming")]
-pub use window::{TimeWindow, WindowManager, WindowType};
+pub use window::{TimeSemantics, TimeWindow, TimestampExtractor, WindowManager, WindowType};
 
 /// Re-export for non-streaming builds
 #[cfg(not(feature = "streaming"))]
@@ -137,7 +137,7 @@ impl StreamRuleEngine {
     }
 
     /// Send event to streaming engine (requires streaming feature)
-    pub async fn send_event(&self, _event: StreamEvent) -> Result<()> {
+    pub async fn send_event(&self, _event: StreamEvent) -> Result<SendOutcome> {
         Err(crate::RuleEngineError::FeatureNotEnabled {
             feature: "streaming".to_string(),
             message: "Streaming rule engine requires the 'streaming' feature to be enabled"
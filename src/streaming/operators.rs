@@ -23,9 +23,9 @@
 //! ```
 
 use crate::streaming::event::StreamEvent;
-use crate::streaming::window::{TimeWindow, WindowType};
+use crate::streaming::window::{hopping_window_starts, TimeWindow, WindowType};
 use crate::types::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -326,6 +326,19 @@ where
         }
     }
 
+    /// Split each key's events into sessions that close after `gap` of
+    /// inactivity
+    ///
+    /// ```rust,ignore
+    /// let clicks_per_session = DataStream::from_events(clicks)
+    ///     .key_by(|e| e.get_string("userId").unwrap_or_default().to_string())
+    ///     .session(Duration::from_secs(30))
+    ///     .count();
+    /// ```
+    pub fn session(self, gap: Duration) -> KeyedWindowedStream<K> {
+        self.window(WindowConfig::session(gap))
+    }
+
     /// Count events per key
     pub fn count(self) -> HashMap<K, usize> {
         self.keyed_events
@@ -385,6 +398,19 @@ impl WindowConfig {
         }
     }
 
+    /// Create a sliding window of `size` that advances every `slide`
+    ///
+    /// With `slide < size` windows overlap and each event is copied into
+    /// `ceil(size / slide)` windows, so keep the ratio modest for
+    /// high-volume streams.
+    pub fn hopping(size: Duration, slide: Duration) -> Self {
+        Self {
+            window_type: WindowType::Hopping { size, slide },
+            duration: size,
+            max_events: 10000,
+        }
+    }
+
     /// Set maximum events per window
     pub fn with_max_events(mut self, max_events: usize) -> Self {
         self.max_events = max_events;
@@ -434,7 +460,61 @@ impl WindowedStream {
                     windows.push(window);
                 }
             }
-            WindowType::Sliding | WindowType::Session { .. } => {
+            WindowType::Hopping { size, slide } => {
+                // Every event joins each overlapping window covering it
+                let mut window_map: BTreeMap<u64, Vec<StreamEvent>> = BTreeMap::new();
+
+                for event in events {
+                    for start in hopping_window_starts(event.metadata.timestamp, size, slide) {
+                        window_map.entry(start).or_default().push(event.clone());
+                    }
+                }
+
+                for (start_time, window_events) in window_map {
+                    let mut window = TimeWindow::new(
+                        config.window_type.clone(),
+                        size,
+                        start_time,
+                        config.max_events,
+                    );
+
+                    for event in window_events {
+                        window.add_event(event);
+                    }
+
+                    windows.push(window);
+                }
+            }
+            WindowType::Session { timeout } => {
+                // A session ends once the gap to the next event reaches the timeout
+                let gap_ms = timeout.as_millis() as u64;
+                let mut events = events;
+                events.sort_by_key(|e| e.metadata.timestamp);
+
+                let mut session: Option<TimeWindow> = None;
+                for event in events {
+                    let timestamp = event.metadata.timestamp;
+                    match session.as_mut() {
+                        Some(open) if timestamp < open.end_time => {
+                            open.end_time = timestamp + gap_ms;
+                            open.add_event(event);
+                        }
+                        _ => {
+                            windows.extend(session.take());
+                            let mut next = TimeWindow::new(
+                                config.window_type.clone(),
+                                timeout,
+                                timestamp,
+                                config.max_events,
+                            );
+                            next.add_event(event);
+                            session = Some(next);
+                        }
+                    }
+                }
+                windows.extend(session);
+            }
+            WindowType::Sliding => {
                 // For sliding windows, create overlapping windows
                 // Simplified implementation: create one window per unique timestamp
                 let window_ms = config.duration.as_millis() as u64;
@@ -503,6 +583,54 @@ impl WindowedStream {
             .collect()
     }
 
+    /// Drop events whose `key` field repeats a value already seen in the same window
+    ///
+    /// The first event for each value passes; events without the field always
+    /// pass. Values of different types never match (`1` and `"1"` are distinct).
+    ///
+    /// Each window is deduplicated on its own with a `HashSet` that lives only
+    /// while that window is processed, so memory grows with the number of
+    /// distinct keys in the largest window, not with the whole stream. For
+    /// the same reason duplicates are not tracked across windows: an event in
+    /// overlapping sliding or hopping windows is checked in each of them, and
+    /// a late event windowed in a later batch is not compared with events the
+    /// same window held in an earlier batch.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// stream.window(WindowConfig::tumbling(Duration::from_secs(60))).distinct("deviceId")
+    /// ```
+    pub fn distinct(self, key: &str) -> Self {
+        self.distinct_by(|event| event.data.get(key).map(canonical_key))
+    }
+
+    /// Drop events identical to an earlier event in the same window
+    ///
+    /// Events are identical when their type and payload match; ids and
+    /// metadata are ignored. Memory use and late data behave as for
+    /// [`distinct`](Self::distinct).
+    pub fn distinct_events(self) -> Self {
+        self.distinct_by(|event| {
+            Some(format!(
+                "{:?}{}",
+                event.event_type,
+                canonical_fields(&event.data)
+            ))
+        })
+    }
+
+    /// Keep the first event per key in each window; events without a key pass
+    fn distinct_by<F>(mut self, key_of: F) -> Self
+    where
+        F: Fn(&StreamEvent) -> Option<String>,
+    {
+        for window in &mut self.windows {
+            let mut seen = HashSet::new();
+            window.retain(|event| key_of(event).is_none_or(|key| seen.insert(key)));
+        }
+        self
+    }
+
     /// Get all windows
     pub fn windows(&self) -> &[TimeWindow] {
         &self.windows
@@ -525,6 +653,28 @@ impl WindowedStream {
     }
 }
 
+/// Dedup key for `value`: equal keys for equal values, whatever the
+/// iteration order of the objects inside it
+fn canonical_key(value: &Value) -> String {
+    match value {
+        Value::Object(fields) => canonical_fields(fields),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_key).collect();
+            format!("Array({:?})", items)
+        }
+        other => format!("{:?}", other),
+    }
+}
+
+/// [`canonical_key`] of an object's fields, sorted by name
+fn canonical_fields(fields: &HashMap<String, Value>) -> String {
+    let sorted: BTreeMap<&String, String> = fields
+        .iter()
+        .map(|(name, value)| (name, canonical_key(value)))
+        .collect();
+    format!("Object({:?})", sorted)
+}
+
 /// Keyed stream with windowing
 pub struct KeyedWindowedStream<K>
 where
@@ -553,6 +703,17 @@ where
             .collect()
     }
 
+    /// Count events in each of a key's windows
+    pub fn count(self) -> HashMap<K, Vec<usize>> {
+        self.keyed_events
+            .into_iter()
+            .map(|(key, events)| {
+                let windowed = WindowedStream::new(events, self.config.clone());
+                (key, windowed.counts())
+            })
+            .collect()
+    }
+
     /// Reduce events within each key's window
     pub fn reduce<F>(self, reducer: F) -> HashMap<K, Vec<StreamEvent>>
     where
@@ -959,4 +1120,133 @@ mod tests {
 
         assert!(!windowed.windows().is_empty());
     }
+
+    #[test]
+    fn test_hopping_window_aggregates_per_window() {
+        let events: Vec<StreamEvent> = [(0, 1.0), (90_000, 2.0), (250_000, 4.0)]
+            .into_iter()
+            .map(|(timestamp, value)| {
+                let mut data = HashMap::new();
+                data.insert("value".to_string(), Value::Number(value));
+                StreamEvent::with_timestamp("TestEvent", data, "test", timestamp)
+            })
+            .collect();
+
+        // 5-minute windows sliding every minute
+        let config = WindowConfig::hopping(Duration::from_secs(300), Duration::from_secs(60));
+        let windowed = DataStream::from_events(events.clone()).window(config.clone());
+        let starts: Vec<u64> = windowed.windows().iter().map(|w| w.start_time).collect();
+        assert_eq!(starts, vec![0, 60_000, 120_000, 180_000, 240_000]);
+
+        let counts: Vec<f64> = windowed
+            .aggregate(Count)
+            .iter()
+            .filter_map(|r| r.as_number())
+            .collect();
+        assert_eq!(counts, vec![3.0, 2.0, 1.0, 1.0, 1.0]);
+
+        let sums: Vec<f64> = DataStream::from_events(events)
+            .window(config)
+            .aggregate(Sum::new("value"))
+            .iter()
+            .filter_map(|r| r.as_number())
+            .collect();
+        assert_eq!(sums, vec![7.0, 6.0, 4.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn test_keyed_session_count() {
+        let clicks: Vec<StreamEvent> = [
+            ("alice", 0),
+            ("bob", 5_000),
+            ("alice", 10_000),
+            ("alice", 20_000),
+            ("alice", 70_000),
+        ]
+        .into_iter()
+        .map(|(user, timestamp)| {
+            let mut data = HashMap::new();
+            data.insert("userId".to_string(), Value::String(user.to_string()));
+            StreamEvent::with_timestamp("ClickEvent", data, "web", timestamp)
+        })
+        .collect();
+
+        let counts = DataStream::from_events(clicks)
+            .key_by(|e| e.get_string("userId").unwrap_or_default().to_string())
+            .session(Duration::from_secs(30))
+            .count();
+
+        // alice idles for 50s between 20s and 70s, which closes her first session
+        assert_eq!(counts["alice"], vec![3, 1]);
+        assert_eq!(counts["bob"], vec![1]);
+    }
+
+    #[test]
+    fn test_distinct_keeps_first_event_per_key_in_each_window() {
+        let reading = |timestamp: u64, device: Option<&str>, value: f64| {
+            let mut data = HashMap::new();
+            data.insert("value".to_string(), Value::Number(value));
+            if let Some(device) = device {
+                data.insert("deviceId".to_string(), Value::String(device.to_string()));
+            }
+            StreamEvent::with_timestamp("Reading", data, "sensor", timestamp)
+        };
+        let events = vec![
+            reading(1_000, Some("d1"), 1.0),
+            reading(2_000, Some("d1"), 2.0),
+            reading(3_000, Some("d2"), 3.0),
+            reading(4_000, None, 4.0),
+            reading(5_000, None, 5.0),
+            reading(61_000, Some("d1"), 6.0),
+            reading(62_000, Some("d1"), 7.0),
+        ];
+        let config = WindowConfig::tumbling(Duration::from_secs(60));
+
+        let distinct = DataStream::from_events(events.clone())
+            .window(config.clone())
+            .distinct("deviceId");
+        let mut windows: Vec<_> = distinct.windows().iter().collect();
+        windows.sort_by_key(|w| w.start_time);
+        let values = |window: &TimeWindow| -> Vec<f64> {
+            window
+                .events()
+                .iter()
+                .filter_map(|e| e.get_numeric("value"))
+                .collect()
+        };
+        assert_eq!(values(windows[0]), vec![1.0, 3.0, 4.0, 5.0]);
+        assert_eq!(values(windows[1]), vec![6.0]);
+
+        // Whole-event dedup only drops exact repeats of type and payload
+        let mut repeated = events[..3].to_vec();
+        repeated.push(reading(9_000, Some("d1"), 1.0));
+        let distinct = DataStream::from_events(repeated)
+            .window(config)
+            .distinct_events();
+        assert_eq!(distinct.counts(), vec![3]);
+
+        // Object keys match regardless of field insertion order
+        let located = |timestamp: u64, fields: &[(&str, i64)]| {
+            let location = fields
+                .iter()
+                .map(|(name, value)| (name.to_string(), Value::Integer(*value)))
+                .collect();
+            let mut data = HashMap::new();
+            data.insert("location".to_string(), Value::Object(location));
+            StreamEvent::with_timestamp("Ping", data, "sensor", timestamp)
+        };
+        let pings = vec![
+            located(1_000, &[("lat", 1), ("lon", 2), ("alt", 3)]),
+            located(2_000, &[("alt", 3), ("lon", 2), ("lat", 1)]),
+            located(3_000, &[("lon", 2), ("alt", 3), ("lat", 1)]),
+        ];
+        let distinct = DataStream::from_events(pings.clone())
+            .window(WindowConfig::tumbling(Duration::from_secs(60)))
+            .distinct("location");
+        assert_eq!(distinct.counts(), vec![1]);
+        let distinct = DataStream::from_events(pings)
+            .window(WindowConfig::tumbling(Duration::from_secs(60)))
+            .distinct_events();
+        assert_eq!(distinct.counts(), vec![1]);
+    }
 }
@@ -521,28 +521,19 @@ impl StateStore {
             StateBackend::File { path } => {
                 // Serialize and save to file
                 let checkpoint_path = path.join(&checkpoint_id);
-                fs::create_dir_all(&checkpoint_path).map_err(|e| {
-                    RuleEngineError::ExecutionError(format!(
-                        "Failed to create checkpoint dir: {}",
-                        e
-                    ))
-                })?;
+                fs::create_dir_all(&checkpoint_path)
+                    .map_err(|e| RuleEngineError::io(&checkpoint_path, e))?;
 
                 let data_path = checkpoint_path.join("state.json");
                 let json = serde_json::to_string_pretty(&snapshot).map_err(|e| {
                     RuleEngineError::ExecutionError(format!("Failed to serialize state: {}", e))
                 })?;
 
-                let mut file = fs::File::create(&data_path).map_err(|e| {
-                    RuleEngineError::ExecutionError(format!(
-                        "Failed to create checkpoint file: {}",
-                        e
-                    ))
-                })?;
+                let mut file =
+                    fs::File::create(&data_path).map_err(|e| RuleEngineError::io(&data_path, e))?;
 
-                file.write_all(json.as_bytes()).map_err(|e| {
-                    RuleEngineError::ExecutionError(format!("Failed to write checkpoint: {}", e))
-                })?;
+                file.write_all(json.as_bytes())
+                    .map_err(|e| RuleEngineError::io(&data_path, e))?;
 
                 let metadata = CheckpointMetadata {
                     id: checkpoint_id.clone(),
@@ -620,17 +611,12 @@ impl StateStore {
                     )));
                 }
 
-                let mut file = fs::File::open(&data_path).map_err(|e| {
-                    RuleEngineError::ExecutionError(format!(
-                        "Failed to open checkpoint file: {}",
-                        e
-                    ))
-                })?;
+                let mut file =
+                    fs::File::open(&data_path).map_err(|e| RuleEngineError::io(&data_path, e))?;
 
                 let mut json = String::new();
-                file.read_to_string(&mut json).map_err(|e| {
-                    RuleEngineError::ExecutionError(format!("Failed to read checkpoint: {}", e))
-                })?;
+                file.read_to_string(&mut json)
+                    .map_err(|e| RuleEngineError::io(&data_path, e))?;
 
                 let snapshot: HashMap<String, Value> =
                     serde_json::from_str(&json).map_err(|e| {
@@ -192,6 +192,7 @@ pub enum LateDataStrategy {
 }
 
 /// Handler for late data events
+#[derive(Debug)]
 pub struct LateDataHandler {
     /// Strategy for handling late data
     strategy: LateDataStrategy,
@@ -199,6 +200,9 @@ pub struct LateDataHandler {
     /// Side output for late events
     side_output: Vec<StreamEvent>,
 
+    /// Route events beyond the allowed lateness to the side output instead of dropping them
+    side_output_beyond_lateness: bool,
+
     /// Statistics about late events
     late_count: usize,
     dropped_count: usize,
@@ -211,12 +215,20 @@ impl LateDataHandler {
         Self {
             strategy,
             side_output: Vec::new(),
+            side_output_beyond_lateness: false,
             late_count: 0,
             dropped_count: 0,
             allowed_count: 0,
         }
     }
 
+    /// With [`LateDataStrategy::AllowedLateness`], keep events that are too late
+    /// in the side output instead of dropping them
+    pub fn with_side_output(mut self) -> Self {
+        self.side_output_beyond_lateness = true;
+        self
+    }
+
     /// Handle a late event according to the strategy
     pub fn handle_late_event(
         &mut self,
@@ -239,6 +251,9 @@ impl LateDataHandler {
                 if lateness <= max_lateness_ms {
                     self.allowed_count += 1;
                     LateEventDecision::Process(event)
+                } else if self.side_output_beyond_lateness {
+                    self.side_output.push(event.clone());
+                    LateEventDecision::SideOutput(event)
                 } else {
                     self.dropped_count += 1;
                     LateEventDecision::Drop
@@ -1,10 +1,22 @@
 //! Time Window Management for Stream Processing
 //!
 //! Provides time-based windows for event aggregation and analysis.
+//!
+//! ## Hopping windows and memory
+//!
+//! [`WindowType::Hopping`] windows overlap whenever `slide < size`: each event
+//! belongs to `ceil(size / slide)` windows and is cloned into every one of
+//! them. A 5-minute window sliding every second keeps 300 copies of each
+//! event, so memory grows with `size / slide` as well as with the event
+//! rate. Prefer a slide that is a sizeable fraction of the window, and bound
+//! retention with `max_events_per_window` and `max_windows`.
 
 use crate::streaming::event::StreamEvent;
+use crate::streaming::watermark::{LateDataHandler, LateEventDecision, Watermark};
 use std::collections::VecDeque;
-use std::time::Duration;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Type of time window
 #[derive(Debug, Clone, PartialEq)]
@@ -13,8 +25,73 @@ pub enum WindowType {
     Sliding,
     /// Tumbling window - non-overlapping fixed intervals
     Tumbling,
-    /// Session window - based on inactivity gaps
+    /// Session window - based on inactivity gaps; a session closes once no
+    /// event arrives within `timeout` of the previous one
     Session { timeout: Duration },
+    /// Sliding window of `size` that advances every `slide` (a hopping
+    /// window); with `slide < size` windows overlap and each event is
+    /// assigned to every window covering its timestamp
+    Hopping { size: Duration, slide: Duration },
+}
+
+/// Reads an event's occurrence time in milliseconds since epoch
+pub type TimestampExtractor = Arc<dyn Fn(&StreamEvent) -> Option<u64> + Send + Sync>;
+
+/// Which clock places events into windows
+///
+/// The resolved time is written to the event's `metadata.timestamp` before
+/// windowing, so window boundaries, expiry and anything downstream that reads
+/// the timestamp (such as watermarks) all see the same notion of time.
+#[derive(Clone)]
+pub enum TimeSemantics {
+    /// Time at which the event occurred, as read by the extractor; events
+    /// for which it returns `None` keep their metadata timestamp
+    EventTime(TimestampExtractor),
+    /// Time at which the window manager receives the event
+    ProcessingTime,
+}
+
+impl TimeSemantics {
+    /// Event time taken from the event's metadata timestamp
+    pub fn event_time() -> Self {
+        TimeSemantics::EventTime(Arc::new(|event| Some(event.metadata.timestamp)))
+    }
+
+    /// Event time read from a numeric data field holding epoch milliseconds
+    pub fn event_time_field(field: impl Into<String>) -> Self {
+        let field = field.into();
+        TimeSemantics::EventTime(Arc::new(move |event| {
+            event.get_numeric(&field).map(|millis| millis as u64)
+        }))
+    }
+
+    /// Timestamp used to place `event` into windows
+    pub fn timestamp(&self, event: &StreamEvent) -> u64 {
+        match self {
+            TimeSemantics::EventTime(extractor) => {
+                extractor(event).unwrap_or(event.metadata.timestamp)
+            }
+            TimeSemantics::ProcessingTime => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as u64,
+        }
+    }
+}
+
+impl Default for TimeSemantics {
+    fn default() -> Self {
+        Self::event_time()
+    }
+}
+
+impl fmt::Debug for TimeSemantics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeSemantics::EventTime(_) => f.write_str("EventTime"),
+            TimeSemantics::ProcessingTime => f.write_str("ProcessingTime"),
+        }
+    }
 }
 
 /// Time-based window for event processing
@@ -32,6 +109,8 @@ pub struct TimeWindow {
     pub end_time: u64,
     /// Maximum number of events to retain
     max_events: usize,
+    /// Key the window belongs to, for keyed session windows
+    pub key: Option<String>,
 }
 
 impl TimeWindow {
@@ -51,6 +130,7 @@ impl TimeWindow {
             start_time,
             end_time,
             max_events,
+            key: None,
         }
     }
 
@@ -136,6 +216,14 @@ impl TimeWindow {
         self.events.clear();
     }
 
+    /// Keep only the events for which `keep` returns true, in order
+    pub fn retain<F>(&mut self, keep: F)
+    where
+        F: FnMut(&StreamEvent) -> bool,
+    {
+        self.events.retain(keep);
+    }
+
     /// Get events filtered by type
     pub fn events_by_type(&self, event_type: &str) -> Vec<&StreamEvent> {
         self.events
@@ -216,51 +304,116 @@ pub struct WindowManager {
     max_events_per_window: usize,
     /// Maximum number of windows to keep
     max_windows: usize,
+    /// Clock used to place events into windows
+    time_semantics: TimeSemantics,
+    /// Event field that keys session windows
+    session_key: Option<String>,
+    /// Receives events that arrive after their session closed
+    late_handler: Option<LateDataHandler>,
+    /// Latest event time seen, which decides when sessions close
+    max_event_time: u64,
+    /// Windows that closed since the last `take_closed_windows`, oldest first
+    closed: Vec<TimeWindow>,
 }
 
 impl WindowManager {
     /// Create a new window manager
+    ///
+    /// For [`WindowType::Hopping`] the window length is taken from its `size`,
+    /// and for [`WindowType::Session`] from its `timeout`; `duration` is
+    /// ignored for both.
     pub fn new(
         window_type: WindowType,
         duration: Duration,
         max_events_per_window: usize,
         max_windows: usize,
     ) -> Self {
+        let duration = match window_type {
+            WindowType::Hopping { size, .. } => size,
+            WindowType::Session { timeout } => timeout,
+            _ => duration,
+        };
+
         Self {
             windows: Vec::new(),
             window_type,
             duration,
             max_events_per_window,
             max_windows,
+            time_semantics: TimeSemantics::default(),
+            session_key: None,
+            late_handler: None,
+            max_event_time: 0,
+            closed: Vec::new(),
         }
     }
 
-    /// Process a new event through the window system
-    pub fn process_event(&mut self, event: StreamEvent) {
-        let event_time = event.metadata.timestamp;
+    /// Keep a separate session per value of `field` (e.g. `"userId"`)
+    ///
+    /// Only affects [`WindowType::Session`]; events without the field share
+    /// one unkeyed session.
+    pub fn with_session_key(mut self, field: impl Into<String>) -> Self {
+        self.session_key = Some(field.into());
+        self
+    }
 
-        // Find or create appropriate window
-        let mut added = false;
+    /// Route events that arrive after their session closed through `handler`
+    ///
+    /// Without a handler such events are dropped.
+    pub fn with_late_data_handler(mut self, handler: LateDataHandler) -> Self {
+        self.late_handler = Some(handler);
+        self
+    }
+
+    /// Handler receiving late session events, if configured
+    pub fn late_data_handler(&self) -> Option<&LateDataHandler> {
+        self.late_handler.as_ref()
+    }
+
+    /// Set the clock used to place events into windows
+    pub fn with_time_semantics(mut self, time_semantics: TimeSemantics) -> Self {
+        self.time_semantics = time_semantics;
+        self
+    }
+
+    /// Clock used to place events into windows
+    pub fn time_semantics(&self) -> &TimeSemantics {
+        &self.time_semantics
+    }
 
-        for window in &mut self.windows {
-            if window.add_event(event.clone()) {
-                added = true;
-                break;
+    /// Process a new event through the window system
+    pub fn process_event(&mut self, mut event: StreamEvent) {
+        let event_time = self.time_semantics.timestamp(&event);
+        event.metadata.timestamp = event_time;
+
+        if let WindowType::Hopping { slide, .. } = self.window_type {
+            self.add_to_hopping_windows(event, slide);
+        } else if let WindowType::Session { timeout } = self.window_type {
+            self.add_to_session(event, timeout);
+        } else {
+            // Find or create appropriate window
+            let mut added = false;
+
+            for window in &mut self.windows {
+                if window.add_event(event.clone()) {
+                    added = true;
+                    break;
+                }
             }
-        }
 
-        if !added {
-            // Create new window for this event
-            let window_start = self.calculate_window_start(event_time);
-            let mut new_window = TimeWindow::new(
-                self.window_type.clone(),
-                self.duration,
-                window_start,
-                self.max_events_per_window,
-            );
-
-            new_window.add_event(event);
-            self.windows.push(new_window);
+            if !added {
+                // Create new window for this event
+                let window_start = self.calculate_window_start(event_time);
+                let mut new_window = TimeWindow::new(
+                    self.window_type.clone(),
+                    self.duration,
+                    window_start,
+                    self.max_events_per_window,
+                );
+
+                new_window.add_event(event);
+                self.windows.push(new_window);
+            }
         }
 
         // Clean up expired windows
@@ -275,6 +428,81 @@ impl WindowManager {
         self.windows.sort_by_key(|w| w.start_time);
     }
 
+    /// Add an event to every hopping window that covers it, opening
+    /// windows that do not exist yet
+    fn add_to_hopping_windows(&mut self, event: StreamEvent, slide: Duration) {
+        let starts = hopping_window_starts(event.metadata.timestamp, self.duration, slide);
+
+        for start in starts {
+            let index = match self.windows.iter().position(|w| w.start_time == start) {
+                Some(index) => index,
+                None => {
+                    self.windows.push(TimeWindow::new(
+                        self.window_type.clone(),
+                        self.duration,
+                        start,
+                        self.max_events_per_window,
+                    ));
+                    self.windows.len() - 1
+                }
+            };
+            self.windows[index].add_event(event.clone());
+        }
+    }
+
+    /// Extend the event's open session or start a new one
+    ///
+    /// An event is late when any session containing it would already have
+    /// closed, i.e. the latest event seen is at least `gap` past it.
+    fn add_to_session(&mut self, mut event: StreamEvent, gap: Duration) {
+        let gap_ms = gap.as_millis() as u64;
+        let mut event_time = event.metadata.timestamp;
+
+        if event_time + gap_ms <= self.max_event_time {
+            let watermark = Watermark::new(self.max_event_time.saturating_sub(gap_ms));
+            let decision = match &mut self.late_handler {
+                Some(handler) => handler.handle_late_event(event, &watermark),
+                None => LateEventDecision::Drop,
+            };
+            match decision {
+                LateEventDecision::Process(late) | LateEventDecision::Recompute(late) => {
+                    event = late;
+                    event_time = event.metadata.timestamp;
+                }
+                LateEventDecision::Drop | LateEventDecision::SideOutput(_) => return,
+            }
+        }
+        self.max_event_time = self.max_event_time.max(event_time);
+
+        let key = self
+            .session_key
+            .as_deref()
+            .and_then(|field| event.get_string(field))
+            .map(str::to_string);
+
+        let open = self.windows.iter_mut().find(|w| {
+            w.key == key && event_time + gap_ms > w.start_time && event_time < w.end_time
+        });
+        match open {
+            Some(session) => {
+                session.start_time = session.start_time.min(event_time);
+                session.end_time = session.end_time.max(event_time + gap_ms);
+                session.add_event(event);
+            }
+            None => {
+                let mut session = TimeWindow::new(
+                    self.window_type.clone(),
+                    gap,
+                    event_time,
+                    self.max_events_per_window,
+                );
+                session.key = key;
+                session.add_event(event);
+                self.windows.push(session);
+            }
+        }
+    }
+
     /// Calculate window start time based on window type
     fn calculate_window_start(&self, event_time: u64) -> u64 {
         match self.window_type {
@@ -282,14 +510,44 @@ impl WindowManager {
                 let window_ms = self.duration.as_millis() as u64;
                 (event_time / window_ms) * window_ms
             }
+            WindowType::Hopping { slide, .. } => {
+                let slide_ms = (slide.as_millis() as u64).max(1);
+                (event_time / slide_ms) * slide_ms
+            }
             WindowType::Sliding | WindowType::Session { .. } => event_time,
         }
     }
 
     /// Remove expired windows
+    ///
+    /// Expired windows are kept for [`take_closed_windows`](Self::take_closed_windows),
+    /// up to `max_windows` of them.
     fn cleanup_expired_windows(&mut self, current_time: u64) {
-        self.windows
-            .retain(|window| !window.is_expired(current_time));
+        let (expired, open): (Vec<TimeWindow>, Vec<TimeWindow>) = self
+            .windows
+            .drain(..)
+            .partition(|window| window.is_expired(current_time));
+        self.windows = open;
+
+        self.closed.extend(expired);
+        if self.closed.len() > self.max_windows {
+            let excess = self.closed.len() - self.max_windows;
+            self.closed.drain(..excess);
+        }
+    }
+
+    /// Close every window that ends at or before `watermark`
+    ///
+    /// Windows otherwise close only when a later event arrives; advancing the
+    /// watermark closes them when the stream goes quiet.
+    pub fn advance_watermark(&mut self, watermark: Watermark) {
+        self.max_event_time = self.max_event_time.max(watermark.timestamp);
+        self.cleanup_expired_windows(watermark.timestamp);
+    }
+
+    /// Remove and return the windows that closed since the last call
+    pub fn take_closed_windows(&mut self) -> Vec<TimeWindow> {
+        std::mem::take(&mut self.closed)
     }
 
     /// Get all active windows
@@ -339,6 +597,29 @@ impl WindowManager {
     }
 }
 
+/// Start times of the hopping windows of `size` advancing every `slide` that
+/// contain `timestamp`, oldest first
+///
+/// Windows are aligned to multiples of `slide` since the epoch; a zero slide
+/// is treated as one millisecond.
+pub(crate) fn hopping_window_starts(timestamp: u64, size: Duration, slide: Duration) -> Vec<u64> {
+    let size_ms = size.as_millis() as u64;
+    let slide_ms = (slide.as_millis() as u64).max(1);
+
+    let mut starts = Vec::new();
+    let mut start = (timestamp / slide_ms) * slide_ms;
+    while start + size_ms > timestamp {
+        starts.push(start);
+        match start.checked_sub(slide_ms) {
+            Some(previous) => start = previous,
+            None => break,
+        }
+    }
+
+    starts.reverse();
+    starts
+}
+
 /// Statistics about window manager state
 #[derive(Debug, Clone)]
 pub struct WindowStatistics {
@@ -466,4 +747,149 @@ mod tests {
         assert_eq!(manager.active_windows().len(), 1);
         assert_eq!(manager.total_event_count(), 1);
     }
+
+    #[test]
+    fn test_hopping_window_manager_assigns_overlapping_windows() {
+        // 5s windows sliding every 1s
+        let mut manager = WindowManager::new(
+            WindowType::Hopping {
+                size: Duration::from_secs(5),
+                slide: Duration::from_secs(1),
+            },
+            Duration::from_secs(60),
+            100,
+            100,
+        );
+
+        for (timestamp, amount) in [(10_000, 1.0), (12_500, 2.0)] {
+            let mut data = HashMap::new();
+            data.insert("amount".to_string(), Value::Number(amount));
+            manager.process_event(StreamEvent::with_timestamp(
+                "Payment", data, "test", timestamp,
+            ));
+        }
+
+        // Windows starting at 6s and 7s closed once the 12.5s event arrived
+        let windows = manager.active_windows();
+        let starts: Vec<u64> = windows.iter().map(|w| w.start_time).collect();
+        assert_eq!(starts, vec![8_000, 9_000, 10_000, 11_000, 12_000]);
+        assert!(windows.iter().all(|w| w.duration_ms() == 5_000));
+
+        let counts: Vec<usize> = windows.iter().map(|w| w.count()).collect();
+        assert_eq!(counts, vec![2, 2, 2, 1, 1]);
+        let sums: Vec<f64> = windows.iter().map(|w| w.sum("amount")).collect();
+        assert_eq!(sums, vec![3.0, 3.0, 3.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_hopping_window_starts() {
+        let size = Duration::from_millis(300);
+        let slide = Duration::from_millis(100);
+
+        assert_eq!(
+            hopping_window_starts(1_250, size, slide),
+            vec![1_000, 1_100, 1_200]
+        );
+        assert_eq!(hopping_window_starts(50, size, slide), vec![0]);
+
+        // Slide larger than size leaves gaps between windows
+        let gapped = hopping_window_starts(
+            1_350,
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+        );
+        assert!(gapped.is_empty());
+    }
+
+    #[test]
+    fn test_time_semantics_place_events_differently() {
+        let events: Vec<StreamEvent> = [7_200_000.0, 1_000.0]
+            .into_iter()
+            .map(|occurred_at| {
+                let mut data = HashMap::new();
+                data.insert("occurred_at".to_string(), Value::Number(occurred_at));
+                StreamEvent::new("Reading", data, "sensor")
+            })
+            .collect();
+
+        let mut event_time =
+            WindowManager::new(WindowType::Tumbling, Duration::from_secs(3600), 100, 10)
+                .with_time_semantics(TimeSemantics::event_time_field("occurred_at"));
+        let mut processing_time =
+            WindowManager::new(WindowType::Tumbling, Duration::from_secs(3600), 100, 10)
+                .with_time_semantics(TimeSemantics::ProcessingTime);
+
+        for event in &events {
+            event_time.process_event(event.clone());
+            processing_time.process_event(event.clone());
+        }
+
+        // Occurrence times two hours apart land in separate hourly windows
+        let starts: Vec<u64> = event_time
+            .active_windows()
+            .iter()
+            .map(|w| w.start_time)
+            .collect();
+        assert_eq!(starts, vec![0, 7_200_000]);
+
+        // Arrival times are milliseconds apart, so both share the current hour
+        let windows = processing_time.active_windows();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].count(), 2);
+        assert!(windows[0].start_time > 7_200_000);
+    }
+
+    fn click(user: &str, timestamp: u64) -> StreamEvent {
+        let mut data = HashMap::new();
+        data.insert("userId".to_string(), Value::String(user.to_string()));
+        StreamEvent::with_timestamp("Click", data, "web", timestamp)
+    }
+
+    #[test]
+    fn test_keyed_session_windows_close_after_gap() {
+        use crate::streaming::watermark::LateDataStrategy;
+
+        let mut manager = WindowManager::new(
+            WindowType::Session {
+                timeout: Duration::from_secs(30),
+            },
+            Duration::from_secs(60),
+            100,
+            10,
+        )
+        .with_session_key("userId")
+        .with_late_data_handler(LateDataHandler::new(LateDataStrategy::SideOutput));
+
+        manager.process_event(click("alice", 0));
+        manager.process_event(click("bob", 5_000));
+        manager.process_event(click("alice", 20_000));
+
+        let sessions: Vec<(Option<&str>, usize, u64)> = manager
+            .active_windows()
+            .iter()
+            .map(|w| (w.key.as_deref(), w.count(), w.end_time))
+            .collect();
+        assert_eq!(
+            sessions,
+            vec![(Some("alice"), 2, 50_000), (Some("bob"), 1, 35_000)]
+        );
+
+        // bob has been idle for 30s, so only alice's session stays open
+        manager.process_event(click("alice", 45_000));
+        assert_eq!(manager.active_windows().len(), 1);
+        assert_eq!(manager.active_windows()[0].count(), 3);
+        assert_eq!(manager.active_windows()[0].end_time, 75_000);
+
+        // A 40s gap closes alice's session and starts a new one
+        manager.process_event(click("alice", 85_000));
+        assert_eq!(manager.active_windows().len(), 1);
+        assert_eq!(manager.active_windows()[0].start_time, 85_000);
+
+        // bob's click at 10s belongs to a session that has already closed
+        manager.process_event(click("bob", 10_000));
+        let handler = manager.late_data_handler().unwrap();
+        assert_eq!(handler.stats().total_late, 1);
+        assert_eq!(handler.side_output().len(), 1);
+        assert_eq!(manager.active_windows().len(), 1);
+    }
 }
@@ -1,4 +1,8 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 /// Represents a value that can be used in rule conditions and actions
@@ -20,6 +24,24 @@ pub enum Value {
     Null,
     /// Expression to be evaluated at runtime (e.g., "Order.quantity * Order.price")
     Expression(String),
+    /// Numeric range (`1..31` excludes the end, `1..=31` includes it)
+    Range {
+        /// Lower bound (always inclusive)
+        start: f64,
+        /// Upper bound
+        end: f64,
+        /// Whether `end` itself is part of the range
+        inclusive: bool,
+    },
+    /// Point in time, compared chronologically
+    DateTime(DateTime<Utc>),
+    /// Length of time, e.g. `30m` or the difference of two datetimes
+    Duration(Duration),
+    /// Binary payload such as a hash or token, written in GRL as `b64"SGVsbG8="`
+    Bytes(Vec<u8>),
+    /// Exact decimal number for money, written in GRL as `19.99d`
+    #[cfg(feature = "decimal")]
+    Decimal(Decimal),
 }
 
 impl Value {
@@ -35,6 +57,12 @@ impl Value {
             Value::Object(_) => "[Object]".to_string(),
             Value::Null => "null".to_string(),
             Value::Expression(expr) => format!("[Expr: {}]", expr),
+            Value::Range { .. } => format!("{}", self),
+            Value::DateTime(dt) => dt.to_rfc3339(),
+            Value::Duration(_) => format!("{}", self),
+            Value::Bytes(_) => format!("{}", self),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => d.to_string(),
         }
     }
 
@@ -49,6 +77,12 @@ impl Value {
             Value::Object(_) => std::borrow::Cow::Borrowed("[Object]"),
             Value::Null => std::borrow::Cow::Borrowed("null"),
             Value::Expression(expr) => std::borrow::Cow::Owned(format!("[Expr: {}]", expr)),
+            Value::Range { .. } => std::borrow::Cow::Owned(format!("{}", self)),
+            Value::DateTime(dt) => std::borrow::Cow::Owned(dt.to_rfc3339()),
+            Value::Duration(_) => std::borrow::Cow::Owned(format!("{}", self)),
+            Value::Bytes(_) => std::borrow::Cow::Owned(format!("{}", self)),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => std::borrow::Cow::Owned(d.to_string()),
         }
     }
 
@@ -58,6 +92,8 @@ impl Value {
             Value::Number(n) => Some(*n),
             Value::Integer(i) => Some(*i as f64),
             Value::String(s) => s.parse::<f64>().ok(),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => rust_decimal::prelude::ToPrimitive::to_f64(d),
             _ => None,
         }
     }
@@ -78,6 +114,151 @@ impl Value {
         }
     }
 
+    /// Check whether `item` is contained in this value
+    ///
+    /// - strings test for a substring
+    /// - arrays test for an element equal to `item`, compared structurally
+    /// - objects test for a key named `item`
+    /// - ranges test numeric membership of a scalar
+    ///
+    /// Any other value contains nothing.
+    pub fn contains(&self, item: &Value) -> bool {
+        match self {
+            Value::Range {
+                start,
+                end,
+                inclusive,
+            } => match item.to_number() {
+                Some(n) if *inclusive => n >= *start && n <= *end,
+                Some(n) => n >= *start && n < *end,
+                None => false,
+            },
+            Value::String(s) => item.as_string_ref().is_some_and(|sub| s.contains(sub)),
+            Value::Array(items) => items.iter().any(|element| values_equal(element, item)),
+            Value::Object(fields) => item.as_string_ref().is_some_and(|k| fields.contains_key(k)),
+            _ => false,
+        }
+    }
+
+    /// Get datetime value, parsing ISO-8601 strings when needed
+    pub fn as_datetime(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Value::DateTime(dt) => Some(*dt),
+            Value::String(s) => Value::parse_datetime(s),
+            _ => None,
+        }
+    }
+
+    /// Parse an ISO-8601 date or datetime
+    ///
+    /// Accepts RFC 3339 (`2024-01-01T10:00:00Z`, `2024-01-01T10:00:00+02:00`),
+    /// datetimes without an offset and plain dates; the latter two are taken as UTC.
+    pub fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
+        let s = s.trim();
+        if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+            return Some(dt.with_timezone(&Utc));
+        }
+        for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(s, format) {
+                return Some(naive.and_utc());
+            }
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|naive| naive.and_utc())
+    }
+
+    /// Get duration value, parsing literals such as `30m` when needed
+    pub fn as_duration(&self) -> Option<Duration> {
+        match self {
+            Value::Duration(d) => Some(*d),
+            Value::String(s) => Value::parse_duration(s),
+            _ => None,
+        }
+    }
+
+    /// Get decimal value, converting integers, floats and numeric strings
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Value::Decimal(d) => Some(*d),
+            Value::Integer(i) => Some(Decimal::from(*i)),
+            Value::Number(n) => Decimal::try_from(*n).ok(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Parse a decimal literal such as `19.99d`
+    ///
+    /// The digits must contain a decimal point, so `30d` stays a duration of
+    /// 30 days.
+    #[cfg(feature = "decimal")]
+    pub fn parse_decimal(s: &str) -> Option<Decimal> {
+        let digits = s.trim().strip_suffix('d')?;
+        if !digits.contains('.') {
+            return None;
+        }
+        digits.parse().ok()
+    }
+
+    /// Parse a duration literal
+    ///
+    /// A sequence of whole numbers with a unit: `ms`, `s`, `m`, `h`, `d` or `w`,
+    /// e.g. `30m`, `24h` or `1h30m`. A leading `-` negates the whole duration.
+    pub fn parse_duration(s: &str) -> Option<Duration> {
+        let s = s.trim();
+        let (negative, mut rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        if rest.is_empty() {
+            return None;
+        }
+
+        let mut total = Duration::zero();
+        while !rest.is_empty() {
+            let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+            if digits == 0 {
+                return None;
+            }
+            let amount: i64 = rest[..digits].parse().ok()?;
+            let unit_len = rest[digits..]
+                .find(|c: char| c.is_ascii_digit())
+                .unwrap_or(rest.len() - digits);
+            let part = match &rest[digits..digits + unit_len] {
+                "ms" => Duration::try_milliseconds(amount)?,
+                "s" => Duration::try_seconds(amount)?,
+                "m" => Duration::try_minutes(amount)?,
+                "h" => Duration::try_hours(amount)?,
+                "d" => Duration::try_days(amount)?,
+                "w" => Duration::try_weeks(amount)?,
+                _ => return None,
+            };
+            total = total.checked_add(&part)?;
+            rest = &rest[digits + unit_len..];
+        }
+
+        Some(if negative { -total } else { total })
+    }
+
+    /// Get the bytes if this is a byte value
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Parse a base64 bytes literal such as `b64"SGVsbG8="`
+    ///
+    /// Uses the standard alphabet; trailing `=` padding is optional.
+    pub fn parse_bytes_literal(s: &str) -> Option<Vec<u8>> {
+        let encoded = s.trim().strip_prefix("b64\"")?.strip_suffix('"')?;
+        decode_base64(encoded)
+    }
+
     /// Get integer value if this is an integer
     pub fn as_integer(&self) -> Option<i64> {
         match self {
@@ -113,6 +294,22 @@ impl Value {
             Value::Object(obj) => !obj.is_empty(),
             Value::Null => false,
             Value::Expression(_) => false, // Expression needs to be evaluated first
+            Value::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                if *inclusive {
+                    start <= end
+                } else {
+                    start < end
+                }
+            }
+            Value::DateTime(_) => true,
+            Value::Duration(d) => !d.is_zero(),
+            Value::Bytes(bytes) => !bytes.is_empty(),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => !d.is_zero(),
         }
     }
 
@@ -203,6 +400,24 @@ impl From<bool> for Value {
     }
 }
 
+impl From<DateTime<Utc>> for Value {
+    fn from(dt: DateTime<Utc>) -> Self {
+        Value::DateTime(dt)
+    }
+}
+
+impl From<Duration> for Value {
+    fn from(d: Duration) -> Self {
+        Value::Duration(d)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(bytes: Vec<u8>) -> Self {
+        Value::Bytes(bytes)
+    }
+}
+
 impl From<serde_json::Value> for Value {
     fn from(json_value: serde_json::Value) -> Self {
         match json_value {
@@ -232,6 +447,54 @@ impl From<serde_json::Value> for Value {
     }
 }
 
+/// Order two values chronologically when at least one of them is a datetime
+/// or a duration
+fn temporal_ordering(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::DateTime(l), other) => Some(l.cmp(&other.as_datetime()?)),
+        (other, Value::DateTime(r)) => Some(other.as_datetime()?.cmp(r)),
+        (Value::Duration(l), other) => Some(l.cmp(&other.as_duration()?)),
+        (other, Value::Duration(r)) => Some(other.as_duration()?.cmp(r)),
+        _ => None,
+    }
+}
+
+/// Equality behind `==`, `!=` and the membership operators
+///
+/// Integers and floats compare by numeric value, so `2 == 2.0`. Arrays and
+/// objects compare element by element with the same rule.
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Integer(i), Value::Number(n)) | (Value::Number(n), Value::Integer(i)) => {
+            *i as f64 == *n
+        }
+        (Value::Array(l), Value::Array(r)) => {
+            l.len() == r.len() && l.iter().zip(r).all(|(a, b)| values_equal(a, b))
+        }
+        (Value::Object(l), Value::Object(r)) => {
+            l.len() == r.len()
+                && l.iter()
+                    .all(|(key, value)| r.get(key).is_some_and(|other| values_equal(value, other)))
+        }
+        _ => left == right,
+    }
+}
+
+/// Compare two values exactly when at least one of them is a decimal
+#[cfg(feature = "decimal")]
+fn decimal_ordering(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Decimal(l), other) => Some(l.cmp(&other.as_decimal()?)),
+        (other, Value::Decimal(r)) => Some(other.as_decimal()?.cmp(r)),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "decimal"))]
+fn decimal_ordering(_left: &Value, _right: &Value) -> Option<Ordering> {
+    None
+}
+
 /// Comparison operators for rule conditions
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Operator {
@@ -259,6 +522,26 @@ pub enum Operator {
     Matches,
     /// Array membership check (value in array)
     In,
+    /// Negated array membership check (value not in array)
+    NotIn,
+    /// Array holds every element of the right-hand array (subset check)
+    ContainsAll,
+    /// Array holds at least one element of the right-hand array
+    ContainsAny,
+    /// Unary type test: value is a number (integer or float)
+    IsNumber,
+    /// Unary type test: value is a string
+    IsString,
+    /// Unary type test: value is a boolean
+    IsBoolean,
+    /// Unary type test: value is an array
+    IsArray,
+    /// Unary type test: value is an object
+    IsObject,
+    /// Unary test: value is null or the field is missing (`is null`)
+    IsNull,
+    /// Unary test: value is present and not null (`is not null`)
+    IsNotNull,
 }
 
 impl Operator {
@@ -278,12 +561,101 @@ impl Operator {
             "ends_with" | "endsWith" => Some(Operator::EndsWith),
             "matches" => Some(Operator::Matches),
             "in" => Some(Operator::In),
+            "not_in" => Some(Operator::NotIn),
+            _ if s.split_whitespace().eq(["not", "in"]) => Some(Operator::NotIn),
+            "contains_all" | "containsAll" => Some(Operator::ContainsAll),
+            "contains_any" | "containsAny" => Some(Operator::ContainsAny),
+            "is_number" => Some(Operator::IsNumber),
+            "is_string" => Some(Operator::IsString),
+            "is_boolean" => Some(Operator::IsBoolean),
+            "is_array" => Some(Operator::IsArray),
+            "is_object" => Some(Operator::IsObject),
+            "is_null" => Some(Operator::IsNull),
+            "is_not_null" => Some(Operator::IsNotNull),
+            _ if s.split_whitespace().eq(["is", "null"]) => Some(Operator::IsNull),
+            _ if s.split_whitespace().eq(["is", "not", "null"]) => Some(Operator::IsNotNull),
             _ => None,
         }
     }
 
+    /// Whether the operator only inspects the left value (type and null tests)
+    pub fn is_unary(&self) -> bool {
+        matches!(
+            self,
+            Operator::IsNumber
+                | Operator::IsString
+                | Operator::IsBoolean
+                | Operator::IsArray
+                | Operator::IsObject
+                | Operator::IsNull
+                | Operator::IsNotNull
+        )
+    }
+
+    /// Whether the operator orders its operands (`>`, `>=`, `<`, `<=`)
+    pub fn is_ordering(&self) -> bool {
+        matches!(
+            self,
+            Operator::GreaterThan
+                | Operator::GreaterThanOrEqual
+                | Operator::LessThan
+                | Operator::LessThanOrEqual
+        )
+    }
+
+    /// Evaluate the operator with `compare` ordering the two values
+    ///
+    /// Only `==`, `!=`, `>`, `>=`, `<` and `<=` consult `compare`; every other
+    /// operator, and any comparison involving null, behaves as in
+    /// [`evaluate`](Self::evaluate).
+    pub fn evaluate_with(
+        &self,
+        left: &Value,
+        right: &Value,
+        compare: &dyn Fn(&Value, &Value) -> Ordering,
+    ) -> bool {
+        if *left == Value::Null || *right == Value::Null {
+            return self.evaluate(left, right);
+        }
+        match self {
+            Operator::Equal => compare(left, right).is_eq(),
+            Operator::NotEqual => compare(left, right).is_ne(),
+            Operator::GreaterThan => compare(left, right).is_gt(),
+            Operator::GreaterThanOrEqual => compare(left, right).is_ge(),
+            Operator::LessThan => compare(left, right).is_lt(),
+            Operator::LessThanOrEqual => compare(left, right).is_le(),
+            _ => self.evaluate(left, right),
+        }
+    }
+
     /// Evaluate the operator against two values
+    ///
+    /// Objects and arrays compare structurally with `==` and `!=`: nested values
+    /// must match and object key order is irrelevant. They have no ordering.
+    ///
+    /// Null only equals null: `>`, `>=`, `<` and `<=` are false when either side
+    /// is null. Use `IsNull` / `IsNotNull` to branch on a missing value.
     pub fn evaluate(&self, left: &Value, right: &Value) -> bool {
+        if self.is_ordering() && (*left == Value::Null || *right == Value::Null) {
+            return false;
+        }
+
+        // Dates and durations compare by time and decimals exactly; a string on
+        // the other side is parsed first
+        if let Some(ordering) =
+            temporal_ordering(left, right).or_else(|| decimal_ordering(left, right))
+        {
+            match self {
+                Operator::Equal => return ordering.is_eq(),
+                Operator::NotEqual => return ordering.is_ne(),
+                Operator::GreaterThan => return ordering.is_gt(),
+                Operator::GreaterThanOrEqual => return ordering.is_ge(),
+                Operator::LessThan => return ordering.is_lt(),
+                Operator::LessThanOrEqual => return ordering.is_le(),
+                _ => {}
+            }
+        }
+
         match self {
             Operator::Equal => {
                 // Special handling for null comparison
@@ -297,7 +669,7 @@ impl Operator {
 
                     left_is_null == right_is_null
                 } else {
-                    left == right
+                    values_equal(left, right)
                 }
             }
             Operator::NotEqual => {
@@ -310,7 +682,7 @@ impl Operator {
 
                     left_is_null != right_is_null
                 } else {
-                    left != right
+                    !values_equal(left, right)
                 }
             }
             Operator::GreaterThan => {
@@ -341,20 +713,16 @@ impl Operator {
                     false
                 }
             }
-            Operator::Contains => {
-                if let (Some(l), Some(r)) = (left.as_string_ref(), right.as_string_ref()) {
-                    l.contains(r)
-                } else {
-                    false
+            // See `Value::contains` for what each kind of left-hand value contains
+            Operator::Contains => left.contains(right),
+            // Only a container of the right kind can fail to contain something
+            Operator::NotContains => match left {
+                Value::String(_) | Value::Object(_) if right.as_string_ref().is_none() => false,
+                Value::String(_) | Value::Array(_) | Value::Object(_) | Value::Range { .. } => {
+                    !left.contains(right)
                 }
-            }
-            Operator::NotContains => {
-                if let (Some(l), Some(r)) = (left.as_string_ref(), right.as_string_ref()) {
-                    !l.contains(r)
-                } else {
-                    false
-                }
-            }
+                _ => false,
+            },
             Operator::StartsWith => {
                 if let (Some(l), Some(r)) = (left.as_string_ref(), right.as_string_ref()) {
                     l.starts_with(r)
@@ -379,12 +747,41 @@ impl Operator {
                 }
             }
             Operator::In => {
-                // Check if left value is in right array
+                // Check if left value is in right array, range or object keys
                 match right {
-                    Value::Array(arr) => arr.contains(left),
+                    Value::Array(arr) => arr.iter().any(|element| values_equal(element, left)),
+                    Value::Range { .. } => right.contains(left),
+                    Value::Object(obj) => left.as_string_ref().is_some_and(|k| obj.contains_key(k)),
                     _ => false,
                 }
             }
+            Operator::NotIn => match right {
+                Value::Array(_) | Value::Range { .. } | Value::Object(_) => {
+                    !Operator::In.evaluate(left, right)
+                }
+                _ => false,
+            },
+            // Both sides must be arrays; elements compare by deep equality
+            Operator::ContainsAll => match (left, right) {
+                (Value::Array(have), Value::Array(wanted)) => wanted
+                    .iter()
+                    .all(|item| have.iter().any(|element| values_equal(element, item))),
+                _ => false,
+            },
+            Operator::ContainsAny => match (left, right) {
+                (Value::Array(have), Value::Array(wanted)) => wanted
+                    .iter()
+                    .any(|item| have.iter().any(|element| values_equal(element, item))),
+                _ => false,
+            },
+            // Type tests ignore the right-hand side
+            Operator::IsNumber => matches!(left, Value::Number(_) | Value::Integer(_)),
+            Operator::IsString => matches!(left, Value::String(_)),
+            Operator::IsBoolean => matches!(left, Value::Boolean(_)),
+            Operator::IsArray => matches!(left, Value::Array(_)),
+            Operator::IsObject => matches!(left, Value::Object(_)),
+            Operator::IsNull => matches!(left, Value::Null),
+            Operator::IsNotNull => !matches!(left, Value::Null),
         }
     }
 }
@@ -396,6 +793,8 @@ pub enum LogicalOperator {
     And,
     /// Logical OR
     Or,
+    /// Logical exclusive OR: true when exactly one side holds
+    Xor,
     /// Logical NOT
     Not,
 }
@@ -407,6 +806,7 @@ impl LogicalOperator {
         match s.to_lowercase().as_str() {
             "and" | "&&" => Some(LogicalOperator::And),
             "or" | "||" => Some(LogicalOperator::Or),
+            "xor" | "^^" => Some(LogicalOperator::Xor),
             "not" | "!" => Some(LogicalOperator::Not),
             _ => None,
         }
@@ -483,6 +883,31 @@ pub enum ActionType {
         /// Value to append
         value: Value,
     },
+    /// Stop rule execution once the current rule's actions have run
+    Halt,
+    /// Record an event for the application, returned in the execution result
+    Emit {
+        /// Event type name
+        event_type: String,
+        /// Payload fields, resolved against facts when the event is emitted
+        payload: HashMap<String, Value>,
+    },
+    /// Add a weight to a numeric score field, starting from zero if it is unset
+    AddScore {
+        /// Score field to accumulate into
+        target: String,
+        /// Weight added each time the rule fires
+        amount: f64,
+    },
+    /// Call a registered function and store its result in a field
+    CallAndBind {
+        /// Field receiving the result
+        field: String,
+        /// Name of the registered function
+        function: String,
+        /// Function arguments, resolved against facts when called
+        args: Vec<Value>,
+    },
 }
 
 // Efficient Display implementation for Value to avoid unnecessary cloning
@@ -497,6 +922,91 @@ impl std::fmt::Display for Value {
             Value::Object(_) => write!(f, "[Object]"),
             Value::Null => write!(f, "null"),
             Value::Expression(expr) => write!(f, "[Expr: {}]", expr),
+            Value::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let op = if *inclusive { "..=" } else { ".." };
+                write!(f, "{}{}{}", start, op, end)
+            }
+            Value::DateTime(dt) => write!(f, "{}", dt.to_rfc3339()),
+            Value::Duration(d) => write_duration(f, *d),
+            Value::Bytes(bytes) => write!(f, "b64\"{}\"", encode_base64(bytes)),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(d) => write!(f, "{}", d),
         }
     }
 }
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as padded standard base64
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (i, byte)| {
+            acc | ((*byte as u32) << (16 - 8 * i))
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3f;
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Decode standard base64, with or without trailing padding
+fn decode_base64(encoded: &str) -> Option<Vec<u8>> {
+    let digits = encoded.trim_end_matches('=');
+    if digits.len() % 4 == 1 || encoded.len() - digits.len() > 2 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in digits.bytes() {
+        let sextet = BASE64_ALPHABET.iter().position(|&b| b == c)? as u32;
+        buffer = (buffer << 6) | sextet;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+/// Write a duration in literal form (`1h30m`), so it parses back unchanged
+fn write_duration(f: &mut std::fmt::Formatter<'_>, duration: Duration) -> std::fmt::Result {
+    if duration.is_zero() {
+        return write!(f, "0s");
+    }
+    if duration < Duration::zero() {
+        write!(f, "-")?;
+    }
+
+    let mut millis = duration.num_milliseconds().unsigned_abs();
+    for (unit, size) in [
+        ("d", 86_400_000),
+        ("h", 3_600_000),
+        ("m", 60_000),
+        ("s", 1_000),
+    ] {
+        if millis >= size {
+            write!(f, "{}{}", millis / size, unit)?;
+            millis %= size;
+        }
+    }
+    if millis > 0 {
+        write!(f, "{}ms", millis)?;
+    }
+    Ok(())
+}
@@ -117,31 +117,7 @@ fn data_driven_grl_cases() -> Result<(), Box<dyn std::error::Error>> {
             }
             Ok(())
         });
-        // Register action handlers for method calls used in method_calls.grl
-        engine.register_action_handler("setSpeed", |params, facts| {
-            if let Some(speed_value) = params.get("value") {
-                if let Some(car) = facts.get("TestCar") {
-                    if let Value::Object(mut car_obj) = car.clone() {
-                        car_obj.insert("Speed".to_string(), speed_value.clone());
-                        // Note: In a real implementation, you'd update the fact in the facts collection
-                        // For this test, we just ensure the handler exists and doesn't panic
-                    }
-                }
-            }
-            Ok(())
-        });
 
-        engine.register_action_handler("setSpeedUp", |params, facts| {
-            if let Some(speed_up_value) = params.get("value") {
-                if let Some(car) = facts.get("TestCar") {
-                    if let Value::Object(mut car_obj) = car.clone() {
-                        car_obj.insert("SpeedUp".to_string(), speed_up_value.clone());
-                        // Note: In a real implementation, you'd update the fact in the facts collection
-                    }
-                }
-            }
-            Ok(())
-        });
         // Register common functions used by some GRL examples (car_functions.grl etc.)
         engine.register_function("checkSpeedLimit", |args, facts| {
             let speed = args.first().map(|v| v.to_string()).unwrap_or_default();
@@ -317,63 +293,6 @@ fn data_driven_grl_cases() -> Result<(), Box<dyn std::error::Error>> {
             Ok(())
         });
 
-        // Action handler for setCurrentDistance used in complete_speedup.grl
-        engine.register_action_handler("setCurrentDistance", |params, facts| {
-            if let Some(val) = params
-                .get("0")
-                .cloned()
-                .or_else(|| params.get("value").cloned())
-            {
-                if let Ok(v) = val.to_string().parse::<f64>() {
-                    if let Some(existing) = facts
-                        .get("DistanceRecord")
-                        .or_else(|| facts.get("distanceRecord"))
-                    {
-                        if let Value::Object(obj) = existing {
-                            let mut updated = obj.clone();
-                            updated.insert("CurrentDistance".to_string(), Value::Number(v));
-                            facts
-                                .add_value("DistanceRecord", Value::Object(updated))
-                                .map_err(|e| {
-                                    rust_rule_engine::errors::RuleEngineError::EvaluationError {
-                                        message: format!("setCurrentDistance failed: {}", e),
-                                    }
-                                })?;
-                        }
-                    }
-                }
-            }
-            Ok(())
-        });
-        // Action handler for setTotalDistance used in complete_speedup.grl
-        engine.register_action_handler("setTotalDistance", |params, facts| {
-            if let Some(val) = params
-                .get("0")
-                .cloned()
-                .or_else(|| params.get("value").cloned())
-            {
-                if let Ok(v) = val.to_string().parse::<f64>() {
-                    if let Some(existing) = facts
-                        .get("DistanceRecord")
-                        .or_else(|| facts.get("distanceRecord"))
-                    {
-                        if let Value::Object(obj) = existing {
-                            let mut updated = obj.clone();
-                            updated.insert("TotalDistance".to_string(), Value::Number(v));
-                            facts
-                                .add_value("DistanceRecord", Value::Object(updated))
-                                .map_err(|e| {
-                                    rust_rule_engine::errors::RuleEngineError::EvaluationError {
-                                        message: format!("setTotalDistance failed: {}", e),
-                                    }
-                                })?;
-                        }
-                    }
-                }
-            }
-            Ok(())
-        });
-
         // Action handler for updatePerformanceMetrics used in complete_speedup.grl
         engine.register_action_handler("updatePerformanceMetrics", |params, facts| {
             let speed = params